@@ -4,7 +4,7 @@ use bincode::deserialize;
 use dotenv::dotenv;
 use jup_ag_sdk::{
     JupiterClient,
-    types::{Instruction, QuoteGetSwapModeEnum, QuoteRequest, SwapRequest, SwapResponse},
+    types::{Instruction, QuoteRequest, SwapMode, SwapRequest, SwapResponse},
 };
 use solana_sdk::{
     address_lookup_table::state::AddressLookupTable,
@@ -28,7 +28,7 @@ pub async fn swap() {
         "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
         1_000_000, // 1 USDC (6 decimals)
     )
-    .swap_mode(QuoteGetSwapModeEnum::ExactOut); // Swap some SOL for exact 1 USDC
+    .swap_mode(SwapMode::ExactOut); // Swap some SOL for exact 1 USDC
 
     let quote_res = client.get_quote(&quote).await.expect("Failed to get quote");
 
@@ -91,7 +91,7 @@ pub async fn swap_with_instructions() {
         "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
         1_000_000, // 1 USDC (6 decimals)
     )
-    .swap_mode(QuoteGetSwapModeEnum::ExactOut); // Swap some SOL for exact 1 USDC
+    .swap_mode(SwapMode::ExactOut); // Swap some SOL for exact 1 USDC
 
     let quote_res = client.get_quote(&quote).await.expect("Failed to get quote");
 
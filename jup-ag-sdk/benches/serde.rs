@@ -0,0 +1,167 @@
+//! Deserialization benchmarks for the largest Jupiter response payloads.
+//!
+//! Run with `cargo bench -p jup-ag-sdk`, or `cargo bench -p jup-ag-sdk --features simd-json`
+//! to compare against the simd-json backend.
+
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+use jup_ag_sdk::{
+    json,
+    types::{OrderResponse, QuoteResponse, TokenInfoResponse},
+};
+use serde_json::{Value, json};
+
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const JUP_MINT: &str = "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+fn quote_body(hops: usize) -> Vec<u8> {
+    let route_plan: Vec<Value> = (0..hops)
+        .map(|i| {
+            json!({
+                "swapInfo": {
+                    "ammKey": format!("AmmKey{i:0>38}"),
+                    "label": "Meteora DLMM",
+                    "inputMint": SOL_MINT,
+                    "outputMint": USDC_MINT,
+                    "inAmount": "1000000000",
+                    "outAmount": "150123456",
+                    "feeAmount": "25000",
+                    "feeMint": SOL_MINT
+                },
+                "percent": 100 / hops.max(1)
+            })
+        })
+        .collect();
+
+    serde_json::to_vec(&json!({
+        "inputMint": SOL_MINT,
+        "inAmount": "1000000000",
+        "outputMint": USDC_MINT,
+        "outAmount": "150123456",
+        "otherAmountThreshold": "149372839",
+        "swapMode": "ExactIn",
+        "slippageBps": 50,
+        "platformFee": null,
+        "priceImpactPct": "0.0001",
+        "routePlan": route_plan,
+        "contextSlot": 338_000_000u64,
+        "timeTaken": 0.0123
+    }))
+    .unwrap()
+}
+
+fn trigger_page_body(orders: usize) -> Vec<u8> {
+    let trade = json!({
+        "orderKey": "OrderKey1111111111111111111111111111111111",
+        "keeper": "Keeper111111111111111111111111111111111111",
+        "inputMint": SOL_MINT,
+        "outputMint": JUP_MINT,
+        "inputAmount": "1.0",
+        "outputAmount": "400.0",
+        "rawInputAmount": "1000000000",
+        "rawOutputAmount": "400000000",
+        "feeMint": JUP_MINT,
+        "feeAmount": "0.4",
+        "rawFeeAmount": "400000",
+        "txId": "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW",
+        "confirmedAt": "2025-05-01T00:00:00Z",
+        "action": "Fill",
+        "productMeta": null
+    });
+    let orders: Vec<Value> = (0..orders)
+        .map(|_| {
+            json!({
+                "userPubkey": "EXBdeRCdiNChKyD7akt64n9HgSXEpUtpPEhmbnm4L6iH",
+                "orderKey": "OrderKey1111111111111111111111111111111111",
+                "inputMint": SOL_MINT,
+                "outputMint": JUP_MINT,
+                "makingAmount": "1.0",
+                "takingAmount": "400.0",
+                "remainingMakingAmount": "0",
+                "remainingTakingAmount": "0",
+                "rawMakingAmount": "1000000000",
+                "rawTakingAmount": "400000000",
+                "rawRemainingMakingAmount": "0",
+                "rawRemainingTakingAmount": "0",
+                "slippageBps": "0",
+                "expiredAt": null,
+                "createdAt": "2025-05-01T00:00:00Z",
+                "updatedAt": "2025-05-01T00:00:00Z",
+                "status": "Completed",
+                "openTx": "open",
+                "closeTx": "close",
+                "programVersion": "j1o2qRpjcyUwEvwtcfhEQefh773ZgjxcVRry7LDqg5X",
+                "trades": [trade.clone(), trade.clone()]
+            })
+        })
+        .collect();
+
+    serde_json::to_vec(&json!({
+        "user": "EXBdeRCdiNChKyD7akt64n9HgSXEpUtpPEhmbnm4L6iH",
+        "orderStatus": "history",
+        "orders": orders,
+        "totalPages": 10,
+        "page": 1
+    }))
+    .unwrap()
+}
+
+fn token_list_body(tokens: usize) -> Vec<u8> {
+    let tokens: Vec<Value> = (0..tokens)
+        .map(|i| {
+            json!({
+                "address": format!("Mint{i:0>40}"),
+                "name": format!("Token {i}"),
+                "symbol": format!("TK{i}"),
+                "decimals": 6,
+                "logoURI": "https://example.com/logo.png",
+                "tags": ["verified", "strict"],
+                "daily_volume": 12345.67,
+                "created_at": "2024-04-26T10:56:58.893768Z",
+                "freeze_authority": null,
+                "mint_authority": null,
+                "permanent_delegate": null,
+                "minted_at": "2024-01-25T08:54:23Z",
+                "extensions": { "coingeckoId": "jupiter-exchange-solana" }
+            })
+        })
+        .collect();
+
+    serde_json::to_vec(&tokens).unwrap()
+}
+
+fn bench_decode<T: serde::de::DeserializeOwned>(
+    c: &mut Criterion,
+    group: &str,
+    sizes: &[usize],
+    body: fn(usize) -> Vec<u8>,
+) {
+    let mut group = c.benchmark_group(group);
+    for &size in sizes {
+        let bytes = body(size);
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &bytes, |b, bytes| {
+            b.iter_batched_ref(
+                || bytes.clone(),
+                |buf| json::from_slice::<T>(black_box(buf)).unwrap(),
+                criterion::BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn quote_response(c: &mut Criterion) {
+    bench_decode::<QuoteResponse>(c, "quote_response", &[1, 4, 16, 64], quote_body);
+}
+
+fn trigger_orders(c: &mut Criterion) {
+    bench_decode::<OrderResponse>(c, "trigger_orders", &[10, 100, 500], trigger_page_body);
+}
+
+fn token_list(c: &mut Criterion) {
+    bench_decode::<Vec<TokenInfoResponse>>(c, "token_list", &[100, 1_000, 10_000], token_list_body);
+}
+
+criterion_group!(benches, quote_response, trigger_orders, token_list);
+criterion_main!(benches);
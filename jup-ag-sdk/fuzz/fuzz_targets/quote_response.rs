@@ -0,0 +1,8 @@
+#![no_main]
+
+use jup_ag_sdk::{json, types::QuoteResponse};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = json::from_slice::<QuoteResponse>(&mut data.to_vec());
+});
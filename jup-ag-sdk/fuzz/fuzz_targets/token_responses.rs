@@ -0,0 +1,13 @@
+#![no_main]
+
+use jup_ag_sdk::{
+    json,
+    types::{NewTokens, TokenInfoResponse, TokenPriceResponse},
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = json::from_slice::<TokenPriceResponse>(&mut data.to_vec());
+    let _ = json::from_slice::<Vec<TokenInfoResponse>>(&mut data.to_vec());
+    let _ = json::from_slice::<Vec<NewTokens>>(&mut data.to_vec());
+});
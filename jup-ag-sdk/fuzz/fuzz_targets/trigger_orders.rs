@@ -0,0 +1,13 @@
+#![no_main]
+
+use jup_ag_sdk::{
+    json,
+    types::{OrderResponse, RecurringOrders, TriggerResponse},
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = json::from_slice::<OrderResponse>(&mut data.to_vec());
+    let _ = json::from_slice::<TriggerResponse>(&mut data.to_vec());
+    let _ = json::from_slice::<RecurringOrders>(&mut data.to_vec());
+});
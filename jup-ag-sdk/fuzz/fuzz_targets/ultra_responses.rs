@@ -0,0 +1,14 @@
+#![no_main]
+
+use jup_ag_sdk::{
+    json,
+    types::{Shield, TokenBalancesResponse, UltraExecuteOrderResponse, UltraOrderResponse},
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = json::from_slice::<UltraOrderResponse>(&mut data.to_vec());
+    let _ = json::from_slice::<UltraExecuteOrderResponse>(&mut data.to_vec());
+    let _ = json::from_slice::<TokenBalancesResponse>(&mut data.to_vec());
+    let _ = json::from_slice::<Shield>(&mut data.to_vec());
+});
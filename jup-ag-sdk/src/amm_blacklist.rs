@@ -0,0 +1,265 @@
+//! Steering routing away from AMMs that keep failing at execution.
+//!
+//! Report the outcome of each executed route to an [`AmmBlacklist`]. Once an AMM has
+//! failed [`failure_threshold`](AmmBlacklistConfig::failure_threshold) times within the
+//! [`failure_window`](AmmBlacklistConfig::failure_window), it is blacklisted for a while
+//! and its DEX is added to `exclude_dexes` of later quotes. Bans expire so the venue is
+//! retried; an AMM that fails again right after is banned for twice as long, up to
+//! [`max_ban`](AmmBlacklistConfig::max_ban).
+//!
+//! The Swap API can only exclude whole DEXes, not single pools, so a blacklisted pool
+//! excludes every pool of its DEX. Use [`AmmBlacklist::allows`] to check a route's
+//! individual AMMs instead.
+//!
+//! # Example
+//! ```
+//! use std::sync::Arc;
+//! use jup_ag_sdk::{JupiterClient, amm_blacklist::AmmBlacklist};
+//!
+//! let blacklist = Arc::new(AmmBlacklist::default());
+//! let client = JupiterClient::builder("https://lite-api.jup.ag")
+//!     .amm_blacklist(blacklist.clone())
+//!     .build()?;
+//!
+//! let quote = client.get_quote(&request).await?;
+//! let landed = execute(&quote).await.is_ok();
+//! blacklist.record_execution(&quote, landed);
+//! ```
+
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use crate::{
+    runtime::Instant,
+    types::{QuoteRequest, QuoteResponse},
+};
+
+/// When an [`AmmBlacklist`] bans an AMM and for how long.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AmmBlacklistConfig {
+    /// Failures within [`failure_window`](Self::failure_window) that get an AMM banned.
+    /// Default 3.
+    pub failure_threshold: u32,
+    /// How long a failure counts towards the threshold. Default 10 minutes.
+    pub failure_window: Duration,
+    /// Length of a first ban. Default 5 minutes.
+    pub ban: Duration,
+    /// Longest ban, reached by doubling on repeated bans. Default 1 hour.
+    pub max_ban: Duration,
+}
+
+impl Default for AmmBlacklistConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 3,
+            failure_window: Duration::from_secs(10 * 60),
+            ban: Duration::from_secs(5 * 60),
+            max_ban: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+impl AmmBlacklistConfig {
+    /// Sets [`failure_threshold`](Self::failure_threshold).
+    pub fn failure_threshold(mut self, failures: u32) -> Self {
+        self.failure_threshold = failures.max(1);
+        self
+    }
+
+    /// Sets [`failure_window`](Self::failure_window).
+    pub fn failure_window(mut self, window: Duration) -> Self {
+        self.failure_window = window;
+        self
+    }
+
+    /// Sets [`ban`](Self::ban) and raises [`max_ban`](Self::max_ban) to it if needed.
+    pub fn ban(mut self, ban: Duration) -> Self {
+        self.ban = ban;
+        self.max_ban = self.max_ban.max(ban);
+        self
+    }
+
+    /// Sets [`max_ban`](Self::max_ban).
+    pub fn max_ban(mut self, max_ban: Duration) -> Self {
+        self.max_ban = max_ban.max(self.ban);
+        self
+    }
+}
+
+/// A currently blacklisted AMM.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlacklistedAmm {
+    pub amm_key: String,
+    /// The DEX label of the AMM, e.g. `Meteora DLMM`.
+    pub label: String,
+    /// Time left until the AMM is retried.
+    pub remaining: Duration,
+}
+
+#[derive(Debug)]
+struct AmmState {
+    label: String,
+    /// Recent failures, oldest first.
+    failures: Vec<Instant>,
+    banned_until: Option<Instant>,
+    /// Consecutive bans without a success in between, doubling the ban length.
+    strikes: u32,
+}
+
+/// Tracks execution failures per AMM and blacklists the ones that keep failing.
+///
+/// Share it with the client through
+/// [`JupiterClientBuilder::amm_blacklist`](crate::JupiterClientBuilder::amm_blacklist)
+/// to have [`get_quote`](crate::JupiterClient::get_quote) exclude blacklisted DEXes.
+#[derive(Debug, Default)]
+pub struct AmmBlacklist {
+    config: AmmBlacklistConfig,
+    amms: Mutex<HashMap<String, AmmState>>,
+}
+
+impl AmmBlacklist {
+    pub fn new(config: AmmBlacklistConfig) -> Self {
+        Self {
+            config,
+            amms: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn config(&self) -> &AmmBlacklistConfig {
+        &self.config
+    }
+
+    /// Records whether the transaction built from `quote` landed, for every AMM on its route.
+    pub fn record_execution(&self, quote: &QuoteResponse, succeeded: bool) {
+        for item in &quote.route_plan {
+            let info = &item.swap_info;
+            if succeeded {
+                self.record_success(&info.amm_key);
+            } else {
+                self.record_failure(&info.amm_key, &info.label);
+            }
+        }
+    }
+
+    /// Records an execution failure of the AMM `amm_key` of DEX `label`, banning it once
+    /// it reaches the failure threshold.
+    pub fn record_failure(&self, amm_key: &str, label: &str) {
+        let now = Instant::now();
+        let mut amms = self.amms.lock().unwrap();
+        let amm = amms.entry(amm_key.to_string()).or_insert_with(|| AmmState {
+            label: label.to_string(),
+            failures: Vec::new(),
+            banned_until: None,
+            strikes: 0,
+        });
+
+        amm.failures
+            .retain(|failed_at| now.duration_since(*failed_at) <= self.config.failure_window);
+        amm.failures.push(now);
+        if amm.failures.len() >= self.config.failure_threshold as usize
+            && amm.banned_until.is_none_or(|until| until <= now)
+        {
+            let ban = self
+                .config
+                .ban
+                .saturating_mul(2u32.saturating_pow(amm.strikes))
+                .min(self.config.max_ban);
+            amm.banned_until = Some(now + ban);
+            amm.strikes = amm.strikes.saturating_add(1);
+            amm.failures.clear();
+        }
+    }
+
+    /// Records a successful execution through `amm_key`, clearing its failures and
+    /// resetting its ban length. A current ban is left to expire.
+    pub fn record_success(&self, amm_key: &str) {
+        let mut amms = self.amms.lock().unwrap();
+        if let Some(amm) = amms.get_mut(amm_key) {
+            amm.failures.clear();
+            amm.strikes = 0;
+        }
+    }
+
+    /// Returns `true` if `amm_key` is currently banned.
+    pub fn is_blacklisted(&self, amm_key: &str) -> bool {
+        let now = Instant::now();
+        self.amms
+            .lock()
+            .unwrap()
+            .get(amm_key)
+            .and_then(|amm| amm.banned_until)
+            .is_some_and(|until| until > now)
+    }
+
+    /// The currently banned AMMs, sorted by key.
+    pub fn blacklisted(&self) -> Vec<BlacklistedAmm> {
+        let now = Instant::now();
+        let mut banned: Vec<BlacklistedAmm> = self
+            .amms
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|(key, amm)| {
+                let until = amm.banned_until.filter(|until| *until > now)?;
+                Some(BlacklistedAmm {
+                    amm_key: key.clone(),
+                    label: amm.label.clone(),
+                    remaining: until - now,
+                })
+            })
+            .collect();
+        banned.sort_by(|a, b| a.amm_key.cmp(&b.amm_key));
+        banned
+    }
+
+    /// DEX labels of the currently banned AMMs, sorted and deduplicated.
+    pub fn excluded_dexes(&self) -> Vec<String> {
+        let mut labels: Vec<String> = self
+            .blacklisted()
+            .into_iter()
+            .map(|amm| amm.label)
+            .collect();
+        labels.sort();
+        labels.dedup();
+        labels
+    }
+
+    /// Adds the [`excluded_dexes`](Self::excluded_dexes) to `request`'s `exclude_dexes`.
+    ///
+    /// DEXes explicitly listed in `dexes` are left routable.
+    pub fn apply(&self, mut request: QuoteRequest) -> QuoteRequest {
+        let excluded: Vec<String> = self
+            .excluded_dexes()
+            .into_iter()
+            .filter(|label| {
+                request
+                    .dexes
+                    .as_ref()
+                    .is_none_or(|dexes| !dexes.contains(label))
+            })
+            .collect();
+        if excluded.is_empty() {
+            return request;
+        }
+
+        let exclude = request.exclude_dexes.get_or_insert_with(Vec::new);
+        for label in excluded {
+            if !exclude.contains(&label) {
+                exclude.push(label);
+            }
+        }
+        request
+    }
+
+    /// Returns `true` if no AMM on `quote`'s route is blacklisted.
+    pub fn allows(&self, quote: &QuoteResponse) -> bool {
+        quote
+            .route_plan
+            .iter()
+            .all(|item| !self.is_blacklisted(&item.swap_info.amm_key))
+    }
+
+    /// Lifts every ban and forgets all failures.
+    pub fn clear(&self) {
+        self.amms.lock().unwrap().clear();
+    }
+}
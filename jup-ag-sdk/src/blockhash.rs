@@ -0,0 +1,299 @@
+//! Sharing recent blockhashes and durable nonces between concurrent transaction builds.
+//!
+//! Fetching a blockhash for every transaction a batch executor builds wastes an RPC
+//! round trip each time and quickly runs into RPC rate limits. A [`BlockhashManager`],
+//! shared behind an [`Arc`](std::sync::Arc), keeps one recent blockhash and refreshes it
+//! when it gets old, with a single request however many builds are waiting on it.
+//!
+//! Transactions that may sit for longer than a blockhash lives, about a minute, use a
+//! durable nonce instead. Register nonce accounts with
+//! [`nonce_accounts`](BlockhashManager::nonce_accounts) and
+//! [`acquire_nonce`](BlockhashManager::acquire_nonce) hands each one to a single build at
+//! a time.
+//!
+//! Requires the `solana` feature.
+
+use std::{collections::VecDeque, str::FromStr, sync::Mutex, time::Duration};
+
+use serde::Deserialize;
+use serde_json::json;
+use solana_hash::Hash;
+use solana_pubkey::Pubkey;
+
+use crate::{JupiterClient, error::JupiterClientError, runtime::Instant, types::parse_pubkey};
+
+/// How old a blockhash can get before it is refreshed, by default.
+///
+/// Blockhashes expire after 150 slots, roughly a minute, so this leaves a transaction
+/// built with one about 40 seconds to land.
+pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(20);
+
+/// The system program's recent blockhashes sysvar, read by `AdvanceNonceAccount`.
+const RECENT_BLOCKHASHES_SYSVAR: Pubkey =
+    Pubkey::from_str_const("SysvarRecentB1ockHashes11111111111111111111");
+
+/// Size of a nonce account: version, state, authority, nonce and fee calculator.
+const NONCE_ACCOUNT_SIZE: usize = 80;
+
+/// A blockhash fetched from the RPC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecentBlockhash {
+    pub blockhash: Hash,
+    /// Last block height at which a transaction with this blockhash is accepted.
+    pub last_valid_block_height: u64,
+    pub fetched_at: Instant,
+}
+
+impl RecentBlockhash {
+    /// Time since the blockhash was fetched.
+    pub fn age(&self) -> Duration {
+        self.fetched_at.elapsed()
+    }
+}
+
+/// A durable nonce account lent to one transaction build by
+/// [`BlockhashManager::acquire_nonce`].
+///
+/// Use [`nonce`](Self::nonce) as the transaction's blockhash and
+/// [`advance_instruction`](Self::advance_instruction) as its first instruction, then hand
+/// the lease back with [`BlockhashManager::release_nonce`] once the transaction landed or
+/// was dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonceLease {
+    pub account: Pubkey,
+    /// Must sign the transaction.
+    pub authority: Pubkey,
+    /// The nonce stored in the account when it was acquired.
+    pub nonce: Hash,
+}
+
+impl NonceLease {
+    /// The system program `AdvanceNonceAccount` instruction that must come first in a
+    /// transaction using this nonce.
+    pub fn advance_instruction(&self) -> solana_instruction::Instruction {
+        solana_instruction::Instruction {
+            program_id: Pubkey::default(),
+            accounts: vec![
+                solana_instruction::AccountMeta::new(self.account, false),
+                solana_instruction::AccountMeta::new_readonly(RECENT_BLOCKHASHES_SYSVAR, false),
+                solana_instruction::AccountMeta::new_readonly(self.authority, true),
+            ],
+            data: 4u32.to_le_bytes().to_vec(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RpcContextValue<T> {
+    value: T,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcBlockhash {
+    blockhash: String,
+    last_valid_block_height: u64,
+}
+
+/// Keeps a recent blockhash, and optionally a pool of nonce accounts, for many concurrent
+/// transaction builds.
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+/// use jup_ag_sdk::blockhash::BlockhashManager;
+///
+/// let blockhashes = Arc::new(BlockhashManager::new("https://api.mainnet-beta.solana.com"));
+/// for instructions in batch {
+///     let blockhashes = blockhashes.clone();
+///     tasks.spawn(async move {
+///         let recent = blockhashes.get(&client).await?;
+///         let message = v0::Message::try_compile(&payer, &instructions, &tables, recent.blockhash)?;
+///         // sign and send
+///     });
+/// }
+/// ```
+#[derive(Debug)]
+pub struct BlockhashManager {
+    rpc_url: String,
+    max_age: Duration,
+    commitment: String,
+    current: Mutex<Option<RecentBlockhash>>,
+    /// Held while fetching, so concurrent callers wait for one request.
+    refreshing: tokio::sync::Mutex<()>,
+    nonces: Mutex<VecDeque<Pubkey>>,
+}
+
+impl BlockhashManager {
+    /// Creates a manager fetching blockhashes at `confirmed` commitment from the Solana
+    /// RPC at `rpc_url`. Nothing is fetched until the first [`get`](Self::get).
+    pub fn new(rpc_url: &str) -> Self {
+        Self {
+            rpc_url: rpc_url.to_string(),
+            max_age: DEFAULT_MAX_AGE,
+            commitment: "confirmed".to_string(),
+            current: Mutex::new(None),
+            refreshing: tokio::sync::Mutex::new(()),
+            nonces: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Refreshes blockhashes older than `max_age`. Default [`DEFAULT_MAX_AGE`].
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Fetches blockhashes at `commitment`, e.g. `finalized`.
+    pub fn commitment(mut self, commitment: &str) -> Self {
+        self.commitment = commitment.to_string();
+        self
+    }
+
+    /// Adds durable nonce accounts to the pool used by
+    /// [`acquire_nonce`](Self::acquire_nonce). Fails if one isn't a valid address.
+    pub fn nonce_accounts(self, accounts: &[&str]) -> Result<Self, JupiterClientError> {
+        {
+            let mut nonces = self.nonces.lock().unwrap();
+            for account in accounts {
+                nonces.push_back(parse_pubkey("nonce_account", account)?);
+            }
+        }
+        Ok(self)
+    }
+
+    /// The last fetched blockhash, without fetching, even if it is old.
+    pub fn current(&self) -> Option<RecentBlockhash> {
+        *self.current.lock().unwrap()
+    }
+
+    /// A blockhash at most `max_age` old, fetched only if the current one is older.
+    ///
+    /// Callers arriving while a fetch is in flight wait for it instead of sending their own.
+    pub async fn get(&self, client: &JupiterClient) -> Result<RecentBlockhash, JupiterClientError> {
+        if let Some(recent) = self.fresh() {
+            return Ok(recent);
+        }
+        let _refreshing = self.refreshing.lock().await;
+        if let Some(recent) = self.fresh() {
+            return Ok(recent);
+        }
+        self.fetch(client).await
+    }
+
+    /// Fetches a new blockhash now, e.g. after a transaction failed with
+    /// `BlockhashNotFound`.
+    pub async fn refresh(
+        &self,
+        client: &JupiterClient,
+    ) -> Result<RecentBlockhash, JupiterClientError> {
+        let _refreshing = self.refreshing.lock().await;
+        self.fetch(client).await
+    }
+
+    /// Refreshes the blockhash every `period`, forever, sleeping on the client's
+    /// [`runtime`](JupiterClient::runtime), so [`get`](Self::get) never waits on the RPC.
+    ///
+    /// Failed refreshes are retried at the next tick.
+    pub async fn run(&self, client: &JupiterClient, period: Duration) {
+        loop {
+            let started = Instant::now();
+            let _ = self.refresh(client).await;
+            client
+                .runtime()
+                .sleep(period.saturating_sub(started.elapsed()))
+                .await;
+        }
+    }
+
+    /// Takes a nonce account from the pool and reads its current nonce, or returns `None`
+    /// if every account is lent out.
+    ///
+    /// If reading the account fails, it goes back to the pool and the error is returned.
+    pub async fn acquire_nonce(
+        &self,
+        client: &JupiterClient,
+    ) -> Result<Option<NonceLease>, JupiterClientError> {
+        let Some(account) = self.nonces.lock().unwrap().pop_front() else {
+            return Ok(None);
+        };
+        match self.read_nonce(client, account).await {
+            Ok(lease) => Ok(Some(lease)),
+            Err(error) => {
+                self.nonces.lock().unwrap().push_back(account);
+                Err(error)
+            }
+        }
+    }
+
+    /// Returns a nonce account to the pool.
+    pub fn release_nonce(&self, lease: NonceLease) {
+        self.nonces.lock().unwrap().push_back(lease.account);
+    }
+
+    /// Nonce accounts in the pool, not lent out.
+    pub fn available_nonces(&self) -> usize {
+        self.nonces.lock().unwrap().len()
+    }
+
+    fn fresh(&self) -> Option<RecentBlockhash> {
+        self.current().filter(|recent| recent.age() < self.max_age)
+    }
+
+    async fn fetch(&self, client: &JupiterClient) -> Result<RecentBlockhash, JupiterClientError> {
+        let response: RpcContextValue<RpcBlockhash> = client
+            .rpc_call(
+                &self.rpc_url,
+                "getLatestBlockhash",
+                json!([{ "commitment": self.commitment }]),
+            )
+            .await?
+            .ok_or_else(|| {
+                JupiterClientError::deserialization(
+                    "unexpected getLatestBlockhash response".to_string(),
+                )
+            })?;
+        let recent = RecentBlockhash {
+            blockhash: Hash::from_str(&response.value.blockhash).map_err(|e| {
+                JupiterClientError::deserialization(format!("invalid blockhash: {e}"))
+            })?,
+            last_valid_block_height: response.value.last_valid_block_height,
+            fetched_at: Instant::now(),
+        };
+        *self.current.lock().unwrap() = Some(recent);
+        Ok(recent)
+    }
+
+    async fn read_nonce(
+        &self,
+        client: &JupiterClient,
+        account: Pubkey,
+    ) -> Result<NonceLease, JupiterClientError> {
+        let address = account.to_string();
+        let data = match client
+            .get_multiple_accounts(&self.rpc_url, &[&address])
+            .await?
+            .pop()
+            .flatten()
+        {
+            Some(account) => account.data()?,
+            None => {
+                return Err(JupiterClientError::ValidationError(format!(
+                    "nonce account {address} not found"
+                )));
+            }
+        };
+
+        // version, then state, which is 1 once the nonce is initialized
+        if data.len() < NONCE_ACCOUNT_SIZE || data[4..8] != 1u32.to_le_bytes() {
+            return Err(JupiterClientError::ValidationError(format!(
+                "{address} is not an initialized nonce account"
+            )));
+        }
+        Ok(NonceLease {
+            account,
+            authority: Pubkey::try_from(&data[8..40]).expect("slice is 32 bytes"),
+            nonce: Hash::new_from_array(data[40..72].try_into().expect("slice is 32 bytes")),
+        })
+    }
+}
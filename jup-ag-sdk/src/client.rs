@@ -1,14 +1,124 @@
-use reqwest::{
-    Client,
-    header::{HeaderMap, HeaderValue},
+use std::{
+    collections::HashMap,
+    pin::pin,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    time::Duration,
 };
 
+use futures_util::future::{self, Either, select};
+use reqwest::{Client, Method, Request, RequestBuilder, header::HeaderMap};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    amm_blacklist::AmmBlacklist,
+    config::{ApiFamily, ApiPaths, HedgeConfig, RateLimitPlan},
+    error::{self, ErrorContext, JupiterClientError, RequestSummary},
+    events::LifecycleEvent,
+    json,
+    runtime::{Instant, Runtime, SharedRuntime},
+    types::{DexRegistry, ExecutionReport, ExecutionStats, TokenMetadataCache},
+};
+
+pub use builder::JupiterClientBuilder;
+#[cfg(feature = "solana")]
+pub use decimals::RpcDecimals;
+pub use decimals::{DecimalsFuture, DecimalsResolver, Or, StaticDecimals, TokensApiDecimals};
+pub use dry_run::DryRun;
+pub(crate) use executions::transaction_signature;
+pub use failover::{Captured, ResponseMeta};
+pub use fallback::{CachedResponse, Freshness};
+pub use hooks::RequestParts;
+pub use latency::{EndpointLatency, LatencyHistogram};
+pub use rate_limit::RateLimitInfo;
+pub use request_options::{CancellationToken, RequestOptions, Scoped};
+/// DNS resolution types for [`JupiterClientBuilder::dns_resolver`].
+#[cfg(not(target_arch = "wasm32"))]
+pub use reqwest::dns;
+pub(crate) use signing::hmac_sha256_hex;
+pub use signing::{
+    DEFAULT_SIGNATURE_HEADER, DEFAULT_TIMESTAMP_HEADER, HmacAlgorithm, RequestSigner, SigningInput,
+};
+pub use stats::ClientStats;
+pub use transport::{HttpResponse, HttpTransport, ReqwestTransport, TransportFuture};
+
 /// `JupiterClient` is a client wrapper to interact with the Jupiter Aggregator APIs.
 /// It is your gateway to interact with the Jupiter exchange API
-#[derive(Debug)]
+///
+/// Clones are cheap and share everything but the public fields: the connection pool,
+/// settings, caches, rate and concurrency limits, circuit breakers and stats. Clone it
+/// into each task or handler instead of wrapping it in an `Arc`; the client is `Send` and
+/// `Sync`.
+///
+/// # Example
+/// ```
+/// let client = JupiterClient::new("https://lite-api.jup.ag");
+/// let background = client.clone();
+/// tokio::spawn(async move { background.routers().await });
+/// let quote = client.get_quote(&request).await?;
+/// ```
+#[derive(Debug, Clone)]
 pub struct JupiterClient {
     pub client: Client,
     pub base_url: String,
+    /// Path prefixes for each API family, see [`ApiPaths`].
+    pub paths: ApiPaths,
+    shared: Arc<ClientState>,
+}
+
+/// The state of a [`JupiterClient`], shared by its clones.
+#[derive(Debug)]
+struct ClientState {
+    /// Settings the client was built with, reused when `with_*` methods rebuild it.
+    builder: JupiterClientBuilder,
+    concurrency: limits::ConcurrencyLimits,
+    rate_limiter: Option<rate_limit::RateLimiter>,
+    /// The latest [`RateLimitInfo`] of each family.
+    rate_limit_info: Mutex<HashMap<ApiFamily, RateLimitInfo>>,
+    fallback: Option<fallback::FallbackCache>,
+    circuit_breaker: Option<circuit_breaker::CircuitBreaker>,
+    quote_cache: Option<quote_cache::QuoteCache>,
+    /// The highest slot seen, from quotes or [`observe_slot`](JupiterClient::observe_slot).
+    latest_slot: AtomicU64,
+    executions: executions::ExecutionLog,
+    /// Set by [`pause_execution`](JupiterClient::pause_execution).
+    execution_paused: AtomicBool,
+    amm_blacklist: Option<Arc<AmmBlacklist>>,
+    token_metadata: TokenMetadataCache,
+    /// Fetched by [`dex_registry`](JupiterClient::dex_registry); locked while fetching.
+    dex_registry: tokio::sync::Mutex<Option<Arc<DexRegistry>>>,
+    decimals: decimals::SharedDecimalsResolver,
+    transport: transport::SharedTransport,
+    /// For hosts other than Jupiter's, such as Solana RPCs and webhooks: same connection
+    /// settings, but without the default headers or the API key.
+    external: Client,
+    /// Sent with every request unless already set, e.g. `x-api-key`.
+    headers: HeaderMap,
+    runtime: SharedRuntime,
+    stats: stats::StatsCounters,
+    execution_stats: Mutex<ExecutionStats>,
+    latency: latency::LatencyTracker,
+}
+
+// clients are shared across tasks and threads, so they must stay `Send` and `Sync`
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<JupiterClient>();
+};
+
+/// A client for [`LITE_API_URL`](crate::config::LITE_API_URL), or the host and API key
+/// set in the environment, see [`JupiterClientBuilder::from_env`].
+///
+/// # Panics
+/// Panics if `JUP_API_KEY` is not a valid header value.
+impl Default for JupiterClient {
+    fn default() -> Self {
+        JupiterClientBuilder::from_env()
+            .build()
+            .expect("Failed to build client from the environment")
+    }
 }
 
 impl JupiterClient {
@@ -24,20 +134,27 @@ impl JupiterClient {
     /// let api = JupiterClient::new("https://lite-api.jup.ag");
     /// ```
     pub fn new(base_url: &str) -> Self {
-        let mut headers = HeaderMap::new();
-        headers.insert("Accept", "application/json".parse().unwrap());
-        headers.insert("Content-Type", "application/json".parse().unwrap());
-
-        let client = Client::builder()
-            .default_headers(headers)
+        JupiterClientBuilder::new(base_url)
             .build()
-            .expect("Failed to build client with API key");
+            .expect("Failed to build client")
+    }
 
-        // let client = Client::new();
-        JupiterClient {
-            client,
-            base_url: base_url.to_string(),
-        }
+    /// Returns a [`JupiterClientBuilder`] for configuring connection settings
+    /// such as TCP and HTTP/2 keep-alive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use jup_ag_sdk::JupiterClient;
+    ///
+    /// let api = JupiterClient::builder("https://lite-api.jup.ag")
+    ///     .tcp_keepalive(Duration::from_secs(30))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder(base_url: &str) -> JupiterClientBuilder {
+        JupiterClientBuilder::new(base_url)
     }
 
     /// Returns a new JupiterClient with the API key set in headers.
@@ -52,26 +169,716 @@ impl JupiterClient {
     /// let api = JupiterClient::new("https://api.jup.ag").with_api_key('your-api-key');
     /// ```
     pub fn with_api_key(self, api_key: &str) -> Self {
-        let mut headers = HeaderMap::new();
-        headers.insert("x-api-key", HeaderValue::from_str(api_key).unwrap());
-        headers.insert("Accept", "application/json".parse().unwrap());
-        headers.insert("Content-Type", "application/json".parse().unwrap());
+        // base_url and paths are public, so take them from the client in case they were changed
+        JupiterClientBuilder {
+            base_url: self.base_url,
+            paths: self.paths,
+            ..self.shared.builder.clone()
+        }
+        .api_key(api_key)
+        .build()
+        .expect("Failed to build client with API key")
+    }
 
-        let client = Client::builder()
-            .default_headers(headers)
-            .build()
-            .expect("Failed to build client with API key");
+    /// Returns a new JupiterClient using the given path prefixes instead of the defaults
+    /// (`/swap/v1`, `/ultra/v1`, `/price/v2`, ...).
+    ///
+    /// Useful for self-hosted deployments and gateways that mount the APIs elsewhere.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jup_ag_sdk::{JupiterClient, config::ApiPaths};
+    ///
+    /// let api = JupiterClient::new("https://jupiter.internal")
+    ///     .with_paths(ApiPaths::default().swap("/swap").ultra("/ultra"));
+    /// ```
+    pub fn with_paths(self, paths: ApiPaths) -> Self {
+        JupiterClient { paths, ..self }
+    }
 
-        JupiterClient {
-            client,
-            base_url: self.base_url,
+    /// Builds the full URL for `path` within the given API family,
+    /// e.g. `endpoint(ApiFamily::Swap, "/quote")` -> `https://lite-api.jup.ag/swap/v1/quote`.
+    /// Perps API URLs are on the [`perps_base_url`](JupiterClientBuilder::perps_base_url).
+    pub fn endpoint(&self, family: ApiFamily, path: &str) -> String {
+        let base_url = match family {
+            ApiFamily::Perps => &self.shared.builder.perps_base_url,
+            _ => &self.base_url,
+        };
+        format!(
+            "{}{}{}",
+            base_url.trim_end_matches('/'),
+            self.paths.get(family),
+            path
+        )
+    }
+
+    /// Opens and, for HTTPS, TLS-handshakes connections to the Jupiter hosts ahead of time,
+    /// so the first real request of a session doesn't pay connection setup latency.
+    ///
+    /// Sends a `HEAD` request to every distinct host the client may use, concurrently: the
+    /// base URL and the [`failover`](JupiterClientBuilder::failover),
+    /// [`hedge`](JupiterClientBuilder::hedge) and, with the `perps` feature,
+    /// [`perps`](JupiterClientBuilder::perps_base_url) base URLs. Any HTTP status counts as
+    /// success since only the connection matters; if a host can't be reached, the first
+    /// such error is returned once every host was tried. It bypasses rate limits,
+    /// concurrency limits and request hooks.
+    /// Combine with [`JupiterClientBuilder::http2_keep_alive_while_idle`] or
+    /// [`JupiterClientBuilder::tcp_keepalive`] to keep the warmed connections open.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag");
+    /// api.warm_up().await?;
+    /// ```
+    pub async fn warm_up(&self) -> Result<(), JupiterClientError> {
+        let builder = &self.shared.builder;
+        let mut urls = vec![self.base_url.as_str()];
+        urls.extend(builder.failover.as_ref().map(|f| f.base_url.as_str()));
+        urls.extend(builder.hedge.as_ref().map(|h| h.base_url.as_str()));
+        if cfg!(feature = "perps") {
+            urls.push(&builder.perps_base_url);
+        }
+
+        // one connection per scheme, host and port
+        let mut origins = Vec::new();
+        urls.retain(|url| {
+            let origin = reqwest::Url::parse(url)
+                .map(|url| url.origin().ascii_serialization())
+                .unwrap_or_else(|_| url.to_string());
+            let new = !origins.contains(&origin);
+            origins.push(origin);
+            new
+        });
+
+        let results =
+            future::join_all(urls.into_iter().map(|url| self.client.head(url).send())).await;
+        match results.into_iter().find_map(Result::err) {
+            Some(e) => Err(JupiterClientError::RequestError(e)),
+            None => Ok(()),
         }
     }
+
+    /// Number of requests to `family` that can start right now without waiting,
+    /// or `None` if no concurrency limit applies to it.
+    pub fn available_request_slots(&self, family: ApiFamily) -> Option<usize> {
+        self.shared.concurrency.available(family)
+    }
+
+    /// The rate limit currently enforced, if one was configured.
+    ///
+    /// This reflects adjustments made from `x-ratelimit-limit` response headers,
+    /// in which case it is returned as [`RateLimitPlan::Custom`].
+    pub fn rate_limit(&self) -> Option<RateLimitPlan> {
+        self.shared
+            .rate_limiter
+            .as_ref()
+            .map(|limiter| limiter.plan())
+    }
+
+    /// The rate limit headers of the latest response from `family`, `None` before the
+    /// first or if the API sent none.
+    ///
+    /// Available whether or not the client enforces a
+    /// [`rate_limit`](JupiterClientBuilder::rate_limit) itself, to pace requests from the
+    /// allowance the API reports.
+    pub fn rate_limit_info(&self, family: ApiFamily) -> Option<RateLimitInfo> {
+        self.shared
+            .rate_limit_info
+            .lock()
+            .unwrap()
+            .get(&family)
+            .copied()
+    }
+
+    /// Reports the latest slot seen by the application, e.g. from an RPC slot
+    /// subscription, so quotes computed too many slots ago stop being served from the
+    /// [`quote_cache`](JupiterClientBuilder::quote_cache) and are caught by
+    /// [`quote_freshness`](JupiterClientBuilder::quote_freshness).
+    pub fn observe_slot(&self, slot: u64) {
+        self.shared.latest_slot.fetch_max(slot, Ordering::Relaxed);
+        if let Some(cache) = &self.shared.quote_cache {
+            cache.observe_slot(slot);
+        }
+    }
+
+    /// The blacklist set with [`JupiterClientBuilder::amm_blacklist`], if any.
+    pub fn amm_blacklist(&self) -> Option<&AmmBlacklist> {
+        self.shared.amm_blacklist.as_deref()
+    }
+
+    /// Drops every quote in the [`quote_cache`](JupiterClientBuilder::quote_cache).
+    pub fn clear_quote_cache(&self) {
+        if let Some(cache) = &self.shared.quote_cache {
+            cache.clear();
+        }
+    }
+
+    /// The runtime the client sleeps and spawns background tasks on,
+    /// see [`JupiterClientBuilder::runtime`].
+    pub fn runtime(&self) -> &dyn Runtime {
+        &*self.shared.runtime.0
+    }
+
+    /// The HTTP client for hosts other than Jupiter's, which must not get the API key.
+    pub(crate) fn external_client(&self) -> &Client {
+        &self.shared.external
+    }
+
+    /// Returns counters for requests, retries, rate limit and concurrency waits,
+    /// circuit breaker trips, failovers and stale fallback responses.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let stats = api.stats();
+    /// println!("{} requests, {} rate limited", stats.requests, stats.rate_limit_waits);
+    /// ```
+    pub fn stats(&self) -> ClientStats {
+        self.shared.stats.snapshot()
+    }
+
+    /// The response times of the endpoint at URL `path` of `family`, e.g.
+    /// `/swap/v1/quote`, `None` if it wasn't called yet.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let quotes = api.latency(ApiFamily::Swap, "/swap/v1/quote").unwrap();
+    /// println!("p99 {:?} over {} quotes", quotes.percentile(0.99), quotes.count());
+    /// ```
+    pub fn latency(&self, family: ApiFamily, path: &str) -> Option<LatencyHistogram> {
+        self.shared.latency.get(family, path)
+    }
+
+    /// The response times of every endpoint called, sorted by family and path.
+    pub fn latencies(&self) -> Vec<EndpointLatency> {
+        self.shared.latency.all()
+    }
+
+    /// Fill rate and realized slippage per router and DEX of the swaps executed with
+    /// [`ultra_execute_with_report`](Self::ultra_execute_with_report) and those added with
+    /// [`record_execution`](Self::record_execution).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let excluded = api.execution_stats().routers_to_exclude(20, 0.9, 25.0);
+    /// let request = UltraOrderRequest::new(input_mint, output_mint, amount).exclude_routers(excluded);
+    /// ```
+    pub fn execution_stats(&self) -> ExecutionStats {
+        self.shared.execution_stats.lock().unwrap().clone()
+    }
+
+    /// Adds `report` to the [`execution_stats`](Self::execution_stats), e.g. a Swap API
+    /// swap reported with [`ExecutionReport::from_swap`].
+    pub fn record_execution(&self, report: &ExecutionReport) {
+        self.shared.execution_stats.lock().unwrap().record(report);
+    }
+
+    /// Sends `request` and decodes the JSON response.
+    pub(crate) async fn send_request<T: DeserializeOwned>(
+        &self,
+        family: ApiFamily,
+        request: RequestBuilder,
+    ) -> Result<T, JupiterClientError> {
+        let request = request.build()?;
+        let (method, path) = (request.method().clone(), request.url().path().to_string());
+        let summary = self.summarize(&request);
+        let started = Instant::now();
+
+        let (result, attempt) = self.fetch(family, request).await;
+        let result = match result {
+            Ok(mut response) => {
+                let request_id = error::request_id(&response.headers);
+                self.decode::<T>(&method, &path, &mut response.body, request_id)
+            }
+            Err(error) => Err(error),
+        };
+
+        self.report_error(family, &method, &path, attempt, started, summary, result)
+    }
+
+    /// Like [`send_request`](Self::send_request), but remembers successful responses
+    /// and serves the last one, marked stale, when the API is unreachable.
+    ///
+    /// Without [`JupiterClientBuilder::offline_fallback`] this never serves stale data.
+    pub(crate) async fn send_cached_request<T: DeserializeOwned>(
+        &self,
+        family: ApiFamily,
+        request: RequestBuilder,
+    ) -> Result<CachedResponse<T>, JupiterClientError> {
+        let request = request.build()?;
+        let (method, path) = (request.method().clone(), request.url().path().to_string());
+        let summary = self.summarize(&request);
+        let started = Instant::now();
+        let key = request.url().to_string();
+
+        let (result, attempt) = self.fetch(family, request).await;
+        let result = match result {
+            Ok(response) => {
+                let request_id = error::request_id(&response.headers);
+                let body = response.body;
+                self.decode::<T>(&method, &path, &mut body.clone(), request_id)
+                    .map(|data| {
+                        if let Some(cache) = &self.shared.fallback {
+                            cache.store(key, body);
+                        }
+                        CachedResponse {
+                            data,
+                            freshness: Freshness::Fresh,
+                        }
+                    })
+            }
+            Err(error) if fallback::is_outage(&error) => {
+                match self
+                    .shared
+                    .fallback
+                    .as_ref()
+                    .and_then(|cache| cache.get(&key))
+                {
+                    Some((age, mut body)) => {
+                        self.decode::<T>(&method, &path, &mut body, None)
+                            .map(|data| {
+                                stats::StatsCounters::incr(&self.shared.stats.stale_responses);
+                                CachedResponse {
+                                    data,
+                                    freshness: Freshness::Stale { age, cause: error },
+                                }
+                            })
+                    }
+                    None => Err(error),
+                }
+            }
+            Err(error) => Err(error),
+        };
+
+        self.report_error(family, &method, &path, attempt, started, summary, result)
+    }
+
+    /// A summary of `request` to attach to errors, if request context is enabled.
+    fn summarize(&self, request: &Request) -> Option<RequestSummary> {
+        self.shared
+            .builder
+            .request_context_in_errors
+            .then(|| RequestSummary::from_request(request))
+    }
+
+    /// Attaches the request summary to a failed `result` and passes it to the
+    /// `on_error` callback, if one is registered, with the `attempt` that failed and the
+    /// time since the call `started`.
+    #[allow(clippy::too_many_arguments)]
+    fn report_error<T>(
+        &self,
+        family: ApiFamily,
+        method: &Method,
+        path: &str,
+        attempt: u32,
+        started: Instant,
+        summary: Option<RequestSummary>,
+        result: Result<T, JupiterClientError>,
+    ) -> Result<T, JupiterClientError> {
+        let result = match (result, summary) {
+            (Err(error), Some(summary)) => Err(JupiterClientError::WithRequest {
+                request: Box::new(summary),
+                source: Box::new(error),
+            }),
+            (result, _) => result,
+        };
+        if let (Err(error), Some(on_error)) = (&result, &self.shared.builder.on_error) {
+            on_error.call(&ErrorContext::new(
+                family,
+                method,
+                path,
+                attempt,
+                started.elapsed(),
+                error,
+            ));
+        }
+        result
+    }
+
+    /// Parses the body of a response to a `method` request to `path`, rejecting unknown
+    /// fields with [`strict_deserialization`](JupiterClientBuilder::strict_deserialization).
+    /// Errors carry the method, the path, the start of the body and the response's
+    /// `request_id`.
+    fn decode<T: DeserializeOwned>(
+        &self,
+        method: &Method,
+        path: &str,
+        body: &mut [u8],
+        request_id: Option<String>,
+    ) -> Result<T, JupiterClientError> {
+        // taken before parsing, as simd-json parses in place
+        let excerpt = error::body_excerpt(body);
+        let result = if self.shared.builder.strict_deserialization {
+            json::from_slice_strict(body)
+        } else {
+            json::from_slice(body)
+        };
+        result.map_err(|e| e.with_response(Some(method), path, excerpt, request_id))
+    }
+
+    /// Passes the event built by `event` to every subscriber, building it only if there
+    /// are any.
+    pub(crate) fn emit(&self, event: impl FnOnce() -> LifecycleEvent) {
+        if self.shared.builder.event_subscribers.is_empty() {
+            return;
+        }
+        let event = event();
+        for subscriber in &self.shared.builder.event_subscribers {
+            subscriber.0.on_event(&event);
+        }
+    }
+
+    /// Emits the outcome of an `/execute` call: `outcome` maps a response to the
+    /// transaction signature on success, or to the reported error.
+    pub(crate) fn emit_execution<T>(
+        &self,
+        family: ApiFamily,
+        request_id: &str,
+        result: &Result<T, JupiterClientError>,
+        outcome: impl FnOnce(&T) -> Result<Option<String>, String>,
+    ) {
+        self.emit(|| {
+            let request_id = request_id.to_string();
+            match result.as_ref().map_err(|e| e.to_string()).and_then(outcome) {
+                Ok(signature) => LifecycleEvent::ExecutionConfirmed {
+                    family,
+                    request_id,
+                    signature,
+                },
+                Err(error) => LifecycleEvent::ExecutionFailed {
+                    family,
+                    request_id,
+                    error,
+                },
+            }
+        });
+    }
+
+    /// Sends `request` and returns a successful response, retrying rate
+    /// limited requests as configured with
+    /// [`retry_rate_limited`](JupiterClientBuilder::retry_rate_limited). Fails fast while
+    /// the endpoint's [`circuit_breaker`](JupiterClientBuilder::circuit_breaker) is open.
+    ///
+    /// Also returns the number of the last attempt, starting at 1.
+    ///
+    /// With the `otel` feature, the exchange is recorded as an OpenTelemetry client span
+    /// and its trace context is sent along in the request headers.
+    async fn fetch(
+        &self,
+        family: ApiFamily,
+        mut request: Request,
+    ) -> (Result<HttpResponse, JupiterClientError>, u32) {
+        let (method, path) = (request.method().clone(), request.url().path().to_string());
+        if let Some(breaker) = &self.shared.circuit_breaker
+            && let Err(error) = breaker.check(family, &path)
+        {
+            return (Err(error), 1);
+        }
+        #[cfg(feature = "otel")]
+        let span = otel::CallSpan::start(family, &mut request);
+
+        let mut retries = 0;
+        let result = RequestOptions::cancellable(async {
+            loop {
+                // bodies are in memory, so only streamed ones can't be retried
+                let retry = (retries < self.shared.builder.rate_limit_retries)
+                    .then(|| request.try_clone())
+                    .flatten();
+
+                let response = self.routed_exchange(family, request).await;
+                let result = response.and_then(|response| {
+                    #[cfg(feature = "otel")]
+                    span.record_status(response.status);
+
+                    if !response.status.is_success() {
+                        return Err(error::status_error(
+                            response.status,
+                            Some(&method),
+                            &path,
+                            &response.headers,
+                            String::from_utf8_lossy(&response.body).into_owned(),
+                        ));
+                    }
+                    Ok(response)
+                });
+
+                let retry_after = match &result {
+                    Err(JupiterClientError::RateLimited { retry_after, .. }) => Some(*retry_after),
+                    _ => None,
+                };
+                match (retry_after, retry) {
+                    (Some(wait), Some(next)) if wait <= self.shared.builder.max_retry_after => {
+                        self.shared.runtime.0.sleep(wait).await;
+                        stats::StatsCounters::incr(&self.shared.stats.retries);
+                        retries += 1;
+                        request = next;
+                    }
+                    _ => break result,
+                }
+            }
+        })
+        .await;
+
+        // a cancelled call says nothing about the endpoint's health
+        if let Some(breaker) = &self.shared.circuit_breaker
+            && !matches!(result, Err(JupiterClientError::Cancelled))
+            && let Some(cool_down) = breaker.record(family, &path, &result)
+        {
+            stats::StatsCounters::incr(&self.shared.stats.circuit_breaker_trips);
+            self.emit(|| LifecycleEvent::CircuitOpened {
+                family,
+                path,
+                cool_down,
+            });
+        }
+        #[cfg(feature = "otel")]
+        span.end(&result);
+        (result, retries + 1)
+    }
+
+    /// Adds a response time to the endpoint's histogram, and reports it if it exceeds the
+    /// slow call threshold.
+    fn record_latency(&self, family: ApiFamily, method: Method, path: String, latency: Duration) {
+        self.shared.latency.record(family, &path, latency);
+
+        let threshold = self
+            .shared
+            .builder
+            .slow_call_thresholds
+            .get(&family)
+            .or(self.shared.builder.slow_call_threshold.as_ref());
+        if let Some(&threshold) = threshold
+            && latency > threshold
+        {
+            self.emit(|| LifecycleEvent::SlowCall {
+                family,
+                method,
+                path,
+                latency,
+                threshold,
+            });
+        }
+    }
+
+    /// Sends `request` to the client's host, hedged if configured, and to the failover host
+    /// if that fails with a server error or a timeout.
+    async fn routed_exchange(
+        &self,
+        family: ApiFamily,
+        request: Request,
+    ) -> Result<HttpResponse, JupiterClientError> {
+        let failover = self
+            .shared
+            .builder
+            .failover
+            .as_ref()
+            .filter(|failover| failover.applies(family, request.method()))
+            .and_then(|failover| {
+                let request = rebase_request(&request, &self.base_url, &failover.base_url)?;
+                Some((failover, request))
+            });
+        let path = request.url().path().to_string();
+
+        failover::record(&self.base_url, false);
+        let result = match &self.shared.builder.hedge {
+            Some(hedge) if hedge.applies(family, request.method()) => {
+                self.hedged_exchange(family, request, hedge).await
+            }
+            _ => self.exchange(family, request).await,
+        };
+        match failover {
+            Some((failover, request)) if failover::should_fail_over(&result) => {
+                stats::StatsCounters::incr(&self.shared.stats.failovers);
+                self.emit(|| LifecycleEvent::FailedOver {
+                    family,
+                    path,
+                    base_url: failover.base_url.clone(),
+                });
+                failover::record(&failover.base_url, true);
+                self.exchange(family, request).await
+            }
+            _ => result,
+        }
+    }
+
+    /// Sends `request`, and again to the hedge base URL if it hasn't completed after the
+    /// hedge delay, returning the first successful response. The slower request is
+    /// dropped; if both fail, the first request's outcome is returned.
+    async fn hedged_exchange(
+        &self,
+        family: ApiFamily,
+        request: Request,
+        hedge: &HedgeConfig,
+    ) -> Result<HttpResponse, JupiterClientError> {
+        let Some(hedged) = rebase_request(&request, &self.base_url, &hedge.base_url) else {
+            return self.exchange(family, request).await;
+        };
+        let succeeded = |result: &Result<HttpResponse, JupiterClientError>| matches!(result, Ok(response) if response.status.is_success());
+
+        let primary = Box::pin(self.exchange(family, request));
+        let primary = match select(primary, self.shared.runtime.0.sleep(hedge.delay)).await {
+            Either::Left((result, _)) => return result,
+            Either::Right((_, primary)) => primary,
+        };
+
+        stats::StatsCounters::incr(&self.shared.stats.hedges);
+        let secondary = Box::pin(self.exchange(family, hedged));
+        match select(primary, secondary).await {
+            Either::Left((result, _)) if succeeded(&result) => result,
+            Either::Left((result, secondary)) => {
+                let hedged = secondary.await;
+                if succeeded(&hedged) {
+                    stats::StatsCounters::incr(&self.shared.stats.hedge_wins);
+                    failover::record(&hedge.base_url, false);
+                    return hedged;
+                }
+                result
+            }
+            Either::Right((hedged, _)) if succeeded(&hedged) => {
+                stats::StatsCounters::incr(&self.shared.stats.hedge_wins);
+                failover::record(&hedge.base_url, false);
+                hedged
+            }
+            Either::Right((_, primary)) => primary.await,
+        }
+    }
+
+    /// Sends `request` through the transport, waiting first for a free slot if a
+    /// concurrency limit is configured for `family` and for the rate limit. The request
+    /// is signed last, once hooks and default headers have been applied.
+    async fn exchange(
+        &self,
+        family: ApiFamily,
+        mut request: Request,
+    ) -> Result<HttpResponse, JupiterClientError> {
+        let waiting = async {
+            // dry runs send nothing, so they take no slot
+            if dry_run::active() {
+                return None;
+            }
+            let permits = self.shared.concurrency.acquire(family).await;
+            if permits.waited {
+                stats::StatsCounters::incr(&self.shared.stats.concurrency_waits);
+            }
+            if let Some(limiter) = &self.shared.rate_limiter {
+                self.shared
+                    .stats
+                    .record_rate_limit_wait(limiter.acquire(&*self.shared.runtime.0).await);
+            }
+            Some(permits)
+        };
+        // held until the body has been read
+        let permits = match RequestOptions::current_deadline() {
+            // no use waiting past the deadline, the request would fail anyway
+            Some(deadline) => {
+                let expired = (self.shared.runtime.0)
+                    .sleep(deadline.saturating_duration_since(Instant::now()));
+                match select(pin!(waiting), expired).await {
+                    Either::Left((permits, _)) => permits,
+                    Either::Right(_) => return Err(JupiterClientError::Cancelled),
+                }
+            }
+            None => waiting.await,
+        };
+
+        if !self.shared.builder.request_hooks.is_empty() {
+            hooks::apply(&self.shared.builder.request_hooks, family, &mut request)?;
+        }
+
+        if let Some(options) = RequestOptions::current() {
+            options.apply(&mut request)?;
+        }
+        // fetch has no client-wide timeout, so each request carries it
+        #[cfg(target_arch = "wasm32")]
+        if request.timeout().is_none() {
+            *request.timeout_mut() = self.shared.builder.timeout;
+        }
+        for (name, value) in &self.shared.headers {
+            if !request.headers().contains_key(name) {
+                request.headers_mut().insert(name, value.clone());
+            }
+        }
+        if let Some(signer) = &self.shared.builder.request_signer {
+            signer.apply(&mut request)?;
+        }
+        let Some(request) = dry_run::hold(request) else {
+            // the dry run returns the request and drops the call
+            return std::future::pending().await;
+        };
+
+        stats::StatsCounters::incr(&self.shared.stats.requests);
+        let (method, path) = (request.method().clone(), request.url().path().to_string());
+        let started = Instant::now();
+        let response = self.shared.transport.0.execute(request).await;
+        drop(permits);
+        self.record_latency(family, method, path, started.elapsed());
+        let response = response?;
+
+        if let Some(info) = RateLimitInfo::from_headers(&response.headers) {
+            if let Some(limiter) = &self.shared.rate_limiter {
+                limiter.observe(&info);
+            }
+            self.shared
+                .rate_limit_info
+                .lock()
+                .unwrap()
+                .insert(family, info);
+        }
+
+        Ok(response)
+    }
+}
+
+/// A copy of `request` sent to `other_base_url` instead of `base_url`, `None` if its URL
+/// isn't under `base_url` or it can't be copied.
+fn rebase_request(request: &Request, base_url: &str, other_base_url: &str) -> Option<Request> {
+    let path = request
+        .url()
+        .as_str()
+        .strip_prefix(base_url.trim_end_matches('/'))?;
+    let url = format!("{}{path}", other_base_url.trim_end_matches('/'))
+        .parse()
+        .ok()?;
+    let mut hedged = request.try_clone()?;
+    *hedged.url_mut() = url;
+    Some(hedged)
 }
 
 // Include all the API method implementations
+mod builder;
+mod circuit_breaker;
+mod decimals;
+mod dex_registry;
+mod dry_run;
+mod executions;
+mod failover;
+mod fallback;
+mod hooks;
+mod latency;
+mod limits;
+#[cfg(feature = "otel")]
+mod otel;
+mod pagination;
+#[cfg(feature = "perps")]
+mod perps_api;
+mod quote_cache;
+mod rate_limit;
 mod recurring_api;
+#[cfg(feature = "solana")]
+mod referral_api;
+mod request_options;
+mod signing;
+#[cfg(feature = "solana")]
+mod solana_rpc;
+mod stats;
 mod swap_api;
+#[cfg(feature = "solana")]
+mod swap_history;
 mod token_api;
+mod transport;
 mod trigger_api;
 mod ultra_api;
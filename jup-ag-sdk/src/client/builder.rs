@@ -0,0 +1,970 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::net::SocketAddr;
+use std::{collections::HashMap, fmt, sync::Arc, time::Duration};
+
+use reqwest::{
+    Client,
+    header::{self, HeaderMap, HeaderName, HeaderValue},
+};
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::{
+    NoProxy, Proxy,
+    dns::{Name, Resolve, Resolving},
+};
+
+use super::{
+    ClientState, DecimalsResolver, JupiterClient, RequestParts, RequestSigner,
+    circuit_breaker::CircuitBreaker,
+    decimals::SharedDecimalsResolver,
+    fallback::FallbackCache,
+    hooks::{ErrorCallback, RequestHook},
+    limits::ConcurrencyLimits,
+    quote_cache::QuoteCache,
+    rate_limit::RateLimiter,
+    stats::StatsCounters,
+    transport::{HttpTransport, ReqwestTransport, SharedTransport},
+};
+use crate::{
+    amm_blacklist::AmmBlacklist,
+    config::{
+        API_KEY_ENV, ApiFamily, ApiPaths, BASE_URL_ENV, CircuitBreakerConfig, FailoverConfig,
+        HedgeConfig, LITE_API_URL, PERPS_API_URL, PRO_API_URL, ProxyConfig, QuoteCacheConfig,
+        QuoteFreshness, RateLimitPlan, USER_AGENT,
+    },
+    error::{ErrorContext, JupiterClientError},
+    events::{EventSubscriber, SharedSubscriber},
+    runtime::{Runtime, SharedRuntime},
+    types::{DEFAULT_TOKEN_METADATA_CAPACITY, TokenMetadataCache},
+};
+
+/// Builder for a [`JupiterClient`] with custom connection settings.
+///
+/// Start from [`JupiterClient::builder`]. Anything not set keeps reqwest's defaults.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use jup_ag_sdk::JupiterClient;
+///
+/// let client = JupiterClient::builder("https://api.jup.ag")
+///     .api_key("your-api-key")
+///     .tcp_keepalive(Duration::from_secs(30))
+///     .pool_max_idle_per_host(32)
+///     .pool_idle_timeout(Some(Duration::from_secs(300)))
+///     .http2_keep_alive_interval(Duration::from_secs(20))
+///     .http2_keep_alive_timeout(Duration::from_secs(5))
+///     .http2_keep_alive_while_idle(true)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct JupiterClientBuilder {
+    pub(crate) base_url: String,
+    pub(crate) perps_base_url: String,
+    pub(crate) api_key: Option<ApiKey>,
+    pub(crate) paths: ApiPaths,
+    pub(crate) http_client: Option<Client>,
+    pub(crate) default_headers: Vec<(String, String)>,
+    pub(crate) user_agent_suffix: Option<String>,
+    pub(crate) connect_timeout: Option<Duration>,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) tcp_keepalive: Option<Duration>,
+    pub(crate) pool_max_idle_per_host: Option<usize>,
+    pub(crate) pool_idle_timeout: Option<Option<Duration>>,
+    pub(crate) http2_keep_alive_interval: Option<Duration>,
+    pub(crate) http2_keep_alive_timeout: Option<Duration>,
+    pub(crate) http2_keep_alive_while_idle: bool,
+    pub(crate) http2_adaptive_window: bool,
+    pub(crate) proxy: Option<ProxyConfig>,
+    pub(crate) max_concurrent_requests: Option<usize>,
+    pub(crate) max_concurrent_requests_per_family: HashMap<ApiFamily, usize>,
+    pub(crate) slow_call_threshold: Option<Duration>,
+    pub(crate) slow_call_thresholds: HashMap<ApiFamily, Duration>,
+    pub(crate) rate_limit: Option<RateLimitPlan>,
+    pub(crate) rate_limit_retries: u32,
+    pub(crate) max_retry_after: Duration,
+    pub(crate) offline_fallback: Option<Duration>,
+    pub(crate) circuit_breaker: Option<CircuitBreakerConfig>,
+    pub(crate) quote_cache: Option<QuoteCacheConfig>,
+    pub(crate) quote_freshness: Option<QuoteFreshness>,
+    pub(crate) hedge: Option<HedgeConfig>,
+    pub(crate) failover: Option<FailoverConfig>,
+    pub(crate) amm_blacklist: Option<Arc<AmmBlacklist>>,
+    pub(crate) token_metadata_capacity: usize,
+    pub(crate) decimals_resolver: SharedDecimalsResolver,
+    pub(crate) runtime: Option<SharedRuntime>,
+    pub(crate) transport: Option<SharedTransport>,
+    #[cfg(feature = "tower")]
+    pub(crate) service_layer: Option<super::transport::SharedLayer>,
+    pub(crate) request_hooks: Vec<RequestHook>,
+    pub(crate) request_signer: Option<RequestSigner>,
+    pub(crate) event_subscribers: Vec<SharedSubscriber>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) dns_overrides: Vec<(String, Vec<SocketAddr>)>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) dns_resolver: Option<SharedResolver>,
+    pub(crate) on_error: Option<ErrorCallback>,
+    pub(crate) request_context_in_errors: bool,
+    pub(crate) strict_deserialization: bool,
+}
+
+impl JupiterClientBuilder {
+    /// Creates a builder for the given base URL, e.g. `https://lite-api.jup.ag`.
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.to_string(),
+            perps_base_url: PERPS_API_URL.to_string(),
+            api_key: None,
+            paths: ApiPaths::default(),
+            http_client: None,
+            default_headers: Vec::new(),
+            user_agent_suffix: None,
+            connect_timeout: None,
+            timeout: None,
+            tcp_keepalive: None,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout: None,
+            http2_keep_alive_interval: None,
+            http2_keep_alive_timeout: None,
+            http2_keep_alive_while_idle: false,
+            http2_adaptive_window: false,
+            proxy: None,
+            max_concurrent_requests: None,
+            max_concurrent_requests_per_family: HashMap::new(),
+            slow_call_threshold: None,
+            slow_call_thresholds: HashMap::new(),
+            rate_limit: None,
+            rate_limit_retries: 0,
+            max_retry_after: Duration::ZERO,
+            offline_fallback: None,
+            circuit_breaker: None,
+            quote_cache: None,
+            quote_freshness: None,
+            hedge: None,
+            failover: None,
+            amm_blacklist: None,
+            token_metadata_capacity: DEFAULT_TOKEN_METADATA_CAPACITY,
+            decimals_resolver: SharedDecimalsResolver::default(),
+            runtime: SharedRuntime::default_runtime(),
+            transport: None,
+            #[cfg(feature = "tower")]
+            service_layer: None,
+            request_hooks: Vec::new(),
+            request_signer: None,
+            event_subscribers: Vec::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            dns_overrides: Vec::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            dns_resolver: None,
+            on_error: None,
+            request_context_in_errors: false,
+            strict_deserialization: false,
+        }
+    }
+
+    /// Creates a builder configured from the environment, so deployments can switch hosts
+    /// without code changes:
+    ///
+    /// - the base URL is [`JUP_BASE_URL`](BASE_URL_ENV) if set, otherwise
+    ///   [`PRO_API_URL`] when an API key is set and [`LITE_API_URL`] when not;
+    /// - the API key is [`JUP_API_KEY`](API_KEY_ENV), if set.
+    ///
+    /// Empty variables count as unset.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// // JUP_API_KEY=... cargo run
+    /// let client = JupiterClientBuilder::from_env()
+    ///     .tcp_keepalive(Duration::from_secs(30))
+    ///     .build()?;
+    /// ```
+    pub fn from_env() -> Self {
+        let var = |name| std::env::var(name).ok().filter(|value| !value.is_empty());
+        let api_key = var(API_KEY_ENV);
+        let base_url = var(BASE_URL_ENV).unwrap_or_else(|| {
+            let host = if api_key.is_some() {
+                PRO_API_URL
+            } else {
+                LITE_API_URL
+            };
+            host.to_string()
+        });
+
+        let builder = Self::new(&base_url);
+        match api_key {
+            Some(api_key) => builder.api_key(&api_key),
+            None => builder,
+        }
+    }
+
+    /// Sends `api_key` in the `x-api-key` header, required for `https://api.jup.ag`.
+    pub fn api_key(mut self, api_key: &str) -> Self {
+        self.api_key = Some(ApiKey(api_key.to_string()));
+        self
+    }
+
+    /// Sends Perps API requests to `base_url` instead of [`PERPS_API_URL`], e.g. a
+    /// gateway in front of it.
+    pub fn perps_base_url(mut self, base_url: &str) -> Self {
+        self.perps_base_url = base_url.to_string();
+        self
+    }
+
+    /// Overrides the API path prefixes, see [`ApiPaths`].
+    pub fn paths(mut self, paths: ApiPaths) -> Self {
+        self.paths = paths;
+        self
+    }
+
+    /// Sends requests with `client` instead of one built from the connection settings,
+    /// e.g. one with custom root certificates.
+    ///
+    /// The connection settings of this builder (timeouts, keep-alive, DNS, proxies) are
+    /// then ignored; set them on `client`. Default headers and the API key are still added to
+    /// every request.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let http = reqwest::Client::builder()
+    ///     .add_root_certificate(reqwest::Certificate::from_pem(CORP_CA_PEM)?)
+    ///     .build()?;
+    /// let client = JupiterClient::builder("https://lite-api.jup.ag")
+    ///     .http_client(http)
+    ///     .build()?;
+    /// ```
+    pub fn http_client(mut self, client: Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Sends header `name` with every request unless the request sets it, e.g. a
+    /// gateway token. Invalid names or values fail [`build`](Self::build).
+    pub fn default_header(mut self, name: &str, value: &str) -> Self {
+        self.default_headers
+            .push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Appends `suffix` to the `User-Agent` the client sends, e.g. `my-wallet/2.1` for
+    /// `jup-ag-sdk/0.1.6 my-wallet/2.1`, so Jupiter and gateways can attribute the traffic.
+    ///
+    /// Without an [`http_client`](Self::http_client), the client sends [`USER_AGENT`] and
+    /// this suffix; with one, its own `User-Agent`. A `user-agent`
+    /// [`default_header`](Self::default_header) replaces both.
+    pub fn user_agent_suffix(mut self, suffix: &str) -> Self {
+        self.user_agent_suffix = Some(suffix.to_string());
+        self
+    }
+
+    /// Fails requests that can't connect within `timeout`. Ignored on `wasm32`, where the
+    /// browser manages connections.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Fails requests that don't complete within `timeout`, from connecting to reading the
+    /// whole response. Failures are `RequestError`s whose `is_timeout()` is `true`.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sends TCP keepalive probes on idle connections at this interval. Ignored on `wasm32`.
+    pub fn tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.tcp_keepalive = Some(interval);
+        self
+    }
+
+    /// Keeps at most `max` idle connections per host open for reuse, so bursts of calls
+    /// don't each pay for a TCP and TLS handshake. Default unbounded; `0` disables reuse.
+    /// Ignored on `wasm32`.
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Closes connections left idle for `timeout`, `None` to keep them open until the
+    /// server closes them. Default 90 seconds. Ignored on `wasm32`.
+    pub fn pool_idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Sends HTTP/2 PING frames at this interval to keep the connection alive. Ignored on
+    /// `wasm32`, like the other HTTP/2 settings.
+    pub fn http2_keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.http2_keep_alive_interval = Some(interval);
+        self
+    }
+
+    /// Closes the connection if a keep-alive PING is not acknowledged within this timeout.
+    ///
+    /// Only applies when [`http2_keep_alive_interval`](Self::http2_keep_alive_interval) is set.
+    pub fn http2_keep_alive_timeout(mut self, timeout: Duration) -> Self {
+        self.http2_keep_alive_timeout = Some(timeout);
+        self
+    }
+
+    /// Keeps sending HTTP/2 keep-alive PINGs while no requests are in flight.
+    ///
+    /// Without this, PINGs are only sent while a request is open, so an idle
+    /// connection between quotes can still be dropped by a NAT.
+    pub fn http2_keep_alive_while_idle(mut self, enabled: bool) -> Self {
+        self.http2_keep_alive_while_idle = enabled;
+        self
+    }
+
+    /// Enables HTTP/2 adaptive flow control, sizing the receive window from measured bandwidth.
+    pub fn http2_adaptive_window(mut self, enabled: bool) -> Self {
+        self.http2_adaptive_window = enabled;
+        self
+    }
+
+    /// Sends requests through the proxies of `config` instead of those in the proxy
+    /// environment variables, see [`ProxyConfig`]. Invalid proxy URLs fail
+    /// [`build`](Self::build). Not available on `wasm32`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn proxy(mut self, config: ProxyConfig) -> Self {
+        self.proxy = Some(config);
+        self
+    }
+
+    /// Limits how many requests the client has in flight at once, across all APIs.
+    ///
+    /// Further requests wait for a free slot instead of opening more connections.
+    ///
+    /// # Panics
+    /// Panics if `limit` is zero.
+    pub fn max_concurrent_requests(mut self, limit: usize) -> Self {
+        assert!(
+            limit > 0,
+            "max_concurrent_requests must be greater than zero"
+        );
+        self.max_concurrent_requests = Some(limit);
+        self
+    }
+
+    /// Limits how many requests to one API family are in flight at once.
+    ///
+    /// Applies on top of [`max_concurrent_requests`](Self::max_concurrent_requests).
+    ///
+    /// # Panics
+    /// Panics if `limit` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jup_ag_sdk::{JupiterClient, config::ApiFamily};
+    ///
+    /// let client = JupiterClient::builder("https://lite-api.jup.ag")
+    ///     .max_concurrent_requests(32)
+    ///     .max_concurrent_requests_for(ApiFamily::Swap, 8)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn max_concurrent_requests_for(mut self, family: ApiFamily, limit: usize) -> Self {
+        assert!(
+            limit > 0,
+            "max_concurrent_requests_for({family}) must be greater than zero"
+        );
+        self.max_concurrent_requests_per_family
+            .insert(family, limit);
+        self
+    }
+
+    /// Emits [`LifecycleEvent::SlowCall`](crate::events::LifecycleEvent::SlowCall) for
+    /// every request whose response takes longer than `threshold`, e.g. a latency SLO.
+    ///
+    /// Latencies are also kept per endpoint, see
+    /// [`JupiterClient::latencies`](JupiterClient::latencies).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use jup_ag_sdk::{JupiterClient, config::ApiFamily, events::LifecycleEvent};
+    ///
+    /// let client = JupiterClient::builder("https://lite-api.jup.ag")
+    ///     .slow_call_threshold(Duration::from_secs(1))
+    ///     .slow_call_threshold_for(ApiFamily::Swap, Duration::from_millis(300))
+    ///     .event_subscriber(|event: &LifecycleEvent| {
+    ///         if let LifecycleEvent::SlowCall { path, latency, .. } = event {
+    ///             eprintln!("slow Jupiter call: {path} took {latency:?}");
+    ///         }
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn slow_call_threshold(mut self, threshold: Duration) -> Self {
+        self.slow_call_threshold = Some(threshold);
+        self
+    }
+
+    /// Like [`slow_call_threshold`](Self::slow_call_threshold), for requests to one API
+    /// family only. Takes precedence over the threshold for all families.
+    pub fn slow_call_threshold_for(mut self, family: ApiFamily, threshold: Duration) -> Self {
+        self.slow_call_thresholds.insert(family, threshold);
+        self
+    }
+
+    /// Throttles requests to stay within a Jupiter plan's allowance, see [`RateLimitPlan`].
+    ///
+    /// Requests over the allowance wait until the plan permits them rather than failing.
+    ///
+    /// # Panics
+    /// Panics if a [`RateLimitPlan::Custom`] plan allows zero requests or has a zero window.
+    pub fn rate_limit(mut self, plan: RateLimitPlan) -> Self {
+        assert!(
+            plan.requests() > 0 && !plan.window().is_zero(),
+            "rate limit must allow at least one request per non-zero window"
+        );
+        self.rate_limit = Some(plan);
+        self
+    }
+
+    /// Retries requests answered with `429 Too Many Requests` and a `Retry-After` header,
+    /// up to `max_retries` times, after waiting as long as the header asks.
+    ///
+    /// A request asked to wait longer than `max_wait` fails right away with
+    /// [`RateLimited`](JupiterClientError::RateLimited), as does the last attempt. Retries
+    /// are counted in [`ClientStats::retries`](super::ClientStats::retries).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use jup_ag_sdk::JupiterClient;
+    ///
+    /// let client = JupiterClient::builder("https://api.jup.ag")
+    ///     .api_key("your-api-key")
+    ///     .retry_rate_limited(3, Duration::from_secs(5))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn retry_rate_limited(mut self, max_retries: u32, max_wait: Duration) -> Self {
+        self.rate_limit_retries = max_retries;
+        self.max_retry_after = max_wait;
+        self
+    }
+
+    /// Enables offline fallback for read endpoints.
+    ///
+    /// The `*_or_cached` methods (prices, token info, routers, shield) remember their
+    /// last successful response, and when a later call fails because Jupiter is
+    /// unreachable, rate limiting, returning server errors or cut off by the
+    /// [`circuit_breaker`](Self::circuit_breaker), serve it instead marked
+    /// as [`Freshness::Stale`](super::Freshness::Stale). Responses older than `max_age`
+    /// are never served.
+    pub fn offline_fallback(mut self, max_age: Duration) -> Self {
+        self.offline_fallback = Some(max_age);
+        self
+    }
+
+    /// Stops sending requests to an endpoint that keeps failing for a while, failing them
+    /// right away with [`CircuitOpen`](JupiterClientError::CircuitOpen) instead, see
+    /// [`CircuitBreakerConfig`].
+    ///
+    /// Trips are counted in
+    /// [`ClientStats::circuit_breaker_trips`](super::ClientStats::circuit_breaker_trips) and
+    /// emitted as [`LifecycleEvent::CircuitOpened`](crate::events::LifecycleEvent::CircuitOpened).
+    pub fn circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(config);
+        self
+    }
+
+    /// Serves repeated [`get_quote`](JupiterClient::get_quote) calls for the same request
+    /// from a local cache while the quote is recent, see [`QuoteCacheConfig`].
+    ///
+    /// Meant for bursts of identical quote requests, e.g. from a UI re-rendering; staleness
+    /// is bounded by the configured TTL and slot lag. Cache hits are counted in
+    /// [`ClientStats::quote_cache_hits`](super::ClientStats::quote_cache_hits).
+    pub fn quote_cache(mut self, config: QuoteCacheConfig) -> Self {
+        self.quote_cache = Some(config);
+        self
+    }
+
+    /// Checks the age of quotes before building swaps from them, re-quoting or failing when
+    /// they are over budget, see [`QuoteFreshness`].
+    pub fn quote_freshness(mut self, freshness: QuoteFreshness) -> Self {
+        self.quote_freshness = Some(freshness);
+        self
+    }
+
+    /// Hedges slow idempotent requests to a second base URL, see [`HedgeConfig`].
+    ///
+    /// Counted in [`ClientStats::hedges`](super::ClientStats::hedges) and
+    /// [`ClientStats::hedge_wins`](super::ClientStats::hedge_wins).
+    pub fn hedge(mut self, config: HedgeConfig) -> Self {
+        self.hedge = Some(config);
+        self
+    }
+
+    /// Sends idempotent requests that fail with a server error or a timeout again to a
+    /// second base URL, see [`FailoverConfig`].
+    ///
+    /// Counted in [`ClientStats::failovers`](super::ClientStats::failovers) and emitted as
+    /// [`LifecycleEvent::FailedOver`](crate::events::LifecycleEvent::FailedOver).
+    pub fn failover(mut self, config: FailoverConfig) -> Self {
+        self.failover = Some(config);
+        self
+    }
+
+    /// Excludes the DEXes of AMMs blacklisted by `blacklist` from every
+    /// [`get_quote`](JupiterClient::get_quote), see [`amm_blacklist`](crate::amm_blacklist).
+    ///
+    /// Keep a clone of the `Arc` to report execution outcomes to it.
+    pub fn amm_blacklist(mut self, blacklist: Arc<AmmBlacklist>) -> Self {
+        self.amm_blacklist = Some(blacklist);
+        self
+    }
+
+    /// Sets how many tokens the client keeps in its [`TokenMetadataCache`]
+    /// (default [`DEFAULT_TOKEN_METADATA_CAPACITY`]). `0` disables the cache, so every
+    /// [`token_metadata`](JupiterClient::token_metadata) call hits the Tokens API.
+    pub fn token_metadata_cache(mut self, capacity: usize) -> Self {
+        self.token_metadata_capacity = capacity;
+        self
+    }
+
+    /// Sets how the client looks up token decimals for UI amounts, see [`DecimalsResolver`].
+    pub fn decimals_resolver(mut self, resolver: impl DecimalsResolver + 'static) -> Self {
+        self.decimals_resolver = SharedDecimalsResolver(Arc::new(resolver));
+        self
+    }
+
+    /// Sends requests to the Jupiter APIs through `transport` instead of the default
+    /// [`ReqwestTransport`], e.g. to use another HTTP stack or to serve canned responses
+    /// in tests. See [`HttpTransport`].
+    ///
+    /// The client's rate limits, concurrency limits and request hooks still apply, and
+    /// it takes precedence over a [`service_layer`](Self::service_layer). Requests carry
+    /// the client's headers, such as the API key, but connection settings such as
+    /// keep-alive and DNS overrides only affect the default transport.
+    pub fn transport(mut self, transport: impl HttpTransport + 'static) -> Self {
+        self.transport = Some(SharedTransport(Arc::new(transport)));
+        self
+    }
+
+    /// Runs every request to the Jupiter APIs through a tower middleware stack wrapping
+    /// the HTTP client, e.g. for retries, timeouts, load shedding or tracing from the
+    /// tower ecosystem. Setting it again replaces the previous stack.
+    ///
+    /// The stack sits below the client's own rate limits, concurrency limits and request
+    /// hooks. Errors raised by middleware are returned as
+    /// [`JupiterClientError::ServiceError`]. Solana RPC calls are not routed through it.
+    ///
+    /// Requires the `tower` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use jup_ag_sdk::JupiterClient;
+    /// use tower::{ServiceBuilder, timeout::TimeoutLayer};
+    ///
+    /// let client = JupiterClient::builder("https://lite-api.jup.ag")
+    ///     .service_layer(
+    ///         ServiceBuilder::new()
+    ///             .concurrency_limit(16)
+    ///             .layer(TimeoutLayer::new(Duration::from_secs(5))),
+    ///     )
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    #[cfg(feature = "tower")]
+    pub fn service_layer<L>(mut self, layer: L) -> Self
+    where
+        L: tower::Layer<Client> + Send + Sync + 'static,
+        L::Service: tower::Service<reqwest::Request, Response = reqwest::Response>
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        <L::Service as tower::Service<reqwest::Request>>::Error: Into<crate::error::BoxError>,
+        <L::Service as tower::Service<reqwest::Request>>::Future: Send + 'static,
+    {
+        self.service_layer = Some(super::transport::SharedLayer::new(layer));
+        self
+    }
+
+    /// Sets the async runtime used for rate limit waits and background tasks,
+    /// see [`runtime`](crate::runtime).
+    ///
+    /// Defaults to [`TokioRuntime`](crate::runtime::TokioRuntime) with the `tokio-runtime`
+    /// feature; without it, building fails unless a runtime is set.
+    pub fn runtime(mut self, runtime: impl Runtime + 'static) -> Self {
+        self.runtime = Some(SharedRuntime(Arc::new(runtime)));
+        self
+    }
+
+    /// Registers a hook that can edit the query parameters and JSON body of every request
+    /// the client sends, across all API families. Hooks run in the order they were added.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jup_ag_sdk::{JupiterClient, config::ApiFamily};
+    ///
+    /// let client = JupiterClient::builder("https://lite-api.jup.ag")
+    ///     .request_hook(|req| {
+    ///         if req.family == ApiFamily::Swap && req.path.ends_with("/quote") {
+    ///             req.default_query_param("platformFeeBps", 20);
+    ///         }
+    ///         req.default_body_field("referralAccount", "your-referral-account");
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn request_hook(
+        mut self,
+        hook: impl Fn(&mut RequestParts) + Send + Sync + 'static,
+    ) -> Self {
+        self.request_hooks.push(RequestHook::new(hook));
+        self
+    }
+
+    /// Signs every request with `signer`, for gateways in front of Jupiter that
+    /// authenticate callers by an HMAC over the request. Setting it again replaces it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jup_ag_sdk::{JupiterClient, client::RequestSigner};
+    ///
+    /// let client = JupiterClient::builder("https://jupiter.gateway.internal")
+    ///     .request_signer(RequestSigner::hmac_sha256("gateway-secret").prefix("sha256="))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn request_signer(mut self, signer: RequestSigner) -> Self {
+        self.request_signer = Some(signer);
+        self
+    }
+
+    /// Registers a subscriber for the client's
+    /// [`LifecycleEvent`](crate::events::LifecycleEvent)s, see
+    /// [`events`](crate::events). Subscribers are called in the order they were added.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jup_ag_sdk::{JupiterClient, events::LifecycleEvent};
+    ///
+    /// let client = JupiterClient::builder("https://lite-api.jup.ag")
+    ///     .event_subscriber(|event: &LifecycleEvent| println!("{event:?}"))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn event_subscriber(mut self, subscriber: impl EventSubscriber + 'static) -> Self {
+        self.event_subscribers
+            .push(SharedSubscriber(Arc::new(subscriber)));
+        self
+    }
+
+    /// Registers a callback invoked with an [`ErrorContext`] whenever a request fails,
+    /// right before the error is returned to the caller.
+    ///
+    /// Covers transport, API and deserialization errors, not input validation errors
+    /// which are raised before anything is sent. Registering again replaces the callback.
+    /// Meant for error trackers and alerting, without wrapping each call.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jup_ag_sdk::JupiterClient;
+    ///
+    /// let client = JupiterClient::builder("https://lite-api.jup.ag")
+    ///     .on_error(|ctx| {
+    ///         eprintln!(
+    ///             "{} {} failed after {:?} ({}, status {:?}, code {:?}): {}",
+    ///             ctx.method, ctx.endpoint, ctx.duration, ctx.kind, ctx.status, ctx.code, ctx.error
+    ///         );
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn on_error(
+        mut self,
+        callback: impl Fn(&ErrorContext<'_>) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_error = Some(ErrorCallback::new(callback));
+        self
+    }
+
+    /// Wraps errors from requests in [`JupiterClientError::WithRequest`], carrying a
+    /// [`RequestSummary`](crate::error::RequestSummary) of the endpoint and key parameters
+    /// (mints, amount, slippage, wallet), so a failure deep in a pipeline can be
+    /// diagnosed from the error alone. Signed transactions are never included.
+    ///
+    /// Off by default since it changes which variant errors are returned as;
+    /// use [`JupiterClientError::inner`] to match on the underlying error.
+    pub fn request_context_in_errors(mut self, enabled: bool) -> Self {
+        self.request_context_in_errors = enabled;
+        self
+    }
+
+    /// Fails responses carrying fields the SDK's types don't have with a
+    /// `DeserializationError` listing them, instead of ignoring them.
+    ///
+    /// Meant for CI and integration tests, to notice when Jupiter changes a response
+    /// schema; leave it off in production, where new fields shouldn't break anything.
+    /// See [`json::from_slice_strict`](crate::json::from_slice_strict).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jup_ag_sdk::JupiterClient;
+    ///
+    /// let client = JupiterClient::builder("https://lite-api.jup.ag")
+    ///     .strict_deserialization(std::env::var("CI").is_ok())
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn strict_deserialization(mut self, enabled: bool) -> Self {
+        self.strict_deserialization = enabled;
+        self
+    }
+
+    /// Pins `host` to a fixed address, skipping DNS for it.
+    ///
+    /// Useful when colocated near Jupiter's infrastructure, where DNS round-robin
+    /// adds jitter. The port in `addr` is ignored if the base URL specifies one;
+    /// set it to `0` to use the scheme's default port. Not available on `wasm32`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jup_ag_sdk::JupiterClient;
+    ///
+    /// let client = JupiterClient::builder("https://api.jup.ag")
+    ///     .resolve("api.jup.ag", "203.0.113.10:443".parse().unwrap())
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn resolve(self, host: &str, addr: SocketAddr) -> Self {
+        self.resolve_to_addrs(host, &[addr])
+    }
+
+    /// Pins `host` to a set of addresses, tried in order. See [`resolve`](Self::resolve).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn resolve_to_addrs(mut self, host: &str, addrs: &[SocketAddr]) -> Self {
+        self.dns_overrides.push((host.to_string(), addrs.to_vec()));
+        self
+    }
+
+    /// Uses a custom DNS resolver for all hosts without a [`resolve`](Self::resolve) override.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn dns_resolver<R: Resolve + 'static>(mut self, resolver: Arc<R>) -> Self {
+        self.dns_resolver = Some(SharedResolver(resolver));
+        self
+    }
+
+    /// Builds the [`JupiterClient`].
+    ///
+    /// Fails with a `HeaderError` if the API key, a default header or the `User-Agent`
+    /// suffix is not a valid header value, a `RequestError` if the underlying HTTP client cannot be created, or a
+    /// `ValidationError` if a default header name is invalid or no runtime is available.
+    pub fn build(self) -> Result<JupiterClient, JupiterClientError> {
+        let runtime = self.runtime.clone().ok_or_else(|| {
+            JupiterClientError::ValidationError(
+                "no async runtime, enable the `tokio-runtime` feature or set one with `runtime`"
+                    .to_string(),
+            )
+        })?;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("Accept", HeaderValue::from_static("application/json"));
+        headers.insert("Content-Type", HeaderValue::from_static("application/json"));
+        if self.http_client.is_none() {
+            let user_agent = match &self.user_agent_suffix {
+                Some(suffix) => HeaderValue::from_str(&format!("{USER_AGENT} {suffix}"))?,
+                None => HeaderValue::from_static(USER_AGENT),
+            };
+            headers.insert(header::USER_AGENT, user_agent);
+        }
+        for (name, value) in &self.default_headers {
+            let name = HeaderName::from_bytes(name.as_bytes()).map_err(|_| {
+                JupiterClientError::ValidationError(format!("invalid header name: {name}"))
+            })?;
+            headers.insert(name, HeaderValue::from_str(value)?);
+        }
+        if let Some(ApiKey(api_key)) = &self.api_key {
+            let mut value = HeaderValue::from_str(api_key)?;
+            value.set_sensitive(true);
+            headers.insert("x-api-key", value);
+        }
+
+        let (client, external) = match &self.http_client {
+            // default headers are added per request, so the client carries none of them
+            Some(client) => (client.clone(), client.clone()),
+            None => (
+                self.build_http_client(&headers)?,
+                self.build_http_client(&external_headers(&headers))?,
+            ),
+        };
+        let shared = ClientState {
+            transport: self.build_transport(&client),
+            external,
+            headers,
+            concurrency: ConcurrencyLimits::new(
+                self.max_concurrent_requests,
+                &self.max_concurrent_requests_per_family,
+            ),
+            rate_limiter: self.rate_limit.map(RateLimiter::new),
+            rate_limit_info: Default::default(),
+            fallback: self.offline_fallback.map(FallbackCache::new),
+            circuit_breaker: self.circuit_breaker.map(CircuitBreaker::new),
+            quote_cache: self.quote_cache.map(QuoteCache::new),
+            latest_slot: Default::default(),
+            executions: Default::default(),
+            execution_paused: Default::default(),
+            amm_blacklist: self.amm_blacklist.clone(),
+            token_metadata: TokenMetadataCache::new(self.token_metadata_capacity),
+            dex_registry: Default::default(),
+            decimals: self.decimals_resolver.clone(),
+            runtime,
+            stats: StatsCounters::default(),
+            execution_stats: Default::default(),
+            latency: Default::default(),
+            builder: self,
+        };
+        Ok(JupiterClient {
+            client,
+            base_url: shared.builder.base_url.clone(),
+            paths: shared.builder.paths.clone(),
+            shared: Arc::new(shared),
+        })
+    }
+
+    /// A reqwest client with the connection settings of this builder.
+    #[cfg(target_arch = "wasm32")]
+    fn build_http_client(&self, headers: &HeaderMap) -> Result<Client, JupiterClientError> {
+        // the browser owns connections and DNS; timeouts are set on each request
+        Ok(Client::builder().default_headers(headers.clone()).build()?)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn build_http_client(&self, headers: &HeaderMap) -> Result<Client, JupiterClientError> {
+        let mut builder = Client::builder()
+            .default_headers(headers.clone())
+            .http2_keep_alive_while_idle(self.http2_keep_alive_while_idle)
+            .http2_adaptive_window(self.http2_adaptive_window);
+        if let Some(interval) = self.tcp_keepalive {
+            builder = builder.tcp_keepalive(interval);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(interval) = self.http2_keep_alive_interval {
+            builder = builder.http2_keep_alive_interval(interval);
+        }
+        if let Some(timeout) = self.http2_keep_alive_timeout {
+            builder = builder.http2_keep_alive_timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        for (host, addrs) in &self.dns_overrides {
+            builder = builder.resolve_to_addrs(host, addrs);
+        }
+        if let Some(resolver) = &self.dns_resolver {
+            builder = builder.dns_resolver(Arc::new(resolver.clone()));
+        }
+        if let Some(config) = &self.proxy {
+            builder = builder.no_proxy();
+            for proxy in proxies(config)? {
+                builder = builder.proxy(proxy);
+            }
+        }
+        Ok(builder.build()?)
+    }
+
+    /// The transport for a client built on `client`.
+    fn build_transport(&self, client: &Client) -> SharedTransport {
+        if let Some(transport) = &self.transport {
+            return transport.clone();
+        }
+        #[cfg(feature = "tower")]
+        if let Some(layer) = &self.service_layer {
+            return SharedTransport(Arc::new(layer.transport(client.clone())));
+        }
+        SharedTransport(Arc::new(ReqwestTransport(client.clone())))
+    }
+}
+
+/// The proxies of `config`, scheme-specific ones first so they take precedence.
+#[cfg(not(target_arch = "wasm32"))]
+fn proxies(config: &ProxyConfig) -> Result<Vec<Proxy>, JupiterClientError> {
+    let no_proxy = NoProxy::from_string(&config.no_proxy.join(","));
+    let proxy = |url: &str, build: fn(&str) -> reqwest::Result<Proxy>| {
+        build(url)
+            .map(|proxy| proxy.no_proxy(no_proxy.clone()))
+            .map_err(|_| {
+                JupiterClientError::ValidationError(format!(
+                    "invalid proxy URL: {}",
+                    crate::config::redact_credentials(url)
+                ))
+            })
+    };
+    let mut proxies = Vec::new();
+    if let Some(url) = &config.http {
+        proxies.push(proxy(url, |url| Proxy::http(url))?);
+    }
+    if let Some(url) = &config.https {
+        proxies.push(proxy(url, |url| Proxy::https(url))?);
+    }
+    if let Some(url) = &config.all {
+        proxies.push(proxy(url, |url| Proxy::all(url))?);
+    }
+    Ok(proxies)
+}
+
+/// A type-erased [`Resolve`] so the builder stays `Clone` and `Debug`.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone)]
+pub(crate) struct SharedResolver(Arc<dyn Resolve>);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Resolve for SharedResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        self.0.resolve(name)
+    }
+}
+
+/// An API key, redacted in `Debug` output.
+#[derive(Clone)]
+pub(crate) struct ApiKey(String);
+
+impl fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl fmt::Debug for SharedResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedResolver")
+    }
+}
+
+/// The headers of `headers` that may go to hosts other than Jupiter's: none of the
+/// default headers or the API key, only the `User-Agent`.
+fn external_headers(headers: &HeaderMap) -> HeaderMap {
+    let mut external = HeaderMap::new();
+    if let Some(user_agent) = headers.get(header::USER_AGENT) {
+        external.insert(header::USER_AGENT, user_agent.clone());
+    }
+    external
+}
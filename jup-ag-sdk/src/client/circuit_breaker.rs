@@ -0,0 +1,93 @@
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use super::{fallback, latency::endpoint_path};
+use crate::{
+    config::{ApiFamily, CircuitBreakerConfig},
+    error::JupiterClientError,
+    runtime::Instant,
+};
+
+/// Per-endpoint circuit breakers, see [`CircuitBreakerConfig`].
+#[derive(Debug)]
+pub(crate) struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    endpoints: Mutex<HashMap<(ApiFamily, String), State>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum State {
+    Closed {
+        failures: u32,
+    },
+    /// Failing calls until `until`, when the next call is let through as a probe.
+    Open {
+        until: Instant,
+    },
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            endpoints: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Fails with `CircuitOpen` if calls to `path` are cut off. Once the cool-down is
+    /// over, lets one call through and cuts off the others for another cool-down, until
+    /// that probe's outcome is [`record`](Self::record)ed.
+    pub(crate) fn check(&self, family: ApiFamily, path: &str) -> Result<(), JupiterClientError> {
+        let endpoint = endpoint_path(path);
+        let mut endpoints = self.endpoints.lock().unwrap();
+        let Some(State::Open { until }) = endpoints.get_mut(&(family, endpoint.clone())) else {
+            return Ok(());
+        };
+
+        let now = Instant::now();
+        if now < *until {
+            return Err(JupiterClientError::CircuitOpen {
+                family,
+                endpoint,
+                retry_in: *until - now,
+            });
+        }
+        *until = now + self.config.cool_down;
+        Ok(())
+    }
+
+    /// Records the outcome of a call to `path`, returning the cool-down if it tripped the
+    /// breaker. A failed probe opens it again without counting as a trip.
+    pub(crate) fn record<T>(
+        &self,
+        family: ApiFamily,
+        path: &str,
+        result: &Result<T, JupiterClientError>,
+    ) -> Option<Duration> {
+        let failed = match result {
+            Ok(_) => false,
+            Err(error) => fallback::is_outage(error),
+        };
+        let mut endpoints = self.endpoints.lock().unwrap();
+        let state = endpoints
+            .entry((family, endpoint_path(path)))
+            .or_insert(State::Closed { failures: 0 });
+
+        let open = State::Open {
+            until: Instant::now() + self.config.cool_down,
+        };
+        match (*state, failed) {
+            (_, false) => *state = State::Closed { failures: 0 },
+            (State::Closed { failures }, true) if failures + 1 < self.config.failure_threshold => {
+                *state = State::Closed {
+                    failures: failures + 1,
+                }
+            }
+            (State::Closed { .. }, true) => {
+                *state = open;
+                return Some(self.config.cool_down);
+            }
+            (State::Open { .. }, true) => *state = open,
+        }
+        None
+    }
+}
@@ -0,0 +1,168 @@
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use super::JupiterClient;
+use crate::{error::JupiterClientError, runtime::BoxFuture, types::known_tokens};
+
+/// The future returned by [`DecimalsResolver::decimals`].
+pub type DecimalsFuture<'a> = BoxFuture<'a, Result<u8, JupiterClientError>>;
+
+/// Looks up the decimals of a mint, to convert between raw and UI amounts.
+///
+/// The client's resolver backs [`JupiterClient::decimals`] and every helper taking or
+/// formatting UI amounts, so they all agree on a token's decimals. Set it with
+/// [`JupiterClientBuilder::decimals_resolver`](super::JupiterClientBuilder::decimals_resolver);
+/// the default is [`StaticDecimals::well_known`] falling back to [`TokensApiDecimals`].
+///
+/// # Example
+/// ```
+/// use jup_ag_sdk::{JupiterClient, client::{DecimalsResolver, RpcDecimals, StaticDecimals}};
+///
+/// let client = JupiterClient::builder("https://lite-api.jup.ag")
+///     .decimals_resolver(
+///         StaticDecimals::well_known()
+///             .with("2zMMhcVQEXDtdE6vsFS7S7D5oUodfJHE8vd1gnBouauv", 6)
+///             .or(RpcDecimals::new("https://api.mainnet-beta.solana.com")),
+///     )
+///     .build()?;
+/// ```
+pub trait DecimalsResolver: Send + Sync {
+    /// Returns the decimals of `mint`, using `client` for any request it needs.
+    fn decimals<'a>(&'a self, client: &'a JupiterClient, mint: &'a str) -> DecimalsFuture<'a>;
+
+    /// Falls back to `fallback` for mints this resolver fails on.
+    fn or<R: DecimalsResolver>(self, fallback: R) -> Or<Self, R>
+    where
+        Self: Sized,
+    {
+        Or(self, fallback)
+    }
+}
+
+/// Decimals from a fixed map, failing with a `ValidationError` for other mints.
+#[derive(Debug, Clone, Default)]
+pub struct StaticDecimals(HashMap<String, u8>);
+
+impl StaticDecimals {
+    /// An empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decimals of well-known tokens: SOL, USDC, USDT, JUP, BONK, mSOL and JitoSOL.
+    pub fn well_known() -> Self {
+        Self(
+            known_tokens()
+                .map(|(mint, _, decimals)| (mint.to_string(), decimals))
+                .collect(),
+        )
+    }
+
+    /// Adds `mint` with `decimals`.
+    pub fn with(mut self, mint: &str, decimals: u8) -> Self {
+        self.0.insert(mint.to_string(), decimals);
+        self
+    }
+}
+
+impl DecimalsResolver for StaticDecimals {
+    fn decimals<'a>(&'a self, _client: &'a JupiterClient, mint: &'a str) -> DecimalsFuture<'a> {
+        let decimals = self.0.get(mint).copied().ok_or_else(|| {
+            JupiterClientError::ValidationError(format!("decimals of {mint} are not known"))
+        });
+        Box::pin(async move { decimals })
+    }
+}
+
+/// Decimals from the Tokens API, through the client's
+/// [`TokenMetadataCache`](crate::types::TokenMetadataCache).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokensApiDecimals;
+
+impl DecimalsResolver for TokensApiDecimals {
+    fn decimals<'a>(&'a self, client: &'a JupiterClient, mint: &'a str) -> DecimalsFuture<'a> {
+        Box::pin(async move { Ok(client.token_metadata(mint).await?.decimals) })
+    }
+}
+
+/// Decimals read from the mint account through a Solana RPC, for SPL Token and
+/// Token-2022 mints. Every lookup is an RPC request, so put it behind a faster resolver.
+///
+/// Requires the `solana` feature.
+#[cfg(feature = "solana")]
+#[derive(Debug, Clone)]
+pub struct RpcDecimals {
+    rpc_url: String,
+}
+
+#[cfg(feature = "solana")]
+impl RpcDecimals {
+    /// Reads mints from the RPC at `rpc_url`.
+    pub fn new(rpc_url: &str) -> Self {
+        Self {
+            rpc_url: rpc_url.to_string(),
+        }
+    }
+}
+
+/// Offset of `decimals` in an SPL Token mint account, after the mint authority
+/// option (36 bytes) and the supply (8 bytes).
+#[cfg(feature = "solana")]
+const MINT_DECIMALS_OFFSET: usize = 44;
+
+#[cfg(feature = "solana")]
+impl DecimalsResolver for RpcDecimals {
+    fn decimals<'a>(&'a self, client: &'a JupiterClient, mint: &'a str) -> DecimalsFuture<'a> {
+        use crate::referral::{TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID};
+
+        Box::pin(async move {
+            let accounts = client.get_multiple_accounts(&self.rpc_url, &[mint]).await?;
+            let account = accounts.into_iter().flatten().next().ok_or_else(|| {
+                JupiterClientError::ValidationError(format!("mint {mint} not found"))
+            })?;
+            if account.owner != TOKEN_PROGRAM_ID && account.owner != TOKEN_2022_PROGRAM_ID {
+                return Err(JupiterClientError::ValidationError(format!(
+                    "{mint} is not a token mint"
+                )));
+            }
+
+            account
+                .data()?
+                .get(MINT_DECIMALS_OFFSET)
+                .copied()
+                .ok_or_else(|| {
+                    JupiterClientError::deserialization(format!("mint account {mint} is too short"))
+                })
+        })
+    }
+}
+
+/// Two resolvers tried in order, see [`DecimalsResolver::or`].
+#[derive(Debug, Clone)]
+pub struct Or<A, B>(A, B);
+
+impl<A: DecimalsResolver, B: DecimalsResolver> DecimalsResolver for Or<A, B> {
+    fn decimals<'a>(&'a self, client: &'a JupiterClient, mint: &'a str) -> DecimalsFuture<'a> {
+        Box::pin(async move {
+            match self.0.decimals(client, mint).await {
+                Ok(decimals) => Ok(decimals),
+                Err(_) => self.1.decimals(client, mint).await,
+            }
+        })
+    }
+}
+
+/// A type-erased [`DecimalsResolver`] so the builder stays `Clone` and `Debug`.
+#[derive(Clone)]
+pub(crate) struct SharedDecimalsResolver(pub(crate) Arc<dyn DecimalsResolver>);
+
+impl Default for SharedDecimalsResolver {
+    fn default() -> Self {
+        Self(Arc::new(StaticDecimals::well_known().or(TokensApiDecimals)))
+    }
+}
+
+impl fmt::Debug for SharedDecimalsResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedDecimalsResolver")
+    }
+}
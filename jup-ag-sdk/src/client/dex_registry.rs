@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use futures_util::future;
+
+use super::JupiterClient;
+use crate::{
+    error::JupiterClientError,
+    types::{DexRegistry, QuoteRequest},
+};
+
+impl JupiterClient {
+    /// The DEXes and Ultra routers Jupiter routes through, fetched on first use and then
+    /// served from memory.
+    ///
+    /// Concurrent first calls share one fetch of
+    /// [`get_program_id_to_label`](Self::get_program_id_to_label) and
+    /// [`routers`](Self::routers). A failed fetch isn't cached, so the next call retries.
+    ///
+    /// # Example
+    /// ```
+    /// let registry = api.dex_registry().await?;
+    /// for dex in registry.labels() {
+    ///     println!("{dex}: {:?}", registry.program_ids(dex));
+    /// }
+    /// ```
+    pub async fn dex_registry(&self) -> Result<Arc<DexRegistry>, JupiterClientError> {
+        let mut cached = self.shared.dex_registry.lock().await;
+        if let Some(registry) = &*cached {
+            return Ok(registry.clone());
+        }
+        let registry = self.fetch_dex_registry().await?;
+        *cached = Some(registry.clone());
+        Ok(registry)
+    }
+
+    /// Fetches the [`dex_registry`](Self::dex_registry) again, e.g. to pick up DEXes
+    /// Jupiter added since it was cached. On failure the cached one is kept.
+    pub async fn refresh_dex_registry(&self) -> Result<Arc<DexRegistry>, JupiterClientError> {
+        let mut cached = self.shared.dex_registry.lock().await;
+        let registry = self.fetch_dex_registry().await?;
+        *cached = Some(registry.clone());
+        Ok(registry)
+    }
+
+    /// Checks `request`'s `dexes` and `exclude_dexes` against the
+    /// [`dex_registry`](Self::dex_registry), see [`DexRegistry::validate_dexes`].
+    ///
+    /// # Example
+    /// ```
+    /// let request = QuoteRequest::new(SOL_MINT, JUP_MINT, amount).dexes(selected_venues);
+    /// client.validate_dexes(&request).await?;
+    /// let quote = client.get_quote(&request).await?;
+    /// ```
+    pub async fn validate_dexes(&self, request: &QuoteRequest) -> Result<(), JupiterClientError> {
+        self.dex_registry().await?.validate_dexes(request)
+    }
+
+    async fn fetch_dex_registry(&self) -> Result<Arc<DexRegistry>, JupiterClientError> {
+        let (labels, routers) =
+            future::try_join(self.get_program_id_to_label(), self.routers()).await?;
+        Ok(Arc::new(DexRegistry::new(labels, routers)))
+    }
+}
@@ -0,0 +1,111 @@
+use std::{
+    cell::RefCell,
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use reqwest::Request;
+
+use crate::error::JupiterClientError;
+
+thread_local! {
+    /// Where the [`DryRun`] future being polled on this thread holds its request, if any.
+    static SINK: RefCell<Option<Arc<Mutex<Option<Request>>>>> = const { RefCell::new(None) };
+}
+
+/// Runs a call up to the point it would send its first request to Jupiter, and returns
+/// that request instead of sending it, e.g. to check in tests or audits exactly what
+/// would be sent.
+///
+/// The request is the one the transport would get: default headers, request hooks,
+/// [`RequestOptions`](super::RequestOptions) and the
+/// [`request_signer`](super::JupiterClientBuilder::request_signer) are all applied. It
+/// doesn't wait for rate or concurrency limits, skips the quote cache and isn't counted
+/// in [`stats`](super::JupiterClient::stats).
+///
+/// Resolves to `None` if the call completed without sending a request, e.g. when it was
+/// answered from a cache, and to the call's error if it failed before sending one, e.g.
+/// on invalid input. Calls made of several requests stop after the first one.
+///
+/// # Example
+/// ```
+/// let request = DryRun::new(client.get_quote(&quote_request)).await?.unwrap();
+/// assert_eq!(request.url().path(), "/swap/v1/quote");
+///
+/// let execute = DryRun::new(client.ultra_execute_order(&signed)).await?.unwrap();
+/// let body = execute.body().and_then(|body| body.as_bytes());
+/// ```
+pub struct DryRun<F> {
+    request: Arc<Mutex<Option<Request>>>,
+    future: Pin<Box<F>>,
+}
+
+impl<F> DryRun<F> {
+    pub fn new(future: F) -> Self {
+        Self {
+            request: Arc::default(),
+            future: Box::pin(future),
+        }
+    }
+}
+
+impl<F> fmt::Debug for DryRun<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DryRun")
+            .field("request", &self.request)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, F> Future for DryRun<F>
+where
+    F: Future<Output = Result<T, JupiterClientError>>,
+{
+    type Output = Result<Option<Request>, JupiterClientError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let sink = self.request.clone();
+        let outer = SINK.with(|current| current.replace(Some(sink)));
+        // restores the outer sink even if the future panics
+        let restore = Restore(outer);
+        let output = self.future.as_mut().poll(cx);
+        drop(restore);
+
+        // the call waits forever once its request is held, and is dropped here
+        if let Some(request) = self.request.lock().unwrap().take() {
+            return Poll::Ready(Ok(Some(request)));
+        }
+        match output {
+            Poll::Ready(result) => Poll::Ready(result.map(|_| None)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+struct Restore(Option<Arc<Mutex<Option<Request>>>>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let outer = self.0.take();
+        SINK.with(|current| *current.borrow_mut() = outer);
+    }
+}
+
+/// Whether the call being made on this thread is a dry run.
+pub(crate) fn active() -> bool {
+    SINK.with(|current| current.borrow().is_some())
+}
+
+/// Holds `request` back if the call is a dry run, returning it otherwise.
+pub(crate) fn hold(request: Request) -> Option<Request> {
+    SINK.with(|current| match &*current.borrow() {
+        Some(sink) => {
+            sink.lock().unwrap().get_or_insert(request);
+            None
+        }
+        None => Some(request),
+    })
+}
@@ -0,0 +1,263 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, atomic::Ordering},
+};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use reqwest::RequestBuilder;
+use serde::{Serialize, de::DeserializeOwned};
+
+use super::JupiterClient;
+use crate::{
+    config::ApiFamily,
+    error::JupiterClientError,
+    runtime::Instant,
+    types::{ExecuteRecurringResponse, Status, TriggerResponse, UltraExecuteOrderResponse},
+};
+
+/// Most successful executions remembered; the oldest is forgotten first.
+const MAX_ENTRIES: usize = 256;
+
+/// A response of an `/execute` endpoint.
+pub(crate) trait ExecuteResponse: Serialize + DeserializeOwned {
+    /// The error the API reported, `None` on success.
+    fn failure(&self) -> Option<String>;
+
+    /// A successful response for a transaction that landed with `signature`, for when
+    /// the API only says it was already processed. `None` if the response can't be built
+    /// from the signature alone.
+    fn landed(signature: String) -> Option<Self>;
+}
+
+impl ExecuteResponse for UltraExecuteOrderResponse {
+    fn failure(&self) -> Option<String> {
+        match self.status {
+            Status::Success => None,
+            _ => Some(
+                self.error
+                    .clone()
+                    .unwrap_or_else(|| format!("execution failed with code {}", self.code)),
+            ),
+        }
+    }
+
+    fn landed(signature: String) -> Option<Self> {
+        Some(Self {
+            status: Status::Success,
+            signature: Some(signature),
+            slot: None,
+            error: None,
+            code: 0,
+            total_input_amount: None,
+            total_output_amount: None,
+            input_amount_result: None,
+            output_amount_result: None,
+            swap_events: None,
+        })
+    }
+}
+
+impl ExecuteResponse for ExecuteRecurringResponse {
+    fn failure(&self) -> Option<String> {
+        (!self.status.eq_ignore_ascii_case("success"))
+            .then(|| format!("execution {}", self.status.to_lowercase()))
+    }
+
+    fn landed(signature: String) -> Option<Self> {
+        Some(Self {
+            signature,
+            status: "Success".to_string(),
+        })
+    }
+}
+
+impl ExecuteResponse for TriggerResponse {
+    fn failure(&self) -> Option<String> {
+        (self.code != 0).then(|| format!("execution failed with code {}", self.code))
+    }
+
+    fn landed(_: String) -> Option<Self> {
+        None
+    }
+}
+
+/// Successful `/execute` responses by family and request id, with the signature of the
+/// transaction they executed.
+#[derive(Debug, Default)]
+pub(crate) struct ExecutionLog {
+    entries: Mutex<HashMap<(ApiFamily, String), Execution>>,
+}
+
+#[derive(Debug)]
+struct Execution {
+    recorded_at: Instant,
+    signature: Option<String>,
+    response: Vec<u8>,
+}
+
+impl ExecutionLog {
+    fn record<T: Serialize>(
+        &self,
+        family: ApiFamily,
+        request_id: &str,
+        signature: Option<String>,
+        response: &T,
+    ) {
+        let Ok(response) = serde_json::to_vec(response) else {
+            return;
+        };
+        let key = (family, request_id.to_string());
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= MAX_ENTRIES && !entries.contains_key(&key) {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, execution)| execution.recorded_at)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(
+            key,
+            Execution {
+                recorded_at: Instant::now(),
+                signature,
+                response,
+            },
+        );
+    }
+
+    /// The recorded success of `request_id`, unless it executed a transaction other than
+    /// the one with `signature`.
+    fn get<T: DeserializeOwned>(
+        &self,
+        family: ApiFamily,
+        request_id: &str,
+        signature: Option<&str>,
+    ) -> Option<T> {
+        let entries = self.entries.lock().unwrap();
+        let execution = entries.get(&(family, request_id.to_string()))?;
+        if let (Some(recorded), Some(signature)) = (&execution.signature, signature)
+            && recorded != signature
+        {
+            return None;
+        }
+        serde_json::from_slice(&execution.response).ok()
+    }
+}
+
+impl JupiterClient {
+    /// Makes every `/execute` method fail with
+    /// [`ExecutionPaused`](JupiterClientError::ExecutionPaused) until
+    /// [`resume_execution`](Self::resume_execution), e.g. while an incident is
+    /// investigated. Quotes, orders to sign and other read-only requests keep working.
+    ///
+    /// Executions already sent are not affected.
+    ///
+    /// # Example
+    /// ```
+    /// api.pause_execution();
+    /// let err = api.ultra_execute_order(&request).await.unwrap_err();
+    /// assert!(matches!(err, JupiterClientError::ExecutionPaused));
+    /// ```
+    pub fn pause_execution(&self) {
+        self.shared.execution_paused.store(true, Ordering::SeqCst);
+    }
+
+    /// Lets `/execute` methods send requests again after
+    /// [`pause_execution`](Self::pause_execution).
+    pub fn resume_execution(&self) {
+        self.shared.execution_paused.store(false, Ordering::SeqCst);
+    }
+
+    /// Whether execution is paused with [`pause_execution`](Self::pause_execution).
+    pub fn is_execution_paused(&self) -> bool {
+        self.shared.execution_paused.load(Ordering::SeqCst)
+    }
+
+    /// Fails with `ExecutionPaused` if execution is paused.
+    pub(crate) fn ensure_execution_allowed(&self) -> Result<(), JupiterClientError> {
+        if self.is_execution_paused() {
+            return Err(JupiterClientError::ExecutionPaused);
+        }
+        Ok(())
+    }
+
+    /// Sends an `/execute` request so that retrying it is safe.
+    ///
+    /// A request id that already executed `signed_transaction` successfully returns the
+    /// recorded response without sending anything. If Jupiter answers that the transaction
+    /// was already processed, e.g. because the response to an earlier attempt was lost,
+    /// the earlier success is returned instead of the error: the recorded one, or one
+    /// rebuilt from the transaction's signature.
+    pub(crate) async fn send_execute<T: ExecuteResponse>(
+        &self,
+        family: ApiFamily,
+        request_id: &str,
+        signed_transaction: &str,
+        request: RequestBuilder,
+    ) -> Result<T, JupiterClientError> {
+        let signature = transaction_signature(signed_transaction);
+        if let Some(prior) = self
+            .shared
+            .executions
+            .get(family, request_id, signature.as_deref())
+        {
+            return Ok(prior);
+        }
+
+        let result = self.send_request::<T>(family, request).await;
+        let already_processed = match &result {
+            Ok(response) => response
+                .failure()
+                .is_some_and(|error| is_already_processed(&error)),
+            Err(error) => match error.inner() {
+                JupiterClientError::ApiError(error) => is_already_processed(&error.body),
+                _ => false,
+            },
+        };
+
+        if already_processed {
+            if let Some(prior) =
+                self.shared
+                    .executions
+                    .get(family, request_id, signature.as_deref())
+            {
+                return Ok(prior);
+            }
+            if let Some(landed) = signature.clone().and_then(T::landed) {
+                self.shared
+                    .executions
+                    .record(family, request_id, signature, &landed);
+                return Ok(landed);
+            }
+        } else if let Ok(response) = &result
+            && response.failure().is_none()
+        {
+            self.shared
+                .executions
+                .record(family, request_id, signature, response);
+        }
+        result
+    }
+}
+
+/// Whether an error message says the transaction has already landed.
+fn is_already_processed(message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+    message.contains("already processed")
+        || message.contains("already been processed")
+        || message.contains("alreadyprocessed")
+}
+
+/// The base58 fee payer signature of a base64 signed transaction, `None` if it isn't one
+/// or isn't signed yet.
+pub(crate) fn transaction_signature(signed_transaction: &str) -> Option<String> {
+    // a one-byte signature count and the 64-byte first signature, in 88 base64 characters
+    let bytes = STANDARD.decode(signed_transaction.get(..88)?).ok()?;
+    let signature = bytes.get(1..65)?;
+    if bytes[0] == 0 || signature.iter().all(|byte| *byte == 0) {
+        return None;
+    }
+    Some(bs58::encode(signature).into_string())
+}
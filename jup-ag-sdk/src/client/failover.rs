@@ -0,0 +1,105 @@
+use std::{
+    cell::RefCell,
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use super::HttpResponse;
+use crate::error::JupiterClientError;
+
+thread_local! {
+    /// Where the [`Captured`] future being polled on this thread records its meta, if any.
+    static SINK: RefCell<Option<Arc<Mutex<ResponseMeta>>>> = const { RefCell::new(None) };
+}
+
+/// Which host served a call, for clients with a
+/// [`failover`](super::JupiterClientBuilder::failover) or a
+/// [`hedge`](super::JupiterClientBuilder::hedge) host.
+///
+/// # Example
+/// ```
+/// let (quote, meta) = ResponseMeta::capture(client.get_quote(&request)).await;
+/// if meta.failed_over {
+///     tracing::warn!(base_url = ?meta.base_url, "quote served by the failover host");
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResponseMeta {
+    /// Base URL of the host that answered the call's last request, `None` if no request
+    /// was sent, e.g. because the response was cached.
+    pub base_url: Option<String>,
+    /// Whether that request was sent to the failover host after the client's host failed.
+    pub failed_over: bool,
+}
+
+impl ResponseMeta {
+    /// Runs `future`, returning its output with the meta of the requests it sent.
+    pub fn capture<F: Future>(future: F) -> Captured<F> {
+        Captured {
+            meta: Arc::default(),
+            future: Box::pin(future),
+        }
+    }
+}
+
+/// A future returning its output and [`ResponseMeta`], see [`ResponseMeta::capture`].
+pub struct Captured<F> {
+    meta: Arc<Mutex<ResponseMeta>>,
+    future: Pin<Box<F>>,
+}
+
+impl<F> fmt::Debug for Captured<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Captured")
+            .field("meta", &self.meta)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F: Future> Future for Captured<F> {
+    type Output = (F::Output, ResponseMeta);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let sink = self.meta.clone();
+        let outer = SINK.with(|current| current.replace(Some(sink)));
+        // restores the outer sink even if the future panics
+        let _restore = Restore(outer);
+        let output = std::task::ready!(self.future.as_mut().poll(cx));
+        let meta = self.meta.lock().unwrap().clone();
+        Poll::Ready((output, meta))
+    }
+}
+
+struct Restore(Option<Arc<Mutex<ResponseMeta>>>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let outer = self.0.take();
+        SINK.with(|current| *current.borrow_mut() = outer);
+    }
+}
+
+/// Records the host that answered a request, if the call is being captured.
+pub(crate) fn record(base_url: &str, failed_over: bool) {
+    SINK.with(|current| {
+        if let Some(sink) = &*current.borrow() {
+            *sink.lock().unwrap() = ResponseMeta {
+                base_url: Some(base_url.to_string()),
+                failed_over,
+            };
+        }
+    });
+}
+
+/// Whether a request that ended with `result` is sent to the failover host: server
+/// errors, timeouts and connection failures.
+pub(crate) fn should_fail_over(result: &Result<HttpResponse, JupiterClientError>) -> bool {
+    match result {
+        Ok(response) => response.status.is_server_error(),
+        Err(JupiterClientError::RequestError(error)) => error.is_timeout() || error.is_connect(),
+        Err(_) => false,
+    }
+}
@@ -0,0 +1,91 @@
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use crate::{error::JupiterClientError, runtime::Instant};
+
+/// Most response bodies kept for offline fallback; the oldest is evicted first.
+const MAX_ENTRIES: usize = 256;
+
+/// A response from one of the `*_or_cached` methods, marking whether it came
+/// from the network or from the offline fallback cache.
+#[derive(Debug)]
+pub struct CachedResponse<T> {
+    pub data: T,
+    pub freshness: Freshness,
+}
+
+/// Where a [`CachedResponse`] came from.
+#[derive(Debug)]
+pub enum Freshness {
+    /// Fetched from the API just now.
+    Fresh,
+    /// The network call failed and the last successful response was served instead.
+    Stale {
+        /// How long ago the cached response was fetched.
+        age: Duration,
+        /// The error that caused the fallback.
+        cause: JupiterClientError,
+    },
+}
+
+impl<T> CachedResponse<T> {
+    /// Returns `true` if the data was served from the cache after a failed request.
+    pub fn is_stale(&self) -> bool {
+        matches!(self.freshness, Freshness::Stale { .. })
+    }
+
+    /// Returns the data, discarding the freshness marker.
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+}
+
+/// Last successful response bodies of read endpoints, keyed by full request URL.
+#[derive(Debug)]
+pub(crate) struct FallbackCache {
+    max_age: Duration,
+    entries: Mutex<HashMap<String, (Instant, Vec<u8>)>>,
+}
+
+impl FallbackCache {
+    pub(crate) fn new(max_age: Duration) -> Self {
+        Self {
+            max_age,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn store(&self, key: String, body: Vec<u8>) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= MAX_ENTRIES && !entries.contains_key(&key) {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, (fetched_at, _))| *fetched_at)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(key, (Instant::now(), body));
+    }
+
+    /// The cached body for `key` and its age, unless it is older than `max_age`.
+    pub(crate) fn get(&self, key: &str) -> Option<(Duration, Vec<u8>)> {
+        let entries = self.entries.lock().unwrap();
+        let (fetched_at, body) = entries.get(key)?;
+        let age = fetched_at.elapsed();
+        (age <= self.max_age).then(|| (age, body.clone()))
+    }
+}
+
+/// Whether `error` looks like an outage worth papering over with cached data:
+/// connection failures, timeouts, rate limiting, server errors and open circuit breakers.
+pub(crate) fn is_outage(error: &JupiterClientError) -> bool {
+    match error.inner() {
+        JupiterClientError::RequestError(e) => !e.is_decode() && !e.is_builder(),
+        JupiterClientError::ApiError(error) => {
+            error.status.is_server_error() || error.status.as_u16() == 429
+        }
+        JupiterClientError::RateLimited { .. } | JupiterClientError::CircuitOpen { .. } => true,
+        _ => false,
+    }
+}
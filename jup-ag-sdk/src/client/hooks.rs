@@ -0,0 +1,152 @@
+use std::{fmt, sync::Arc};
+
+use reqwest::{Method, Request};
+use serde_json::Value;
+
+use crate::{
+    config::ApiFamily,
+    error::{ErrorContext, JupiterClientError},
+};
+
+/// The editable parts of an outgoing request, passed to request hooks.
+///
+/// See [`JupiterClientBuilder::request_hook`](super::JupiterClientBuilder::request_hook).
+#[derive(Debug, Clone)]
+pub struct RequestParts {
+    /// The API family the request is for.
+    pub family: ApiFamily,
+    pub method: Method,
+    /// The URL path, e.g. `/swap/v1/quote`.
+    pub path: String,
+    /// Query parameters in order.
+    pub query: Vec<(String, String)>,
+    /// The JSON body, `None` for requests without one (e.g. GET).
+    pub body: Option<Value>,
+}
+
+impl RequestParts {
+    /// Returns the first query parameter named `name`.
+    pub fn query_param(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Sets query parameter `name`, replacing any existing values.
+    pub fn set_query_param(&mut self, name: &str, value: impl ToString) {
+        self.query.retain(|(n, _)| n != name);
+        self.query.push((name.to_string(), value.to_string()));
+    }
+
+    /// Sets query parameter `name` only if the request doesn't already have it.
+    pub fn default_query_param(&mut self, name: &str, value: impl ToString) {
+        if self.query_param(name).is_none() {
+            self.query.push((name.to_string(), value.to_string()));
+        }
+    }
+
+    /// Sets a top-level field on a JSON object body, replacing any existing value.
+    ///
+    /// Does nothing if the request has no body or the body is not an object.
+    pub fn set_body_field(&mut self, name: &str, value: impl Into<Value>) {
+        if let Some(Value::Object(body)) = &mut self.body {
+            body.insert(name.to_string(), value.into());
+        }
+    }
+
+    /// Sets a top-level field on a JSON object body only if it is missing or `null`.
+    pub fn default_body_field(&mut self, name: &str, value: impl Into<Value>) {
+        if let Some(Value::Object(body)) = &mut self.body {
+            let field = body.entry(name).or_insert(Value::Null);
+            if field.is_null() {
+                *field = value.into();
+            }
+        }
+    }
+}
+
+type HookFn = dyn Fn(&mut RequestParts) + Send + Sync;
+
+/// A request hook registered on the client builder.
+#[derive(Clone)]
+pub(crate) struct RequestHook(Arc<HookFn>);
+
+impl RequestHook {
+    pub(crate) fn new(hook: impl Fn(&mut RequestParts) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(hook))
+    }
+}
+
+impl fmt::Debug for RequestHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RequestHook")
+    }
+}
+
+type ErrorFn = dyn Fn(&ErrorContext<'_>) + Send + Sync;
+
+/// The `on_error` callback registered on the client builder.
+#[derive(Clone)]
+pub(crate) struct ErrorCallback(Arc<ErrorFn>);
+
+impl ErrorCallback {
+    pub(crate) fn new(callback: impl Fn(&ErrorContext<'_>) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+
+    pub(crate) fn call(&self, context: &ErrorContext<'_>) {
+        (self.0)(context)
+    }
+}
+
+impl fmt::Debug for ErrorCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ErrorCallback")
+    }
+}
+
+/// Runs `hooks` in order over `request`, then writes the edited query and body back.
+pub(crate) fn apply(
+    hooks: &[RequestHook],
+    family: ApiFamily,
+    request: &mut Request,
+) -> Result<(), JupiterClientError> {
+    let body = match request.body().and_then(|b| b.as_bytes()) {
+        Some(bytes) => Some(serde_json::from_slice::<Value>(bytes).map_err(|e| {
+            JupiterClientError::ValidationError(format!("request body is not JSON: {e}"))
+        })?),
+        None => None,
+    };
+
+    let mut parts = RequestParts {
+        family,
+        method: request.method().clone(),
+        path: request.url().path().to_string(),
+        query: request
+            .url()
+            .query_pairs()
+            .map(|(n, v)| (n.into_owned(), v.into_owned()))
+            .collect(),
+        body,
+    };
+
+    for hook in hooks {
+        (hook.0)(&mut parts);
+    }
+
+    let url = request.url_mut();
+    if parts.query.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(&parts.query);
+    }
+
+    if let Some(body) = parts.body {
+        let bytes = serde_json::to_vec(&body)
+            .map_err(|e| JupiterClientError::ValidationError(e.to_string()))?;
+        *request.body_mut() = Some(bytes.into());
+    }
+
+    Ok(())
+}
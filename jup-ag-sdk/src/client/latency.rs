@@ -0,0 +1,149 @@
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use crate::config::ApiFamily;
+
+/// Upper bounds of the histogram buckets, in milliseconds; slower calls fall in a last,
+/// unbounded bucket.
+const BUCKET_BOUNDS_MS: [u64; 12] = [
+    5, 10, 25, 50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 30_000,
+];
+
+/// Distribution of the response times of one endpoint, in fixed buckets from 5ms to 30s.
+///
+/// Returned by [`JupiterClient::latency`](crate::JupiterClient::latency) and
+/// [`JupiterClient::latencies`](crate::JupiterClient::latencies). Times cover the HTTP
+/// exchange only, not waits for rate or concurrency limits.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LatencyHistogram {
+    counts: [u64; BUCKET_BOUNDS_MS.len() + 1],
+    count: u64,
+    total: Duration,
+    max: Duration,
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a call that took `latency`.
+    pub fn record(&mut self, latency: Duration) {
+        let millis = latency.as_millis();
+        let bucket = BUCKET_BOUNDS_MS
+            .iter()
+            .position(|bound| millis <= u128::from(*bound))
+            .unwrap_or(BUCKET_BOUNDS_MS.len());
+        self.counts[bucket] += 1;
+        self.count += 1;
+        self.total += latency;
+        self.max = self.max.max(latency);
+    }
+
+    /// Number of calls recorded.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Average latency, `None` before any call.
+    pub fn mean(&self) -> Option<Duration> {
+        (self.count > 0)
+            .then(|| Duration::from_nanos((self.total.as_nanos() / u128::from(self.count)) as u64))
+    }
+
+    /// The slowest call, `None` before any.
+    pub fn max(&self) -> Option<Duration> {
+        (self.count > 0).then_some(self.max)
+    }
+
+    /// The latency under which a `quantile` (0 to 1) of the calls completed, e.g. `0.99`
+    /// for the p99. Rounded up to its bucket's upper bound, and never above
+    /// [`max`](Self::max). `None` before any call.
+    pub fn percentile(&self, quantile: f64) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+        let rank = ((quantile.clamp(0.0, 1.0) * self.count as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (bucket, count) in self.buckets() {
+            seen += count;
+            if seen >= rank {
+                return Some(bucket.map_or(self.max, |bound| bound.min(self.max)));
+            }
+        }
+        Some(self.max)
+    }
+
+    /// The number of calls in each bucket, by upper bound, e.g. to export to a metrics
+    /// system. The last bucket, bounded by `None`, holds calls slower than 30s. Counts
+    /// aren't cumulative.
+    pub fn buckets(&self) -> impl Iterator<Item = (Option<Duration>, u64)> + '_ {
+        BUCKET_BOUNDS_MS
+            .iter()
+            .map(|bound| Some(Duration::from_millis(*bound)))
+            .chain([None])
+            .zip(self.counts.iter().copied())
+    }
+}
+
+/// The latency of one endpoint, returned by
+/// [`JupiterClient::latencies`](crate::JupiterClient::latencies).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointLatency {
+    pub family: ApiFamily,
+    /// The URL path, with pubkeys replaced by `:pubkey`, e.g. `/tokens/v1/token/:pubkey`.
+    pub path: String,
+    pub histogram: LatencyHistogram,
+}
+
+/// Latency histograms of the endpoints the client called.
+#[derive(Debug, Default)]
+pub(crate) struct LatencyTracker {
+    endpoints: Mutex<HashMap<(ApiFamily, String), LatencyHistogram>>,
+}
+
+impl LatencyTracker {
+    pub(crate) fn record(&self, family: ApiFamily, path: &str, latency: Duration) {
+        self.endpoints
+            .lock()
+            .unwrap()
+            .entry((family, endpoint_path(path)))
+            .or_default()
+            .record(latency);
+    }
+
+    pub(crate) fn get(&self, family: ApiFamily, path: &str) -> Option<LatencyHistogram> {
+        let endpoints = self.endpoints.lock().unwrap();
+        endpoints.get(&(family, endpoint_path(path))).cloned()
+    }
+
+    pub(crate) fn all(&self) -> Vec<EndpointLatency> {
+        let mut all: Vec<_> = self
+            .endpoints
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|((family, path), histogram)| EndpointLatency {
+                family: *family,
+                path: path.clone(),
+                histogram: histogram.clone(),
+            })
+            .collect();
+        all.sort_by(|a, b| (a.family.as_str(), &a.path).cmp(&(b.family.as_str(), &b.path)));
+        all
+    }
+}
+
+/// `path` with its pubkey segments, e.g. a mint or wallet, replaced by `:pubkey`, so
+/// every token or wallet shares one histogram.
+pub(crate) fn endpoint_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| {
+            let base58 = (32..=44).contains(&segment.len())
+                && segment
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() && !b"0OIl".contains(&b));
+            if base58 { ":pubkey" } else { segment }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
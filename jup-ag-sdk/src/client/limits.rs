@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use crate::config::ApiFamily;
+
+/// Caps on the number of in-flight requests, client-wide and per API family.
+#[derive(Debug, Default)]
+pub(crate) struct ConcurrencyLimits {
+    global: Option<Semaphore>,
+    per_family: HashMap<ApiFamily, Semaphore>,
+}
+
+/// Permits held for the duration of a request, released on drop.
+pub(crate) struct ConcurrencyPermits<'a> {
+    _global: Option<SemaphorePermit<'a>>,
+    _family: Option<SemaphorePermit<'a>>,
+    /// Whether any limit was saturated and the request had to wait.
+    pub(crate) waited: bool,
+}
+
+impl ConcurrencyLimits {
+    pub(crate) fn new(global: Option<usize>, per_family: &HashMap<ApiFamily, usize>) -> Self {
+        Self {
+            global: global.map(Semaphore::new),
+            per_family: per_family
+                .iter()
+                .map(|(family, limit)| (*family, Semaphore::new(*limit)))
+                .collect(),
+        }
+    }
+
+    /// Waits until a slot is free in both the family and the client-wide limit.
+    ///
+    /// The family permit is taken first so a saturated family doesn't hold on to
+    /// client-wide slots other families could use.
+    pub(crate) async fn acquire(&self, family: ApiFamily) -> ConcurrencyPermits<'_> {
+        let mut waited = false;
+        let family = match self.per_family.get(&family) {
+            Some(semaphore) => Some(acquire(semaphore, &mut waited).await),
+            None => None,
+        };
+        let global = match &self.global {
+            Some(semaphore) => Some(acquire(semaphore, &mut waited).await),
+            None => None,
+        };
+
+        ConcurrencyPermits {
+            _global: global,
+            _family: family,
+            waited,
+        }
+    }
+
+    /// Number of free slots for `family`, `None` if the family is unlimited.
+    pub(crate) fn available(&self, family: ApiFamily) -> Option<usize> {
+        let family = self
+            .per_family
+            .get(&family)
+            .map(Semaphore::available_permits);
+        let global = self.global.as_ref().map(Semaphore::available_permits);
+        match (family, global) {
+            (Some(f), Some(g)) => Some(f.min(g)),
+            (f, g) => f.or(g),
+        }
+    }
+}
+
+async fn acquire<'a>(semaphore: &'a Semaphore, waited: &mut bool) -> SemaphorePermit<'a> {
+    if let Ok(permit) = semaphore.try_acquire() {
+        return permit;
+    }
+    *waited = true;
+    // the semaphores are never closed
+    semaphore.acquire().await.expect("semaphore closed")
+}
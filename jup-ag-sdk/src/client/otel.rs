@@ -0,0 +1,99 @@
+use opentelemetry::{
+    Context, KeyValue, global,
+    propagation::Injector,
+    trace::{SpanKind, Status, TraceContextExt, Tracer},
+};
+use reqwest::{
+    Request, StatusCode,
+    header::{HeaderMap, HeaderName, HeaderValue},
+};
+
+use crate::{config::ApiFamily, error::JupiterClientError};
+
+/// Instrumentation scope of the spans created by the client.
+const TRACER_NAME: &str = "jup-ag-sdk";
+
+/// The client span of one API call, a child of the caller's current OpenTelemetry context.
+///
+/// Spans come from the global tracer provider and trace context is injected with the
+/// global propagator, so nothing is recorded or sent until the application installs them.
+pub(crate) struct CallSpan(Context);
+
+impl CallSpan {
+    /// Starts the span for `request` and adds its trace context headers (e.g.
+    /// `traceparent`) to it. The query string is left out of `url.full`, as it carries
+    /// wallet addresses and amounts.
+    pub(crate) fn start(family: ApiFamily, request: &mut Request) -> Self {
+        let url = request.url();
+        let mut attributes = vec![
+            KeyValue::new("http.request.method", request.method().to_string()),
+            KeyValue::new(
+                "url.full",
+                format!("{}{}", url.origin().ascii_serialization(), url.path()),
+            ),
+            KeyValue::new("jupiter.api_family", family.as_str()),
+        ];
+        if let Some(host) = url.host_str() {
+            attributes.push(KeyValue::new("server.address", host.to_string()));
+        }
+        if let Some(port) = url.port_or_known_default() {
+            attributes.push(KeyValue::new("server.port", i64::from(port)));
+        }
+
+        let tracer = global::tracer(TRACER_NAME);
+        let span = tracer
+            .span_builder(format!("{} {}", request.method(), url.path()))
+            .with_kind(SpanKind::Client)
+            .with_attributes(attributes)
+            .start_with_context(&tracer, &Context::current());
+        let cx = Context::current_with_span(span);
+
+        global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&cx, &mut HeaderInjector(request.headers_mut()))
+        });
+        Self(cx)
+    }
+
+    /// Records the response status.
+    pub(crate) fn record_status(&self, status: StatusCode) {
+        self.0.span().set_attribute(KeyValue::new(
+            "http.response.status_code",
+            i64::from(status.as_u16()),
+        ));
+    }
+
+    /// Ends the span, marking it as failed with `error` if the call failed.
+    pub(crate) fn end<T>(self, result: &Result<T, JupiterClientError>) {
+        let span = self.0.span();
+        if let Err(error) = result {
+            span.set_attribute(KeyValue::new("error.type", error_type(error)));
+            span.set_status(Status::error(error.to_string()));
+        }
+        span.end();
+    }
+}
+
+/// The low-cardinality `error.type` of a failed call.
+fn error_type(error: &JupiterClientError) -> String {
+    match error {
+        JupiterClientError::ApiError(error) => error.status.as_u16().to_string(),
+        JupiterClientError::RateLimited { .. } => "429".to_string(),
+        JupiterClientError::RequestError(e) if e.is_timeout() => "timeout".to_string(),
+        JupiterClientError::RequestError(_) => "request".to_string(),
+        JupiterClientError::DeserializationError { .. } => "deserialization".to_string(),
+        _ => "other".to_string(),
+    }
+}
+
+struct HeaderInjector<'a>(&'a mut HeaderMap);
+
+impl Injector for HeaderInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(key.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            self.0.insert(name, value);
+        }
+    }
+}
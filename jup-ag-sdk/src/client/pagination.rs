@@ -0,0 +1,40 @@
+use std::future::Future;
+
+use futures_util::{StreamExt, TryStreamExt, stream};
+
+use crate::error::JupiterClientError;
+
+/// Most pages the `*_all` methods keep in flight at once.
+pub(crate) const MAX_PARALLEL_PAGES: usize = 4;
+
+/// Fails unless `max_pages` is `None` or at least 1.
+pub(crate) fn validate_max_pages(max_pages: Option<u64>) -> Result<(), JupiterClientError> {
+    if max_pages == Some(0) {
+        return Err(JupiterClientError::ValidationError(
+            "max_pages must be greater than zero".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// The last page to fetch of `total_pages`, at most `max_pages` and at least 1.
+pub(crate) fn last_page(total_pages: u64, max_pages: Option<u64>) -> u64 {
+    total_pages.min(max_pages.unwrap_or(u64::MAX)).max(1)
+}
+
+/// Fetches `pages` with `fetch`, [`MAX_PARALLEL_PAGES`] at a time, returning them in
+/// order. Fails with the first error.
+pub(crate) async fn fetch_pages<T, F, Fut>(
+    pages: impl Iterator<Item = u64>,
+    fetch: F,
+) -> Result<Vec<T>, JupiterClientError>
+where
+    F: Fn(u64) -> Fut,
+    Fut: Future<Output = Result<T, JupiterClientError>>,
+{
+    stream::iter(pages)
+        .map(fetch)
+        .buffered(MAX_PARALLEL_PAGES)
+        .try_collect()
+        .await
+}
@@ -0,0 +1,72 @@
+use super::JupiterClient;
+use crate::{
+    config::ApiFamily,
+    error::JupiterClientError,
+    types::{PerpsMarket, PerpsMarketStats, PerpsPoolInfo, PerpsPositions, validate_pubkey},
+};
+
+impl JupiterClient {
+    /// Returns the open Jupiter Perps positions of `wallet`.
+    ///
+    /// Perps requests go to the [`perps_base_url`](super::JupiterClientBuilder::perps_base_url)
+    /// with the client's headers, limits and retries.
+    ///
+    /// # Example
+    /// ```
+    /// let positions = client.get_perps_positions(wallet).await?;
+    /// for position in &positions.data_list {
+    ///     println!("{:?} {:?}: {:?} PnL", position.side, position.market(), position.pnl_after_fees);
+    /// }
+    /// ```
+    pub async fn get_perps_positions(
+        &self,
+        wallet: &str,
+    ) -> Result<PerpsPositions, JupiterClientError> {
+        validate_pubkey("wallet", wallet)?;
+
+        let request = self
+            .client
+            .get(self.endpoint(ApiFamily::Perps, "/positions"))
+            .query(&[("walletAddress", wallet)]);
+
+        self.send_request::<PerpsPositions>(ApiFamily::Perps, request)
+            .await
+    }
+
+    /// Returns the price statistics of `market` over the last 24 hours.
+    pub async fn get_perps_market_stats(
+        &self,
+        market: PerpsMarket,
+    ) -> Result<PerpsMarketStats, JupiterClientError> {
+        let request = self
+            .client
+            .get(self.endpoint(ApiFamily::Perps, "/market-stats"))
+            .query(&[("mint", market.mint())]);
+
+        self.send_request::<PerpsMarketStats>(ApiFamily::Perps, request)
+            .await
+    }
+
+    /// Returns the liquidity, utilization and borrow rates of `market`, the cost of holding
+    /// a position there.
+    ///
+    /// # Example
+    /// ```
+    /// for market in PerpsMarket::ALL {
+    ///     let pool = client.get_perps_pool_info(market).await?;
+    ///     println!("{}: {:?}%/h", market.symbol(), pool.borrow_rate_percent(PerpsSide::Long));
+    /// }
+    /// ```
+    pub async fn get_perps_pool_info(
+        &self,
+        market: PerpsMarket,
+    ) -> Result<PerpsPoolInfo, JupiterClientError> {
+        let request = self
+            .client
+            .get(self.endpoint(ApiFamily::Perps, "/pool-info"))
+            .query(&[("mint", market.mint())]);
+
+        self.send_request::<PerpsPoolInfo>(ApiFamily::Perps, request)
+            .await
+    }
+}
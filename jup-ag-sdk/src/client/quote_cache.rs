@@ -0,0 +1,115 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use crate::{
+    config::QuoteCacheConfig,
+    runtime::Instant,
+    types::{QuoteRequest, QuoteResponse},
+};
+
+/// Recent Swap API quotes keyed by request, see [`QuoteCacheConfig`].
+#[derive(Debug)]
+pub(crate) struct QuoteCache {
+    config: QuoteCacheConfig,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// The highest slot seen, from quotes or [`QuoteCache::observe_slot`].
+    latest_slot: u64,
+    entries: HashMap<String, Entry>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    fetched_at: Instant,
+    quote: QuoteResponse,
+}
+
+impl QuoteCache {
+    pub(crate) fn new(config: QuoteCacheConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// The cache key of `params`: the request with its amount bucketed.
+    pub(crate) fn key(&self, params: &QuoteRequest) -> String {
+        let mut params = params.clone();
+        if let Some(digits) = self.config.amount_precision {
+            params.amount = bucket(params.amount, digits);
+        }
+        // a QuoteRequest always serializes
+        serde_json::to_string(&params).unwrap_or_default()
+    }
+
+    /// The cached quote for `key`, if it is still within the TTL and slot lag.
+    pub(crate) fn get(&self, key: &str) -> Option<QuoteResponse> {
+        let state = self.state.lock().unwrap();
+        let entry = state.entries.get(key)?;
+        self.is_fresh(entry, state.latest_slot, Instant::now())
+            .then(|| entry.quote.clone())
+    }
+
+    pub(crate) fn insert(&self, key: String, quote: QuoteResponse) {
+        if self.config.capacity == 0 {
+            return;
+        }
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        state.latest_slot = state.latest_slot.max(quote.context_slot);
+
+        if state.entries.len() >= self.config.capacity && !state.entries.contains_key(&key) {
+            let latest_slot = state.latest_slot;
+            state
+                .entries
+                .retain(|_, entry| self.is_fresh(entry, latest_slot, now));
+        }
+        if state.entries.len() >= self.config.capacity && !state.entries.contains_key(&key) {
+            let oldest = state
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.fetched_at)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                state.entries.remove(&oldest);
+            }
+        }
+        state.entries.insert(
+            key,
+            Entry {
+                fetched_at: now,
+                quote,
+            },
+        );
+    }
+
+    pub(crate) fn observe_slot(&self, slot: u64) {
+        let mut state = self.state.lock().unwrap();
+        state.latest_slot = state.latest_slot.max(slot);
+    }
+
+    pub(crate) fn clear(&self) {
+        self.state.lock().unwrap().entries.clear();
+    }
+
+    fn is_fresh(&self, entry: &Entry, latest_slot: u64, now: Instant) -> bool {
+        now.saturating_duration_since(entry.fetched_at) <= self.config.ttl
+            && latest_slot
+                <= entry
+                    .quote
+                    .context_slot
+                    .saturating_add(self.config.max_slot_lag)
+    }
+}
+
+/// Rounds `amount` down to its `digits` most significant digits.
+fn bucket(amount: u64, digits: u32) -> u64 {
+    let len = amount.checked_ilog10().map_or(1, |log| log + 1);
+    if digits == 0 || len <= digits {
+        return amount;
+    }
+    let unit = 10u64.pow(len - digits);
+    amount / unit * unit
+}
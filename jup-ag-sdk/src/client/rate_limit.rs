@@ -0,0 +1,162 @@
+use std::{sync::Mutex, time::Duration};
+
+use reqwest::header::HeaderMap;
+
+use crate::{
+    config::RateLimitPlan,
+    runtime::{Instant, Runtime, SystemTime, UNIX_EPOCH},
+};
+
+/// The allowance reported by the rate limit headers of a response:
+/// `x-ratelimit-limit`, `x-ratelimit-remaining` and `x-ratelimit-reset`, or the same
+/// without the `x-` prefix.
+///
+/// The latest for each API family is kept by the client, see
+/// [`JupiterClient::rate_limit_info`](super::JupiterClient::rate_limit_info).
+///
+/// # Example
+/// ```
+/// if let Some(info) = client.rate_limit_info(ApiFamily::Swap)
+///     && info.remaining == Some(0)
+/// {
+///     tokio::time::sleep(info.reset.unwrap_or(Duration::from_secs(1))).await;
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    /// Requests allowed per window.
+    pub limit: Option<u32>,
+    /// Requests left in the current window.
+    pub remaining: Option<u32>,
+    /// Time from [`observed_at`](Self::observed_at) until the window resets. The header
+    /// is read as seconds, or as a Unix timestamp if it is one.
+    pub reset: Option<Duration>,
+    /// When the response was received.
+    pub observed_at: Instant,
+}
+
+impl RateLimitInfo {
+    /// Parses the rate limit headers of a response, `None` if it has none of them.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name: &str| {
+            [format!("x-{name}"), name.to_string()]
+                .iter()
+                .find_map(|name| headers.get(name.as_str()))
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok())
+        };
+        let count = |name| header(name).map(|n| n.min(u32::MAX as u64) as u32);
+
+        let info = Self {
+            limit: count("ratelimit-limit"),
+            remaining: count("ratelimit-remaining"),
+            reset: header("ratelimit-reset").map(reset_duration),
+            observed_at: Instant::now(),
+        };
+        (info.limit.is_some() || info.remaining.is_some() || info.reset.is_some()).then_some(info)
+    }
+
+    /// When the window resets, if the response said.
+    pub fn resets_at(&self) -> Option<Instant> {
+        Some(self.observed_at + self.reset?)
+    }
+}
+
+/// Values past a year of seconds are Unix timestamps rather than delays.
+fn reset_duration(value: u64) -> Duration {
+    const YEAR: u64 = 365 * 24 * 60 * 60;
+    if value <= YEAR {
+        return Duration::from_secs(value);
+    }
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    Duration::from_secs(value).saturating_sub(now)
+}
+
+/// Token bucket refilled continuously at `requests / window`, holding at most `requests` tokens.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    plan: RateLimitPlan,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl Bucket {
+    fn refill(&mut self, now: Instant) {
+        let capacity = self.plan.requests() as f64;
+        let rate = capacity / self.plan.window().as_secs_f64();
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(capacity);
+        self.last_refill = now;
+    }
+
+    /// Time until one full token is available.
+    fn wait_time(&self) -> Duration {
+        let rate = self.plan.requests() as f64 / self.plan.window().as_secs_f64();
+        Duration::from_secs_f64((1.0 - self.tokens) / rate)
+    }
+}
+
+impl RateLimiter {
+    pub(crate) fn new(plan: RateLimitPlan) -> Self {
+        Self {
+            bucket: Mutex::new(Bucket {
+                plan,
+                tokens: plan.requests() as f64,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// The allowance currently enforced, which may differ from the configured
+    /// preset after the API reported a different limit.
+    pub(crate) fn plan(&self) -> RateLimitPlan {
+        self.bucket.lock().unwrap().plan
+    }
+
+    /// Waits until the plan allows another request, returning how long it had to wait.
+    pub(crate) async fn acquire(&self, runtime: &dyn Runtime) -> Duration {
+        let mut waited = Duration::ZERO;
+        loop {
+            let wait = {
+                let mut bucket = self.bucket.lock().unwrap();
+                bucket.refill(Instant::now());
+                if bucket.tokens >= 1.0 {
+                    bucket.tokens -= 1.0;
+                    return waited;
+                }
+                bucket.wait_time()
+            };
+            let start = Instant::now();
+            runtime.sleep(wait).await;
+            waited += start.elapsed();
+        }
+    }
+
+    /// Adjusts to the allowance reported by a response's rate limit headers.
+    pub(crate) fn observe(&self, info: &RateLimitInfo) {
+        let mut bucket = self.bucket.lock().unwrap();
+        if let Some(limit) = info
+            .limit
+            .filter(|limit| *limit > 0 && *limit != bucket.plan.requests())
+        {
+            bucket.refill(Instant::now());
+            bucket.plan = RateLimitPlan::Custom {
+                requests: limit,
+                window: bucket.plan.window(),
+            };
+            bucket.tokens = bucket.tokens.min(limit as f64);
+        }
+        if let Some(remaining) = info.remaining {
+            bucket.tokens = bucket.tokens.min(remaining as f64);
+        }
+    }
+}
@@ -1,14 +1,19 @@
 use crate::{
     JupiterClientError,
-    error::handle_response,
+    config::ApiFamily,
+    events::LifecycleEvent,
     types::{
         CancelRecurringOrderRequest, CreateRecurringOrderRequest, ExecuteRecurringRequest,
         ExecuteRecurringResponse, GetRecurringOrders, PriceDeposit, PriceWithdraw, RecurringOrders,
-        RecurringResponse,
+        RecurringResponse, Validate,
     },
 };
 
-use super::JupiterClient;
+use super::{
+    JupiterClient,
+    executions::ExecuteResponse,
+    pagination::{fetch_pages, last_page, validate_max_pages},
+};
 
 impl JupiterClient {
     /// Sends a request to create a new recurring order.
@@ -18,23 +23,22 @@ impl JupiterClient {
         &self,
         data: &CreateRecurringOrderRequest,
     ) -> Result<RecurringResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .post(format!("{}/recurring/v1/createOrder", self.base_url))
-            .json(data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
+        data.validate()?;
 
-        match response.json::<RecurringResponse>().await {
-            Ok(resp) => Ok(resp),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        let request = self
+            .client
+            .post(self.endpoint(ApiFamily::Recurring, "/createOrder"))
+            .json(data);
+
+        let response = self
+            .send_request::<RecurringResponse>(ApiFamily::Recurring, request)
+            .await?;
+
+        self.emit(|| LifecycleEvent::OrderCreated {
+            family: ApiFamily::Recurring,
+            request_id: response.request_id.clone(),
+        });
+        Ok(response)
     }
 
     /// Request for a base64-encoded unsigned recurring order cancellation transaction
@@ -42,23 +46,15 @@ impl JupiterClient {
         &self,
         data: &CancelRecurringOrderRequest,
     ) -> Result<RecurringResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .post(format!("{}/recurring/v1/cancelOrder", self.base_url))
-            .json(data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+        data.validate()?;
 
-        let response = handle_response(response).await?;
+        let request = self
+            .client
+            .post(self.endpoint(ApiFamily::Recurring, "/cancelOrder"))
+            .json(data);
 
-        match response.json::<RecurringResponse>().await {
-            Ok(resp) => Ok(resp),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        self.send_request::<RecurringResponse>(ApiFamily::Recurring, request)
+            .await
     }
 
     /// Request for a base64-encoded unsigned price-based recurring order deposit transaction
@@ -66,23 +62,15 @@ impl JupiterClient {
         &self,
         data: &PriceDeposit,
     ) -> Result<RecurringResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .post(format!("{}/recurring/v1/priceDeposit", self.base_url))
-            .json(data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+        data.validate()?;
 
-        let response = handle_response(response).await?;
+        let request = self
+            .client
+            .post(self.endpoint(ApiFamily::Recurring, "/priceDeposit"))
+            .json(data);
 
-        match response.json::<RecurringResponse>().await {
-            Ok(resp) => Ok(resp),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        self.send_request::<RecurringResponse>(ApiFamily::Recurring, request)
+            .await
     }
 
     /// Request for a base64-encoded unsigned price-based recurring order withdrawal transaction
@@ -90,47 +78,55 @@ impl JupiterClient {
         &self,
         data: &PriceWithdraw,
     ) -> Result<RecurringResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .post(format!("{}/recurring/v1/priceWithdraw", self.base_url))
-            .json(data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+        data.validate()?;
 
-        let response = handle_response(response).await?;
+        let request = self
+            .client
+            .post(self.endpoint(ApiFamily::Recurring, "/priceWithdraw"))
+            .json(data);
 
-        match response.json::<RecurringResponse>().await {
-            Ok(resp) => Ok(resp),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        self.send_request::<RecurringResponse>(ApiFamily::Recurring, request)
+            .await
     }
 
     /// execute a recurring order
+    ///
+    /// Safe to retry, like [`ultra_execute_order`](Self::ultra_execute_order).
     pub async fn execute_recurring_order(
         &self,
         data: &ExecuteRecurringRequest,
     ) -> Result<ExecuteRecurringResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .post(format!("{}/recurring/v1/execute", self.base_url))
-            .json(data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
+        data.validate()?;
+        self.ensure_execution_allowed()?;
 
-        match response.json::<ExecuteRecurringResponse>().await {
-            Ok(resp) => Ok(resp),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        let request = self
+            .client
+            .post(self.endpoint(ApiFamily::Recurring, "/execute"))
+            .json(data);
+
+        self.emit(|| LifecycleEvent::ExecutionSubmitted {
+            family: ApiFamily::Recurring,
+            request_id: data.request_id.clone(),
+        });
+        let result = self
+            .send_execute::<ExecuteRecurringResponse>(
+                ApiFamily::Recurring,
+                &data.request_id,
+                &data.signed_transaction,
+                request,
+            )
+            .await;
+
+        self.emit_execution(
+            ApiFamily::Recurring,
+            &data.request_id,
+            &result,
+            |response| match response.failure() {
+                None => Ok(Some(response.signature.clone())),
+                Some(error) => Err(error),
+            },
+        );
+        result
     }
 
     /// Request for the active or historical orders associated to the provided account
@@ -138,22 +134,50 @@ impl JupiterClient {
         &self,
         data: &GetRecurringOrders,
     ) -> Result<RecurringOrders, JupiterClientError> {
-        let response = match self
+        data.validate()?;
+
+        let request = self
             .client
-            .get(format!("{}/recurring/v1/getRecurringOrders", self.base_url))
-            .query(&data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+            .get(self.endpoint(ApiFamily::Recurring, "/getRecurringOrders"))
+            .query(&data);
 
-        let response = handle_response(response).await?;
+        self.send_request::<RecurringOrders>(ApiFamily::Recurring, request)
+            .await
+    }
 
-        match response.json::<RecurringOrders>().await {
-            Ok(orders) => Ok(orders),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+    /// Every page of the orders matching `data`, fetched a few pages at a time and
+    /// returned as one list of the `all`, `time` and `price` orders of each page. Starts
+    /// from page 1, whatever the page of `data`.
+    ///
+    /// `max_pages` caps how many pages are read, e.g. for wallets with a long history;
+    /// `Some(0)` fails with a `ValidationError`.
+    ///
+    /// # Example
+    /// ```
+    /// let request = GetRecurringOrders::new(RecurringOrderType::All, OrderStatus::History, wallet);
+    /// let history = api.get_recurring_orders_all(&request, Some(20)).await?;
+    /// ```
+    pub async fn get_recurring_orders_all(
+        &self,
+        data: &GetRecurringOrders,
+        max_pages: Option<u64>,
+    ) -> Result<Vec<serde_json::Value>, JupiterClientError> {
+        validate_max_pages(max_pages)?;
+        let page_request = |page: u64| data.clone().with_page(page);
+
+        let first = self.get_recurring_orders(&page_request(1)).await?;
+        let last = last_page(first.total_pages, max_pages);
+        let rest = fetch_pages(2..=last, |page| {
+            let request = page_request(page);
+            async move { self.get_recurring_orders(&request).await }
+        })
+        .await?;
+
+        Ok(std::iter::once(first)
+            .chain(rest)
+            .flat_map(|response| [response.all, response.time, response.price])
+            .flatten()
+            .flatten()
+            .collect())
     }
 }
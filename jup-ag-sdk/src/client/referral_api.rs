@@ -0,0 +1,191 @@
+use super::JupiterClient;
+use crate::{
+    error::JupiterClientError,
+    referral::{
+        ReferralAccount, ReferralFeeAccount, TOKEN_PROGRAM_ID, create_referral_account_instruction,
+        create_referral_token_account_instruction, referral_account, referral_token_account,
+    },
+    types::UltraOrderRequest,
+};
+
+impl JupiterClient {
+    /// Derives the referral token account collecting fees in `mint` for `referral_account`
+    /// and checks on chain, through the Solana RPC at `rpc_url`, whether it exists.
+    ///
+    /// If it doesn't, the result carries an instruction creating it, paid for by `payer`,
+    /// using the token program that owns `mint`.
+    ///
+    /// Requires the `solana` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let fee_account = client
+    ///     .referral_fee_account(
+    ///         "https://api.mainnet-beta.solana.com",
+    ///         "YourReferralAccount...",
+    ///         referral::fee_mint(&quote),
+    ///         "YourPubKey...",
+    ///     )
+    ///     .await?;
+    ///
+    /// if let Some(create) = fee_account.create_instruction {
+    ///     // send `create` before swapping
+    /// }
+    /// let payload = SwapRequest::new("YourPubKey...", quote).fee_account(fee_account.address);
+    /// ```
+    pub async fn referral_fee_account(
+        &self,
+        rpc_url: &str,
+        referral_account: &str,
+        mint: &str,
+        payer: &str,
+    ) -> Result<ReferralFeeAccount, JupiterClientError> {
+        let mut accounts = self
+            .referral_fee_accounts(rpc_url, referral_account, &[mint], payer)
+            .await?;
+        Ok(accounts.remove(0))
+    }
+
+    /// Derives the referral account named `name` and checks on chain, through the Solana
+    /// RPC at `rpc_url`, whether it exists.
+    ///
+    /// If it doesn't, the result carries an instruction creating it for `partner`, the
+    /// wallet that claims its fees, paid for by `payer`.
+    ///
+    /// Requires the `solana` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let account = client
+    ///     .named_referral_account(rpc_url, "my-app", "YourPubKey...", "YourPubKey...")
+    ///     .await?;
+    /// if let Some(create) = account.create_instruction {
+    ///     let tx = referral::creation_transaction("YourPubKey...", &[create], blockhash)?;
+    ///     // sign and send `tx`
+    /// }
+    /// ```
+    pub async fn named_referral_account(
+        &self,
+        rpc_url: &str,
+        name: &str,
+        partner: &str,
+        payer: &str,
+    ) -> Result<ReferralAccount, JupiterClientError> {
+        let address = referral_account(name)?;
+
+        let exists = self
+            .get_multiple_accounts(rpc_url, &[&address])
+            .await?
+            .pop()
+            .flatten()
+            .is_some();
+        let create_instruction = if exists {
+            None
+        } else {
+            Some(create_referral_account_instruction(payer, partner, name)?)
+        };
+
+        Ok(ReferralAccount {
+            address,
+            create_instruction,
+        })
+    }
+
+    /// Checks the referral token accounts of the referral account set on `request`, for
+    /// both of its mints, with a single call to the Solana RPC at `rpc_url`.
+    ///
+    /// Ultra takes the referral fee in whichever mint it prefers, and only when the
+    /// referral token account for that mint exists, so create any missing one before
+    /// placing the order to be sure to collect the fee.
+    ///
+    /// Returns a `ValidationError` if `request` has no referral account.
+    ///
+    /// Requires the `solana` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let request = UltraOrderRequest::new(SOL, JUP, 1_000_000_000)
+    ///     .add_taker("YourPubKey...")
+    ///     .add_referral_account("YourReferralAccount...")
+    ///     .add_referral_fee(100);
+    ///
+    /// let create: Vec<_> = client
+    ///     .ultra_referral_fee_accounts(rpc_url, &request, "YourPubKey...")
+    ///     .await?
+    ///     .into_iter()
+    ///     .filter_map(|account| account.create_instruction)
+    ///     .collect();
+    /// ```
+    pub async fn ultra_referral_fee_accounts(
+        &self,
+        rpc_url: &str,
+        request: &UltraOrderRequest,
+        payer: &str,
+    ) -> Result<Vec<ReferralFeeAccount>, JupiterClientError> {
+        let referral_account = request.referral_account.as_deref().ok_or_else(|| {
+            JupiterClientError::ValidationError(
+                "referral_account is required to collect referral fees".to_string(),
+            )
+        })?;
+
+        self.referral_fee_accounts(
+            rpc_url,
+            referral_account,
+            &[&request.input_mint, &request.output_mint],
+            payer,
+        )
+        .await
+    }
+
+    /// Referral token accounts of `referral_account` for `mints`, fetched along with the
+    /// mints, which tell the token program to create missing ones with.
+    async fn referral_fee_accounts(
+        &self,
+        rpc_url: &str,
+        referral_account: &str,
+        mints: &[&str],
+        payer: &str,
+    ) -> Result<Vec<ReferralFeeAccount>, JupiterClientError> {
+        let addresses = mints
+            .iter()
+            .map(|mint| referral_token_account(referral_account, mint))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let keys: Vec<&str> = addresses
+            .iter()
+            .map(String::as_str)
+            .chain(mints.iter().copied())
+            .collect();
+        let accounts = self.get_multiple_accounts(rpc_url, &keys).await?;
+        let (fee_accounts, mint_accounts) = accounts.split_at(mints.len());
+
+        addresses
+            .into_iter()
+            .zip(mints)
+            .zip(fee_accounts.iter().zip(mint_accounts))
+            .map(|((address, mint), (fee_account, mint_account))| {
+                let create_instruction = match fee_account {
+                    Some(_) => None,
+                    None => {
+                        let token_program = mint_account
+                            .as_ref()
+                            .map_or(TOKEN_PROGRAM_ID, |account| account.owner.as_str());
+                        Some(create_referral_token_account_instruction(
+                            payer,
+                            referral_account,
+                            mint,
+                            token_program,
+                        )?)
+                    }
+                };
+                Ok(ReferralFeeAccount {
+                    address,
+                    create_instruction,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,275 @@
+use std::{
+    cell::RefCell,
+    fmt,
+    future::Future,
+    pin::{Pin, pin},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures_util::future::{Either, select};
+use tokio::sync::Notify;
+
+use reqwest::{
+    Request,
+    header::{HeaderName, HeaderValue},
+};
+
+use crate::{error::JupiterClientError, runtime::Instant};
+
+thread_local! {
+    /// The options of the [`Scoped`] future being polled on this thread, if any.
+    static CURRENT: RefCell<Option<Arc<RequestOptions>>> = const { RefCell::new(None) };
+}
+
+/// Settings for the requests of a single call: extra headers and query parameters, e.g. a
+/// tenant id or tracing headers expected by an API gateway, and a timeout or deadline
+/// tighter or looser than the client's.
+///
+/// Pass them to [`get_quote_with`](super::JupiterClient::get_quote_with), or
+/// [`scope`](Self::scope) any other call with them.
+/// Headers replace those the client would send; query parameters are appended. Invalid
+/// header names or values fail the call with a `ValidationError` or `HeaderError`.
+///
+/// # Example
+/// ```
+/// let options = RequestOptions::new()
+///     .header("x-tenant-id", "acme")
+///     .query("traceId", "4bf92f3577b34da6");
+/// let quote = client.get_quote_with(&request, options.clone()).await?;
+/// let order = options.scope(client.get_ultra_order(&order_request)).await?;
+///
+/// // a tight budget for quotes in a trading loop, while balances may take their time
+/// let fast = RequestOptions::new().timeout(Duration::from_millis(300));
+/// let quote = client.get_quote_with(&request, fast).await?;
+/// ```
+///
+/// # Cancellation
+///
+/// Dropping a call's future, e.g. the losing branch of `tokio::select!`, is safe: its
+/// rate limit and concurrency slots are released and nothing more is sent. A request
+/// already sent may still be processed though, so a dropped `/execute` call can land. To
+/// abort calls from elsewhere, give them a [`cancellation_token`](Self::cancellation_token).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestOptions {
+    headers: Vec<(String, String)>,
+    query: Vec<(String, String)>,
+    timeout: Option<Duration>,
+    deadline: Option<Instant>,
+    cancellation: Option<CancellationToken>,
+}
+
+impl RequestOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends header `name` with the call's requests.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Appends query parameter `name` to the call's requests.
+    pub fn query(mut self, name: &str, value: &str) -> Self {
+        self.query.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Fails each of the call's requests that doesn't complete within `timeout`, instead of
+    /// the client's [`timeout`](super::JupiterClientBuilder::timeout). Retried requests get
+    /// the full `timeout` again.
+    ///
+    /// Failures are `RequestError`s whose `is_timeout()` is `true`. Enforced by the default
+    /// transport; a custom [`HttpTransport`](super::HttpTransport) has to honor
+    /// [`reqwest::Request::timeout`] itself.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Fails the call's requests still running at `deadline`, including retries and those
+    /// that waited for a rate or concurrency limit. Combined with a
+    /// [`timeout`](Self::timeout), whichever is sooner applies.
+    ///
+    /// A call still waiting for a limit at the deadline fails with
+    /// [`Cancelled`](JupiterClientError::Cancelled) without being sent.
+    pub fn deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Fails the call with [`Cancelled`](JupiterClientError::Cancelled) as soon as `token`
+    /// is cancelled, wherever it is: waiting for a limit, a response or a retry.
+    ///
+    /// # Example
+    /// ```
+    /// let cycle = CancellationToken::new();
+    /// let options = RequestOptions::new().cancellation_token(cycle.clone());
+    /// let quote = tokio::spawn(async move { options.scope(client.get_quote(&request)).await });
+    /// // a new block arrived, this quote is of no use anymore
+    /// cycle.cancel();
+    /// assert!(matches!(quote.await?, Err(JupiterClientError::Cancelled)));
+    /// ```
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Runs `future`, e.g. a call on a [`JupiterClient`](super::JupiterClient), with these
+    /// options added to every request it sends. Nested scopes replace the outer options.
+    pub fn scope<F: Future>(self, future: F) -> Scoped<F> {
+        Scoped {
+            options: Arc::new(self),
+            future: Box::pin(future),
+        }
+    }
+
+    /// Adds the options to `request`.
+    pub(crate) fn apply(&self, request: &mut Request) -> Result<(), JupiterClientError> {
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_bytes(name.as_bytes()).map_err(|_| {
+                JupiterClientError::ValidationError(format!("invalid header name: {name}"))
+            })?;
+            request
+                .headers_mut()
+                .insert(name, HeaderValue::from_str(value)?);
+        }
+        if !self.query.is_empty() {
+            request
+                .url_mut()
+                .query_pairs_mut()
+                .extend_pairs(&self.query);
+        }
+        // a deadline already passed leaves no time, so the request times out right away
+        let remaining = self
+            .deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()));
+        if let Some(timeout) = match (self.timeout, remaining) {
+            (Some(timeout), Some(remaining)) => Some(timeout.min(remaining)),
+            (timeout, remaining) => timeout.or(remaining),
+        } {
+            *request.timeout_mut() = Some(timeout);
+        }
+        Ok(())
+    }
+
+    /// The options of the call being made on this thread, if any.
+    pub(crate) fn current() -> Option<Arc<RequestOptions>> {
+        CURRENT.with(|current| current.borrow().clone())
+    }
+
+    /// The deadline of the call being made on this thread, if any.
+    pub(crate) fn current_deadline() -> Option<Instant> {
+        CURRENT.with(|current| current.borrow().as_ref()?.deadline)
+    }
+
+    /// Runs `future`, part of the call being made on this thread, failing with `Cancelled`
+    /// if the call's token is cancelled first.
+    pub(crate) async fn cancellable<T>(
+        future: impl Future<Output = Result<T, JupiterClientError>>,
+    ) -> Result<T, JupiterClientError> {
+        let token = CURRENT.with(|current| current.borrow().as_ref()?.cancellation.clone());
+        let Some(token) = token else {
+            return future.await;
+        };
+        match select(pin!(future), pin!(token.cancelled())).await {
+            Either::Left((result, _)) => result,
+            Either::Right(_) => Err(JupiterClientError::Cancelled),
+        }
+    }
+}
+
+/// Cancels the calls it was given to with
+/// [`RequestOptions::cancellation_token`]. Clones cancel the same calls.
+///
+/// Doesn't depend on an async runtime.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<Cancellation>);
+
+#[derive(Debug, Default)]
+struct Cancellation {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the calls running with the token, and those started with it later.
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::Release);
+        self.0.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Completes once the token is cancelled.
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.0.notify.notified();
+            let mut notified = pin!(notified);
+            // registered before checking, so a cancel in between isn't missed
+            notified.as_mut().enable();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+impl PartialEq for CancellationToken {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for CancellationToken {}
+
+/// Runs a future with [`RequestOptions`] applying to the requests it sends, returned by
+/// [`RequestOptions::scope`].
+///
+/// The options are set while the future is polled, so they don't leak into other tasks,
+/// and requests sent from tasks it spawns don't get them.
+pub struct Scoped<F> {
+    options: Arc<RequestOptions>,
+    future: Pin<Box<F>>,
+}
+
+impl<F> fmt::Debug for Scoped<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scoped")
+            .field("options", &self.options)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F: Future> Future for Scoped<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        let options = self.options.clone();
+        let outer = CURRENT.with(|current| current.replace(Some(options)));
+        // restores the outer options even if the future panics
+        let _restore = Restore(outer);
+        self.future.as_mut().poll(cx)
+    }
+}
+
+struct Restore(Option<Arc<RequestOptions>>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let outer = self.0.take();
+        CURRENT.with(|current| *current.borrow_mut() = outer);
+    }
+}
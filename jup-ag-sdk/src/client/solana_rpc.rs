@@ -0,0 +1,168 @@
+use std::str::FromStr;
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use serde::{Deserialize, de::DeserializeOwned};
+use serde_json::json;
+use solana_message::AddressLookupTableAccount;
+use solana_pubkey::Pubkey;
+
+use super::JupiterClient;
+use crate::error::{JupiterApiError, JupiterClientError, handle_response};
+
+/// Size of the metadata before the addresses in an address lookup table account.
+const LOOKUP_TABLE_META_SIZE: usize = 56;
+
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct RpcAccounts {
+    value: Vec<Option<RpcAccount>>,
+}
+
+/// An account as returned by `getMultipleAccounts` with base64 encoding.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RpcAccount {
+    pub(crate) owner: String,
+    /// `[data, "base64"]`
+    data: (String, String),
+    #[cfg(feature = "program-test")]
+    #[serde(default)]
+    pub(crate) lamports: u64,
+    #[cfg(feature = "program-test")]
+    #[serde(default)]
+    pub(crate) executable: bool,
+    #[cfg(feature = "program-test")]
+    #[serde(default)]
+    pub(crate) rent_epoch: u64,
+}
+
+impl RpcAccount {
+    pub(crate) fn data(&self) -> Result<Vec<u8>, JupiterClientError> {
+        STANDARD.decode(&self.data.0).map_err(|e| {
+            JupiterClientError::deserialization(format!("account data is not base64: {e}"))
+        })
+    }
+}
+
+impl JupiterClient {
+    /// Calls JSON-RPC `method` with `params` on the Solana RPC at `rpc_url`, `None` if the
+    /// result is `null`.
+    pub(crate) async fn rpc_call<T: DeserializeOwned>(
+        &self,
+        rpc_url: &str,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<Option<T>, JupiterClientError> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        let response = handle_response(
+            self.external_client()
+                .post(rpc_url)
+                .json(&body)
+                .send()
+                .await?,
+        )
+        .await?;
+        let status = response.status();
+        let endpoint = response.url().path().to_string();
+        let mut bytes = response.bytes().await?.to_vec();
+        let response: RpcResponse<T> = crate::json::from_slice(&mut bytes)?;
+
+        match response.error {
+            Some(error) => Err(JupiterClientError::ApiError(Box::new(
+                JupiterApiError::new(status, &endpoint, error.to_string()),
+            ))),
+            None => Ok(response.result),
+        }
+    }
+
+    /// Fetches `addresses` from the Solana RPC at `rpc_url`, `None` for accounts that don't exist.
+    pub(crate) async fn get_multiple_accounts(
+        &self,
+        rpc_url: &str,
+        addresses: &[&str],
+    ) -> Result<Vec<Option<RpcAccount>>, JupiterClientError> {
+        let accounts: Option<RpcAccounts> = self
+            .rpc_call(
+                rpc_url,
+                "getMultipleAccounts",
+                json!([addresses, { "encoding": "base64" }]),
+            )
+            .await?;
+
+        match accounts {
+            Some(accounts) if accounts.value.len() == addresses.len() => Ok(accounts.value),
+            _ => Err(JupiterClientError::deserialization(
+                "unexpected getMultipleAccounts response".to_string(),
+            )),
+        }
+    }
+
+    /// Fetches the address lookup tables at `addresses`, e.g. a swap's
+    /// `address_lookup_table_addresses`, from the Solana RPC at `rpc_url`.
+    ///
+    /// Requires the `solana` feature.
+    ///
+    /// # Example
+    /// ```
+    /// let instructions = api.get_swap_instructions(&payload).await?;
+    /// let tables = api
+    ///     .resolve_address_lookup_tables(
+    ///         "https://api.mainnet-beta.solana.com",
+    ///         &instructions.address_lookup_table_addresses,
+    ///     )
+    ///     .await?;
+    /// ```
+    pub async fn resolve_address_lookup_tables(
+        &self,
+        rpc_url: &str,
+        addresses: &[String],
+    ) -> Result<Vec<AddressLookupTableAccount>, JupiterClientError> {
+        if addresses.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let keys: Vec<&str> = addresses.iter().map(String::as_str).collect();
+        let accounts = self.get_multiple_accounts(rpc_url, &keys).await?;
+
+        keys.iter()
+            .zip(accounts)
+            .map(|(address, account)| {
+                let account = account.ok_or_else(|| {
+                    JupiterClientError::ValidationError(format!(
+                        "address lookup table {address} not found"
+                    ))
+                })?;
+                let data = account.data()?;
+                let addresses = data
+                    .get(LOOKUP_TABLE_META_SIZE..)
+                    .ok_or_else(|| {
+                        JupiterClientError::deserialization(format!(
+                            "{address} is not an address lookup table"
+                        ))
+                    })?
+                    .chunks_exact(32)
+                    .map(|key| Pubkey::try_from(key).expect("chunks are 32 bytes"))
+                    .collect();
+
+                Ok(AddressLookupTableAccount {
+                    key: Pubkey::from_str(address).map_err(|_| {
+                        JupiterClientError::ValidationError(format!(
+                            "address lookup table is not a valid base58 public key: {address:?}"
+                        ))
+                    })?,
+                    addresses,
+                })
+            })
+            .collect()
+    }
+}
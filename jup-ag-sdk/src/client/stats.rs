@@ -0,0 +1,83 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// A snapshot of how much work the client's resilience machinery has done,
+/// returned by [`JupiterClient::stats`](super::JupiterClient::stats).
+///
+/// Counters are cumulative since the client was built.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ClientStats {
+    /// Requests sent to the API, including retries.
+    pub requests: u64,
+    /// Requests sent again after a failed attempt.
+    pub retries: u64,
+    /// Requests that had to wait for the rate limiter.
+    pub rate_limit_waits: u64,
+    /// Total time spent waiting for the rate limiter.
+    pub rate_limit_wait_time: Duration,
+    /// Requests that had to wait for a concurrency slot.
+    pub concurrency_waits: u64,
+    /// Times the circuit breaker opened.
+    pub circuit_breaker_trips: u64,
+    /// Requests moved to a fallback endpoint.
+    pub failovers: u64,
+    /// Responses served stale from the offline fallback cache.
+    pub stale_responses: u64,
+    /// Quotes served from the quote cache instead of the API.
+    pub quote_cache_hits: u64,
+    /// Hedged requests sent to the secondary base URL.
+    pub hedges: u64,
+    /// Hedged requests whose response was returned, the first having been slower.
+    pub hedge_wins: u64,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct StatsCounters {
+    pub(crate) requests: AtomicU64,
+    pub(crate) retries: AtomicU64,
+    pub(crate) rate_limit_waits: AtomicU64,
+    pub(crate) rate_limit_wait_nanos: AtomicU64,
+    pub(crate) concurrency_waits: AtomicU64,
+    pub(crate) circuit_breaker_trips: AtomicU64,
+    pub(crate) failovers: AtomicU64,
+    pub(crate) stale_responses: AtomicU64,
+    pub(crate) quote_cache_hits: AtomicU64,
+    pub(crate) hedges: AtomicU64,
+    pub(crate) hedge_wins: AtomicU64,
+}
+
+impl StatsCounters {
+    pub(crate) fn incr(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_rate_limit_wait(&self, waited: Duration) {
+        if !waited.is_zero() {
+            Self::incr(&self.rate_limit_waits);
+            self.rate_limit_wait_nanos.fetch_add(
+                waited.as_nanos().min(u64::MAX as u128) as u64,
+                Ordering::Relaxed,
+            );
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> ClientStats {
+        let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+        ClientStats {
+            requests: load(&self.requests),
+            retries: load(&self.retries),
+            rate_limit_waits: load(&self.rate_limit_waits),
+            rate_limit_wait_time: Duration::from_nanos(load(&self.rate_limit_wait_nanos)),
+            concurrency_waits: load(&self.concurrency_waits),
+            circuit_breaker_trips: load(&self.circuit_breaker_trips),
+            failovers: load(&self.failovers),
+            stale_responses: load(&self.stale_responses),
+            quote_cache_hits: load(&self.quote_cache_hits),
+            hedges: load(&self.hedges),
+            hedge_wins: load(&self.hedge_wins),
+        }
+    }
+}
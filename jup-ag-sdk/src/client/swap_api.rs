@@ -1,7 +1,22 @@
-use super::JupiterClient;
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    sync::{Arc, atomic::Ordering},
+};
+
+use futures_util::future;
+
+use super::{JupiterClient, RequestOptions, dry_run, stats::StatsCounters};
 use crate::{
-    error::{JupiterClientError, handle_response},
-    types::{QuoteRequest, QuoteResponse, SwapInstructions, SwapRequest, SwapResponse},
+    config::{ApiFamily, StaleQuotePolicy},
+    error::{JupiterClientError, QuoteErrorKind},
+    events::LifecycleEvent,
+    runtime::Instant,
+    types::{
+        AccountConstraint, FittedSwap, MAX_TRANSACTION_SIZE, Quote, QuoteOrigin, QuoteRequest,
+        QuoteResponse, RouteExplanation, RouteRestriction, SwapInstructions, SwapRequest,
+        SwapResponse, Validate, route_explanation::route_dexes,
+    },
 };
 
 impl JupiterClient {
@@ -11,6 +26,9 @@ impl JupiterClient {
     ///
     /// * `params` - A [`QuoteRequest`] containing query parameters like mint addresses, amount, slippage, and more.
     ///
+    /// With an [`amm_blacklist`](super::JupiterClientBuilder::amm_blacklist), the DEXes of
+    /// blacklisted AMMs are added to `exclude_dexes`.
+    ///
     /// # Returns
     ///
     /// * `Ok(QuoteResponse)` on success.
@@ -33,23 +51,87 @@ impl JupiterClient {
         &self,
         params: &QuoteRequest,
     ) -> Result<QuoteResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .get(format!("{}/swap/v1/quote", &self.base_url))
-            .query(&params)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
+        self.fetch_quote(params, true).await
+    }
+
+    /// Like [`get_quote`](Self::get_quote), with extra headers or query parameters, e.g.
+    /// for an API gateway, or its own timeout.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let options = RequestOptions::new().header("x-tenant-id", "acme");
+    /// let quote = api.get_quote_with(&req, options).await?;
+    /// ```
+    pub async fn get_quote_with(
+        &self,
+        params: &QuoteRequest,
+        options: RequestOptions,
+    ) -> Result<QuoteResponse, JupiterClientError> {
+        options.scope(self.get_quote(params)).await
+    }
+
+    /// [`get_quote`](Self::get_quote), skipping the quote cache unless `use_cache`.
+    async fn fetch_quote(
+        &self,
+        quote_request: &QuoteRequest,
+        use_cache: bool,
+    ) -> Result<QuoteResponse, JupiterClientError> {
+        quote_request.validate()?;
+
+        let params = match &self.shared.amm_blacklist {
+            Some(blacklist) => Cow::Owned(blacklist.apply(quote_request.clone())),
+            None => Cow::Borrowed(quote_request),
         };
 
-        let response = handle_response(response).await?;
+        // a dry run must reach the request
+        let use_cache = use_cache && !dry_run::active();
+        let quote_cache = self.shared.quote_cache.as_ref().filter(|_| use_cache);
+        let cache_key = quote_cache.map(|cache| cache.key(&params));
+        if let (Some(cache), Some(key)) = (quote_cache, &cache_key)
+            && let Some(mut quote) = cache.get(key)
+        {
+            StatsCounters::incr(&self.shared.stats.quote_cache_hits);
+            // the cached quote may be for a nearby amount, flag it and re-quote with this one
+            if let Some(origin) = &mut quote.origin
+                && origin.request.amount != quote_request.amount
+            {
+                origin.request = Arc::new(quote_request.clone());
+                origin.approximate = true;
+            }
+            return Ok(quote);
+        }
+
+        let request = self
+            .client
+            .get(self.endpoint(ApiFamily::Swap, "/quote"))
+            .query(&params);
 
-        match response.json::<QuoteResponse>().await {
-            Ok(quote_response) => Ok(quote_response),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
+        let fetched_at = Instant::now();
+        let mut quote = self
+            .send_request::<QuoteResponse>(ApiFamily::Swap, request)
+            .await?;
+        quote.origin = Some(QuoteOrigin {
+            fetched_at,
+            request: Arc::new(quote_request.clone()),
+            approximate: false,
+        });
+        self.shared
+            .latest_slot
+            .fetch_max(quote.context_slot, Ordering::Relaxed);
+        if let (Some(cache), Some(key)) = (quote_cache, cache_key) {
+            cache.insert(key, quote.clone());
         }
+
+        self.emit(|| LifecycleEvent::QuoteFetched {
+            family: ApiFamily::Swap,
+            input_mint: quote.input_mint.clone(),
+            output_mint: quote.output_mint.clone(),
+            in_amount: quote.in_amount,
+            out_amount: quote.out_amount,
+            server_time: quote.server_time(),
+        });
+        Ok(quote)
     }
 
     /// Fetches a swap transaction from Jupiter's `/swap` endpoint.
@@ -57,6 +139,9 @@ impl JupiterClient {
     /// # Arguments
     /// * `data` - The [`SwapRequest`]payload.
     ///
+    /// With [`quote_freshness`](super::JupiterClientBuilder::quote_freshness), a stale
+    /// quote is re-quoted or rejected first.
+    ///
     /// # Returns
     /// A `Result` containing the `SwapResponse` with the  base64-encoded unsigned transaction or an error.
     ///
@@ -69,23 +154,23 @@ impl JupiterClient {
         &self,
         data: &SwapRequest,
     ) -> Result<SwapResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .post(format!("{}/swap/v1/swap", self.base_url))
-            .json(&data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+        data.validate()?;
+        let data = self.fresh_swap_request(data).await?;
 
-        let response = handle_response(response).await?;
+        let request = self
+            .client
+            .post(self.endpoint(ApiFamily::Swap, "/swap"))
+            .json(&*data);
 
-        match response.json::<SwapResponse>().await {
-            Ok(swap_response) => Ok(swap_response),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        let mut response = self
+            .send_request::<SwapResponse>(ApiFamily::Swap, request)
+            .await?;
+        response.priority_level = data
+            .prioritization_fee_lamports
+            .as_ref()
+            .filter(|fee| fee.jito_tip_lamports.is_none())
+            .map(|fee| fee.priority_level_with_max_lamports.priority_level);
+        Ok(response)
     }
 
     /// Fetches a swap transaction from Jupiter's `/swap` endpoint.
@@ -93,6 +178,9 @@ impl JupiterClient {
     /// # Arguments
     /// * `data` - The [`SwapRequest`]payload.
     ///
+    /// With [`quote_freshness`](super::JupiterClientBuilder::quote_freshness), a stale
+    /// quote is re-quoted or rejected first.
+    ///
     /// # Returns
     /// A `Result` containing the `SwapInstructions`or an error.
     ///
@@ -105,22 +193,189 @@ impl JupiterClient {
         &self,
         data: &SwapRequest,
     ) -> Result<SwapInstructions, JupiterClientError> {
-        let response = match self
+        data.validate()?;
+        let data = self.fresh_swap_request(data).await?;
+
+        let request = self
             .client
-            .post(format!("{}/swap/v1/swap-instructions", self.base_url))
-            .json(&data)
-            .send()
+            .post(self.endpoint(ApiFamily::Swap, "/swap-instructions"))
+            .json(&*data);
+
+        self.send_request::<SwapInstructions>(ApiFamily::Swap, request)
             .await
+    }
+
+    /// `data`, with its quote re-fetched if it is
+    /// [approximate](QuoteResponse::is_approximate) or over the
+    /// [`quote_freshness`](super::JupiterClientBuilder::quote_freshness) budget.
+    async fn fresh_swap_request<'a>(
+        &self,
+        data: &'a SwapRequest,
+    ) -> Result<Cow<'a, SwapRequest>, JupiterClientError> {
+        if let Some(origin) = &data.quote_response.origin
+            && origin.approximate
         {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
+            let quote_response = self.fetch_quote(&origin.request, false).await?;
+            return Ok(Cow::Owned(SwapRequest {
+                quote_response,
+                ..data.clone()
+            }));
+        }
+        let Some(freshness) = &self.shared.builder.quote_freshness else {
+            return Ok(Cow::Borrowed(data));
         };
+        let quote = &data.quote_response;
+        let age = quote.age();
+        let slot_lag = self
+            .shared
+            .latest_slot
+            .load(Ordering::Relaxed)
+            .saturating_sub(quote.context_slot);
+        if !freshness.is_stale(age, slot_lag) {
+            return Ok(Cow::Borrowed(data));
+        }
 
-        let response = handle_response(response).await?;
+        match (freshness.on_stale, &quote.origin) {
+            (StaleQuotePolicy::Requote, Some(origin)) => {
+                let quote_response = self.fetch_quote(&origin.request, false).await?;
+                Ok(Cow::Owned(SwapRequest {
+                    quote_response,
+                    ..data.clone()
+                }))
+            }
+            _ => Err(JupiterClientError::StaleQuote { age, slot_lag }),
+        }
+    }
 
-        match response.json::<SwapInstructions>().await {
-            Ok(swap_instructions) => Ok(swap_instructions),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
+    /// Quotes and builds a swap transaction, re-quoting with a lower `maxAccounts` and then
+    /// `onlyDirectRoutes` while the transaction is over [`MAX_TRANSACTION_SIZE`] or
+    /// simulation reports that it locks too many accounts.
+    ///
+    /// `build` turns each quote into the swap request. The result says which
+    /// [`AccountConstraint`] was needed. If even direct routes don't fit, the last error is
+    /// returned, or a `ValidationError` if the API built an oversized transaction.
+    ///
+    /// # Example
+    /// ```
+    /// let quote = QuoteRequest::new(input_mint, output_mint, 1_000_000_000);
+    /// let fitted = api
+    ///     .get_fitted_swap_transaction(&quote, |quote| SwapRequest::new("YourPubKey...", quote))
+    ///     .await?;
+    /// if fitted.constraint != AccountConstraint::None {
+    ///     println!("needed {:?} to fit", fitted.constraint);
+    /// }
+    /// ```
+    pub async fn get_fitted_swap_transaction(
+        &self,
+        quote: &QuoteRequest,
+        build: impl Fn(QuoteResponse) -> SwapRequest,
+    ) -> Result<FittedSwap, JupiterClientError> {
+        let mut fallbacks = AccountConstraint::fallbacks(quote).into_iter();
+        let mut constraint = AccountConstraint::None;
+        let mut attempts = 0;
+
+        loop {
+            attempts += 1;
+            let result = match self.get_quote(&constraint.apply(quote.clone())).await {
+                Ok(response) => self.get_swap_transaction(&build(response)).await,
+                Err(error) => Err(error),
+            };
+
+            let too_large = match &result {
+                Ok(response) => response.exceeds_transaction_limits(),
+                Err(error) => error.quote_error_kind() == Some(QuoteErrorKind::TransactionTooLarge),
+            };
+            if !too_large {
+                return result.map(|response| FittedSwap {
+                    response,
+                    constraint,
+                    attempts,
+                });
+            }
+
+            match fallbacks.next() {
+                Some(next) => constraint = next,
+                None => {
+                    return match result {
+                        Ok(response) => Err(JupiterClientError::ValidationError(format!(
+                            "swap transaction does not fit even with {constraint:?}: {} bytes (limit {MAX_TRANSACTION_SIZE})",
+                            response.transaction_size()
+                        ))),
+                        Err(error) => Err(error),
+                    };
+                }
+            }
         }
     }
+
+    /// Explains what the route of `quote`, fetched with `request`, gains over simpler
+    /// routes for the same swap: direct routes only, and each DEX of the route on its own.
+    ///
+    /// The comparison quotes are fetched concurrently with the options of `request`. A
+    /// restriction Jupiter finds no route for is reported without a quote; any other
+    /// error fails the explanation.
+    ///
+    /// # Example
+    /// ```
+    /// let request = QuoteRequest::new(input_mint, output_mint, 1_000_000_000);
+    /// let quote = api.get_quote(&request).await?;
+    /// let explanation = api.explain_route(&request, &quote).await?;
+    /// if let Some(bps) = explanation.improvement_bps() {
+    ///     println!("routing saved {bps:.1} bps");
+    /// }
+    /// println!("{explanation}");
+    /// ```
+    pub async fn explain_route(
+        &self,
+        request: &QuoteRequest,
+        quote: &QuoteResponse,
+    ) -> Result<RouteExplanation, JupiterClientError> {
+        let mut restrictions = vec![RouteRestriction::Direct];
+        restrictions.extend(
+            route_dexes(quote)
+                .into_iter()
+                .map(|dex| RouteRestriction::SingleDex(dex.to_string())),
+        );
+
+        let quotes = future::join_all(restrictions.iter().map(|restriction| {
+            let restricted = match restriction {
+                RouteRestriction::Direct => request.clone().only_direct_routes(true),
+                RouteRestriction::SingleDex(dex) => request.clone().dexes(vec![dex.clone()]),
+            };
+            async move { self.get_quote(&restricted).await }
+        }))
+        .await;
+
+        let mut alternatives = Vec::with_capacity(restrictions.len());
+        for (restriction, result) in restrictions.into_iter().zip(quotes) {
+            let alternative = match result {
+                Ok(alternative) => Some(alternative),
+                Err(error) if error.is_no_route() => None,
+                Err(error) => return Err(error),
+            };
+            alternatives.push((restriction, alternative));
+        }
+        Ok(RouteExplanation::new(quote.clone(), alternatives))
+    }
+
+    /// Fetches the labels of the DEX programs the Swap API routes through, by program id.
+    ///
+    /// The labels are the names accepted by [`QuoteRequest::dexes`] and `exclude_dexes`;
+    /// [`dex_registry`](Self::dex_registry) caches them with lookups both ways.
+    ///
+    /// # Example
+    /// ```
+    /// let labels = api.get_program_id_to_label().await?;
+    /// println!("{} DEXes", labels.len());
+    /// ```
+    pub async fn get_program_id_to_label(
+        &self,
+    ) -> Result<HashMap<String, String>, JupiterClientError> {
+        let request = self
+            .client
+            .get(self.endpoint(ApiFamily::Swap, "/program-id-to-label"));
+
+        self.send_request::<HashMap<String, String>>(ApiFamily::Swap, request)
+            .await
+    }
 }
@@ -0,0 +1,347 @@
+use std::{cmp::Reverse, collections::BTreeMap};
+
+use futures_util::{StreamExt, TryStreamExt, stream};
+use serde::Deserialize;
+use serde_json::json;
+
+use super::JupiterClient;
+use crate::{
+    error::JupiterClientError,
+    types::{
+        Amount, ExecutionReport, ExecutionStatus, JUPITER_PROGRAM_ID, SwapHistoryRequest, Validate,
+        WSOL_MINT,
+    },
+};
+
+/// Most `getTransaction` calls in flight at once.
+const MAX_PARALLEL_TRANSACTIONS: usize = 8;
+
+/// Base fee per transaction signature, in lamports.
+const LAMPORTS_PER_SIGNATURE: u64 = 5000;
+
+#[derive(Deserialize)]
+struct SignatureInfo {
+    signature: String,
+    err: Option<serde_json::Value>,
+}
+
+/// A transaction as returned by `getTransaction` with `json` encoding.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcTransaction {
+    slot: u64,
+    block_time: Option<i64>,
+    meta: Option<RpcMeta>,
+    transaction: RpcTransactionBody,
+}
+
+#[derive(Deserialize)]
+struct RpcTransactionBody {
+    signatures: Vec<String>,
+    message: RpcMessage,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcMessage {
+    account_keys: Vec<String>,
+    instructions: Vec<RpcInstruction>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcInstruction {
+    program_id_index: usize,
+    /// Base58 encoded.
+    data: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcMeta {
+    err: Option<serde_json::Value>,
+    fee: u64,
+    pre_balances: Vec<u64>,
+    post_balances: Vec<u64>,
+    #[serde(default)]
+    pre_token_balances: Vec<RpcTokenBalance>,
+    #[serde(default)]
+    post_token_balances: Vec<RpcTokenBalance>,
+    #[serde(default)]
+    inner_instructions: Vec<RpcInnerInstructions>,
+    #[serde(default)]
+    loaded_addresses: Option<RpcLoadedAddresses>,
+}
+
+#[derive(Deserialize)]
+struct RpcInnerInstructions {
+    instructions: Vec<RpcInstruction>,
+}
+
+#[derive(Deserialize)]
+struct RpcLoadedAddresses {
+    writable: Vec<String>,
+    readonly: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcTokenBalance {
+    mint: String,
+    owner: Option<String>,
+    ui_token_amount: RpcTokenAmount,
+}
+
+#[derive(Deserialize)]
+struct RpcTokenAmount {
+    amount: String,
+}
+
+/// The arguments at the end of a Jupiter v6 route instruction.
+struct RouteArgs {
+    in_amount: Option<u64>,
+    out_amount: Option<u64>,
+    slippage_bps: u16,
+}
+
+/// How the arguments after the route plan of a route instruction are laid out.
+#[derive(Clone, Copy)]
+enum RouteLayout {
+    /// `in_amount, quoted_out_amount, slippage_bps, platform_fee_bps`
+    ExactIn,
+    /// `out_amount, quoted_in_amount, slippage_bps, platform_fee_bps`
+    ExactOut,
+    /// `quoted_out_amount, slippage_bps, platform_fee_bps`, the input read from the token ledger.
+    TokenLedger,
+}
+
+/// Anchor discriminators of the v6 route instructions.
+const ROUTE_INSTRUCTIONS: [([u8; 8], RouteLayout); 6] = [
+    // route
+    ([229, 23, 203, 151, 122, 227, 173, 42], RouteLayout::ExactIn),
+    // shared_accounts_route
+    ([193, 32, 155, 51, 65, 214, 156, 129], RouteLayout::ExactIn),
+    // exact_out_route
+    ([208, 51, 239, 151, 123, 43, 237, 92], RouteLayout::ExactOut),
+    // shared_accounts_exact_out_route
+    (
+        [176, 209, 105, 168, 154, 125, 69, 62],
+        RouteLayout::ExactOut,
+    ),
+    // route_with_token_ledger
+    (
+        [150, 86, 71, 116, 167, 93, 14, 104],
+        RouteLayout::TokenLedger,
+    ),
+    // shared_accounts_route_with_token_ledger
+    (
+        [230, 121, 143, 80, 119, 159, 106, 170],
+        RouteLayout::TokenLedger,
+    ),
+];
+
+impl JupiterClient {
+    /// Reads a wallet's recent transactions from the Solana RPC at `rpc_url` and returns
+    /// its Jupiter swaps as [`ExecutionReport`]s, newest first.
+    ///
+    /// A transaction is a Jupiter swap if it invokes the Jupiter v6 program, directly or
+    /// through another program. Executed amounts are the wallet's balance changes, with
+    /// native SOL counted as wSOL; the SOL side of a swap also includes rent paid or
+    /// refunded for token accounts opened or closed in the transaction. Quoted amounts and
+    /// slippage are decoded from the route instruction when it is one of the v6 route
+    /// instructions. Failed transactions move no tokens and are skipped.
+    ///
+    /// Requires the `solana` feature.
+    ///
+    /// # Example
+    /// ```
+    /// let request = SwapHistoryRequest::new("YourPubKey...").limit(200);
+    /// let swaps = client
+    ///     .wallet_swap_history("https://api.mainnet-beta.solana.com", &request)
+    ///     .await?;
+    /// ExecutionReport::write_csv(&swaps, std::fs::File::create("swaps.csv")?)?;
+    /// ```
+    pub async fn wallet_swap_history(
+        &self,
+        rpc_url: &str,
+        request: &SwapHistoryRequest,
+    ) -> Result<Vec<ExecutionReport>, JupiterClientError> {
+        request.validate()?;
+
+        let signatures: Vec<SignatureInfo> = self
+            .rpc_call(
+                rpc_url,
+                "getSignaturesForAddress",
+                json!([request.wallet, request]),
+            )
+            .await?
+            .unwrap_or_default();
+
+        let transactions: Vec<Option<RpcTransaction>> = stream::iter(
+            signatures
+                .into_iter()
+                .filter(|signature| signature.err.is_none()),
+        )
+        .map(|signature| async move {
+            self.rpc_call(
+                rpc_url,
+                "getTransaction",
+                json!([
+                    signature.signature,
+                    { "encoding": "json", "maxSupportedTransactionVersion": 0 }
+                ]),
+            )
+            .await
+        })
+        .buffered(MAX_PARALLEL_TRANSACTIONS)
+        .try_collect()
+        .await?;
+
+        Ok(transactions
+            .into_iter()
+            .flatten()
+            .filter_map(|transaction| swap_report(&request.wallet, transaction))
+            .collect())
+    }
+}
+
+/// The report of `transaction` if it is a successful Jupiter swap by `wallet`.
+fn swap_report(wallet: &str, transaction: RpcTransaction) -> Option<ExecutionReport> {
+    let meta = transaction.meta?;
+    if meta.err.is_some() {
+        return None;
+    }
+
+    let message = &transaction.transaction.message;
+    let mut keys: Vec<&str> = message.account_keys.iter().map(String::as_str).collect();
+    if let Some(loaded) = &meta.loaded_addresses {
+        keys.extend(loaded.writable.iter().map(String::as_str));
+        keys.extend(loaded.readonly.iter().map(String::as_str));
+    }
+    let mut jupiter_instructions = message
+        .instructions
+        .iter()
+        .chain(
+            meta.inner_instructions
+                .iter()
+                .flat_map(|inner| &inner.instructions),
+        )
+        .filter(|ix| keys.get(ix.program_id_index) == Some(&JUPITER_PROGRAM_ID))
+        .peekable();
+    jupiter_instructions.peek()?;
+    let route =
+        jupiter_instructions.find_map(|ix| decode_route(&bs58::decode(&ix.data).into_vec().ok()?));
+
+    let changes = balance_changes(wallet, &keys, &meta);
+    let (input_mint, input) = pick(&changes, |delta| delta < 0)?;
+    let (output_mint, output) = pick(&changes, |delta| delta > 0)?;
+
+    let signatures = transaction.transaction.signatures.len() as u64;
+    Some(ExecutionReport {
+        api: None,
+        router: None,
+        status: ExecutionStatus::Success,
+        request_id: None,
+        signature: transaction.transaction.signatures.into_iter().next(),
+        slot: Some(transaction.slot),
+        error: None,
+        input_mint,
+        output_mint,
+        quoted_in_amount: route
+            .as_ref()
+            .and_then(|route| route.in_amount)
+            .map(Amount::new),
+        quoted_out_amount: route
+            .as_ref()
+            .and_then(|route| route.out_amount)
+            .map(Amount::new),
+        executed_in_amount: Some(Amount::new(input.unsigned_abs().try_into().ok()?)),
+        executed_out_amount: Some(Amount::new(output.try_into().ok()?)),
+        slippage_bps: route.map(|route| route.slippage_bps),
+        fee_bps: None,
+        platform_fee_amount: None,
+        prioritization_fee_lamports: Some(
+            meta.fee.saturating_sub(signatures * LAMPORTS_PER_SIGNATURE),
+        ),
+        route: Vec::new(),
+        quoted_at_ms: None,
+        executed_at_ms: transaction
+            .block_time
+            .map(|seconds| seconds.max(0) as u64 * 1000),
+    })
+}
+
+/// The raw balance change of `wallet` per mint, with native SOL (fee excluded) added to wSOL.
+fn balance_changes(wallet: &str, keys: &[&str], meta: &RpcMeta) -> BTreeMap<String, i128> {
+    let mut changes = BTreeMap::new();
+    let amount = |balance: &RpcTokenBalance| balance.ui_token_amount.amount.parse::<i128>().ok();
+    for balance in &meta.post_token_balances {
+        if balance.owner.as_deref() == Some(wallet) {
+            *changes.entry(balance.mint.clone()).or_default() += amount(balance).unwrap_or(0);
+        }
+    }
+    for balance in &meta.pre_token_balances {
+        if balance.owner.as_deref() == Some(wallet) {
+            *changes.entry(balance.mint.clone()).or_default() -= amount(balance).unwrap_or(0);
+        }
+    }
+
+    if let Some(index) = keys.iter().position(|key| *key == wallet)
+        && let (Some(pre), Some(post)) =
+            (meta.pre_balances.get(index), meta.post_balances.get(index))
+    {
+        let fee = if index == 0 { meta.fee } else { 0 };
+        *changes.entry(WSOL_MINT.to_string()).or_default() +=
+            i128::from(*post) - i128::from(*pre) + i128::from(fee);
+    }
+
+    changes.retain(|_, delta| *delta != 0);
+    changes
+}
+
+/// The change matching `side`, preferring a token over SOL, which also moves for rent.
+fn pick(changes: &BTreeMap<String, i128>, side: impl Fn(i128) -> bool) -> Option<(String, i128)> {
+    changes
+        .iter()
+        .filter(|(_, delta)| side(**delta))
+        .min_by_key(|(mint, delta)| (*mint == WSOL_MINT, Reverse(delta.abs())))
+        .map(|(mint, delta)| (mint.clone(), *delta))
+}
+
+/// Decodes the trailing arguments of a v6 route instruction, which follow a
+/// variable-length route plan.
+fn decode_route(data: &[u8]) -> Option<RouteArgs> {
+    let (_, layout) = ROUTE_INSTRUCTIONS
+        .iter()
+        .find(|(discriminator, _)| data.starts_with(discriminator))?;
+    let tail_len = match layout {
+        RouteLayout::ExactIn | RouteLayout::ExactOut => 19,
+        RouteLayout::TokenLedger => 11,
+    };
+    // discriminator, the route plan's length prefix, then the arguments
+    let tail = data.get(
+        data.len()
+            .checked_sub(tail_len)
+            .filter(|start| *start >= 12)?..,
+    )?;
+    let u64_at = |at: usize| u64::from_le_bytes(tail[at..at + 8].try_into().unwrap());
+    let u16_at = |at: usize| u16::from_le_bytes(tail[at..at + 2].try_into().unwrap());
+
+    Some(match layout {
+        RouteLayout::ExactIn => RouteArgs {
+            in_amount: Some(u64_at(0)),
+            out_amount: Some(u64_at(8)),
+            slippage_bps: u16_at(16),
+        },
+        RouteLayout::ExactOut => RouteArgs {
+            in_amount: Some(u64_at(8)),
+            out_amount: Some(u64_at(0)),
+            slippage_bps: u16_at(16),
+        },
+        RouteLayout::TokenLedger => RouteArgs {
+            in_amount: None,
+            out_amount: Some(u64_at(0)),
+            slippage_bps: u16_at(8),
+        },
+    })
+}
@@ -1,11 +1,30 @@
-use super::JupiterClient;
+use std::collections::HashMap;
+
+use futures_util::{StreamExt, stream};
+use reqwest::RequestBuilder;
+
+use super::{
+    CachedResponse, JupiterClient,
+    pagination::{MAX_PARALLEL_PAGES, fetch_pages, validate_max_pages},
+};
 use crate::{
-    error::{JupiterClientError, handle_response},
-    types::{NewTokens, TokenInfoResponse, TokenPriceRequest, TokenPriceResponse},
+    config::ApiFamily,
+    error::JupiterClientError,
+    types::{
+        Amount, NewTokens, TokenInfoResponse, TokenMetadata, TokenMetadataCache, TokenPriceRequest,
+        TokenPriceResponse, TokenPricesV3, TokenTag, TradableMints, Validate, known_mint,
+        validate_pubkey,
+    },
 };
 
 // TODO: examples for reccuring
 
+/// Most mints the Price API v3 accepts in one request.
+const MAX_PRICE_V3_IDS: usize = 50;
+
+/// Most token info requests [`JupiterClient::get_token_infos`] keeps in flight at once.
+const MAX_PARALLEL_TOKEN_INFO: usize = 8;
+
 impl JupiterClient {
     /// Returns prices of specified tokens.
     /// ```
@@ -35,23 +54,81 @@ impl JupiterClient {
         &self,
         params: &TokenPriceRequest,
     ) -> Result<TokenPriceResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .get(format!("{}/price/v2", self.base_url))
-            .query(&params)
-            .send()
+        let request = self.token_price_request(params)?;
+        self.send_request::<TokenPriceResponse>(ApiFamily::Price, request)
             .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
+    }
 
-        match response.json::<TokenPriceResponse>().await {
-            Ok(token_price) => Ok(token_price),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
+    /// Returns the USD prices of `mints` from the Price API v3, with the slot each price
+    /// was computed at. Up to 50 mints per call.
+    ///
+    /// Unlike [`get_token_price`](Self::get_token_price), prices are numbers, always in
+    /// USD, and mints Jupiter can't price reliably are missing from the response.
+    ///
+    /// # Example
+    /// ```
+    /// let prices = client.get_prices_v3(&[SOL_MINT.to_string()]).await?;
+    /// if let Some(sol) = prices.get(SOL_MINT) {
+    ///     println!("SOL: ${} at slot {:?}", sol.usd_price, sol.block_id);
+    /// }
+    /// ```
+    pub async fn get_prices_v3(
+        &self,
+        mints: &[String],
+    ) -> Result<TokenPricesV3, JupiterClientError> {
+        if mints.is_empty() || mints.len() > MAX_PRICE_V3_IDS {
+            return Err(JupiterClientError::ValidationError(format!(
+                "between 1 and {MAX_PRICE_V3_IDS} mints are required, got {}",
+                mints.len()
+            )));
         }
+        mints
+            .iter()
+            .try_for_each(|mint| validate_pubkey("mints", mint))?;
+
+        let url = format!(
+            "{}{}",
+            self.base_url.trim_end_matches('/'),
+            self.paths.price_v3
+        );
+        let request = self.client.get(&url).query(&[("ids", mints.join(","))]);
+
+        self.send_request::<TokenPricesV3>(ApiFamily::Price, request)
+            .await
+    }
+
+    /// Same as [`get_token_price`](Self::get_token_price), but falls back to the last
+    /// successful response for the same request when Jupiter is unreachable.
+    ///
+    /// Requires [`offline_fallback`](super::JupiterClientBuilder::offline_fallback)
+    /// to be enabled, otherwise the response is always fresh or an error.
+    ///
+    /// ```
+    /// let price = client.get_token_price_or_cached(&params).await?;
+    /// if price.is_stale() {
+    ///     println!("showing cached prices: {:?}", price.freshness);
+    /// }
+    /// ```
+    pub async fn get_token_price_or_cached(
+        &self,
+        params: &TokenPriceRequest,
+    ) -> Result<CachedResponse<TokenPriceResponse>, JupiterClientError> {
+        let request = self.token_price_request(params)?;
+        self.send_cached_request::<TokenPriceResponse>(ApiFamily::Price, request)
+            .await
+    }
+
+    /// The request of [`get_token_price`](Self::get_token_price), shared with its cached
+    /// variant.
+    fn token_price_request(
+        &self,
+        params: &TokenPriceRequest,
+    ) -> Result<RequestBuilder, JupiterClientError> {
+        params.validate()?;
+        Ok(self
+            .client
+            .get(self.endpoint(ApiFamily::Price, ""))
+            .query(params))
     }
 
     /// Returns the specified mint address's token information and metadata.
@@ -66,18 +143,148 @@ impl JupiterClient {
         &self,
         mint_address: &str,
     ) -> Result<TokenInfoResponse, JupiterClientError> {
-        let url = format!("{}/tokens/v1/token/{}", self.base_url, mint_address);
-        let response = match self.client.get(&url).send().await {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+        let request = self.token_info_request(mint_address)?;
+        self.send_request::<TokenInfoResponse>(ApiFamily::Tokens, request)
+            .await
+    }
 
-        let response = handle_response(response).await?;
+    /// Same as [`get_token_info`](Self::get_token_info), but falls back to the last
+    /// successful response for the mint when Jupiter is unreachable.
+    /// See [`get_token_price_or_cached`](Self::get_token_price_or_cached).
+    pub async fn get_token_info_or_cached(
+        &self,
+        mint_address: &str,
+    ) -> Result<CachedResponse<TokenInfoResponse>, JupiterClientError> {
+        let request = self.token_info_request(mint_address)?;
+        self.send_cached_request::<TokenInfoResponse>(ApiFamily::Tokens, request)
+            .await
+    }
 
-        match response.json::<TokenInfoResponse>().await {
-            Ok(token_info) => Ok(token_info),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
+    /// The request of [`get_token_info`](Self::get_token_info), shared with its cached
+    /// variant.
+    fn token_info_request(&self, mint_address: &str) -> Result<RequestBuilder, JupiterClientError> {
+        validate_pubkey("mint_address", mint_address)?;
+        let url = self.endpoint(ApiFamily::Tokens, &format!("/token/{mint_address}"));
+        Ok(self.client.get(&url))
+    }
+
+    /// Returns the token information of each of `mints`, keyed by mint.
+    ///
+    /// Requests run in parallel, a few at a time, and go through the client's rate and
+    /// concurrency limits, so a configured [`RateLimitPlan`](crate::config::RateLimitPlan)
+    /// paces them instead of Jupiter rejecting them. Duplicate mints are fetched once and
+    /// mints unknown to Jupiter are left out of the map. Results also fill the
+    /// [`TokenMetadataCache`].
+    ///
+    /// ```
+    /// let infos = client.get_token_infos(&portfolio_mints).await?;
+    /// for (mint, info) in &infos {
+    ///     println!("{mint}: {} ({} decimals)", info.symbol, info.decimals);
+    /// }
+    /// ```
+    pub async fn get_token_infos(
+        &self,
+        mints: &[String],
+    ) -> Result<HashMap<String, TokenInfoResponse>, JupiterClientError> {
+        let mut unique: Vec<&str> = mints.iter().map(String::as_str).collect();
+        unique.sort_unstable();
+        unique.dedup();
+        unique
+            .iter()
+            .try_for_each(|mint| validate_pubkey("mints", mint))?;
+
+        let mut responses = stream::iter(unique)
+            .map(|mint| async move { (mint, self.get_token_info(mint).await) })
+            .buffer_unordered(MAX_PARALLEL_TOKEN_INFO);
+
+        let mut infos = HashMap::new();
+        while let Some((mint, result)) = responses.next().await {
+            match result {
+                Ok(info) => {
+                    if let Ok(metadata) = TokenMetadata::try_from(&info) {
+                        self.shared.token_metadata.insert(metadata);
+                    }
+                    infos.insert(mint.to_string(), info);
+                }
+                Err(error) if is_not_found(&error) => {}
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(infos)
+    }
+
+    /// Returns the symbol, decimals and logo of `mint`, from the client's
+    /// [`TokenMetadataCache`] if present, otherwise fetched with
+    /// [`get_token_info`](Self::get_token_info) and cached.
+    ///
+    /// ```
+    /// let jup = client.token_metadata("JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN").await?;
+    /// println!("{} has {} decimals", jup.symbol, jup.decimals);
+    /// ```
+    pub async fn token_metadata(&self, mint: &str) -> Result<TokenMetadata, JupiterClientError> {
+        if let Some(metadata) = self.shared.token_metadata.get(mint) {
+            return Ok(metadata);
         }
+
+        let metadata = TokenMetadata::try_from(&self.get_token_info(mint).await?)?;
+        self.shared.token_metadata.insert(metadata.clone());
+        Ok(metadata)
+    }
+
+    /// The client's token metadata cache, e.g. for
+    /// [`QuoteResponse::display_with`](crate::types::QuoteResponse::display_with).
+    pub fn token_metadata_cache(&self) -> &TokenMetadataCache {
+        &self.shared.token_metadata
+    }
+
+    /// Returns the decimals of `mint` from the client's
+    /// [`DecimalsResolver`](super::DecimalsResolver).
+    pub async fn decimals(&self, mint: &str) -> Result<u8, JupiterClientError> {
+        self.shared.decimals.0.decimals(self, mint).await
+    }
+
+    /// The mint of `token`, given as a symbol or a mint address.
+    ///
+    /// Symbols of well-known tokens (SOL, USDC, USDT, JUP, BONK, mSOL, JitoSOL) are
+    /// resolved first, then those of tokens in the client's [`TokenMetadataCache`] that no
+    /// other cached token shares. Anything else must be a mint address.
+    ///
+    /// ```
+    /// assert_eq!(client.resolve_mint("usdc")?, "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+    /// ```
+    pub fn resolve_mint(&self, token: &str) -> Result<String, JupiterClientError> {
+        if let Some(mint) = known_mint(token) {
+            return Ok(mint.to_string());
+        }
+        if let Some(mint) = self.shared.token_metadata.mint_for_symbol(token) {
+            return Ok(mint);
+        }
+        validate_pubkey("token", token).map_err(|_| {
+            JupiterClientError::ValidationError(format!(
+                "{token} is neither a known token symbol nor a mint address"
+            ))
+        })?;
+        Ok(token.to_string())
+    }
+
+    /// Converts a UI amount of `mint` (e.g. `1.5` JUP) into a raw amount,
+    /// resolving its decimals through [`decimals`](Self::decimals).
+    pub async fn amount_from_ui(
+        &self,
+        mint: &str,
+        ui_amount: f64,
+    ) -> Result<Amount, JupiterClientError> {
+        Ok(Amount::from_ui(ui_amount, self.decimals(mint).await?))
+    }
+
+    /// Formats a raw amount of `mint` with its decimals, e.g. `1.5`,
+    /// resolving them through [`decimals`](Self::decimals).
+    pub async fn amount_to_ui_string(
+        &self,
+        mint: &str,
+        amount: Amount,
+    ) -> Result<String, JupiterClientError> {
+        Ok(amount.to_ui_string(self.decimals(mint).await?))
     }
 
     /// Returns the mints involved in a market.
@@ -85,38 +292,36 @@ impl JupiterClient {
         &self,
         market_address: &str,
     ) -> Result<Vec<String>, JupiterClientError> {
-        let url = format!(
-            "{}/tokens/v1/market/{}/mints",
-            self.base_url, market_address
-        );
-        let response = match self.client.get(&url).send().await {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+        validate_pubkey("market_address", market_address)?;
 
-        let response = handle_response(response).await?;
+        let url = self.endpoint(
+            ApiFamily::Tokens,
+            &format!("/market/{market_address}/mints"),
+        );
+        let request = self.client.get(&url);
 
-        match response.json::<Vec<String>>().await {
-            Ok(mints) => Ok(mints),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        self.send_request::<Vec<String>>(ApiFamily::Tokens, request)
+            .await
     }
 
     /// Returns a list of all mints tradable via Jupiter routing.
     /// This endpoint returns greater than 32MB amount of data. May take a while to complete.
     pub async fn get_tradable_mints(&self) -> Result<Vec<String>, JupiterClientError> {
-        let url = format!("{}/tokens/v1/mints/tradable", self.base_url);
-        let response = match self.client.get(&url).send().await {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+        let url = self.endpoint(ApiFamily::Tokens, "/mints/tradable");
+        let request = self.client.get(&url);
 
-        let response = handle_response(response).await?;
+        self.send_request::<Vec<String>>(ApiFamily::Tokens, request)
+            .await
+    }
 
-        match response.json::<Vec<String>>().await {
-            Ok(mints) => Ok(mints),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+    /// [`get_tradable_mints`](Self::get_tradable_mints) as a set, to check mints against
+    /// before quoting them.
+    pub async fn get_tradable_mint_set(&self) -> Result<TradableMints, JupiterClientError> {
+        let url = self.endpoint(ApiFamily::Tokens, "/mints/tradable");
+        let request = self.client.get(&url);
+
+        self.send_request::<TradableMints>(ApiFamily::Tokens, request)
+            .await
     }
 
     /// Returns a list of mints with specified tag(s) along with their metadata.
@@ -133,66 +338,124 @@ impl JupiterClient {
         &self,
         tags: &[String],
     ) -> Result<Vec<TokenInfoResponse>, JupiterClientError> {
-        let url = format!("{}/tokens/v1/tagged/{}", self.base_url, tags.join(","));
-        let response = match self.client.get(&url).send().await {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+        let url = self.endpoint(ApiFamily::Tokens, &format!("/tagged/{}", tags.join(",")));
+        let request = self.client.get(&url);
 
-        let response = handle_response(response).await?;
+        self.send_request::<Vec<TokenInfoResponse>>(ApiFamily::Tokens, request)
+            .await
+    }
 
-        match response.json::<Vec<TokenInfoResponse>>().await {
-            Ok(mints) => Ok(mints),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+    /// Returns the tokens carrying `tag`, with their metadata, e.g. the verified list for a
+    /// token picker.
+    ///
+    /// # Example
+    /// ```
+    /// let verified = client.get_tokens_by_tag(TokenTag::Verified).await?;
+    /// let staked = client.get_tokens_by_tag("lst".into()).await?;
+    /// ```
+    pub async fn get_tokens_by_tag(
+        &self,
+        tag: TokenTag,
+    ) -> Result<Vec<TokenInfoResponse>, JupiterClientError> {
+        tag.validate()?;
+
+        let url = self.endpoint(ApiFamily::Tokens, &format!("/tagged/{tag}"));
+        let request = self.client.get(&url);
+
+        self.send_request::<Vec<TokenInfoResponse>>(ApiFamily::Tokens, request)
+            .await
     }
 
-    /// get new tokens with metadata, created at timestamp and markets.
+    /// Returns the newest tokens with their metadata, creation time and markets, newest
+    /// first: `limit` of them after skipping `offset`. Both default to the API's.
+    ///
+    /// See [`get_new_tokens_all`](Self::get_new_tokens_all) to read every page.
+    ///
+    /// # Example
+    /// ```
+    /// let fresh = client.get_new_tokens(Some(50), None).await?;
+    /// let is_recent = |age: Duration| age < Duration::from_secs(60);
+    /// for token in fresh.iter().filter(|t| t.age().is_some_and(is_recent)) {
+    ///     println!("{} ({}) created at {}", token.symbol, token.mint, token.created_at);
+    /// }
+    /// ```
     pub async fn get_new_tokens(
         &self,
         limit: Option<u64>,
         offset: Option<u64>,
     ) -> Result<Vec<NewTokens>, JupiterClientError> {
-        let mut url = format!("{}/tokens/v1/new", self.base_url);
-        if let Some(l) = limit {
-            url.push_str(&format!("?limit={}", l));
-        }
-        if let Some(o) = offset {
-            if url.contains('?') {
-                url.push_str(&format!("&offset={}", o));
-            } else {
-                url.push_str(&format!("?offset={}", o));
-            }
+        if limit == Some(0) {
+            return Err(JupiterClientError::ValidationError(
+                "limit must be greater than zero".to_string(),
+            ));
         }
-        let response = match self.client.get(&url).send().await {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
 
-        let response = handle_response(response).await?;
+        let url = self.endpoint(ApiFamily::Tokens, "/new");
+        let query: Vec<_> = [("limit", limit), ("offset", offset)]
+            .into_iter()
+            .filter_map(|(name, value)| Some((name, value?)))
+            .collect();
+        let request = self.client.get(&url).query(&query);
+
+        self.send_request::<Vec<NewTokens>>(ApiFamily::Tokens, request)
+            .await
+    }
 
-        match response.json::<Vec<NewTokens>>().await {
-            Ok(tokens) => Ok(tokens),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
+    /// Every new token, fetched `page_size` at a time, a few pages at once, until a page
+    /// comes back short. Newest first.
+    ///
+    /// `max_pages` caps how many pages are read; `Some(0)` fails with a `ValidationError`.
+    ///
+    /// # Example
+    /// ```
+    /// let tokens = api.get_new_tokens_all(100, Some(10)).await?;
+    /// ```
+    pub async fn get_new_tokens_all(
+        &self,
+        page_size: u64,
+        max_pages: Option<u64>,
+    ) -> Result<Vec<NewTokens>, JupiterClientError> {
+        if page_size == 0 {
+            return Err(JupiterClientError::ValidationError(
+                "page_size must be greater than zero".to_string(),
+            ));
+        }
+        validate_max_pages(max_pages)?;
+        let max_pages = max_pages.unwrap_or(u64::MAX);
+
+        let mut tokens = Vec::new();
+        let mut next = 0;
+        while next < max_pages {
+            let batch = next..max_pages.min(next + MAX_PARALLEL_PAGES as u64);
+            next = batch.end;
+            let pages = fetch_pages(batch, |page| {
+                self.get_new_tokens(Some(page_size), Some(page * page_size))
+            })
+            .await?;
+            for page in pages {
+                let last = (page.len() as u64) < page_size;
+                tokens.extend(page);
+                if last {
+                    return Ok(tokens);
+                }
+            }
         }
+        Ok(tokens)
     }
 
     /// Returns all tokens with all metadata.
     /// Do note that calling this endpoint's resource will return a large payload of 300+MB, which would introduce some latency in the call.
     /// Please use carefully and intentionally, else utilize the other endpoints.
     pub async fn get_all_tokens(&self) -> Result<Vec<TokenInfoResponse>, JupiterClientError> {
-        let url = format!("{}/tokens/v1/all", self.base_url);
+        let url = self.endpoint(ApiFamily::Tokens, "/all");
 
-        let response = match self.client.get(&url).send().await {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+        let request = self.client.get(&url);
 
-        let response = handle_response(response).await?;
-
-        match response.json::<Vec<TokenInfoResponse>>().await {
-            Ok(tokens) => Ok(tokens),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        self.send_request::<Vec<TokenInfoResponse>>(ApiFamily::Tokens, request)
+            .await
     }
 }
+
+fn is_not_found(error: &JupiterClientError) -> bool {
+    matches!(error.inner(), JupiterClientError::ApiError(error) if error.status.as_u16() == 404)
+}
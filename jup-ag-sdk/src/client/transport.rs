@@ -0,0 +1,147 @@
+use std::{fmt, sync::Arc};
+
+use reqwest::{Client, Request, StatusCode, header::HeaderMap};
+
+use crate::{error::JupiterClientError, runtime::BoxFuture};
+
+/// A response read in full by an [`HttpTransport`].
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+/// The future returned by [`HttpTransport::execute`].
+pub type TransportFuture<'a> = BoxFuture<'a, Result<HttpResponse, JupiterClientError>>;
+
+/// Sends the HTTP requests of a [`JupiterClient`](super::JupiterClient) to the Jupiter APIs.
+///
+/// The client builds each request, applies its rate limits, concurrency limits and
+/// request hooks, and hands it to the transport; the transport only has to send it and
+/// read the response. [`ReqwestTransport`] is the default. Set another with
+/// [`JupiterClientBuilder::transport`](super::JupiterClientBuilder::transport) to use a
+/// different HTTP stack, or an in-memory one to test code using the client without a socket.
+///
+/// Non-success statuses are not errors at this level; the client turns them into
+/// `ApiError`s.
+///
+/// # Example
+/// ```
+/// use jup_ag_sdk::client::{HttpResponse, HttpTransport, TransportFuture};
+/// use reqwest::{StatusCode, header::HeaderMap};
+///
+/// #[derive(Debug)]
+/// struct Canned(&'static str);
+///
+/// impl HttpTransport for Canned {
+///     fn execute(&self, _request: reqwest::Request) -> TransportFuture<'_> {
+///         let body = self.0.as_bytes().to_vec();
+///         Box::pin(async move {
+///             Ok(HttpResponse { status: StatusCode::OK, headers: HeaderMap::new(), body })
+///         })
+///     }
+/// }
+///
+/// let client = JupiterClient::builder("https://lite-api.jup.ag")
+///     .transport(Canned(r#"["metis"]"#))
+///     .build()?;
+/// ```
+pub trait HttpTransport: Send + Sync + fmt::Debug {
+    /// Sends `request` and reads the whole response.
+    fn execute(&self, request: Request) -> TransportFuture<'_>;
+}
+
+/// Sends requests with a [`reqwest::Client`], the default transport.
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport(pub Client);
+
+impl HttpTransport for ReqwestTransport {
+    fn execute(&self, request: Request) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let response = self.0.execute(request).await?;
+            Ok(HttpResponse {
+                status: response.status(),
+                headers: response.headers().clone(),
+                body: response.bytes().await?.to_vec(),
+            })
+        })
+    }
+}
+
+/// The transport a client uses, shared with the builder it was built from.
+#[derive(Debug, Clone)]
+pub(crate) struct SharedTransport(pub(crate) Arc<dyn HttpTransport>);
+
+#[cfg(feature = "tower")]
+pub(crate) use tower_stack::SharedLayer;
+
+#[cfg(feature = "tower")]
+mod tower_stack {
+    use std::{fmt, sync::Arc};
+
+    use reqwest::{Client, Request, Response};
+    use tower::{Layer, Service, ServiceExt, util::BoxCloneSyncService};
+
+    use super::{HttpResponse, HttpTransport, TransportFuture};
+    use crate::error::{BoxError, JupiterClientError};
+
+    /// The type-erased service a
+    /// [`service_layer`](crate::JupiterClientBuilder::service_layer) stack is turned into.
+    type HttpService = BoxCloneSyncService<Request, Response, BoxError>;
+
+    type LayerFn = dyn Fn(Client) -> HttpService + Send + Sync;
+
+    /// A tower layer registered on the client builder, applied to the HTTP client on every build.
+    #[derive(Clone)]
+    pub(crate) struct SharedLayer(Arc<LayerFn>);
+
+    impl SharedLayer {
+        pub(crate) fn new<L>(layer: L) -> Self
+        where
+            L: Layer<Client> + Send + Sync + 'static,
+            L::Service: Service<Request, Response = Response> + Clone + Send + Sync + 'static,
+            <L::Service as Service<Request>>::Error: Into<BoxError>,
+            <L::Service as Service<Request>>::Future: Send + 'static,
+        {
+            Self(Arc::new(move |client| {
+                BoxCloneSyncService::new(layer.layer(client).map_err(Into::into))
+            }))
+        }
+
+        /// The transport sending requests through this stack on top of `client`.
+        pub(crate) fn transport(&self, client: Client) -> impl HttpTransport + use<> {
+            TowerTransport((self.0)(client))
+        }
+    }
+
+    impl fmt::Debug for SharedLayer {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("SharedLayer")
+        }
+    }
+
+    #[derive(Debug)]
+    struct TowerTransport(HttpService);
+
+    impl HttpTransport for TowerTransport {
+        fn execute(&self, request: Request) -> TransportFuture<'_> {
+            Box::pin(async move {
+                let response =
+                    self.0
+                        .clone()
+                        .oneshot(request)
+                        .await
+                        .map_err(|error| match error.downcast::<reqwest::Error>() {
+                            Ok(error) => JupiterClientError::RequestError(*error),
+                            Err(error) => JupiterClientError::ServiceError(error),
+                        })?;
+                Ok(HttpResponse {
+                    status: response.status(),
+                    headers: response.headers().clone(),
+                    body: response.bytes().await?.to_vec(),
+                })
+            })
+        }
+    }
+}
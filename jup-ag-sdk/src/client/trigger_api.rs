@@ -1,13 +1,18 @@
 use crate::{
     JupiterClientError,
-    error::handle_response,
+    config::ApiFamily,
+    events::LifecycleEvent,
     types::{
         CancelTriggerOrder, CancelTriggerOrders, CreateTriggerOrder, ExecuteTriggerOrder,
-        GetTriggerOrders, OrderResponse, TriggerResponse,
+        GetTriggerOrders, Order, OrderResponse, OrderStatus, Params, TriggerOrderReplacement,
+        TriggerResponse, Validate, validate_pubkey,
     },
 };
 
-use super::JupiterClient;
+use super::{
+    JupiterClient,
+    pagination::{fetch_pages, last_page, validate_max_pages},
+};
 
 impl JupiterClient {
     /// Creates a new trigger order on Jupiter
@@ -34,7 +39,7 @@ impl JupiterClient {
     ///     1000000000, // 1 SOL (in lamports)
     ///     400000000,  // 400 JUP (in smallest unit)
     /// )
-    /// .slippage_bps("50") // 0.5% slippage
+    /// .slippage_bps(50) // 0.5% slippage
     /// .expired_at("1704067200"); // Unix timestamp
     ///
     /// let response = client.create_trigger_order(&create_order).await?;
@@ -44,23 +49,22 @@ impl JupiterClient {
         &self,
         data: &CreateTriggerOrder,
     ) -> Result<TriggerResponse, JupiterClientError> {
-        let response = match self
+        data.validate()?;
+
+        let request = self
             .client
-            .post(format!("{}/trigger/v1/createOrder", self.base_url))
-            .json(&data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+            .post(self.endpoint(ApiFamily::Trigger, "/createOrder"))
+            .json(&data);
 
-        let response = handle_response(response).await?;
+        let response = self
+            .send_request::<TriggerResponse>(ApiFamily::Trigger, request)
+            .await?;
 
-        match response.json::<TriggerResponse>().await {
-            Ok(create_order_response) => Ok(create_order_response),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        self.emit(|| LifecycleEvent::OrderCreated {
+            family: ApiFamily::Trigger,
+            request_id: response.request_id.clone(),
+        });
+        Ok(response)
     }
 
     /// Executes a trigger(create, cancel) order by submitting the signed transaction
@@ -73,6 +77,9 @@ impl JupiterClient {
     /// # Returns
     /// * `Result<TriggerResponse, JupiterClientError>` - Success returns TriggerResponse with execution details
     ///
+    /// Retrying a request id that already executed successfully through this client
+    /// returns the earlier response without sending the transaction again.
+    ///
     /// # Example
     /// ```rust
     /// use jupiter_client::types::ExecuteTriggerOrder;
@@ -90,23 +97,29 @@ impl JupiterClient {
         &self,
         data: &ExecuteTriggerOrder,
     ) -> Result<TriggerResponse, JupiterClientError> {
-        let response = match self
+        data.validate()?;
+        self.ensure_execution_allowed()?;
+
+        let request = self
             .client
-            .post(format!("{}/trigger/v1/execute", self.base_url))
-            .json(&data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+            .post(self.endpoint(ApiFamily::Trigger, "/execute"))
+            .json(&data);
 
-        let response = handle_response(response).await?;
+        self.emit(|| LifecycleEvent::ExecutionSubmitted {
+            family: ApiFamily::Trigger,
+            request_id: data.request_id.clone(),
+        });
+        let result = self
+            .send_execute::<TriggerResponse>(
+                ApiFamily::Trigger,
+                &data.request_id,
+                &data.signed_transaction,
+                request,
+            )
+            .await;
 
-        match response.json::<TriggerResponse>().await {
-            Ok(execute_order_response) => Ok(execute_order_response),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        self.emit_execution(ApiFamily::Trigger, &data.request_id, &result, |_| Ok(None));
+        result
     }
 
     /// Request for a base64-encoded unsigned trigger order cancellation transaction
@@ -137,23 +150,15 @@ impl JupiterClient {
         &self,
         data: &CancelTriggerOrder,
     ) -> Result<TriggerResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .post(format!("{}/trigger/v1/cancelOrder", self.base_url))
-            .json(&data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+        data.validate()?;
 
-        let response = handle_response(response).await?;
+        let request = self
+            .client
+            .post(self.endpoint(ApiFamily::Trigger, "/cancelOrder"))
+            .json(&data);
 
-        match response.json::<TriggerResponse>().await {
-            Ok(cancel_order_response) => Ok(cancel_order_response),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        self.send_request::<TriggerResponse>(ApiFamily::Trigger, request)
+            .await
     }
 
     /// Cancels multiple trigger orders in a single transaction
@@ -188,45 +193,154 @@ impl JupiterClient {
         &self,
         data: &CancelTriggerOrders,
     ) -> Result<TriggerResponse, JupiterClientError> {
-        let response = match self
+        data.validate()?;
+
+        let request = self
             .client
-            .post(format!("{}/trigger/v1/cancelOrders", self.base_url))
-            .json(&data)
-            .send()
+            .post(self.endpoint(ApiFamily::Trigger, "/cancelOrders"))
+            .json(&data);
+
+        self.send_request::<TriggerResponse>(ApiFamily::Trigger, request)
             .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+    }
 
-        let response = handle_response(response).await?;
+    /// Moves an existing trigger order to new parameters, e.g. a new limit price.
+    ///
+    /// Builds the transaction cancelling `old_order` and the one creating a new order
+    /// for the same mints and maker with `new_params`. Nothing changes on chain until both
+    /// are signed and executed, cancel first.
+    ///
+    /// # Example
+    /// ```rust
+    /// let orders = client
+    ///     .get_trigger_orders(&GetTriggerOrders::new("YourMakerWalletAddress...", OrderStatus::Active))
+    ///     .await?;
+    /// let old = &orders.orders[0];
+    ///
+    /// let replacement = client
+    ///     .replace_trigger_order(old, Params::new(1_000_000_000, 450_000_000))
+    ///     .await?;
+    /// let [cancel, create] = replacement.transactions();
+    /// // sign `cancel` and `create`, then
+    /// for request in replacement.execute_requests(&signed_cancel, &signed_create) {
+    ///     client.execute_trigger_order(&request).await?;
+    /// }
+    /// ```
+    pub async fn replace_trigger_order(
+        &self,
+        old_order: &Order,
+        new_params: Params,
+    ) -> Result<TriggerOrderReplacement, JupiterClientError> {
+        let mut create = CreateTriggerOrder::new(
+            &old_order.input_mint,
+            &old_order.output_mint,
+            &old_order.user_pubkey,
+            &old_order.user_pubkey,
+            0,
+            0,
+        );
+        create.params = new_params;
+        // validate both before sending anything
+        create.validate()?;
+        let cancel = CancelTriggerOrder::new(&old_order.user_pubkey, &old_order.order_key);
+        cancel.validate()?;
 
-        match response.json::<TriggerResponse>().await {
-            Ok(cancel_order_response) => Ok(cancel_order_response),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        let cancel = self.cancel_trigger_order(&cancel).await?;
+        let create = self.create_trigger_order(&create).await?;
+
+        Ok(TriggerOrderReplacement { cancel, create })
     }
 
     pub async fn get_trigger_orders(
         &self,
         data: &GetTriggerOrders,
     ) -> Result<OrderResponse, JupiterClientError> {
-        let response = match self
+        data.validate()?;
+
+        let request = self
             .client
-            .get(format!("{}/trigger/v1/getTriggerOrders", self.base_url))
-            .query(&data)
-            .send()
+            .get(self.endpoint(ApiFamily::Trigger, "/getTriggerOrders"))
+            .query(&data);
+
+        self.send_request::<OrderResponse>(ApiFamily::Trigger, request)
             .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+    }
 
-        let response = handle_response(response).await?;
+    /// The trigger order `order_key` of `user`, active or in its history, with its trades;
+    /// `None` if the user has no such order.
+    ///
+    /// The Trigger API has no single order endpoint, so this pages through the user's
+    /// active orders, then their history, one page at a time until it finds the order.
+    ///
+    /// # Example
+    /// ```
+    /// if let Some(order) = api.get_trigger_order(wallet, &order_key).await? {
+    ///     let (sold, bought) = order.filled_amounts();
+    ///     println!("{:?}: sold {sold}, bought {bought}", order.state());
+    /// }
+    /// ```
+    pub async fn get_trigger_order(
+        &self,
+        user: &str,
+        order_key: &str,
+    ) -> Result<Option<Order>, JupiterClientError> {
+        validate_pubkey("user", user)?;
+        validate_pubkey("order_key", order_key)?;
 
-        match response.json::<OrderResponse>().await {
-            Ok(orders) => Ok(orders),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
+        for status in [OrderStatus::Active, OrderStatus::History] {
+            let mut page = 1;
+            loop {
+                let request = GetTriggerOrders::new(user, status).page(&page.to_string());
+                let response = self.get_trigger_orders(&request).await?;
+                let total_pages = response.total_pages;
+                if let Some(order) = response
+                    .orders
+                    .into_iter()
+                    .find(|order| order.order_key == order_key)
+                {
+                    return Ok(Some(order));
+                }
+                if page >= total_pages {
+                    break;
+                }
+                page += 1;
+            }
         }
+        Ok(None)
+    }
+
+    /// Every page of the orders matching `data`, fetched a few pages at a time and
+    /// returned as one list. Starts from page 1, whatever the page of `data`.
+    ///
+    /// `max_pages` caps how many pages are read, e.g. for wallets with a long history;
+    /// `Some(0)` fails with a `ValidationError`.
+    ///
+    /// # Example
+    /// ```
+    /// let history = api
+    ///     .get_trigger_orders_all(&GetTriggerOrders::new(wallet, OrderStatus::History), Some(20))
+    ///     .await?;
+    /// ```
+    pub async fn get_trigger_orders_all(
+        &self,
+        data: &GetTriggerOrders,
+        max_pages: Option<u64>,
+    ) -> Result<Vec<Order>, JupiterClientError> {
+        validate_max_pages(max_pages)?;
+        let page_request = |page: u64| data.clone().page(&page.to_string());
+
+        let first = self.get_trigger_orders(&page_request(1)).await?;
+        let last = last_page(u64::from(first.total_pages), max_pages);
+        let rest = fetch_pages(2..=last, |page| {
+            let request = page_request(page);
+            async move { self.get_trigger_orders(&request).await }
+        })
+        .await?;
+
+        Ok(first
+            .orders
+            .into_iter()
+            .chain(rest.into_iter().flat_map(|response| response.orders))
+            .collect())
     }
 }
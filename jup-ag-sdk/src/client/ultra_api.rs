@@ -1,13 +1,27 @@
+use reqwest::RequestBuilder;
+
+use super::{CachedResponse, executions::ExecuteResponse};
 use crate::{
-    error::{JupiterClientError, handle_response},
+    config::ApiFamily,
+    error::JupiterClientError,
+    events::LifecycleEvent,
+    runtime::SystemTime,
     types::{
-        Router, Shield, TokenBalancesResponse, UltraExecuteOrderRequest, UltraExecuteOrderResponse,
-        UltraOrderRequest, UltraOrderResponse,
+        ExecutionReport, Holdings, PortfolioRiskReport, Quote, Router, Shield, TokenBalancesExt,
+        TokenBalancesResponse, TokenPriceRequest, TokenPriceResponse, TokenSearchResult,
+        UltraExecuteOrderRequest, UltraExecuteOrderResponse, UltraOrderRequest, UltraOrderResponse,
+        Validate, WalletBalance, shield_mint, validate_pubkey,
     },
 };
 
 use super::JupiterClient;
 
+/// Most mints the Price API accepts in one request.
+const MAX_PRICE_IDS: usize = 100;
+
+/// Most mints sent to Shield in one request by [`JupiterClient::screen_wallet`].
+const MAX_SHIELD_MINTS: usize = 100;
+
 impl JupiterClient {
     /// Fetches a swap order from Jupiter's Ultra API based on the provided parameters.
     ///
@@ -34,34 +48,32 @@ impl JupiterClient {
         &self,
         params: &UltraOrderRequest,
     ) -> Result<UltraOrderResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .get(format!("{}/ultra/v1/order", self.base_url))
-            .query(&params)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+        params.validate()?;
 
-        let response = handle_response(response).await?;
-
-        // CODE to debug the deseraliztion error
-        let text = response.text().await.map_err(|e| {
-            JupiterClientError::DeserializationError(format!("Failed to read response text: {}", e))
-        })?;
+        let request = self
+            .client
+            .get(self.endpoint(ApiFamily::Ultra, "/order"))
+            .query(&params);
 
-        // DEBUG: print raw JSON string
-        println!("Raw response body: {}", text);
+        let order = self
+            .send_request::<UltraOrderResponse>(ApiFamily::Ultra, request)
+            .await?;
 
-        match serde_json::from_str::<UltraOrderResponse>(&text) {
-            Ok(ultra_order_response) => Ok(ultra_order_response),
-            Err(e) => {
-                eprintln!("Deserialization error: {}", e);
-                Err(JupiterClientError::DeserializationError(e.to_string()))
-            }
+        self.emit(|| LifecycleEvent::QuoteFetched {
+            family: ApiFamily::Ultra,
+            input_mint: order.input_mint.clone(),
+            output_mint: order.output_mint.clone(),
+            in_amount: order.in_amount,
+            out_amount: order.out_amount,
+            server_time: order.server_time(),
+        });
+        if order.transaction.is_some() {
+            self.emit(|| LifecycleEvent::OrderCreated {
+                family: ApiFamily::Ultra,
+                request_id: order.request_id.clone(),
+            });
         }
+        Ok(order)
     }
 
     /// Executes a signed swap order using Jupiter's Ultra API.
@@ -75,6 +87,14 @@ impl JupiterClient {
     /// * `Ok(UltraExecuteOrderResponse)` on success.
     /// * `Err` if the request or deserialization fails.
     ///
+    /// Safe to retry, e.g. after a timeout: a request id whose transaction already
+    /// executed successfully through this client returns that response without sending it
+    /// again, and if Jupiter answers that the transaction was already processed, a success
+    /// with its signature is returned instead of the error.
+    ///
+    /// Fails with [`ExecutionPaused`](JupiterClientError::ExecutionPaused) without sending
+    /// anything while execution is [paused](Self::pause_execution).
+    ///
     /// # Jupiter API Reference
     ///
     /// - [Execute Order Endpoint](https://dev.jup.ag/docs/api/ultra-api/execute)
@@ -89,23 +109,68 @@ impl JupiterClient {
         &self,
         data: &UltraExecuteOrderRequest,
     ) -> Result<UltraExecuteOrderResponse, JupiterClientError> {
-        let response = match self
+        data.validate()?;
+        self.ensure_execution_allowed()?;
+
+        let request = self
             .client
-            .post(format!("{}/ultra/v1/execute", self.base_url))
-            .json(&data)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+            .post(self.endpoint(ApiFamily::Ultra, "/execute"))
+            .json(&data);
 
-        let response = handle_response(response).await?;
+        self.emit(|| LifecycleEvent::ExecutionSubmitted {
+            family: ApiFamily::Ultra,
+            request_id: data.request_id.clone(),
+        });
+        let result = self
+            .send_execute::<UltraExecuteOrderResponse>(
+                ApiFamily::Ultra,
+                &data.request_id,
+                &data.signed_transaction,
+                request,
+            )
+            .await;
 
-        match response.json::<UltraExecuteOrderResponse>().await {
-            Ok(swap_response) => Ok(swap_response),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        self.emit_execution(
+            ApiFamily::Ultra,
+            &data.request_id,
+            &result,
+            |response| match response.failure() {
+                None => Ok(response.signature.clone()),
+                Some(error) => Err(error),
+            },
+        );
+        result
+    }
+
+    /// Executes `order` with its `signed_transaction` and reports the outcome as an
+    /// [`ExecutionReport`].
+    ///
+    /// An execution the API reports as failed is returned as a
+    /// [`Failed`](crate::types::ExecutionStatus::Failed) report; `Err` means the outcome is
+    /// unknown, e.g. the request did not go through. Reports are added to the
+    /// [`execution_stats`](Self::execution_stats).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let order = api.get_ultra_order(&UltraOrderRequest::new(input_mint, output_mint, amount).add_taker(taker)).await?;
+    /// let report = api.ultra_execute_with_report(&order, &signed_tx).await?;
+    /// println!("{}", report.to_json()?);
+    /// ```
+    pub async fn ultra_execute_with_report(
+        &self,
+        order: &UltraOrderResponse,
+        signed_transaction: &str,
+    ) -> Result<ExecutionReport, JupiterClientError> {
+        let execution = self
+            .ultra_execute_order(&UltraExecuteOrderRequest::new(
+                signed_transaction,
+                &order.request_id,
+            ))
+            .await?;
+        let report = ExecutionReport::from_ultra(order, &execution, SystemTime::now());
+        self.record_execution(&report);
+        Ok(report)
     }
 
     /// Fetches token balances for a given wallet address using Jupiter's Ultra API.
@@ -134,22 +199,76 @@ impl JupiterClient {
         &self,
         address: &str,
     ) -> Result<TokenBalancesResponse, JupiterClientError> {
-        let response = match self
+        validate_pubkey("address", address)?;
+
+        let request = self
             .client
-            .get(format!("{}/ultra/v1/balances/{}", self.base_url, address))
-            .send()
+            .get(self.endpoint(ApiFamily::Ultra, &format!("/balances/{address}")));
+
+        self.send_request::<TokenBalancesResponse>(ApiFamily::Ultra, request)
             .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+    }
+
+    /// Fetches everything `address` holds: its SOL and each token account with its
+    /// program, frozen state and whether it is the associated token account.
+    ///
+    /// Richer than [`get_token_balances`](Self::get_token_balances), which sums the
+    /// accounts of each mint.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let holdings = api.get_holdings("3X2LFoTQecbpqCR7G5tL1kczqBKurjKPHhKSZrJ4wgWc").await?;
+    /// println!("{} SOL", holdings.ui_amount_string);
+    /// for mint in holdings.frozen_mints() {
+    ///     println!("{mint} is frozen");
+    /// }
+    /// ```
+    pub async fn get_holdings(&self, address: &str) -> Result<Holdings, JupiterClientError> {
+        validate_pubkey("address", address)?;
 
-        let response = handle_response(response).await?;
+        let request = self
+            .client
+            .get(self.endpoint(ApiFamily::Ultra, &format!("/holdings/{address}")));
+
+        self.send_request::<Holdings>(ApiFamily::Ultra, request)
+            .await
+    }
+
+    /// Fetches the balances of `address` with their USD value, ready to display in a wallet:
+    /// wrapped SOL merged into SOL, balances worth less than `min_usd_value` dropped,
+    /// sorted by value, highest first.
+    ///
+    /// Makes one balances request and one price request per 100 tokens.
+    /// See [`TokenBalancesExt`] to post-process balances yourself.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let balances = api.get_wallet_balances("3X2LFoTQecbpqCR7G5tL1kczqBKurjKPHhKSZrJ4wgWc", 0.01).await?;
+    /// for balance in balances {
+    ///     println!("{}: {} (${:.2?})", balance.mint, balance.ui_amount, balance.usd_value);
+    /// }
+    /// ```
+    pub async fn get_wallet_balances(
+        &self,
+        address: &str,
+        min_usd_value: f64,
+    ) -> Result<Vec<WalletBalance>, JupiterClientError> {
+        let mut balances = self.get_token_balances(address).await?;
+        balances.merge_wsol();
 
-        match response.json::<TokenBalancesResponse>().await {
-            Ok(token_balances) => Ok(token_balances),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
+        let mut prices = TokenPriceResponse {
+            data: Default::default(),
+            time_taken: 0.0,
+        };
+        for mints in balances.price_mints().chunks(MAX_PRICE_IDS) {
+            let response = self.get_token_price(&TokenPriceRequest::new(mints)).await?;
+            prices.data.extend(response.data);
+            prices.time_taken += response.time_taken;
         }
+
+        Ok(balances.to_display_list(&prices, min_usd_value))
     }
 
     /// Fetches token safety information for given mints using Jupiter's Ultra Shield API.
@@ -180,44 +299,132 @@ impl JupiterClient {
     /// println!("{:#?}", shield_info);
     /// ```
     pub async fn shield(&self, mints: &[String]) -> Result<Shield, JupiterClientError> {
-        let query_params = vec![("mints", mints.join(","))];
+        let request = self.shield_request(mints)?;
+        self.send_request::<Shield>(ApiFamily::Ultra, request).await
+    }
 
-        let response = match self
-            .client
-            .get(format!("{}/ultra/v1/shield", self.base_url))
-            .query(&query_params)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
+    /// Screens every token `address` holds with Shield and returns the warnings per token
+    /// with a portfolio summary, e.g. for compliance checks.
+    ///
+    /// Pulls the balances as [`get_wallet_balances`](Self::get_wallet_balances) does, then
+    /// sends the mints to Shield 100 at a time, one request after the other, so the
+    /// screening stays within the client's [`rate_limit`](super::JupiterClientBuilder::rate_limit).
+    /// Fails if any request fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let report = api.screen_wallet("3X2LFoTQecbpqCR7G5tL1kczqBKurjKPHhKSZrJ4wgWc").await?;
+    /// for mint in report.flagged() {
+    ///     println!("{} ({:?}): {:?}", mint.mint, mint.level, mint.warnings);
+    /// }
+    /// std::fs::write("screening.json", report.to_json()?)?;
+    /// ```
+    pub async fn screen_wallet(
+        &self,
+        address: &str,
+    ) -> Result<PortfolioRiskReport, JupiterClientError> {
+        let balances = self.get_wallet_balances(address, 0.0).await?;
+
+        let mints: Vec<String> = balances
+            .iter()
+            .map(|balance| shield_mint(&balance.mint).to_string())
+            .collect();
+        let mut shield = Shield {
+            warnings: Default::default(),
         };
+        for mints in mints.chunks(MAX_SHIELD_MINTS) {
+            shield.warnings.extend(self.shield(mints).await?.warnings);
+        }
+
+        Ok(PortfolioRiskReport::new(
+            address,
+            balances,
+            shield,
+            SystemTime::now(),
+        ))
+    }
+
+    /// Same as [`shield`](Self::shield), but falls back to the last successful response
+    /// for the same mints when Jupiter is unreachable.
+    ///
+    /// Requires [`offline_fallback`](super::JupiterClientBuilder::offline_fallback)
+    /// to be enabled, otherwise the response is always fresh or an error.
+    pub async fn shield_or_cached(
+        &self,
+        mints: &[String],
+    ) -> Result<CachedResponse<Shield>, JupiterClientError> {
+        let request = self.shield_request(mints)?;
+        self.send_cached_request::<Shield>(ApiFamily::Ultra, request)
+            .await
+    }
+
+    /// The request of [`shield`](Self::shield), shared with its cached variant.
+    fn shield_request(&self, mints: &[String]) -> Result<RequestBuilder, JupiterClientError> {
+        mints
+            .iter()
+            .try_for_each(|mint| validate_pubkey("mints", mint))?;
+
+        let query_params = vec![("mints", mints.join(","))];
 
-        let response = handle_response(response).await?;
+        Ok(self
+            .client
+            .get(self.endpoint(ApiFamily::Ultra, "/shield"))
+            .query(&query_params))
+    }
 
-        match response.json::<Shield>().await {
-            Ok(token_balances) => Ok(token_balances),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
+    /// Searches tokens by symbol, name or mint, returning matches ranked by Jupiter with
+    /// the best first, e.g. to resolve a symbol typed by a user to its mint.
+    ///
+    /// Several comma separated mints are looked up at once. Symbols aren't unique, so
+    /// prefer [verified](TokenSearchResult::is_verified) results.
+    ///
+    /// # Example
+    /// ```
+    /// let results = client.search_tokens("JUP").await?;
+    /// let jup = results.iter().find(|token| token.is_verified()).expect("no verified match");
+    /// println!("{} is {}", jup.symbol, jup.mint);
+    /// ```
+    pub async fn search_tokens(
+        &self,
+        query: &str,
+    ) -> Result<Vec<TokenSearchResult>, JupiterClientError> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Err(JupiterClientError::ValidationError(
+                "query must not be empty".to_string(),
+            ));
         }
+
+        let request = self
+            .client
+            .get(self.endpoint(ApiFamily::Ultra, "/search"))
+            .query(&[("query", query)]);
+
+        self.send_request::<Vec<TokenSearchResult>>(ApiFamily::Ultra, request)
+            .await
     }
 
     /// Request for the list of routers available in the routing engine of Ultra, which is Juno
     pub async fn routers(&self) -> Result<Vec<Router>, JupiterClientError> {
-        let response = match self
-            .client
-            .get(format!("{}/ultra/v1/order/routers", self.base_url))
-            .send()
+        let request = self.routers_request();
+        self.send_request::<Vec<Router>>(ApiFamily::Ultra, request)
             .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
+    }
 
-        response
-            .json::<Vec<Router>>()
+    /// Same as [`routers`](Self::routers), but falls back to the last successful response
+    /// when Jupiter is unreachable. See [`shield_or_cached`](Self::shield_or_cached).
+    pub async fn routers_or_cached(
+        &self,
+    ) -> Result<CachedResponse<Vec<Router>>, JupiterClientError> {
+        let request = self.routers_request();
+        self.send_cached_request::<Vec<Router>>(ApiFamily::Ultra, request)
             .await
-            .map_err(|e| JupiterClientError::DeserializationError(e.to_string()))
+    }
+
+    /// The request of [`routers`](Self::routers), shared with its cached variant.
+    fn routers_request(&self) -> RequestBuilder {
+        self.client
+            .get(self.endpoint(ApiFamily::Ultra, "/order/routers"))
     }
 }
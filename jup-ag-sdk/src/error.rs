@@ -1,4 +1,19 @@
-use reqwest::{Response, StatusCode};
+use std::{fmt, time::Duration};
+
+use reqwest::{
+    Method, Request, Response, StatusCode,
+    header::{HeaderMap, RETRY_AFTER},
+};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    config::ApiFamily,
+    runtime::{SystemTime, UNIX_EPOCH},
+    types::{Amount, Quote},
+};
+
+/// A boxed error from a user-provided component, such as tower middleware.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
 
 #[derive(Debug, thiserror::Error)]
 pub enum JupiterClientError {
@@ -8,21 +23,800 @@ pub enum JupiterClientError {
     #[error("Invalid header value: {0}")]
     HeaderError(#[from] reqwest::header::InvalidHeaderValue),
 
-    #[error("API returned error: {0}, Status Code: {1}")]
-    ApiError(String, StatusCode),
+    /// A non-success response from the API, with its error payload parsed, see
+    /// [`JupiterApiError`].
+    #[error("API returned error: {0}")]
+    ApiError(Box<JupiterApiError>),
+
+    /// A `429 Too Many Requests` response whose `Retry-After` header asks to wait
+    /// `retry_after` before sending again. 429s without the header are `ApiError`s.
+    ///
+    /// Retried automatically with
+    /// [`retry_rate_limited`](crate::JupiterClientBuilder::retry_rate_limited).
+    /// `endpoint` is the URL path, e.g. `/swap/v1/quote`, `method` the HTTP method of the
+    /// request if known, and `request_id` is read from the [`REQUEST_ID_HEADERS`].
+    #[error("Rate limited, retry after {retry_after:?}: {body}")]
+    RateLimited {
+        retry_after: Duration,
+        body: String,
+        method: Option<Method>,
+        endpoint: String,
+        request_id: Option<String>,
+    },
+
+    /// A call not sent because the
+    /// [`circuit_breaker`](crate::JupiterClientBuilder::circuit_breaker) of its endpoint is
+    /// open after repeated failures. `endpoint` is the URL path, e.g. `/swap/v1/quote`; the
+    /// next call is let through in `retry_in`.
+    #[error("Circuit open for {family} {endpoint}, retry in {retry_in:?}")]
+    CircuitOpen {
+        family: ApiFamily,
+        endpoint: String,
+        retry_in: Duration,
+    },
+
+    /// A body that couldn't be parsed, with the parser's `message`.
+    ///
+    /// For API responses, `endpoint` is the URL path, e.g. `/swap/v1/quote`, `method` the
+    /// HTTP method of the request if known, `body` the start of the response as received,
+    /// up to [`BODY_EXCERPT_LEN`] bytes, to diagnose schema changes from logs, and
+    /// `request_id` read from the [`REQUEST_ID_HEADERS`].
+    #[error(
+        "Failed to deserialize response{}: {message}{}{}",
+        .endpoint.as_ref().map(|endpoint| format!(" from {endpoint}")).unwrap_or_default(),
+        .body.as_ref().map(|body| format!(", body: {body}")).unwrap_or_default(),
+        .request_id.as_ref().map(|id| format!(" (request id {id})")).unwrap_or_default()
+    )]
+    DeserializationError {
+        message: String,
+        method: Option<Method>,
+        endpoint: Option<String>,
+        body: Option<String>,
+        request_id: Option<String>,
+    },
+
+    #[error("Invalid input: {0}")]
+    ValidationError(String),
+
+    /// An error raised by middleware added with
+    /// [`service_layer`](crate::JupiterClientBuilder::service_layer), e.g. a timeout
+    /// or a rejected request. Transport errors still come back as `RequestError`.
+    #[error("Service error: {0}")]
+    ServiceError(BoxError),
+
+    /// An `/execute` request refused because execution is paused with
+    /// [`pause_execution`](crate::JupiterClient::pause_execution). Nothing was sent.
+    #[error("Execution is paused")]
+    ExecutionPaused,
+
+    /// A call given up on because its
+    /// [`CancellationToken`](crate::client::CancellationToken) was cancelled, or its
+    /// [`deadline`](crate::client::RequestOptions::deadline) passed while it waited for a
+    /// rate or concurrency limit. Requests already sent may still be processed by the API.
+    #[error("Call cancelled")]
+    Cancelled,
+
+    /// A quote over the [`quote_freshness`](crate::JupiterClientBuilder::quote_freshness)
+    /// budget that wasn't re-quoted, either because the policy is
+    /// [`StaleQuotePolicy::Error`](crate::config::StaleQuotePolicy::Error) or because the
+    /// client didn't fetch it. Nothing was sent.
+    ///
+    /// `age` is `None` for quotes the client didn't fetch.
+    #[error(
+        "Quote is stale: {slot_lag} slots behind{}",
+        .age.map(|age| format!(", fetched {age:?} ago")).unwrap_or_default()
+    )]
+    StaleQuote {
+        age: Option<Duration>,
+        slot_lag: u64,
+    },
+
+    /// A swap that failed because the price moved past its slippage tolerance, Jupiter
+    /// program error 6001 (`0x1771`): re-quote and try again.
+    ///
+    /// Built for failed executions and simulations by
+    /// [`UltraExecuteOrderResponse::slippage_error`](crate::types::UltraExecuteOrderResponse::slippage_error)
+    /// and [`SwapResponse::slippage_error`](crate::types::SwapResponse::slippage_error),
+    /// with the amounts of the quote and, if the API reported it, the output the swap
+    /// would have made.
+    #[error(
+        "Slippage tolerance exceeded: quoted {quoted_out_amount}, minimum {min_out_amount}{}",
+        .actual_out_amount.map(|amount| format!(", actual {amount}")).unwrap_or_default()
+    )]
+    SlippageExceeded {
+        quoted_out_amount: Amount,
+        min_out_amount: Amount,
+        actual_out_amount: Option<Amount>,
+        signature: Option<String>,
+    },
+
+    /// An error reading or writing a [`StateStore`](crate::state_store::StateStore).
+    #[error("State store error: {0}")]
+    StoreError(BoxError),
 
-    #[error("Failed to deserialize response: {0}")]
-    DeserializationError(String),
+    /// Any of the errors above, together with a summary of the request that caused it.
+    ///
+    /// Only produced when
+    /// [`request_context_in_errors`](crate::JupiterClientBuilder::request_context_in_errors)
+    /// is enabled. Use [`inner`](JupiterClientError::inner) to match on the underlying error.
+    #[error("{source} [{request}]")]
+    WithRequest {
+        request: Box<RequestSummary>,
+        source: Box<JupiterClientError>,
+    },
+}
+
+impl JupiterClientError {
+    /// A `DeserializationError` with no response attached.
+    pub(crate) fn deserialization(message: impl Into<String>) -> Self {
+        JupiterClientError::DeserializationError {
+            message: message.into(),
+            method: None,
+            endpoint: None,
+            body: None,
+            request_id: None,
+        }
+    }
+
+    /// Attaches the request `method`, the `endpoint`, body `excerpt` and request id of the
+    /// response to a `DeserializationError`; other errors are returned unchanged.
+    pub(crate) fn with_response(
+        self,
+        method: Option<&Method>,
+        endpoint: &str,
+        excerpt: String,
+        request_id: Option<String>,
+    ) -> Self {
+        match self {
+            JupiterClientError::DeserializationError { message, .. } => {
+                JupiterClientError::DeserializationError {
+                    message,
+                    method: method.cloned(),
+                    endpoint: Some(endpoint.to_string()),
+                    body: Some(excerpt),
+                    request_id,
+                }
+            }
+            other => other,
+        }
+    }
+
+    /// A `SlippageExceeded` error for a swap of `quote`.
+    pub(crate) fn slippage_exceeded(
+        quote: &impl Quote,
+        actual_out_amount: Option<Amount>,
+        signature: Option<String>,
+    ) -> Self {
+        JupiterClientError::SlippageExceeded {
+            quoted_out_amount: quote.out_amount(),
+            min_out_amount: quote.min_out_amount(),
+            actual_out_amount,
+            signature,
+        }
+    }
+
+    /// The kind of error, looking through
+    /// [`WithRequest`](JupiterClientError::WithRequest).
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            JupiterClientError::RequestError(e) if e.is_timeout() => ErrorKind::Timeout,
+            JupiterClientError::RequestError(_) => ErrorKind::Transport,
+            JupiterClientError::HeaderError(_) | JupiterClientError::ValidationError(_) => {
+                ErrorKind::Validation
+            }
+            JupiterClientError::ApiError(_) => ErrorKind::Api,
+            JupiterClientError::RateLimited { .. } => ErrorKind::RateLimited,
+            JupiterClientError::CircuitOpen { .. } => ErrorKind::CircuitOpen,
+            JupiterClientError::DeserializationError { .. } => ErrorKind::Deserialization,
+            JupiterClientError::ServiceError(_) => ErrorKind::Service,
+            JupiterClientError::ExecutionPaused => ErrorKind::ExecutionPaused,
+            JupiterClientError::Cancelled => ErrorKind::Cancelled,
+            JupiterClientError::StaleQuote { .. } => ErrorKind::StaleQuote,
+            JupiterClientError::SlippageExceeded { .. } => ErrorKind::SlippageExceeded,
+            JupiterClientError::StoreError(_) => ErrorKind::Store,
+            JupiterClientError::WithRequest { source, .. } => source.kind(),
+        }
+    }
+
+    /// The URL path of the endpoint that failed, e.g. `/swap/v1/quote`, if known.
+    ///
+    /// Known for API errors, rate limits, undecodable responses, open circuits, transport
+    /// errors with a URL and every error with
+    /// [request context](crate::JupiterClientBuilder::request_context_in_errors). Errors
+    /// raised before a request is made, such as validation errors, have none.
+    pub fn endpoint(&self) -> Option<&str> {
+        match self {
+            JupiterClientError::WithRequest { request, .. } => Some(&request.endpoint),
+            JupiterClientError::RequestError(e) => e.url().map(|url| url.path()),
+            JupiterClientError::ApiError(error) => Some(&error.endpoint),
+            JupiterClientError::RateLimited { endpoint, .. } => Some(endpoint),
+            JupiterClientError::CircuitOpen { endpoint, .. } => Some(endpoint),
+            JupiterClientError::DeserializationError { endpoint, .. } => endpoint.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The HTTP method of the request that failed, if known: for API errors, rate limits
+    /// and undecodable API responses, and every error with
+    /// [request context](crate::JupiterClientBuilder::request_context_in_errors).
+    ///
+    /// Transport errors (`RequestError`) and open circuits only carry the endpoint; enable
+    /// request context to get their method too.
+    pub fn method(&self) -> Option<&str> {
+        match self {
+            JupiterClientError::WithRequest { request, .. } => Some(&request.method),
+            JupiterClientError::ApiError(error) => error.method.as_ref().map(Method::as_str),
+            JupiterClientError::RateLimited { method, .. }
+            | JupiterClientError::DeserializationError { method, .. } => {
+                method.as_ref().map(Method::as_str)
+            }
+            _ => None,
+        }
+    }
+
+    /// The id the API assigned to the failed request, read from the
+    /// [`REQUEST_ID_HEADERS`] of its response, to quote when reporting an issue to
+    /// Jupiter. `None` if no response was received or it had none.
+    ///
+    /// # Example
+    /// ```
+    /// if let Err(e) = client.ultra_execute_order(&request).await {
+    ///     tracing::error!(
+    ///         method = e.method(),
+    ///         endpoint = e.endpoint(),
+    ///         request_id = e.request_id(),
+    ///         "execute failed: {e}"
+    ///     );
+    /// }
+    /// ```
+    pub fn request_id(&self) -> Option<&str> {
+        match self.inner() {
+            JupiterClientError::ApiError(error) => error.request_id.as_deref(),
+            JupiterClientError::RateLimited { request_id, .. }
+            | JupiterClientError::DeserializationError { request_id, .. } => request_id.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The underlying error, looking through [`WithRequest`](JupiterClientError::WithRequest).
+    pub fn inner(&self) -> &JupiterClientError {
+        match self {
+            JupiterClientError::WithRequest { source, .. } => source.inner(),
+            other => other,
+        }
+    }
+
+    /// The error response from the API, if the call failed with one.
+    ///
+    /// # Example
+    /// ```
+    /// match client.get_quote(&request).await {
+    ///     Err(e) if e.api_error().is_some_and(|api| api.code.as_deref() == Some("TOKEN_NOT_TRADABLE")) => {
+    ///         skip(request.input_mint)
+    ///     }
+    ///     result => handle(result?),
+    /// }
+    /// ```
+    pub fn api_error(&self) -> Option<&JupiterApiError> {
+        match self.inner() {
+            JupiterClientError::ApiError(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// The summary of the failed request, if request context is enabled.
+    pub fn request(&self) -> Option<&RequestSummary> {
+        match self {
+            JupiterClientError::WithRequest { request, .. } => Some(request),
+            _ => None,
+        }
+    }
+
+    /// Classifies a Swap API error into a [`QuoteErrorKind`], if it is one of the
+    /// well-known failures. Returns `None` for other errors.
+    ///
+    /// # Example
+    /// ```
+    /// use jup_ag_sdk::{JupiterClientError, error::QuoteErrorKind};
+    ///
+    /// fn should_skip(err: &JupiterClientError) -> bool {
+    ///     matches!(
+    ///         err.quote_error_kind(),
+    ///         Some(QuoteErrorKind::NoRouteFound | QuoteErrorKind::TokenNotTradable)
+    ///     )
+    /// }
+    /// ```
+    pub fn quote_error_kind(&self) -> Option<QuoteErrorKind> {
+        self.api_error().and_then(JupiterApiError::quote_error_kind)
+    }
+
+    /// Returns `true` if Jupiter could not find a route for the requested swap.
+    pub fn is_no_route(&self) -> bool {
+        self.quote_error_kind() == Some(QuoteErrorKind::NoRouteFound)
+    }
+}
+
+/// An error response from a Jupiter API.
+///
+/// The quote, swap, Ultra and Trigger APIs answer errors with a JSON payload such as
+/// `{"error": "Could not find any route", "errorCode": "COULD_NOT_FIND_ANY_ROUTE"}` or
+/// `{"error": "Insufficient funds", "code": -1}`; its code and message are parsed out so
+/// callers can match on them.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error(
+    "{status} from {}{endpoint}: {message}{}{}",
+    .method.as_ref().map(|method| format!("{method} ")).unwrap_or_default(),
+    .code.as_ref().map(|code| format!(" ({code})")).unwrap_or_default(),
+    .request_id.as_ref().map(|id| format!(" (request id {id})")).unwrap_or_default()
+)]
+#[non_exhaustive]
+pub struct JupiterApiError {
+    pub status: StatusCode,
+    /// The `errorCode` or `code` of the payload, e.g. `COULD_NOT_FIND_ANY_ROUTE`.
+    /// Numeric codes, as returned by Ultra, are kept as text, e.g. `-1`.
+    pub code: Option<String>,
+    /// The `error`, `message` or `errorMessage` of the payload, or the whole body if it
+    /// has none or isn't JSON.
+    pub message: String,
+    /// The URL path of the endpoint, e.g. `/swap/v1/quote`.
+    pub endpoint: String,
+    /// The method of the request, `None` if the response was handled without it, as by
+    /// [`handle_response`].
+    pub method: Option<Method>,
+    /// The id of the request, read from the [`REQUEST_ID_HEADERS`] of the response.
+    pub request_id: Option<String>,
+    /// The response body as received.
+    pub body: String,
+}
+
+impl JupiterApiError {
+    /// Parses the `body` of a `status` response from `endpoint`.
+    pub fn new(status: StatusCode, endpoint: &str, body: String) -> Self {
+        let json = serde_json::from_str::<serde_json::Value>(&body).ok();
+        let code = json.as_ref().and_then(error_code);
+        let message = json
+            .as_ref()
+            .and_then(|json| {
+                ["error", "message", "errorMessage"]
+                    .iter()
+                    .find_map(|key| json.get(key)?.as_str())
+            })
+            .map_or_else(|| body.trim().to_string(), str::to_string);
+
+        Self {
+            status,
+            code,
+            message,
+            endpoint: endpoint.to_string(),
+            method: None,
+            request_id: None,
+            body,
+        }
+    }
+
+    /// Classifies the error into a [`QuoteErrorKind`], if it is one of the well-known
+    /// failures, preferring the code over the message.
+    pub fn quote_error_kind(&self) -> Option<QuoteErrorKind> {
+        self.code
+            .as_deref()
+            .and_then(QuoteErrorKind::from_code)
+            .or_else(|| QuoteErrorKind::from_message(&self.message))
+    }
+}
+
+/// The kind of a [`JupiterClientError`], a low-cardinality label for metrics and alerting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The request timed out.
+    Timeout,
+    /// The request couldn't be sent or its response read.
+    Transport,
+    /// Invalid input, including header values; nothing was sent.
+    Validation,
+    /// The API answered with an error status.
+    Api,
+    RateLimited,
+    CircuitOpen,
+    Deserialization,
+    Service,
+    ExecutionPaused,
+    Cancelled,
+    StaleQuote,
+    SlippageExceeded,
+    Store,
+}
+
+impl ErrorKind {
+    /// The kind in snake case, e.g. `rate_limited`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorKind::Timeout => "timeout",
+            ErrorKind::Transport => "transport",
+            ErrorKind::Validation => "validation",
+            ErrorKind::Api => "api",
+            ErrorKind::RateLimited => "rate_limited",
+            ErrorKind::CircuitOpen => "circuit_open",
+            ErrorKind::Deserialization => "deserialization",
+            ErrorKind::Service => "service",
+            ErrorKind::ExecutionPaused => "execution_paused",
+            ErrorKind::Cancelled => "cancelled",
+            ErrorKind::StaleQuote => "stale_quote",
+            ErrorKind::SlippageExceeded => "slippage_exceeded",
+            ErrorKind::Store => "store",
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Well-known reasons the Swap API rejects a quote or swap request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum QuoteErrorKind {
+    /// No route exists between the two mints for this amount.
+    NoRouteFound,
+    /// One of the mints cannot be traded on Jupiter.
+    TokenNotTradable,
+    /// The amount is too small to be routed.
+    AmountTooSmall,
+    /// Input and output mints are the same.
+    CircularArbitrageDisabled,
+    /// The best route cannot swap the full amount.
+    RoutePlanDoesNotConsumeAllTheAmount,
+    /// The slippage threshold could not be computed for the route.
+    CannotComputeOtherAmountThreshold,
+    /// The swap transaction is over the size limit or locks too many accounts.
+    TransactionTooLarge,
+    /// The taker doesn't hold enough of the input token, or SOL for fees.
+    InsufficientFunds,
+    /// The price moved past the slippage tolerance before the swap landed.
+    SlippageToleranceExceeded,
+}
+
+impl QuoteErrorKind {
+    /// Maps an `errorCode` such as `COULD_NOT_FIND_ANY_ROUTE`.
+    pub fn from_code(code: &str) -> Option<Self> {
+        let kind = match code {
+            "COULD_NOT_FIND_ANY_ROUTE" | "NO_ROUTES_FOUND" => QuoteErrorKind::NoRouteFound,
+            "TOKEN_NOT_TRADABLE" => QuoteErrorKind::TokenNotTradable,
+            "AMOUNT_TOO_SMALL" => QuoteErrorKind::AmountTooSmall,
+            "CIRCULAR_ARBITRAGE_IS_DISABLED" => QuoteErrorKind::CircularArbitrageDisabled,
+            "ROUTE_PLAN_DOES_NOT_CONSUME_ALL_THE_AMOUNT" => {
+                QuoteErrorKind::RoutePlanDoesNotConsumeAllTheAmount
+            }
+            "CANNOT_COMPUTE_OTHER_AMOUNT_THRESHOLD" => {
+                QuoteErrorKind::CannotComputeOtherAmountThreshold
+            }
+            "INSUFFICIENT_FUNDS" | "INSUFFICIENT_BALANCE" => QuoteErrorKind::InsufficientFunds,
+            "SLIPPAGE_TOLERANCE_EXCEEDED" | "6001" => QuoteErrorKind::SlippageToleranceExceeded,
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    /// Maps a human readable error message such as `"Could not find any route"`.
+    pub fn from_message(message: &str) -> Option<Self> {
+        let message = message.to_ascii_lowercase();
+        let kind = if message.contains("could not find any route")
+            || message.contains("no routes found")
+        {
+            QuoteErrorKind::NoRouteFound
+        } else if message.contains("not tradable") {
+            QuoteErrorKind::TokenNotTradable
+        } else if message.contains("too small") {
+            QuoteErrorKind::AmountTooSmall
+        } else if message.contains("circular arbitrage") {
+            QuoteErrorKind::CircularArbitrageDisabled
+        } else if message.contains("does not consume all the amount") {
+            QuoteErrorKind::RoutePlanDoesNotConsumeAllTheAmount
+        } else if message.contains("cannot compute other amount threshold") {
+            QuoteErrorKind::CannotComputeOtherAmountThreshold
+        } else if message.contains("transaction too large")
+            || message.contains("too many account")
+            || message.contains("toomanyaccountlocks")
+        {
+            QuoteErrorKind::TransactionTooLarge
+        } else if message.contains("insufficient funds") || message.contains("insufficient balance")
+        {
+            QuoteErrorKind::InsufficientFunds
+        } else if message.contains("slippage tolerance exceeded")
+            || message.contains("slippagetoleranceexceeded")
+            || message.contains("0x1771")
+            || message.contains("custom program error: 6001")
+        {
+            QuoteErrorKind::SlippageToleranceExceeded
+        } else {
+            return None;
+        };
+        Some(kind)
+    }
+
+    /// Classifies an error response body, preferring its `errorCode` over the message.
+    /// Bodies that aren't JSON are matched as plain messages.
+    pub fn from_body(body: &str) -> Option<Self> {
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(body) else {
+            return Self::from_message(body);
+        };
+
+        error_code(&json)
+            .and_then(|code| Self::from_code(&code))
+            .or_else(|| {
+                json.get("error")
+                    .or_else(|| json.get("message"))
+                    .and_then(|message| message.as_str())
+                    .and_then(Self::from_message)
+            })
+    }
 }
 
 pub async fn handle_response(response: Response) -> Result<Response, JupiterClientError> {
     if !response.status().is_success() {
         let status = response.status();
+        let headers = response.headers().clone();
+        let endpoint = response.url().path().to_string();
         let error_text = response
             .text()
             .await
             .unwrap_or_else(|_| "Unable to get error details".to_string());
-        return Err(JupiterClientError::ApiError(error_text, status));
+        return Err(status_error(status, None, &endpoint, &headers, error_text));
     }
     Ok(response)
 }
+
+/// The error for a non-success response to a `method` request to `endpoint`:
+/// `RateLimited` for a 429 with a `Retry-After` header, `ApiError` otherwise.
+pub(crate) fn status_error(
+    status: StatusCode,
+    method: Option<&Method>,
+    endpoint: &str,
+    headers: &HeaderMap,
+    body: String,
+) -> JupiterClientError {
+    let request_id = request_id(headers);
+    match retry_after(headers) {
+        Some(retry_after) if status == StatusCode::TOO_MANY_REQUESTS => {
+            JupiterClientError::RateLimited {
+                retry_after,
+                body,
+                method: method.cloned(),
+                endpoint: endpoint.to_string(),
+                request_id,
+            }
+        }
+        _ => {
+            let mut error = JupiterApiError::new(status, endpoint, body);
+            error.method = method.cloned();
+            error.request_id = request_id;
+            JupiterClientError::ApiError(Box::new(error))
+        }
+    }
+}
+
+/// Response headers carrying the id of a request, checked in order. Cloudflare's
+/// `cf-ray` is last, as the API sits behind it.
+pub const REQUEST_ID_HEADERS: &[&str] =
+    &["x-request-id", "request-id", "x-correlation-id", "cf-ray"];
+
+/// The request id of a response, from the first of the [`REQUEST_ID_HEADERS`] it has.
+pub(crate) fn request_id(headers: &HeaderMap) -> Option<String> {
+    REQUEST_ID_HEADERS
+        .iter()
+        .find_map(|name| headers.get(*name)?.to_str().ok())
+        .map(str::to_string)
+}
+
+/// The delay asked for by a `Retry-After` header, in seconds or as an HTTP date.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    // httpdate parses into a `std` time, which can't tell the current time on wasm32.
+    let date = httpdate::parse_http_date(value)
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    Some(date.saturating_sub(now))
+}
+
+/// Reads the full response body and deserializes it into `T`.
+///
+/// Parsing goes through [`crate::json::from_slice`], so it honours the `simd-json` feature.
+pub async fn decode_response<T: DeserializeOwned>(
+    response: Response,
+) -> Result<T, JupiterClientError> {
+    let endpoint = response.url().path().to_string();
+    let request_id = request_id(response.headers());
+    let mut body = response.bytes().await?.to_vec();
+    let excerpt = body_excerpt(&body);
+    crate::json::from_slice(&mut body)
+        .map_err(|e| e.with_response(None, &endpoint, excerpt, request_id))
+}
+
+/// How much of a response body a [`DeserializationError`](JupiterClientError::DeserializationError)
+/// keeps, in bytes.
+pub const BODY_EXCERPT_LEN: usize = 512;
+
+/// The start of `body` as text, up to [`BODY_EXCERPT_LEN`] bytes, marked with the full
+/// length if cut.
+pub(crate) fn body_excerpt(body: &[u8]) -> String {
+    if body.len() <= BODY_EXCERPT_LEN {
+        return String::from_utf8_lossy(body).into_owned();
+    }
+    format!(
+        "{}... ({} bytes)",
+        String::from_utf8_lossy(&body[..BODY_EXCERPT_LEN]),
+        body.len()
+    )
+}
+
+/// Details about a failed request, passed to the
+/// [`on_error`](crate::JupiterClientBuilder::on_error) callback.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ErrorContext<'a> {
+    /// The API family the request was for.
+    pub family: ApiFamily,
+    pub method: &'a Method,
+    /// The URL path of the endpoint, e.g. `/swap/v1/quote`.
+    pub endpoint: &'a str,
+    /// Which attempt failed, starting at 1.
+    pub attempt: u32,
+    /// How long the call took, including rate limit retries and waits for a free slot.
+    pub duration: Duration,
+    /// The HTTP status, if a response was received.
+    pub status: Option<StatusCode>,
+    /// The error code from the API error body (`errorCode` or `code`), if any.
+    pub code: Option<String>,
+    /// The kind of [`error`](Self::error).
+    pub kind: ErrorKind,
+    /// The error about to be returned to the caller.
+    pub error: &'a JupiterClientError,
+}
+
+impl<'a> ErrorContext<'a> {
+    pub(crate) fn new(
+        family: ApiFamily,
+        method: &'a Method,
+        endpoint: &'a str,
+        attempt: u32,
+        duration: Duration,
+        error: &'a JupiterClientError,
+    ) -> Self {
+        let (status, code) = match error.inner() {
+            JupiterClientError::ApiError(error) => (Some(error.status), error.code.clone()),
+            JupiterClientError::RateLimited { body, .. } => {
+                (Some(StatusCode::TOO_MANY_REQUESTS), api_error_code(body))
+            }
+            JupiterClientError::RequestError(e) => (e.status(), None),
+            _ => (None, None),
+        };
+
+        Self {
+            family,
+            method,
+            endpoint,
+            attempt,
+            duration,
+            status,
+            code,
+            kind: error.kind(),
+            error,
+        }
+    }
+}
+
+/// Extracts the error code from a Jupiter error body such as
+/// `{"error": "Could not find any route", "errorCode": "COULD_NOT_FIND_ANY_ROUTE"}`.
+pub(crate) fn api_error_code(body: &str) -> Option<String> {
+    error_code(&serde_json::from_str(body).ok()?)
+}
+
+fn error_code(body: &serde_json::Value) -> Option<String> {
+    match body.get("errorCode").or_else(|| body.get("code"))? {
+        serde_json::Value::String(code) => Some(code.clone()),
+        serde_json::Value::Number(code) => Some(code.to_string()),
+        _ => None,
+    }
+}
+
+/// Request parameters worth including in a [`RequestSummary`]. Anything else, in
+/// particular signed transactions, is left out.
+const SUMMARY_PARAMS: &[&str] = &[
+    "inputMint",
+    "outputMint",
+    "amount",
+    "swapMode",
+    "slippageBps",
+    "platformFeeBps",
+    "onlyDirectRoutes",
+    "maxAccounts",
+    "taker",
+    "userPublicKey",
+    "user",
+    "maker",
+    "payer",
+    "order",
+    "orders",
+    "requestId",
+    "makingAmount",
+    "takingAmount",
+    "mints",
+    "ids",
+    "vsToken",
+    "orderStatus",
+    "recurringType",
+    "page",
+];
+
+/// A compact, secret-free description of a request, attached to errors by
+/// [`JupiterClientError::WithRequest`].
+///
+/// Renders as e.g. `GET /swap/v1/quote inputMint=So11…1112 outputMint=JUPy…DvCN amount=1000000000`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestSummary {
+    pub method: String,
+    /// The URL path, e.g. `/swap/v1/quote`.
+    pub endpoint: String,
+    /// Key parameters from the query string and JSON body, in order.
+    pub params: Vec<(String, String)>,
+}
+
+impl RequestSummary {
+    pub(crate) fn from_request(request: &Request) -> Self {
+        let mut params: Vec<(String, String)> = request
+            .url()
+            .query_pairs()
+            .filter(|(name, _)| SUMMARY_PARAMS.contains(&name.as_ref()))
+            .map(|(name, value)| (name.into_owned(), value.into_owned()))
+            .collect();
+
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .and_then(|bytes| serde_json::from_slice::<serde_json::Value>(bytes).ok());
+        if let Some(serde_json::Value::Object(body)) = body {
+            // nested objects such as the trigger `params` are flattened one level
+            let nested = body.values().filter_map(|value| value.as_object());
+            for (name, value) in std::iter::once(&body).chain(nested).flatten() {
+                if SUMMARY_PARAMS.contains(&name.as_str()) {
+                    let value = match value {
+                        serde_json::Value::String(s) => s.clone(),
+                        serde_json::Value::Null => continue,
+                        other => other.to_string(),
+                    };
+                    params.push((name.clone(), value));
+                }
+            }
+        }
+
+        Self {
+            method: request.method().to_string(),
+            endpoint: request.url().path().to_string(),
+            params,
+        }
+    }
+}
+
+impl fmt::Display for RequestSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.endpoint)?;
+        for (name, value) in &self.params {
+            // shorten addresses and long lists so the summary stays on one line
+            if value.is_ascii() && value.len() > 16 {
+                write!(f, " {name}={}…{}", &value[..4], &value[value.len() - 4..])?;
+            } else {
+                write!(f, " {name}={value}")?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+//! Lifecycle events emitted by the client's trading flows.
+//!
+//! Quotes, order creation, signing and execution each emit a [`LifecycleEvent`] to every
+//! [`EventSubscriber`] registered with
+//! [`JupiterClientBuilder::event_subscriber`](crate::JupiterClientBuilder::event_subscriber),
+//! so an audit log or a UI can follow swaps and orders from one place instead of wrapping
+//! every call.
+//!
+//! # Example
+//! ```
+//! use jup_ag_sdk::{JupiterClient, events::LifecycleEvent};
+//!
+//! let client = JupiterClient::builder("https://lite-api.jup.ag")
+//!     .event_subscriber(|event: &LifecycleEvent| match event {
+//!         LifecycleEvent::ExecutionConfirmed { request_id, signature, .. } => {
+//!             println!("{request_id} landed: {signature:?}");
+//!         }
+//!         LifecycleEvent::ExecutionFailed { request_id, error, .. } => {
+//!             eprintln!("{request_id} failed: {error}");
+//!         }
+//!         _ => {}
+//!     })
+//!     .build()?;
+//! ```
+
+use std::{fmt, sync::Arc, time::Duration};
+
+use reqwest::Method;
+
+use crate::{config::ApiFamily, types::Amount};
+
+/// A step of a quote, order or execution flow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LifecycleEvent {
+    /// A quote was returned by the Swap API `/quote` or the Ultra API `/order`.
+    QuoteFetched {
+        family: ApiFamily,
+        input_mint: String,
+        output_mint: String,
+        in_amount: Amount,
+        out_amount: Amount,
+        /// How long Jupiter took to compute the quote, as it reports it.
+        server_time: Duration,
+    },
+    /// An unsigned order transaction was returned: an Ultra order with a taker, or a new
+    /// trigger or recurring order.
+    OrderCreated {
+        family: ApiFamily,
+        request_id: String,
+    },
+    /// A transaction was signed through
+    /// [`JupiterClient::sign_transaction`](crate::JupiterClient::sign_transaction).
+    /// `signature` is the fee payer's, i.e. the transaction's id.
+    TransactionSigned { signature: String },
+    /// A signed transaction was sent to an `/execute` endpoint.
+    ExecutionSubmitted {
+        family: ApiFamily,
+        request_id: String,
+    },
+    /// Jupiter reported the execution as successful.
+    ExecutionConfirmed {
+        family: ApiFamily,
+        request_id: String,
+        signature: Option<String>,
+    },
+    /// The execution failed, either on chain or because the request itself failed.
+    ExecutionFailed {
+        family: ApiFamily,
+        request_id: String,
+        error: String,
+    },
+    /// A request took longer than its
+    /// [`slow_call_threshold`](crate::JupiterClientBuilder::slow_call_threshold), a sign
+    /// Jupiter is degraded. `path` is the URL path, e.g. `/swap/v1/quote`.
+    SlowCall {
+        family: ApiFamily,
+        method: Method,
+        path: String,
+        latency: Duration,
+        threshold: Duration,
+    },
+    /// The [`circuit_breaker`](crate::JupiterClientBuilder::circuit_breaker) of an endpoint
+    /// tripped: calls to `path` fail without being sent for `cool_down`.
+    CircuitOpened {
+        family: ApiFamily,
+        path: String,
+        cool_down: Duration,
+    },
+    /// A request to `path` failed on the client's host and was sent to the
+    /// [`failover`](crate::JupiterClientBuilder::failover) host at `base_url`.
+    FailedOver {
+        family: ApiFamily,
+        path: String,
+        base_url: String,
+    },
+}
+
+/// Receives the [`LifecycleEvent`]s of a client.
+///
+/// Subscribers are called inline, in registration order, from the task making the call,
+/// so they should return quickly; hand events off to a channel for slow work.
+/// Implemented for closures taking `&LifecycleEvent`.
+pub trait EventSubscriber: Send + Sync {
+    fn on_event(&self, event: &LifecycleEvent);
+}
+
+impl<F> EventSubscriber for F
+where
+    F: Fn(&LifecycleEvent) + Send + Sync,
+{
+    fn on_event(&self, event: &LifecycleEvent) {
+        self(event)
+    }
+}
+
+/// An event subscriber registered on the client builder.
+#[derive(Clone)]
+pub(crate) struct SharedSubscriber(pub(crate) Arc<dyn EventSubscriber>);
+
+impl fmt::Debug for SharedSubscriber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedSubscriber")
+    }
+}
@@ -0,0 +1,300 @@
+//! JSON decoding used for every Jupiter API response.
+//!
+//! Responses are parsed with `serde_json` by default. Enabling the `simd-json`
+//! feature switches the parser to [simd-json](https://docs.rs/simd-json), which is
+//! considerably faster on large payloads such as multi-hop route plans and token lists.
+//!
+//! Fields the SDK doesn't know are ignored, so new fields Jupiter adds don't break
+//! parsing. [`from_slice_strict`] rejects them instead, which is what
+//! [`JupiterClientBuilder::strict_deserialization`](crate::JupiterClientBuilder::strict_deserialization)
+//! uses to catch schema drift in CI.
+
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer, MapAccess,
+    SeqAccess, VariantAccess, Visitor,
+};
+use serde_json::Value;
+
+use crate::error::JupiterClientError;
+
+/// Deserializes a value of type `T` from a raw JSON body.
+///
+/// The buffer is taken mutably because `simd-json` parses in place; with the
+/// default `serde_json` backend it is left untouched.
+///
+/// # Example
+/// ```
+/// use jup_ag_sdk::{json, types::TokenPrice};
+///
+/// let mut body = br#"{"id":"So11111111111111111111111111111111111111112","type":"derivedPrice","price":"150.12"}"#.to_vec();
+/// let price: TokenPrice = json::from_slice(&mut body).unwrap();
+/// assert_eq!(price.price, "150.12");
+/// ```
+pub fn from_slice<T: DeserializeOwned>(bytes: &mut [u8]) -> Result<T, JupiterClientError> {
+    #[cfg(feature = "simd-json")]
+    let result = simd_json::serde::from_slice::<T>(bytes).map_err(|e| e.to_string());
+
+    #[cfg(not(feature = "simd-json"))]
+    let result = serde_json::from_slice::<T>(bytes).map_err(|e| e.to_string());
+
+    result.map_err(JupiterClientError::deserialization)
+}
+
+/// Like [`from_slice`], but fails with a `DeserializationError` naming every field of
+/// the body that `T` doesn't have, e.g. `unknown fields: routePlan[0].swapInfo.newField`.
+///
+/// Fields captured by a `#[serde(flatten)]` map count as known. Always parses with
+/// `serde_json`, whether or not the `simd-json` feature is enabled.
+///
+/// # Example
+/// ```
+/// use jup_ag_sdk::{json, types::TokenPrice};
+///
+/// let mut body = br#"{"id":"So11111111111111111111111111111111111111112","type":"derivedPrice","price":"150.12","new":1}"#.to_vec();
+/// let err = json::from_slice_strict::<TokenPrice>(&mut body).unwrap_err();
+/// assert!(err.to_string().contains("unknown fields: new"));
+/// ```
+pub fn from_slice_strict<T: DeserializeOwned>(bytes: &mut [u8]) -> Result<T, JupiterClientError> {
+    let value: Value = serde_json::from_slice(bytes)
+        .map_err(|e| JupiterClientError::deserialization(e.to_string()))?;
+
+    let mut unknown = Vec::new();
+    let result = T::deserialize(Tracking {
+        value: &value,
+        path: String::new(),
+        unknown: &mut unknown,
+    });
+    let parsed = result.map_err(|e| JupiterClientError::deserialization(e.to_string()))?;
+    if !unknown.is_empty() {
+        unknown.sort();
+        return Err(JupiterClientError::deserialization(format!(
+            "unknown fields: {}",
+            unknown.join(", ")
+        )));
+    }
+    Ok(parsed)
+}
+
+/// Deserializes from a JSON value, recording the path of every value the target type
+/// skips as an unknown field.
+struct Tracking<'v, 'u> {
+    value: &'v Value,
+    path: String,
+    unknown: &'u mut Vec<String>,
+}
+
+impl<'de, 'v, 'u> Deserializer<'de> for Tracking<'v, 'u> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Null => visitor.visit_unit(),
+            Value::Bool(b) => visitor.visit_bool(*b),
+            Value::Number(n) => {
+                if let Some(n) = n.as_u64() {
+                    visitor.visit_u64(n)
+                } else if let Some(n) = n.as_i64() {
+                    visitor.visit_i64(n)
+                } else {
+                    visitor.visit_f64(n.as_f64().unwrap_or(f64::NAN))
+                }
+            }
+            Value::String(s) => visitor.visit_str(s),
+            Value::Array(items) => visitor.visit_seq(TrackingSeq {
+                items: items.iter().enumerate(),
+                path: self.path,
+                unknown: self.unknown,
+            }),
+            Value::Object(fields) => visitor.visit_map(TrackingMap {
+                fields: fields.iter(),
+                value: None,
+                path: self.path,
+                unknown: self.unknown,
+            }),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::String(variant) => visitor.visit_enum(variant.as_str().into_deserializer()),
+            Value::Object(fields) if fields.len() == 1 => {
+                let (variant, value) = fields.iter().next().expect("one field");
+                let path = join(&self.path, variant);
+                visitor.visit_enum(TrackingEnum {
+                    variant,
+                    value: Tracking {
+                        value,
+                        path,
+                        unknown: self.unknown,
+                    },
+                })
+            }
+            _ => Err(de::Error::custom("expected an enum variant")),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let path = if self.path.is_empty() {
+            "<root>".to_string()
+        } else {
+            self.path
+        };
+        self.unknown.push(path);
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        unit unit_struct seq tuple tuple_struct map struct identifier
+    }
+}
+
+struct TrackingSeq<'v, 'u> {
+    items: std::iter::Enumerate<std::slice::Iter<'v, Value>>,
+    path: String,
+    unknown: &'u mut Vec<String>,
+}
+
+impl<'de, 'v, 'u> SeqAccess<'de> for TrackingSeq<'v, 'u> {
+    type Error = serde_json::Error;
+
+    fn next_element_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, Self::Error> {
+        let Some((index, value)) = self.items.next() else {
+            return Ok(None);
+        };
+        let path = format!("{}[{index}]", self.path);
+        seed.deserialize(Tracking {
+            value,
+            path,
+            unknown: self.unknown,
+        })
+        .map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+}
+
+struct TrackingMap<'v, 'u> {
+    fields: serde_json::map::Iter<'v>,
+    value: Option<(&'v String, &'v Value)>,
+    path: String,
+    unknown: &'u mut Vec<String>,
+}
+
+impl<'de, 'v, 'u> MapAccess<'de> for TrackingMap<'v, 'u> {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some((key, value)) = self.fields.next() else {
+            return Ok(None);
+        };
+        self.value = Some((key, value));
+        seed.deserialize(key.as_str().into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<S::Value, Self::Error> {
+        let (key, value) = self
+            .value
+            .take()
+            .ok_or_else(|| de::Error::custom("value requested before key"))?;
+        let path = join(&self.path, key);
+        seed.deserialize(Tracking {
+            value,
+            path,
+            unknown: self.unknown,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.fields.len())
+    }
+}
+
+struct TrackingEnum<'v, 'u> {
+    variant: &'v str,
+    value: Tracking<'v, 'u>,
+}
+
+impl<'de, 'v, 'u> EnumAccess<'de> for TrackingEnum<'v, 'u> {
+    type Error = serde_json::Error;
+    type Variant = Tracking<'v, 'u>;
+
+    fn variant_seed<S: DeserializeSeed<'de>>(
+        self,
+        seed: S,
+    ) -> Result<(S::Value, Self::Variant), Self::Error> {
+        let variant = seed.deserialize(self.variant.into_deserializer())?;
+        Ok((variant, self.value))
+    }
+}
+
+impl<'de, 'v, 'u> VariantAccess<'de> for Tracking<'v, 'u> {
+    type Error = serde_json::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        de::Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<S: DeserializeSeed<'de>>(
+        self,
+        seed: S,
+    ) -> Result<S::Value, Self::Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_any(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_any(visitor)
+    }
+}
+
+/// `parent.key`, or `key` at the root.
+fn join(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{parent}.{key}")
+    }
+}
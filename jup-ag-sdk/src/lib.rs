@@ -10,8 +10,7 @@
 //! ## Example
 //!
 //! ```rust
-//! use jup_ag_sdk::JupiterClient;
-//! use jup_ag_sdk::types::QuoteRequest;
+//! use jup_ag_sdk::prelude::*;
 //!
 //! #[tokio::main]
 //! async fn main() {
@@ -30,9 +29,32 @@
 //! }
 //! ```
 
-pub use client::JupiterClient;
+pub use client::{JupiterClient, JupiterClientBuilder};
 pub use error::JupiterClientError;
 
+pub mod amm_blacklist;
+#[cfg(feature = "solana")]
+pub mod blockhash;
 pub mod client;
+pub mod config;
 pub mod error;
+pub mod events;
+pub mod json;
+pub mod notifier;
+pub mod order_monitor;
+mod poller;
+pub mod portfolio;
+pub mod prelude;
+pub mod price_alert;
+#[cfg(feature = "program-test")]
+pub mod program_test;
+#[cfg(feature = "solana")]
+pub mod referral;
+pub mod runtime;
+#[cfg(feature = "solana")]
+pub mod signer;
+pub mod state_store;
+pub mod token_list;
+#[cfg(feature = "solana")]
+pub mod transaction;
 pub mod types;
@@ -0,0 +1,254 @@
+//! Delivering order events to callbacks and webhooks.
+//!
+//! A [`Notifier`] takes the [`OrderEvent`]s of an [`OrderMonitor`](crate::order_monitor::OrderMonitor)
+//! and hands each one to its callbacks and POSTs it as JSON to its [`Webhook`]s, retrying
+//! failed deliveries with exponential backoff.
+//!
+//! Webhooks with a [`secret`](Webhook::secret) are signed: the request carries the Unix
+//! time in [`TIMESTAMP_HEADER`] and `sha256=<hex HMAC-SHA256 of "{timestamp}.{body}">` in
+//! [`SIGNATURE_HEADER`]. Receivers recompute it with [`signature`] and should reject old
+//! timestamps to prevent replays.
+//!
+//! # Example
+//! ```
+//! use std::time::Duration;
+//! use jup_ag_sdk::{
+//!     notifier::{Notifier, Webhook},
+//!     order_monitor::{OrderEvent, OrderMonitor},
+//! };
+//!
+//! let mut monitor = OrderMonitor::new("YourPubKey...");
+//! let notifier = Notifier::new()
+//!     .callback(|event: &OrderEvent| println!("{event:?}"))
+//!     .webhook(Webhook::new("https://example.com/hooks/jupiter").secret("whsec..."));
+//!
+//! let events = monitor.subscribe();
+//! let background = client.clone();
+//! client.runtime().spawn(Box::pin(async move {
+//!     notifier.run(&background, events).await;
+//! }));
+//!
+//! monitor.run(&client, Duration::from_secs(30)).await;
+//! ```
+
+use std::{fmt, sync::Arc, time::Duration};
+
+use reqwest::{StatusCode, header::CONTENT_TYPE};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::{
+    JupiterClient,
+    client::hmac_sha256_hex,
+    error::{JupiterApiError, JupiterClientError},
+    order_monitor::OrderEvent,
+    runtime::{SystemTime, UNIX_EPOCH},
+};
+
+/// Header carrying the Unix time, in seconds, a webhook delivery was signed at.
+pub const TIMESTAMP_HEADER: &str = "x-jup-timestamp";
+
+/// Header carrying the signature of a webhook delivery.
+pub const SIGNATURE_HEADER: &str = "x-jup-signature";
+
+/// The signature of a webhook delivery of `body` at `timestamp` with `secret`, as sent in
+/// [`SIGNATURE_HEADER`].
+///
+/// # Example
+/// ```
+/// use jup_ag_sdk::notifier::{SIGNATURE_HEADER, signature};
+///
+/// let expected = signature("whsec...", timestamp, &body);
+/// assert_eq!(Some(expected.as_str()), request.header(SIGNATURE_HEADER));
+/// ```
+pub fn signature(secret: &str, timestamp: u64, body: &[u8]) -> String {
+    let mut message = format!("{timestamp}.").into_bytes();
+    message.extend_from_slice(body);
+    format!("sha256={}", hmac_sha256_hex(secret.as_bytes(), &message))
+}
+
+/// An HTTP endpoint receiving events as JSON `POST`s.
+#[derive(Clone)]
+pub struct Webhook {
+    pub url: String,
+    secret: Option<String>,
+    /// Retries after a failed delivery. Default 3.
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for each later one. Default 500ms.
+    pub backoff: Duration,
+}
+
+impl Webhook {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            secret: None,
+            max_retries: 3,
+            backoff: Duration::from_millis(500),
+        }
+    }
+
+    /// Signs deliveries with `secret`.
+    pub fn secret(mut self, secret: &str) -> Self {
+        self.secret = Some(secret.to_string());
+        self
+    }
+
+    /// Sets [`max_retries`](Self::max_retries).
+    pub fn max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// Sets [`backoff`](Self::backoff).
+    pub fn backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Delivers `body`, retrying network errors, 429s and 5xx responses.
+    async fn deliver(&self, client: &JupiterClient, body: &[u8]) -> Result<(), JupiterClientError> {
+        let mut attempt = 0;
+        loop {
+            let result = self.send(client, body).await;
+            let retryable = match &result {
+                Ok(()) => return Ok(()),
+                Err(JupiterClientError::ApiError(error)) => {
+                    error.status == StatusCode::TOO_MANY_REQUESTS || error.status.is_server_error()
+                }
+                Err(JupiterClientError::RequestError(_)) => true,
+                Err(_) => false,
+            };
+            if !retryable || attempt >= self.max_retries {
+                return result;
+            }
+
+            client
+                .runtime()
+                .sleep(self.backoff.saturating_mul(2u32.saturating_pow(attempt)))
+                .await;
+            attempt += 1;
+        }
+    }
+
+    async fn send(&self, client: &JupiterClient, body: &[u8]) -> Result<(), JupiterClientError> {
+        let mut request = client
+            .external_client()
+            .post(&self.url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body.to_vec());
+        if let Some(secret) = &self.secret {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs());
+            request = request
+                .header(TIMESTAMP_HEADER, timestamp)
+                .header(SIGNATURE_HEADER, signature(secret, timestamp, body));
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            let endpoint = response.url().path().to_string();
+            Err(JupiterClientError::ApiError(Box::new(
+                JupiterApiError::new(status, &endpoint, response.text().await.unwrap_or_default()),
+            )))
+        }
+    }
+}
+
+impl fmt::Debug for Webhook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Webhook")
+            .field("url", &self.url)
+            .field("secret", &self.secret.as_ref().map(|_| "<redacted>"))
+            .field("max_retries", &self.max_retries)
+            .field("backoff", &self.backoff)
+            .finish()
+    }
+}
+
+type Callback = Arc<dyn Fn(&OrderEvent) + Send + Sync>;
+
+/// Hands order events to callbacks and webhooks.
+#[derive(Clone, Default)]
+pub struct Notifier {
+    callbacks: Vec<Callback>,
+    webhooks: Vec<Webhook>,
+}
+
+impl Notifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Calls `callback` with every event, before the webhooks are sent.
+    pub fn callback(mut self, callback: impl Fn(&OrderEvent) + Send + Sync + 'static) -> Self {
+        self.callbacks.push(Arc::new(callback));
+        self
+    }
+
+    /// Delivers every event to `webhook`.
+    pub fn webhook(mut self, webhook: Webhook) -> Self {
+        self.webhooks.push(webhook);
+        self
+    }
+
+    pub fn webhooks(&self) -> &[Webhook] {
+        &self.webhooks
+    }
+
+    /// Hands `event` to the callbacks, then delivers it to each webhook in turn.
+    ///
+    /// Every webhook is tried even if an earlier one fails; the first failure is returned
+    /// once all retries are exhausted.
+    pub async fn notify(
+        &self,
+        client: &JupiterClient,
+        event: &OrderEvent,
+    ) -> Result<(), JupiterClientError> {
+        for callback in &self.callbacks {
+            callback(event);
+        }
+        if self.webhooks.is_empty() {
+            return Ok(());
+        }
+
+        let body = serde_json::to_vec(event)
+            .map_err(|e| JupiterClientError::deserialization(e.to_string()))?;
+        let mut result = Ok(());
+        for webhook in &self.webhooks {
+            let delivered = webhook.deliver(client, &body).await;
+            if result.is_ok() {
+                result = delivered;
+            }
+        }
+        result
+    }
+
+    /// Notifies every event received from `events` until the sender is dropped.
+    ///
+    /// Failed deliveries are dropped after their retries; events missed by lagging
+    /// behind the monitor are skipped.
+    pub async fn run(&self, client: &JupiterClient, mut events: broadcast::Receiver<OrderEvent>) {
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    let _ = self.notify(client, &event).await;
+                }
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return,
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Notifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Notifier")
+            .field("callbacks", &self.callbacks.len())
+            .field("webhooks", &self.webhooks)
+            .finish()
+    }
+}
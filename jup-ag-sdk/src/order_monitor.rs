@@ -0,0 +1,514 @@
+//! Following a wallet's trigger and recurring orders as they fill.
+//!
+//! [`OrderMonitor`] polls a user's active orders and reports each new fill and each order
+//! that leaves the active list as an [`OrderEvent`], returned by
+//! [`refresh`](OrderMonitor::refresh) and broadcast to every
+//! [`subscribe`](OrderMonitor::subscribe)r. Hand the events to a
+//! [`Notifier`](crate::notifier::Notifier) to deliver them to callbacks or webhooks.
+//!
+//! For an account activity feed, [`JupiterClient::subscribe_fills`] merges the fills of
+//! both kinds of orders into one stream of [`Fill`]s, ordered by confirmation time.
+
+use std::{
+    collections::{BTreeSet, HashMap, VecDeque},
+    sync::Arc,
+    time::Duration,
+};
+
+use futures_util::{Stream, stream};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+use crate::{
+    JupiterClient,
+    config::ApiFamily,
+    error::JupiterClientError,
+    poller::{self, Events, Refresh},
+    runtime::Instant,
+    state_store::StateStore,
+    types::{Amount, GetRecurringOrders, GetTriggerOrders, Order, OrderStatus, RecurringOrderType},
+};
+
+/// Most pages of order history read per refresh for the last fills of closed orders.
+/// Active orders are always read in full, so none is mistaken for closed.
+const MAX_HISTORY_PAGES: u64 = 20;
+
+/// One execution of an order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderFill {
+    pub tx_id: String,
+    pub input_mint: String,
+    pub output_mint: String,
+    /// Raw amounts, when the API reports them.
+    pub input_amount: Option<Amount>,
+    pub output_amount: Option<Amount>,
+    pub confirmed_at: Option<String>,
+}
+
+/// A change to one of the monitored orders.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum OrderEvent {
+    /// The order was executed, possibly partially.
+    #[serde(rename_all = "camelCase")]
+    Filled {
+        family: ApiFamily,
+        order_key: String,
+        fill: OrderFill,
+    },
+    /// The order is no longer active: completed, cancelled or expired. Its history entry
+    /// (`OrderStatus::History`) says which. Fills made since the last refresh, such as the
+    /// one that completed it, are reported just before.
+    #[serde(rename_all = "camelCase")]
+    Closed {
+        family: ApiFamily,
+        order_key: String,
+    },
+}
+
+impl OrderEvent {
+    /// The fill a `Filled` event reports.
+    pub fn fill(&self) -> Option<Fill> {
+        match self {
+            OrderEvent::Filled {
+                family,
+                order_key,
+                fill,
+            } => Some(Fill {
+                family: *family,
+                order_key: order_key.clone(),
+                tx_id: fill.tx_id.clone(),
+                input_mint: fill.input_mint.clone(),
+                output_mint: fill.output_mint.clone(),
+                input_amount: fill.input_amount,
+                output_amount: fill.output_amount,
+                confirmed_at: fill.confirmed_at.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// A fill of a trigger or recurring order, as streamed by
+/// [`subscribe_fills`](JupiterClient::subscribe_fills).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fill {
+    pub family: ApiFamily,
+    pub order_key: String,
+    pub tx_id: String,
+    pub input_mint: String,
+    pub output_mint: String,
+    /// Raw amounts, when the API reports them.
+    pub input_amount: Option<Amount>,
+    pub output_amount: Option<Amount>,
+    pub confirmed_at: Option<String>,
+}
+
+/// The fields of an order the monitor compares. Recurring orders come as raw JSON with
+/// the same names as trigger orders.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawOrder {
+    order_key: String,
+    #[serde(default)]
+    trades: Option<Vec<RawTrade>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTrade {
+    tx_id: String,
+    #[serde(default)]
+    input_mint: String,
+    #[serde(default)]
+    output_mint: String,
+    #[serde(default)]
+    raw_input_amount: Option<Amount>,
+    #[serde(default)]
+    raw_output_amount: Option<Amount>,
+    #[serde(default)]
+    confirmed_at: Option<String>,
+}
+
+/// Active orders by key, with the transactions of their fills.
+type Snapshot = HashMap<String, BTreeSet<String>>;
+
+/// Periodically polls a user's active trigger and recurring orders and reports their fills.
+///
+/// The first refresh only records the orders, since their earlier fills would otherwise
+/// all show up as new; events start from the second one, unless a snapshot is restored
+/// from a [`state_store`](Self::state_store).
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use jup_ag_sdk::order_monitor::{OrderEvent, OrderMonitor};
+///
+/// let mut monitor = OrderMonitor::new("YourPubKey...");
+/// let mut events = monitor.subscribe();
+///
+/// client.runtime().spawn(Box::pin(async move {
+///     while let Ok(event) = events.recv().await {
+///         if let OrderEvent::Filled { order_key, fill, .. } = event {
+///             println!("{order_key} filled in {}", fill.tx_id);
+///         }
+///     }
+/// }));
+///
+/// monitor.run(&client, Duration::from_secs(30)).await;
+/// ```
+#[derive(Debug)]
+pub struct OrderMonitor {
+    user: String,
+    families: Vec<ApiFamily>,
+    snapshots: HashMap<ApiFamily, Snapshot>,
+    events: Events<OrderEvent>,
+    store: Option<Arc<dyn StateStore>>,
+}
+
+impl OrderMonitor {
+    /// Creates a monitor of `user`'s trigger and recurring orders. Nothing is fetched
+    /// until the first refresh.
+    pub fn new(user: &str) -> Self {
+        Self {
+            user: user.to_string(),
+            families: vec![ApiFamily::Trigger, ApiFamily::Recurring],
+            snapshots: HashMap::new(),
+            events: Events::new(),
+            store: None,
+        }
+    }
+
+    /// Persists the snapshot of the orders in `store` after every refresh, and starts from
+    /// the stored one, so fills made while the process was down are reported on the first
+    /// refresh after a restart.
+    pub fn state_store(mut self, store: Arc<dyn StateStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Monitors only trigger orders.
+    pub fn trigger_only(mut self) -> Self {
+        self.families = vec![ApiFamily::Trigger];
+        self
+    }
+
+    /// Monitors only recurring orders.
+    pub fn recurring_only(mut self) -> Self {
+        self.families = vec![ApiFamily::Recurring];
+        self
+    }
+
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    /// Receives the [`OrderEvent`]s found by later refreshes.
+    ///
+    /// A subscriber that falls more than 1024 events behind gets a `Lagged` error and
+    /// misses the oldest ones.
+    pub fn subscribe(&self) -> broadcast::Receiver<OrderEvent> {
+        self.events.subscribe()
+    }
+
+    /// Keys of the active orders at the last refresh, sorted.
+    pub fn active_orders(&self) -> Vec<&str> {
+        let mut keys: Vec<&str> = self
+            .snapshots
+            .values()
+            .flat_map(|snapshot| snapshot.keys().map(String::as_str))
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    /// Fetches the active orders, returns what changed since the previous refresh and
+    /// broadcasts it.
+    ///
+    /// Snapshots are only updated once every family was fetched and stored; if anything
+    /// fails, the error is returned and the next refresh reports the same changes again.
+    pub async fn refresh(
+        &mut self,
+        client: &JupiterClient,
+    ) -> Result<Vec<OrderEvent>, JupiterClientError> {
+        let mut events = Vec::new();
+        let mut snapshots = Vec::with_capacity(self.families.len());
+        for &family in &self.families {
+            let orders = fetch(client, family, &self.user, OrderStatus::Active, |_| false).await?;
+            let snapshot: Snapshot = orders
+                .iter()
+                .map(|order| {
+                    let fills = order.trades.iter().flatten();
+                    (
+                        order.order_key.clone(),
+                        fills.map(|t| t.tx_id.clone()).collect(),
+                    )
+                })
+                .collect();
+            let loaded = if self.snapshots.contains_key(&family) {
+                None
+            } else {
+                self.load(family).await?
+            };
+            if let Some(previous) = self.snapshots.get(&family).or(loaded.as_ref()) {
+                // closed orders leave the active list with their last fills
+                let closed: Vec<&String> = previous
+                    .keys()
+                    .filter(|key| !snapshot.contains_key(*key))
+                    .collect();
+                let history = if closed.is_empty() {
+                    Vec::new()
+                } else {
+                    fetch(
+                        client,
+                        family,
+                        &self.user,
+                        OrderStatus::History,
+                        |history| {
+                            closed
+                                .iter()
+                                .all(|key| history.iter().any(|order| &order.order_key == *key))
+                        },
+                    )
+                    .await?
+                };
+                events.extend(changes(family, previous, &orders, &snapshot, &history));
+            }
+            snapshots.push((family, snapshot));
+        }
+
+        if let Some(store) = &self.store {
+            for (family, snapshot) in &snapshots {
+                store.put_json(&self.store_key(*family), snapshot).await?;
+            }
+        }
+        self.snapshots.extend(snapshots);
+
+        for event in &events {
+            self.events.send(event.clone());
+        }
+        Ok(events)
+    }
+
+    /// The stored snapshot of `family`, if there is a store.
+    async fn load(&self, family: ApiFamily) -> Result<Option<Snapshot>, JupiterClientError> {
+        match &self.store {
+            Some(store) => store.get_json(&self.store_key(family)).await,
+            None => Ok(None),
+        }
+    }
+
+    fn store_key(&self, family: ApiFamily) -> String {
+        format!("order_monitor/{}/{family}", self.user)
+    }
+
+    /// Refreshes now and then every `period`, forever, sleeping on the client's
+    /// [`runtime`](JupiterClient::runtime).
+    ///
+    /// Failed refreshes are retried at the next tick; they are reported through the
+    /// client's [`on_error`](crate::JupiterClientBuilder::on_error) callback.
+    pub async fn run(&mut self, client: &JupiterClient, period: Duration) {
+        poller::run(self, client, period).await
+    }
+
+    /// Refreshes now and then every `period`, like [`run`](Self::run), and streams the
+    /// fills found, each refresh's in order of confirmation time.
+    ///
+    /// The stream never ends; drop it to stop polling.
+    pub fn fills(self, client: &JupiterClient, period: Duration) -> impl Stream<Item = Fill> + '_ {
+        let state = (self, VecDeque::new(), None::<Instant>);
+        stream::unfold(
+            state,
+            move |(mut monitor, mut pending, mut last)| async move {
+                loop {
+                    if let Some(fill) = pending.pop_front() {
+                        return Some((fill, (monitor, pending, last)));
+                    }
+                    if let Some(last) = last {
+                        client
+                            .runtime()
+                            .sleep(period.saturating_sub(last.elapsed()))
+                            .await;
+                    }
+                    last = Some(Instant::now());
+                    if let Ok(events) = monitor.refresh(client).await {
+                        let mut fills: Vec<Fill> =
+                            events.iter().filter_map(OrderEvent::fill).collect();
+                        // unconfirmed fills last, ties in refresh order
+                        fills.sort_by(|a, b| {
+                            (a.confirmed_at.is_none(), &a.confirmed_at)
+                                .cmp(&(b.confirmed_at.is_none(), &b.confirmed_at))
+                        });
+                        pending.extend(fills);
+                    }
+                }
+            },
+        )
+    }
+}
+
+impl Refresh for OrderMonitor {
+    type Output = Vec<OrderEvent>;
+
+    async fn refresh(
+        &mut self,
+        client: &JupiterClient,
+    ) -> Result<Vec<OrderEvent>, JupiterClientError> {
+        OrderMonitor::refresh(self, client).await
+    }
+}
+
+impl JupiterClient {
+    /// Streams the fills of `user`'s trigger and recurring orders as one feed, polling
+    /// every `period` with an [`OrderMonitor`].
+    ///
+    /// Fills made before the stream starts aren't reported. Use
+    /// [`OrderMonitor::fills`] to pick up where a stored snapshot left off or to follow one
+    /// kind of order only.
+    ///
+    /// # Example
+    /// ```
+    /// use std::{pin::pin, time::Duration};
+    /// use futures_util::StreamExt;
+    ///
+    /// let mut fills = pin!(client.subscribe_fills("YourPubKey...", Duration::from_secs(30)));
+    /// while let Some(fill) = fills.next().await {
+    ///     println!("{} {} filled in {}", fill.family, fill.order_key, fill.tx_id);
+    /// }
+    /// ```
+    pub fn subscribe_fills(&self, user: &str, period: Duration) -> impl Stream<Item = Fill> + '_ {
+        OrderMonitor::new(user).fills(self, period)
+    }
+}
+
+/// The fills of `orders` not in `previous`, then the orders of `previous` that are gone
+/// from `snapshot`, each with its fills not seen yet, read from `history`.
+fn changes(
+    family: ApiFamily,
+    previous: &Snapshot,
+    orders: &[RawOrder],
+    snapshot: &Snapshot,
+    history: &[RawOrder],
+) -> Vec<OrderEvent> {
+    let mut events: Vec<OrderEvent> = orders
+        .iter()
+        .flat_map(|order| new_fills(family, order, previous.get(&order.order_key)))
+        .collect();
+
+    let mut closed: Vec<(&String, &BTreeSet<String>)> = previous
+        .iter()
+        .filter(|(key, _)| !snapshot.contains_key(*key))
+        .collect();
+    closed.sort();
+    for (key, seen) in closed {
+        if let Some(order) = history.iter().find(|order| &order.order_key == key) {
+            events.extend(new_fills(family, order, Some(seen)));
+        }
+        events.push(OrderEvent::Closed {
+            family,
+            order_key: key.clone(),
+        });
+    }
+    events
+}
+
+/// `Filled` events for the trades of `order` whose transactions aren't in `seen`.
+fn new_fills<'a>(
+    family: ApiFamily,
+    order: &'a RawOrder,
+    seen: Option<&'a BTreeSet<String>>,
+) -> impl Iterator<Item = OrderEvent> + 'a {
+    order
+        .trades
+        .iter()
+        .flatten()
+        .filter(move |trade| seen.is_none_or(|seen| !seen.contains(&trade.tx_id)))
+        .map(move |trade| OrderEvent::Filled {
+            family,
+            order_key: order.order_key.clone(),
+            fill: OrderFill {
+                tx_id: trade.tx_id.clone(),
+                input_mint: trade.input_mint.clone(),
+                output_mint: trade.output_mint.clone(),
+                input_amount: trade.raw_input_amount,
+                output_amount: trade.raw_output_amount,
+                confirmed_at: trade.confirmed_at.clone(),
+            },
+        })
+}
+
+/// Pages of `user`'s orders of `family` with `status`, read until `enough` returns `true`
+/// for the orders read so far or the last page, and for history at most
+/// [`MAX_HISTORY_PAGES`].
+async fn fetch(
+    client: &JupiterClient,
+    family: ApiFamily,
+    user: &str,
+    status: OrderStatus,
+    enough: impl Fn(&[RawOrder]) -> bool,
+) -> Result<Vec<RawOrder>, JupiterClientError> {
+    let max_pages = match status {
+        OrderStatus::History => MAX_HISTORY_PAGES,
+        _ => u64::MAX,
+    };
+    let mut orders: Vec<RawOrder> = Vec::new();
+    let mut page = 1;
+    loop {
+        let (page_orders, total_pages) = match family {
+            ApiFamily::Recurring => {
+                let request =
+                    GetRecurringOrders::new(RecurringOrderType::All, status, user).with_page(page);
+                let response = client.get_recurring_orders(&request).await?;
+                let page_orders = [response.all, response.time, response.price]
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .map(serde_json::from_value)
+                    .collect::<Result<Vec<RawOrder>, _>>()
+                    .map_err(|e| JupiterClientError::deserialization(e.to_string()))?;
+                (page_orders, response.total_pages)
+            }
+            _ => {
+                let request = GetTriggerOrders::new(user, status).page(&page.to_string());
+                let response = client.get_trigger_orders(&request).await?;
+                let page_orders = response.orders.iter().map(RawOrder::from).collect();
+                (page_orders, u64::from(response.total_pages))
+            }
+        };
+
+        for order in page_orders {
+            if !orders.iter().any(|o| o.order_key == order.order_key) {
+                orders.push(order);
+            }
+        }
+        if enough(&orders) || page >= total_pages || page >= max_pages {
+            return Ok(orders);
+        }
+        page += 1;
+    }
+}
+
+impl From<&Order> for RawOrder {
+    fn from(order: &Order) -> Self {
+        Self {
+            order_key: order.order_key.clone(),
+            trades: Some(
+                order
+                    .trades
+                    .iter()
+                    .map(|trade| RawTrade {
+                        tx_id: trade.tx_id.clone(),
+                        input_mint: trade.input_mint.clone(),
+                        output_mint: trade.output_mint.clone(),
+                        raw_input_amount: Some(trade.raw_input_amount),
+                        raw_output_amount: Some(trade.raw_output_amount),
+                        confirmed_at: Some(trade.confirmed_at.clone()),
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
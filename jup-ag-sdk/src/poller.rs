@@ -0,0 +1,54 @@
+//! Plumbing shared by the services that poll Jupiter on a schedule and broadcast what
+//! changed: [`OrderMonitor`](crate::order_monitor::OrderMonitor),
+//! [`PortfolioService`](crate::portfolio::PortfolioService),
+//! [`PriceAlertManager`](crate::price_alert::PriceAlertManager) and
+//! [`TokenListManager`](crate::token_list::TokenListManager).
+
+use std::time::Duration;
+
+use tokio::sync::broadcast;
+
+use crate::{JupiterClient, error::JupiterClientError, runtime::Instant};
+
+/// Events kept for subscribers that fall behind; older ones are dropped.
+pub(crate) const EVENT_CAPACITY: usize = 1024;
+
+/// The broadcast channel of a poller's events.
+#[derive(Debug)]
+pub(crate) struct Events<E>(broadcast::Sender<E>);
+
+impl<E: Clone> Events<E> {
+    pub(crate) fn new() -> Self {
+        Self(broadcast::channel(EVENT_CAPACITY).0)
+    }
+
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<E> {
+        self.0.subscribe()
+    }
+
+    pub(crate) fn send(&self, event: E) {
+        // no subscribers is fine
+        let _ = self.0.send(event);
+    }
+}
+
+/// A service refreshed by [`run`].
+pub(crate) trait Refresh {
+    type Output;
+
+    async fn refresh(&mut self, client: &JupiterClient)
+    -> Result<Self::Output, JupiterClientError>;
+}
+
+/// Refreshes `poller` now and then every `period`, forever, sleeping on the client's
+/// runtime. Failed refreshes are retried at the next tick.
+pub(crate) async fn run(poller: &mut impl Refresh, client: &JupiterClient, period: Duration) {
+    loop {
+        let started = Instant::now();
+        let _ = poller.refresh(client).await;
+        client
+            .runtime()
+            .sleep(period.saturating_sub(started.elapsed()))
+            .await;
+    }
+}
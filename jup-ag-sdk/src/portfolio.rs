@@ -0,0 +1,383 @@
+//! Tracking the balances and value of a set of wallets.
+//!
+//! A [`PortfolioService`] refreshes the balances of every wallet it tracks concurrently,
+//! prices all their tokens together and keeps the result as a [`PortfolioSnapshot`] to
+//! query, e.g. the value of one wallet or the holdings of all of them. Balance changes and
+//! large swings in a wallet's value between refreshes are reported as [`PortfolioEvent`]s,
+//! returned by [`refresh`](PortfolioService::refresh) and broadcast to every
+//! [`subscribe`](PortfolioService::subscribe)r.
+
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
+
+use futures_util::{StreamExt, TryStreamExt, stream};
+use tokio::sync::broadcast;
+
+use crate::{
+    JupiterClient,
+    error::JupiterClientError,
+    poller::{self, Events, Refresh},
+    runtime::Instant,
+    types::{
+        Amount, TokenBalancesExt, TokenBalancesResponse, TokenPriceRequest, TokenPriceResponse,
+        WalletBalance, validate_pubkey,
+    },
+};
+
+/// Most mints the Price API accepts in one request.
+const MAX_PRICE_IDS: usize = 100;
+
+/// Requests a new [`PortfolioService`] makes at once.
+pub const DEFAULT_CONCURRENCY: usize = 8;
+
+/// Value change of a new [`PortfolioService`] reported as an event: 5%.
+pub const DEFAULT_VALUE_CHANGE: f64 = 0.05;
+
+/// The balances of a wallet at a refresh.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WalletSnapshot {
+    pub address: String,
+    /// Non-zero balances, wrapped SOL merged into SOL, sorted by value, highest first.
+    pub balances: Vec<WalletBalance>,
+    /// Value in USD of the priced balances.
+    pub usd_value: f64,
+}
+
+impl WalletSnapshot {
+    /// The balance of `mint`, or `"SOL"` for native SOL, if non-zero.
+    pub fn balance(&self, mint: &str) -> Option<&WalletBalance> {
+        self.balances.iter().find(|balance| balance.mint == mint)
+    }
+}
+
+/// The holdings of `mint` across the wallets of a [`PortfolioSnapshot`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Holding {
+    /// Token mint address, or `"SOL"` for native SOL.
+    pub mint: String,
+    pub amount: Amount,
+    pub ui_amount: f64,
+    /// Value in USD, `None` if the token has no price.
+    pub usd_value: Option<f64>,
+    /// How many wallets hold it.
+    pub wallets: usize,
+}
+
+/// The balances of every tracked wallet at a refresh.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortfolioSnapshot {
+    wallets: BTreeMap<String, WalletSnapshot>,
+    refreshed_at: Instant,
+}
+
+impl PortfolioSnapshot {
+    /// The snapshot of `address`, if it is tracked.
+    pub fn wallet(&self, address: &str) -> Option<&WalletSnapshot> {
+        self.wallets.get(address)
+    }
+
+    /// Every wallet, sorted by address.
+    pub fn wallets(&self) -> impl Iterator<Item = &WalletSnapshot> {
+        self.wallets.values()
+    }
+
+    /// Value in USD of the priced balances of all wallets.
+    pub fn usd_value(&self) -> f64 {
+        self.wallets.values().map(|wallet| wallet.usd_value).sum()
+    }
+
+    /// The balances of all wallets summed per token, sorted by value, highest first, then
+    /// tokens without a price.
+    pub fn holdings(&self) -> Vec<Holding> {
+        let mut holdings: BTreeMap<&str, Holding> = BTreeMap::new();
+        for balance in self.wallets.values().flat_map(|wallet| &wallet.balances) {
+            let holding = holdings.entry(&balance.mint).or_insert_with(|| Holding {
+                mint: balance.mint.clone(),
+                amount: Amount::default(),
+                ui_amount: 0.0,
+                usd_value: balance.usd_value.map(|_| 0.0),
+                wallets: 0,
+            });
+            holding.amount += balance.amount;
+            holding.ui_amount += balance.ui_amount;
+            holding.usd_value = holding.usd_value.zip(balance.usd_value).map(|(a, b)| a + b);
+            holding.wallets += 1;
+        }
+
+        let mut holdings: Vec<Holding> = holdings.into_values().collect();
+        holdings.sort_by(|a, b| match (a.usd_value, b.usd_value) {
+            (Some(a), Some(b)) => b.partial_cmp(&a).unwrap_or(Ordering::Equal),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        });
+        holdings
+    }
+
+    /// How long ago the snapshot was taken.
+    pub fn age(&self) -> Duration {
+        self.refreshed_at.elapsed()
+    }
+}
+
+/// A change in a tracked wallet between two refreshes, broadcast by [`PortfolioService`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum PortfolioEvent {
+    /// The balance of `mint` in `wallet` changed, from or to zero if the wallet didn't or
+    /// no longer holds it.
+    BalanceChanged {
+        wallet: String,
+        mint: String,
+        previous: Amount,
+        current: Amount,
+    },
+    /// The value of `wallet` moved by at least the service's
+    /// [`value_change`](PortfolioService::value_change) since it was last reported.
+    ValueChanged {
+        wallet: String,
+        previous_usd: f64,
+        current_usd: f64,
+    },
+}
+
+/// Periodically fetches the balances of a set of wallets and values them in USD.
+///
+/// Each refresh makes one balances request per wallet and one price request per 100
+/// distinct tokens, at most [`concurrency`](Self::concurrency) at a time. The first
+/// refresh only records the wallets; events start from the second one.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use jup_ag_sdk::portfolio::{PortfolioEvent, PortfolioService};
+///
+/// let mut portfolio = PortfolioService::new().concurrency(4);
+/// portfolio.add_wallet("TreasuryPubKey...")?;
+/// portfolio.add_wallet("OpsPubKey...")?;
+///
+/// portfolio.refresh(&client).await?;
+/// let snapshot = portfolio.snapshot().unwrap();
+/// println!("total: ${:.2}", snapshot.usd_value());
+/// for holding in snapshot.holdings() {
+///     println!("{}: {} in {} wallets", holding.mint, holding.ui_amount, holding.wallets);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct PortfolioService {
+    wallets: Vec<String>,
+    concurrency: usize,
+    value_change: f64,
+    snapshot: Option<PortfolioSnapshot>,
+    /// Value of each wallet when its last `ValueChanged` event, or first refresh, happened.
+    reported_values: HashMap<String, f64>,
+    events: Events<PortfolioEvent>,
+}
+
+impl Default for PortfolioService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PortfolioService {
+    /// Creates a service tracking no wallets.
+    pub fn new() -> Self {
+        Self {
+            wallets: Vec::new(),
+            concurrency: DEFAULT_CONCURRENCY,
+            value_change: DEFAULT_VALUE_CHANGE,
+            snapshot: None,
+            reported_values: HashMap::new(),
+            events: Events::new(),
+        }
+    }
+
+    /// Makes at most `concurrency` requests at once, default [`DEFAULT_CONCURRENCY`].
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Reports a `ValueChanged` event when a wallet's value moves by `fraction` of it or
+    /// more, default [`DEFAULT_VALUE_CHANGE`]; `0.0` reports every change.
+    pub fn value_change(mut self, fraction: f64) -> Self {
+        self.value_change = fraction;
+        self
+    }
+
+    /// Tracks `address` from the next refresh on. Fails if it isn't a valid public key.
+    pub fn add_wallet(&mut self, address: &str) -> Result<(), JupiterClientError> {
+        validate_pubkey("address", address)?;
+        if !self.wallets.iter().any(|wallet| wallet == address) {
+            self.wallets.push(address.to_string());
+        }
+        Ok(())
+    }
+
+    /// Stops tracking `address`, returning whether it was tracked.
+    pub fn remove_wallet(&mut self, address: &str) -> bool {
+        let tracked = self.wallets.len();
+        self.wallets.retain(|wallet| wallet != address);
+        self.reported_values.remove(address);
+        if let Some(snapshot) = &mut self.snapshot {
+            snapshot.wallets.remove(address);
+        }
+        self.wallets.len() != tracked
+    }
+
+    /// The tracked wallets, in the order they were added.
+    pub fn wallets(&self) -> &[String] {
+        &self.wallets
+    }
+
+    /// The balances at the last successful refresh, `None` before the first one.
+    pub fn snapshot(&self) -> Option<&PortfolioSnapshot> {
+        self.snapshot.as_ref()
+    }
+
+    /// Receives the [`PortfolioEvent`]s found by later refreshes.
+    ///
+    /// A subscriber that falls more than 1024 events behind gets a `Lagged` error and
+    /// misses the oldest ones.
+    pub fn subscribe(&self) -> broadcast::Receiver<PortfolioEvent> {
+        self.events.subscribe()
+    }
+
+    /// Fetches the balances of every wallet and the prices of their tokens, returns what
+    /// changed since the previous refresh and broadcasts it.
+    ///
+    /// If a request fails, the error is returned and the snapshot is kept.
+    pub async fn refresh(
+        &mut self,
+        client: &JupiterClient,
+    ) -> Result<Vec<PortfolioEvent>, JupiterClientError> {
+        let balances: Vec<(String, TokenBalancesResponse)> = stream::iter(&self.wallets)
+            .map(|wallet| async move {
+                let mut balances = client.get_token_balances(wallet).await?;
+                balances.merge_wsol();
+                Ok::<_, JupiterClientError>((wallet.clone(), balances))
+            })
+            .buffer_unordered(self.concurrency)
+            .try_collect()
+            .await?;
+
+        let mut mints: Vec<String> = balances
+            .iter()
+            .flat_map(|(_, balances)| balances.price_mints())
+            .collect();
+        mints.sort();
+        mints.dedup();
+        let responses: Vec<TokenPriceResponse> = stream::iter(mints.chunks(MAX_PRICE_IDS))
+            .map(
+                |mints| async move { client.get_token_price(&TokenPriceRequest::new(mints)).await },
+            )
+            .buffer_unordered(self.concurrency)
+            .try_collect()
+            .await?;
+        let mut prices = TokenPriceResponse {
+            data: Default::default(),
+            time_taken: 0.0,
+        };
+        for response in responses {
+            prices.data.extend(response.data);
+        }
+
+        let wallets: BTreeMap<String, WalletSnapshot> = balances
+            .into_iter()
+            .map(|(address, balances)| {
+                let balances = balances.to_display_list(&prices, 0.0);
+                let usd_value = balances.iter().filter_map(|b| b.usd_value).sum();
+                let snapshot = WalletSnapshot {
+                    address: address.clone(),
+                    balances,
+                    usd_value,
+                };
+                (address, snapshot)
+            })
+            .collect();
+
+        let mut events = Vec::new();
+        for wallet in wallets.values() {
+            let previous = self
+                .snapshot
+                .as_ref()
+                .and_then(|snapshot| snapshot.wallet(&wallet.address));
+            let Some(previous) = previous else {
+                self.reported_values
+                    .insert(wallet.address.clone(), wallet.usd_value);
+                continue;
+            };
+            events.extend(balance_changes(previous, wallet));
+
+            let reported = self
+                .reported_values
+                .entry(wallet.address.clone())
+                .or_insert(wallet.usd_value);
+            let change = (wallet.usd_value - *reported).abs();
+            if change > 0.0 && change >= reported.abs() * self.value_change {
+                events.push(PortfolioEvent::ValueChanged {
+                    wallet: wallet.address.clone(),
+                    previous_usd: *reported,
+                    current_usd: wallet.usd_value,
+                });
+                *reported = wallet.usd_value;
+            }
+        }
+        self.snapshot = Some(PortfolioSnapshot {
+            wallets,
+            refreshed_at: Instant::now(),
+        });
+
+        for event in &events {
+            self.events.send(event.clone());
+        }
+        Ok(events)
+    }
+
+    /// Refreshes now and then every `period`, forever, sleeping on the client's
+    /// [`runtime`](JupiterClient::runtime).
+    ///
+    /// Failed refreshes are retried at the next tick; they are reported through the
+    /// client's [`on_error`](crate::JupiterClientBuilder::on_error) callback.
+    pub async fn run(&mut self, client: &JupiterClient, period: Duration) {
+        poller::run(self, client, period).await
+    }
+}
+
+impl Refresh for PortfolioService {
+    type Output = Vec<PortfolioEvent>;
+
+    async fn refresh(
+        &mut self,
+        client: &JupiterClient,
+    ) -> Result<Vec<PortfolioEvent>, JupiterClientError> {
+        PortfolioService::refresh(self, client).await
+    }
+}
+
+/// The balances that differ between two snapshots of a wallet, sorted by mint.
+fn balance_changes(before: &WalletSnapshot, after: &WalletSnapshot) -> Vec<PortfolioEvent> {
+    let mut amounts: BTreeMap<&str, (Amount, Amount)> = BTreeMap::new();
+    for balance in &before.balances {
+        amounts.entry(&balance.mint).or_default().0 = balance.amount;
+    }
+    for balance in &after.balances {
+        amounts.entry(&balance.mint).or_default().1 = balance.amount;
+    }
+
+    amounts
+        .into_iter()
+        .filter(|(_, (previous, current))| previous != current)
+        .map(
+            |(mint, (previous, current))| PortfolioEvent::BalanceChanged {
+                wallet: after.address.clone(),
+                mint: mint.to_string(),
+                previous,
+                current,
+            },
+        )
+        .collect()
+}
@@ -0,0 +1,35 @@
+//! The client, its error type and the request and response types used most, for a
+//! single glob import.
+//!
+//! ```
+//! use jup_ag_sdk::prelude::*;
+//!
+//! let client = JupiterClient::new("https://lite-api.jup.ag");
+//! let quote = client
+//!     .get_quote(&QuoteRequest::new(input_mint, output_mint, 1_000_000_000))
+//!     .await?;
+//! let swap = client
+//!     .get_swap_transaction(&SwapRequest::new("YourPubKey...", quote))
+//!     .await?;
+//! ```
+//!
+//! Less common types stay in [`types`](crate::types), [`config`](crate::config) and
+//! the other modules.
+
+pub use crate::{
+    client::{ClientStats, JupiterClient, JupiterClientBuilder},
+    config::{ApiFamily, ApiPaths, QuoteCacheConfig, QuoteFreshness, RateLimitPlan},
+    error::{JupiterApiError, JupiterClientError, QuoteErrorKind},
+    types::{
+        Amount, Bps, CancelRecurringOrderRequest, CancelTriggerOrder, CancelTriggerOrders,
+        CreateRecurringOrderRequest, CreateTriggerOrder, ExecuteRecurringRequest,
+        ExecuteTriggerOrder, GetRecurringOrders, GetTriggerOrders, OrderStatus, PriorityLevel,
+        Quote, QuoteRequest, QuoteResponse, RecurringOrderType, SwapInstructions, SwapMode,
+        SwapRequest, SwapResponse, TokenInfoResponse, TokenPriceRequest, TokenPriceResponse,
+        TokenTag, UltraExecuteOrderRequest, UltraExecuteOrderResponse, UltraOrderRequest,
+        UltraOrderResponse, Validate,
+    },
+};
+
+#[cfg(feature = "solana")]
+pub use crate::{signer::LocalSigner, transaction::SwapTransaction};
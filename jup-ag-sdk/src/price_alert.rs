@@ -0,0 +1,328 @@
+//! Alerts on token prices crossing thresholds.
+//!
+//! Register [`PriceAlert`]s with a [`PriceAlertManager`]; each
+//! [`refresh`](PriceAlertManager::refresh) fetches the prices of the watched mints in as
+//! few requests as possible and reports every alert that fires, or re-arms, as a
+//! [`PriceAlertEvent`], also broadcast to every [`subscribe`](PriceAlertManager::subscribe)r.
+//!
+//! An alert fires once when its condition is met and stays quiet until the price moves
+//! back past the threshold by its [`hysteresis`](PriceAlert::hysteresis), so a price
+//! hovering around the threshold doesn't fire it over and over.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
+
+use tokio::sync::broadcast;
+
+use crate::{
+    JupiterClient,
+    error::JupiterClientError,
+    poller::{self, Events, Refresh},
+    types::{TokenPriceRequest, Validate, validate_pubkey},
+};
+
+/// Most mints the Price API accepts in one request.
+const MAX_PRICE_IDS: usize = 100;
+
+/// Hysteresis of a new [`PriceAlert`]: 1% of the threshold.
+pub const DEFAULT_HYSTERESIS: f64 = 0.01;
+
+/// When a [`PriceAlert`] fires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PriceCondition {
+    /// The price is at or above the threshold.
+    Above,
+    /// The price is at or below the threshold.
+    Below,
+}
+
+/// A rule firing when the price of `mint` meets `condition` against `threshold`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceAlert {
+    pub mint: String,
+    pub condition: PriceCondition,
+    /// In the manager's [`vs_token`](PriceAlertManager::vs_token), USD by default.
+    pub threshold: f64,
+    /// How far past the threshold, as a fraction of it, the price has to move back before
+    /// the alert can fire again. Default [`DEFAULT_HYSTERESIS`].
+    pub hysteresis: f64,
+}
+
+impl PriceAlert {
+    /// Fires when the price of `mint` rises to `threshold` or above.
+    pub fn above(mint: &str, threshold: f64) -> Self {
+        Self::new(mint, PriceCondition::Above, threshold)
+    }
+
+    /// Fires when the price of `mint` falls to `threshold` or below.
+    pub fn below(mint: &str, threshold: f64) -> Self {
+        Self::new(mint, PriceCondition::Below, threshold)
+    }
+
+    pub fn new(mint: &str, condition: PriceCondition, threshold: f64) -> Self {
+        Self {
+            mint: mint.to_string(),
+            condition,
+            threshold,
+            hysteresis: DEFAULT_HYSTERESIS,
+        }
+    }
+
+    /// Sets [`hysteresis`](Self::hysteresis), e.g. `0.05` to re-arm only once the price is
+    /// 5% back on the other side of the threshold.
+    pub fn hysteresis(mut self, hysteresis: f64) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// Whether `price` meets the condition.
+    pub fn is_met(&self, price: f64) -> bool {
+        match self.condition {
+            PriceCondition::Above => price >= self.threshold,
+            PriceCondition::Below => price <= self.threshold,
+        }
+    }
+
+    /// Whether `price` is far enough back from the threshold to re-arm a fired alert.
+    pub fn is_rearmed(&self, price: f64) -> bool {
+        match self.condition {
+            PriceCondition::Above => price < self.threshold * (1.0 - self.hysteresis),
+            PriceCondition::Below => price > self.threshold * (1.0 + self.hysteresis),
+        }
+    }
+}
+
+impl Validate for PriceAlert {
+    fn validate(&self) -> Result<(), JupiterClientError> {
+        validate_pubkey("mint", &self.mint)?;
+        if !self.threshold.is_finite() || self.threshold <= 0.0 {
+            return Err(JupiterClientError::ValidationError(format!(
+                "threshold must be a positive price, got {}",
+                self.threshold
+            )));
+        }
+        if !(0.0..1.0).contains(&self.hysteresis) {
+            return Err(JupiterClientError::ValidationError(format!(
+                "hysteresis must be in [0, 1), got {}",
+                self.hysteresis
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A change in the state of an alert, broadcast by [`PriceAlertManager`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum PriceAlertEvent {
+    /// The condition of alert `id` is met at `price`.
+    Triggered {
+        id: u64,
+        alert: PriceAlert,
+        price: f64,
+    },
+    /// The price of fired alert `id` moved back past its hysteresis to `price`; it fires
+    /// again the next time its condition is met.
+    Rearmed {
+        id: u64,
+        alert: PriceAlert,
+        price: f64,
+    },
+}
+
+#[derive(Debug)]
+struct AlertState {
+    alert: PriceAlert,
+    triggered: bool,
+}
+
+/// Periodically fetches the prices of the watched mints and fires alerts on them.
+///
+/// Alerts whose condition is already met at their first refresh fire right away. Mints
+/// the Price API has no price for are skipped until it has one.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use jup_ag_sdk::price_alert::{PriceAlert, PriceAlertEvent, PriceAlertManager};
+///
+/// let mut manager = PriceAlertManager::new();
+/// manager.add(PriceAlert::above("JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN", 1.0))?;
+/// manager.add(PriceAlert::below("So11111111111111111111111111111111111111112", 120.0).hysteresis(0.02))?;
+/// let mut events = manager.subscribe();
+///
+/// client.runtime().spawn(Box::pin(async move {
+///     while let Ok(event) = events.recv().await {
+///         if let PriceAlertEvent::Triggered { alert, price, .. } = event {
+///             println!("{} is at {price}", alert.mint);
+///         }
+///     }
+/// }));
+///
+/// manager.run(&client, Duration::from_secs(15)).await;
+/// ```
+#[derive(Debug)]
+pub struct PriceAlertManager {
+    vs_token: Option<String>,
+    alerts: BTreeMap<u64, AlertState>,
+    next_id: u64,
+    prices: HashMap<String, f64>,
+    events: Events<PriceAlertEvent>,
+}
+
+impl Default for PriceAlertManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PriceAlertManager {
+    /// Creates a manager without alerts, with thresholds in USD.
+    pub fn new() -> Self {
+        Self {
+            vs_token: None,
+            alerts: BTreeMap::new(),
+            next_id: 0,
+            prices: HashMap::new(),
+            events: Events::new(),
+        }
+    }
+
+    /// Prices in `vs_token` instead of USD, e.g. the SOL mint. Fails if it isn't a valid
+    /// mint address.
+    pub fn vs_token(mut self, vs_token: &str) -> Result<Self, JupiterClientError> {
+        validate_pubkey("vs_token", vs_token)?;
+        self.vs_token = Some(vs_token.to_string());
+        Ok(self)
+    }
+
+    /// Registers `alert` and returns its id. It is checked from the next refresh on.
+    pub fn add(&mut self, alert: PriceAlert) -> Result<u64, JupiterClientError> {
+        alert.validate()?;
+        let id = self.next_id;
+        self.next_id += 1;
+        self.alerts.insert(
+            id,
+            AlertState {
+                alert,
+                triggered: false,
+            },
+        );
+        Ok(id)
+    }
+
+    /// Removes alert `id`, returning it if it was registered.
+    pub fn remove(&mut self, id: u64) -> Option<PriceAlert> {
+        let removed = self.alerts.remove(&id)?;
+        if !self
+            .alerts
+            .values()
+            .any(|state| state.alert.mint == removed.alert.mint)
+        {
+            self.prices.remove(&removed.alert.mint);
+        }
+        Some(removed.alert)
+    }
+
+    /// The registered alerts with their ids, in the order they were added.
+    pub fn alerts(&self) -> impl Iterator<Item = (u64, &PriceAlert)> {
+        self.alerts.iter().map(|(id, state)| (*id, &state.alert))
+    }
+
+    /// Whether alert `id` has fired and not re-armed yet.
+    pub fn is_triggered(&self, id: u64) -> bool {
+        self.alerts.get(&id).is_some_and(|state| state.triggered)
+    }
+
+    /// The price of `mint` at the last refresh, if it is watched and was priced.
+    pub fn price(&self, mint: &str) -> Option<f64> {
+        self.prices.get(mint).copied()
+    }
+
+    /// Receives the [`PriceAlertEvent`]s found by later refreshes.
+    ///
+    /// A subscriber that falls more than 1024 events behind gets a `Lagged` error and
+    /// misses the oldest ones.
+    pub fn subscribe(&self) -> broadcast::Receiver<PriceAlertEvent> {
+        self.events.subscribe()
+    }
+
+    /// Fetches the prices of the watched mints, 100 per request, returns the alerts that
+    /// fired or re-armed and broadcasts them.
+    ///
+    /// If a request fails, the error is returned and no alert changes state.
+    pub async fn refresh(
+        &mut self,
+        client: &JupiterClient,
+    ) -> Result<Vec<PriceAlertEvent>, JupiterClientError> {
+        let mut mints: Vec<String> = self
+            .alerts
+            .values()
+            .map(|state| state.alert.mint.clone())
+            .collect();
+        mints.sort();
+        mints.dedup();
+
+        let mut prices = HashMap::new();
+        for mints in mints.chunks(MAX_PRICE_IDS) {
+            let mut request = TokenPriceRequest::new(mints);
+            if let Some(vs_token) = &self.vs_token {
+                request = request.with_vs_token(vs_token);
+            }
+            let response = client.get_token_price(&request).await?;
+            prices.extend(response.data.into_iter().filter_map(|(mint, price)| {
+                let price = price.price.parse::<f64>().ok()?;
+                Some((mint, price))
+            }));
+        }
+
+        let mut events = Vec::new();
+        for (id, state) in &mut self.alerts {
+            let Some(price) = prices.get(&state.alert.mint).copied() else {
+                continue;
+            };
+            if !state.triggered && state.alert.is_met(price) {
+                state.triggered = true;
+                events.push(PriceAlertEvent::Triggered {
+                    id: *id,
+                    alert: state.alert.clone(),
+                    price,
+                });
+            } else if state.triggered && state.alert.is_rearmed(price) {
+                state.triggered = false;
+                events.push(PriceAlertEvent::Rearmed {
+                    id: *id,
+                    alert: state.alert.clone(),
+                    price,
+                });
+            }
+        }
+        self.prices = prices;
+
+        for event in &events {
+            self.events.send(event.clone());
+        }
+        Ok(events)
+    }
+
+    /// Refreshes now and then every `period`, forever, sleeping on the client's
+    /// [`runtime`](JupiterClient::runtime).
+    ///
+    /// Failed refreshes are retried at the next tick; they are reported through the
+    /// client's [`on_error`](crate::JupiterClientBuilder::on_error) callback.
+    pub async fn run(&mut self, client: &JupiterClient, period: Duration) {
+        poller::run(self, client, period).await
+    }
+}
+
+impl Refresh for PriceAlertManager {
+    type Output = Vec<PriceAlertEvent>;
+
+    async fn refresh(
+        &mut self,
+        client: &JupiterClient,
+    ) -> Result<Vec<PriceAlertEvent>, JupiterClientError> {
+        PriceAlertManager::refresh(self, client).await
+    }
+}
@@ -0,0 +1,321 @@
+//! Cloning the accounts a swap touches, to validate composed transactions locally.
+//!
+//! Running swap instructions in a local bank, such as `solana-program-test`,
+//! `solana-bankrun` or `solana-test-validator`, needs every account they read, down to
+//! the DEX programs and their program data. [`JupiterClient::clone_accounts`] snapshots
+//! them from an RPC into [`AccountFixtures`] once. Committed alongside the tests, the
+//! fixtures let CI validate transactions composed around a swap without a mainnet RPC:
+//! they serialize to the JSON the Solana CLI writes with `solana account --output json`,
+//! one file per account with [`account_json`](AccountFixtures::account_json) for
+//! `solana-test-validator --account-dir`.
+//!
+//! [`AccountFixtures::check`] catches fixtures that can't run the instructions, e.g.
+//! because a program wasn't cloned, before a local bank fails on them.
+//!
+//! Requires the `program-test` feature.
+
+use std::{collections::BTreeMap, fmt};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use serde::{Deserialize, Serialize};
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+
+use crate::{JupiterClient, error::JupiterClientError, types::parse_pubkey};
+
+/// Owner of upgradeable programs, whose code lives in a separate program data account.
+const UPGRADEABLE_LOADER: Pubkey =
+    Pubkey::from_str_const("BPFLoaderUpgradeab1e11111111111111111111111");
+
+/// Most accounts `getMultipleAccounts` returns per request.
+const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+
+/// An account cloned into [`AccountFixtures`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClonedAccount {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+    pub executable: bool,
+    pub rent_epoch: u64,
+}
+
+impl ClonedAccount {
+    /// The program data account of an upgradeable program, whose address the program
+    /// account stores after its `Program` state tag.
+    pub fn program_data_address(&self) -> Option<Pubkey> {
+        if self.owner != UPGRADEABLE_LOADER || !self.executable {
+            return None;
+        }
+        match self.data.get(..36)?.split_at(4) {
+            ([2, 0, 0, 0], address) => Pubkey::try_from(address).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// A problem [`AccountFixtures::check`] found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixtureIssue {
+    /// An instruction calls a program that isn't in the fixtures.
+    MissingProgram(Pubkey),
+    /// An instruction calls an account that isn't executable.
+    NotExecutable(Pubkey),
+    /// An upgradeable program is in the fixtures but its program data isn't.
+    MissingProgramData {
+        program: Pubkey,
+        program_data: Pubkey,
+    },
+}
+
+impl fmt::Display for FixtureIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixtureIssue::MissingProgram(program) => write!(f, "program {program} is not cloned"),
+            FixtureIssue::NotExecutable(program) => write!(f, "{program} is not executable"),
+            FixtureIssue::MissingProgramData {
+                program,
+                program_data,
+            } => write!(f, "program data {program_data} of {program} is not cloned"),
+        }
+    }
+}
+
+/// Accounts cloned from an RPC with [`JupiterClient::clone_accounts`], by address.
+///
+/// Serializes to a list of accounts in the Solana CLI's JSON format, to be saved with the
+/// tests and loaded back in CI. [`missing`](Self::missing) isn't serialized.
+///
+/// # Example
+/// ```
+/// let instructions = api.get_swap_instructions(&payload).await?.to_instructions()?;
+/// let fixtures = api.clone_accounts(rpc_url, &instructions).await?;
+/// assert!(fixtures.check(&instructions).is_empty());
+/// std::fs::write("tests/fixtures/swap.json", serde_json::to_string(&fixtures)?)?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "Vec<AccountEntry>", try_from = "Vec<AccountEntry>")]
+pub struct AccountFixtures {
+    accounts: BTreeMap<Pubkey, ClonedAccount>,
+    missing: Vec<Pubkey>,
+}
+
+impl AccountFixtures {
+    /// The account at `address`, if it was cloned.
+    pub fn get(&self, address: &Pubkey) -> Option<&ClonedAccount> {
+        self.accounts.get(address)
+    }
+
+    /// Adds or replaces the account at `address`, e.g. to fund the user's token account
+    /// before running the swap.
+    pub fn insert(&mut self, address: Pubkey, account: ClonedAccount) {
+        self.missing.retain(|missing| *missing != address);
+        self.accounts.insert(address, account);
+    }
+
+    /// Every cloned account, sorted by address.
+    pub fn iter(&self) -> impl Iterator<Item = (&Pubkey, &ClonedAccount)> {
+        self.accounts.iter()
+    }
+
+    /// How many accounts were cloned.
+    pub fn len(&self) -> usize {
+        self.accounts.len()
+    }
+
+    /// Whether no account was cloned.
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+
+    /// Accounts the instructions reference that didn't exist when they were cloned,
+    /// usually ones the setup instructions create, such as the user's token accounts.
+    pub fn missing(&self) -> &[Pubkey] {
+        &self.missing
+    }
+
+    /// The account at `address` as the Solana CLI's JSON, the file format of
+    /// `solana-test-validator --account-dir`.
+    pub fn account_json(&self, address: &Pubkey) -> Option<String> {
+        let account = self.accounts.get(address)?;
+        let entry = AccountEntry::new(address, account);
+        Some(serde_json::to_string_pretty(&entry).expect("accounts serialize to JSON"))
+    }
+
+    /// Checks that the fixtures can run `instructions`: every program they call is
+    /// cloned, executable and, if upgradeable, has its program data cloned.
+    ///
+    /// Returns one issue per program, in instruction order; empty if none.
+    pub fn check(&self, instructions: &[Instruction]) -> Vec<FixtureIssue> {
+        let mut issues = Vec::new();
+        let mut checked = Vec::new();
+        for instruction in instructions {
+            let program = instruction.program_id;
+            if checked.contains(&program) {
+                continue;
+            }
+            checked.push(program);
+
+            let Some(account) = self.accounts.get(&program) else {
+                issues.push(FixtureIssue::MissingProgram(program));
+                continue;
+            };
+            if !account.executable {
+                issues.push(FixtureIssue::NotExecutable(program));
+            } else if let Some(program_data) = account.program_data_address()
+                && !self.accounts.contains_key(&program_data)
+            {
+                issues.push(FixtureIssue::MissingProgramData {
+                    program,
+                    program_data,
+                });
+            }
+        }
+        issues
+    }
+}
+
+impl JupiterClient {
+    /// Clones every account `instructions` reference, including the programs they call and
+    /// the program data of upgradeable ones, from the Solana RPC at `rpc_url`.
+    ///
+    /// Accounts that don't exist are listed in [`missing`](AccountFixtures::missing).
+    ///
+    /// Requires the `program-test` feature.
+    ///
+    /// # Example
+    /// ```
+    /// let instructions = api.get_swap_instructions(&payload).await?.to_instructions()?;
+    /// let fixtures = api
+    ///     .clone_accounts("https://api.mainnet-beta.solana.com", &instructions)
+    ///     .await?;
+    /// ```
+    pub async fn clone_accounts(
+        &self,
+        rpc_url: &str,
+        instructions: &[Instruction],
+    ) -> Result<AccountFixtures, JupiterClientError> {
+        let mut addresses = Vec::new();
+        for instruction in instructions {
+            let keys = instruction.accounts.iter().map(|account| account.pubkey);
+            for address in [instruction.program_id].into_iter().chain(keys) {
+                if !addresses.contains(&address) {
+                    addresses.push(address);
+                }
+            }
+        }
+
+        let mut fixtures = AccountFixtures::default();
+        self.clone_into(rpc_url, &addresses, &mut fixtures).await?;
+
+        let program_data: Vec<Pubkey> = fixtures
+            .accounts
+            .values()
+            .filter_map(ClonedAccount::program_data_address)
+            .filter(|address| !fixtures.accounts.contains_key(address))
+            .collect();
+        self.clone_into(rpc_url, &program_data, &mut fixtures)
+            .await?;
+
+        Ok(fixtures)
+    }
+
+    async fn clone_into(
+        &self,
+        rpc_url: &str,
+        addresses: &[Pubkey],
+        fixtures: &mut AccountFixtures,
+    ) -> Result<(), JupiterClientError> {
+        for chunk in addresses.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+            let keys: Vec<String> = chunk.iter().map(Pubkey::to_string).collect();
+            let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+            let accounts = self.get_multiple_accounts(rpc_url, &keys).await?;
+
+            for (address, account) in chunk.iter().zip(accounts) {
+                let Some(account) = account else {
+                    fixtures.missing.push(*address);
+                    continue;
+                };
+                let cloned = ClonedAccount {
+                    data: account.data()?,
+                    owner: parse_pubkey("owner", &account.owner)?,
+                    lamports: account.lamports,
+                    executable: account.executable,
+                    rent_epoch: account.rent_epoch,
+                };
+                fixtures.accounts.insert(*address, cloned);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An account in the Solana CLI's JSON format.
+#[derive(Serialize, Deserialize)]
+struct AccountEntry {
+    pubkey: String,
+    account: UiAccount,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UiAccount {
+    lamports: u64,
+    /// `[data, "base64"]`
+    data: (String, String),
+    owner: String,
+    executable: bool,
+    rent_epoch: u64,
+    #[serde(default)]
+    space: usize,
+}
+
+impl AccountEntry {
+    fn new(address: &Pubkey, account: &ClonedAccount) -> Self {
+        Self {
+            pubkey: address.to_string(),
+            account: UiAccount {
+                lamports: account.lamports,
+                data: (STANDARD.encode(&account.data), "base64".to_string()),
+                owner: account.owner.to_string(),
+                executable: account.executable,
+                rent_epoch: account.rent_epoch,
+                space: account.data.len(),
+            },
+        }
+    }
+}
+
+impl From<AccountFixtures> for Vec<AccountEntry> {
+    fn from(fixtures: AccountFixtures) -> Self {
+        fixtures
+            .accounts
+            .iter()
+            .map(|(address, account)| AccountEntry::new(address, account))
+            .collect()
+    }
+}
+
+impl TryFrom<Vec<AccountEntry>> for AccountFixtures {
+    type Error = JupiterClientError;
+
+    fn try_from(entries: Vec<AccountEntry>) -> Result<Self, Self::Error> {
+        let mut fixtures = AccountFixtures::default();
+        for entry in entries {
+            let data = STANDARD.decode(&entry.account.data.0).map_err(|e| {
+                JupiterClientError::deserialization(format!("account data is not base64: {e}"))
+            })?;
+            let account = ClonedAccount {
+                lamports: entry.account.lamports,
+                data,
+                owner: parse_pubkey("owner", &entry.account.owner)?,
+                executable: entry.account.executable,
+                rent_epoch: entry.account.rent_epoch,
+            };
+            fixtures
+                .accounts
+                .insert(parse_pubkey("pubkey", &entry.pubkey)?, account);
+        }
+        Ok(fixtures)
+    }
+}
@@ -0,0 +1,252 @@
+//! Helpers for collecting platform fees through the Jupiter Referral program.
+//!
+//! Platform fees set with `platformFeeBps` on a quote are paid into a referral token
+//! account: a PDA of the referral program, derived from your referral account and the
+//! mint the fee is taken in. Use [`SwapRequest::referral_fee_account`] to set it on a
+//! swap, and [`JupiterClient::referral_fee_account`] to check that it exists before
+//! swapping. Ultra orders take the referral account itself, and
+//! [`JupiterClient::ultra_referral_fee_accounts`] checks the token accounts of both mints.
+//!
+//! A referral account is created once, under a name, with
+//! [`create_referral_account_instruction`]. [`creation_transaction`] wraps creation
+//! instructions in a transaction ready to sign.
+//!
+//! Requires the `solana` feature.
+//!
+//! [`SwapRequest::referral_fee_account`]: crate::types::SwapRequest::referral_fee_account
+//! [`JupiterClient::referral_fee_account`]: crate::JupiterClient::referral_fee_account
+//! [`JupiterClient::ultra_referral_fee_accounts`]: crate::JupiterClient::ultra_referral_fee_accounts
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use solana_hash::Hash;
+use solana_message::Message;
+use solana_pubkey::Pubkey;
+use solana_transaction::Transaction;
+
+use crate::{
+    error::JupiterClientError,
+    types::{AccountMeta, Instruction, QuoteResponse, parse_pubkey},
+};
+
+/// The Jupiter Referral program.
+pub const REFERRAL_PROGRAM_ID: &str = "REFER4ZgmyYx9c6He5XfaTMiGfdLwRnkV4RPp9t9iF3";
+
+/// The referral project Jupiter's swap APIs collect platform fees under.
+pub const JUPITER_PROJECT: &str = "45ruCyfdRkWpRNGEqWzjCiXRHkZs8WXCLQ67Pnpye7Hp";
+
+/// The SPL Token program.
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+/// The SPL Token-2022 program.
+pub const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+/// The System program.
+pub const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
+
+/// Anchor discriminator of `initialize_referral_token_account`,
+/// the first 8 bytes of `sha256("global:initialize_referral_token_account")`.
+const INITIALIZE_REFERRAL_TOKEN_ACCOUNT: [u8; 8] = [125, 18, 70, 95, 86, 179, 221, 190];
+
+/// Anchor discriminator of `initialize_referral_account_with_name`.
+const INITIALIZE_REFERRAL_ACCOUNT_WITH_NAME: [u8; 8] = [241, 190, 107, 26, 244, 236, 119, 229];
+
+/// Longest referral account name, the size limit of a PDA seed.
+pub const MAX_REFERRAL_NAME_LEN: usize = 32;
+
+/// A referral account, as returned by
+/// [`JupiterClient::named_referral_account`](crate::JupiterClient::named_referral_account).
+#[derive(Debug)]
+pub struct ReferralAccount {
+    /// Address of the referral account.
+    pub address: String,
+    /// Instruction creating the account, or `None` if it already exists.
+    pub create_instruction: Option<Instruction>,
+}
+
+impl ReferralAccount {
+    /// Returns `true` if the referral account already exists on chain.
+    pub fn exists(&self) -> bool {
+        self.create_instruction.is_none()
+    }
+}
+
+/// A referral token account to use as `fee_account`, as returned by
+/// [`JupiterClient::referral_fee_account`](crate::JupiterClient::referral_fee_account).
+#[derive(Debug)]
+pub struct ReferralFeeAccount {
+    /// Address of the referral token account.
+    pub address: String,
+    /// Instruction creating the account, or `None` if it already exists.
+    ///
+    /// Include it in a transaction before the swap, otherwise the swap will fail.
+    pub create_instruction: Option<Instruction>,
+}
+
+impl ReferralFeeAccount {
+    /// Returns `true` if the referral token account already exists on chain.
+    pub fn exists(&self) -> bool {
+        self.create_instruction.is_none()
+    }
+}
+
+/// Derives the referral account named `name` under [`JUPITER_PROJECT`].
+///
+/// # Example
+///
+/// ```
+/// use jup_ag_sdk::referral::referral_account;
+///
+/// let address = referral_account("my-app").unwrap();
+/// ```
+pub fn referral_account(name: &str) -> Result<String, JupiterClientError> {
+    validate_name(name)?;
+    let project = Pubkey::from_str_const(JUPITER_PROJECT);
+    let program_id = Pubkey::from_str_const(REFERRAL_PROGRAM_ID);
+
+    let (address, _bump) = Pubkey::find_program_address(
+        &[b"referral", project.as_ref(), name.as_bytes()],
+        &program_id,
+    );
+    Ok(address.to_string())
+}
+
+/// Derives the referral token account collecting fees in `mint` for `referral_account`.
+///
+/// # Example
+///
+/// ```
+/// use jup_ag_sdk::referral::referral_token_account;
+///
+/// let fee_account = referral_token_account(
+///     "45ruCyfdRkWpRNGEqWzjCiXRHkZs8WXCLQ67Pnpye7Hp", // your referral account
+///     "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN",
+/// )
+/// .unwrap();
+/// ```
+pub fn referral_token_account(
+    referral_account: &str,
+    mint: &str,
+) -> Result<String, JupiterClientError> {
+    let referral_account = parse_pubkey("referral_account", referral_account)?;
+    let mint = parse_pubkey("mint", mint)?;
+    let program_id = Pubkey::from_str_const(REFERRAL_PROGRAM_ID);
+
+    let (address, _bump) = Pubkey::find_program_address(
+        &[b"referral_ata", referral_account.as_ref(), mint.as_ref()],
+        &program_id,
+    );
+    Ok(address.to_string())
+}
+
+/// The mint Jupiter takes the platform fee in for `quote`: the output mint for
+/// `ExactIn` swaps and the input mint for `ExactOut` swaps.
+pub fn fee_mint(quote: &QuoteResponse) -> &str {
+    quote
+        .swap_mode
+        .threshold_mint(&quote.input_mint, &quote.output_mint)
+}
+
+/// Builds the referral program instruction that creates the referral token account
+/// for `referral_account` and `mint`, paid for by `payer`.
+///
+/// `token_program` is the program owning `mint`, [`TOKEN_PROGRAM_ID`] or
+/// [`TOKEN_2022_PROGRAM_ID`].
+pub fn create_referral_token_account_instruction(
+    payer: &str,
+    referral_account: &str,
+    mint: &str,
+    token_program: &str,
+) -> Result<Instruction, JupiterClientError> {
+    parse_pubkey("payer", payer)?;
+    parse_pubkey("token_program", token_program)?;
+    let referral_token_account = referral_token_account(referral_account, mint)?;
+
+    Ok(Instruction {
+        program_id: REFERRAL_PROGRAM_ID.to_string(),
+        accounts: vec![
+            account(payer, true, true),
+            account(JUPITER_PROJECT, false, false),
+            account(referral_account, false, false),
+            account(&referral_token_account, false, true),
+            account(mint, false, false),
+            account(SYSTEM_PROGRAM_ID, false, false),
+            account(token_program, false, false),
+        ],
+        data: STANDARD.encode(INITIALIZE_REFERRAL_TOKEN_ACCOUNT),
+    })
+}
+
+/// Builds the referral program instruction that creates the referral account named
+/// `name` under [`JUPITER_PROJECT`], owned by `partner` and paid for by `payer`.
+///
+/// `partner` is the wallet allowed to claim the fees collected by the account.
+pub fn create_referral_account_instruction(
+    payer: &str,
+    partner: &str,
+    name: &str,
+) -> Result<Instruction, JupiterClientError> {
+    parse_pubkey("payer", payer)?;
+    parse_pubkey("partner", partner)?;
+    let referral_account = referral_account(name)?;
+
+    // borsh encoding of the `{ name: String }` params
+    let mut data = INITIALIZE_REFERRAL_ACCOUNT_WITH_NAME.to_vec();
+    data.extend_from_slice(&(name.len() as u32).to_le_bytes());
+    data.extend_from_slice(name.as_bytes());
+
+    Ok(Instruction {
+        program_id: REFERRAL_PROGRAM_ID.to_string(),
+        accounts: vec![
+            account(payer, true, true),
+            account(partner, false, false),
+            account(JUPITER_PROJECT, false, false),
+            account(&referral_account, false, true),
+            account(SYSTEM_PROGRAM_ID, false, false),
+        ],
+        data: STANDARD.encode(data),
+    })
+}
+
+/// Wraps creation `instructions`, e.g. the `create_instruction`s of [`ReferralAccount`]
+/// and [`ReferralFeeAccount`], in an unsigned legacy transaction paid by `payer`.
+///
+/// # Example
+/// ```
+/// let instructions: Vec<_> = fee_accounts
+///     .into_iter()
+///     .filter_map(|account| account.create_instruction)
+///     .collect();
+/// let mut tx = referral::creation_transaction("YourPubKey...", &instructions, blockhash)?;
+/// tx.sign(&[&keypair], blockhash);
+/// ```
+pub fn creation_transaction(
+    payer: &str,
+    instructions: &[Instruction],
+    recent_blockhash: Hash,
+) -> Result<Transaction, JupiterClientError> {
+    let payer = parse_pubkey("payer", payer)?;
+    let instructions = instructions
+        .iter()
+        .map(solana_instruction::Instruction::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let message = Message::new_with_blockhash(&instructions, Some(&payer), &recent_blockhash);
+    Ok(Transaction::new_unsigned(message))
+}
+
+fn account(pubkey: &str, is_signer: bool, is_writable: bool) -> AccountMeta {
+    AccountMeta {
+        pubkey: pubkey.to_string(),
+        is_signer,
+        is_writable,
+    }
+}
+
+fn validate_name(name: &str) -> Result<(), JupiterClientError> {
+    if name.is_empty() || name.len() > MAX_REFERRAL_NAME_LEN {
+        return Err(JupiterClientError::ValidationError(format!(
+            "referral account name must be 1 to {MAX_REFERRAL_NAME_LEN} bytes long"
+        )));
+    }
+    Ok(())
+}
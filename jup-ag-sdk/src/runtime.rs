@@ -0,0 +1,134 @@
+//! The async runtime used by the SDK's timers and background tasks.
+//!
+//! Plain request methods only await the HTTP client, but rate limiting and the helper
+//! subsystems, such as [`TokenListManager::run`](crate::token_list::TokenListManager::run),
+//! also need to sleep and spawn tasks. They do so through the client's [`Runtime`], which
+//! is [`TokioRuntime`] with the default `tokio-runtime` feature.
+//!
+//! To use another executor, disable default features and pass your own implementation to
+//! [`JupiterClientBuilder::runtime`](crate::JupiterClientBuilder::runtime).
+//!
+//! On `wasm32` targets, [`WasmRuntime`] runs tasks on the browser's event loop and is used
+//! by default; build with `default-features = false` there. Futures are not `Send` on
+//! `wasm32`, since those of the browser's fetch API aren't, and [`Instant`] and
+//! [`SystemTime`] read the browser's clocks, which `std` can't.
+//!
+//! # Example
+//! ```
+//! use std::{future::Future, pin::Pin, time::Duration};
+//! use jup_ag_sdk::runtime::{Runtime, Sleep, Task};
+//!
+//! #[derive(Debug)]
+//! struct SmolRuntime;
+//!
+//! impl Runtime for SmolRuntime {
+//!     fn spawn(&self, task: Task) {
+//!         smol::spawn(task).detach();
+//!     }
+//!
+//!     fn sleep(&self, duration: Duration) -> Sleep {
+//!         Box::pin(async move {
+//!             smol::Timer::after(duration).await;
+//!         })
+//!     }
+//! }
+//!
+//! let client = JupiterClient::builder("https://lite-api.jup.ag")
+//!     .runtime(SmolRuntime)
+//!     .build()?;
+//! ```
+
+use std::{fmt, future::Future, pin::Pin, sync::Arc, time::Duration};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use std::time::{Instant, SystemTime, UNIX_EPOCH};
+#[cfg(target_arch = "wasm32")]
+pub use web_time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// A boxed future, `Send` on every target but `wasm32`.
+#[cfg(not(target_arch = "wasm32"))]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// A boxed future, `Send` on every target but `wasm32`.
+#[cfg(target_arch = "wasm32")]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// A future run in the background by [`Runtime::spawn`].
+pub type Task = BoxFuture<'static, ()>;
+
+/// The future returned by [`Runtime::sleep`].
+pub type Sleep = BoxFuture<'static, ()>;
+
+/// Spawning and timers, the parts of an async runtime the SDK relies on.
+pub trait Runtime: Send + Sync + fmt::Debug {
+    /// Runs `task` in the background to completion.
+    fn spawn(&self, task: Task);
+
+    /// Completes after `duration`.
+    fn sleep(&self, duration: Duration) -> Sleep;
+}
+
+/// Runs tasks and timers on the current tokio runtime.
+///
+/// Requires the `tokio-runtime` feature (on by default), and must be used from within
+/// a tokio runtime with the time driver enabled.
+#[cfg(feature = "tokio-runtime")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioRuntime;
+
+#[cfg(feature = "tokio-runtime")]
+impl Runtime for TokioRuntime {
+    fn spawn(&self, task: Task) {
+        tokio::spawn(task);
+    }
+
+    fn sleep(&self, duration: Duration) -> Sleep {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
+/// Runs tasks on the browser's event loop and timers with `setTimeout`, the default on
+/// `wasm32` targets.
+#[cfg(target_arch = "wasm32")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WasmRuntime;
+
+#[cfg(target_arch = "wasm32")]
+impl Runtime for WasmRuntime {
+    fn spawn(&self, task: Task) {
+        wasm_bindgen_futures::spawn_local(task);
+    }
+
+    fn sleep(&self, duration: Duration) -> Sleep {
+        use wasm_bindgen::JsCast;
+
+        let millis = duration.as_millis().min(i32::MAX as u128) as i32;
+        let timer = js_sys::Promise::new(&mut |resolve, _| {
+            let global = js_sys::global();
+            js_sys::Reflect::get(&global, &"setTimeout".into())
+                .expect("the global object has setTimeout")
+                .unchecked_into::<js_sys::Function>()
+                .call2(&global, &resolve, &millis.into())
+                .expect("setTimeout accepts a callback and a delay");
+        });
+        Box::pin(async move {
+            let _ = wasm_bindgen_futures::JsFuture::from(timer).await;
+        })
+    }
+}
+
+/// The runtime a client uses, shared with the builder it was built from.
+#[derive(Debug, Clone)]
+pub(crate) struct SharedRuntime(pub(crate) Arc<dyn Runtime>);
+
+impl SharedRuntime {
+    /// The runtime enabled by default, if any.
+    pub(crate) fn default_runtime() -> Option<Self> {
+        #[cfg(target_arch = "wasm32")]
+        return Some(Self(Arc::new(WasmRuntime)));
+        #[cfg(all(feature = "tokio-runtime", not(target_arch = "wasm32")))]
+        return Some(Self(Arc::new(TokioRuntime)));
+        #[cfg(not(any(feature = "tokio-runtime", target_arch = "wasm32")))]
+        None
+    }
+}
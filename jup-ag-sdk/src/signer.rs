@@ -0,0 +1,142 @@
+//! A keypair signer that keeps its key material out of logs and wipes it from memory.
+//!
+//! [`LocalSigner`] loads a keypair from bytes, base58 or a Solana CLI keypair file and
+//! signs with [`SwapTransaction::sign`](crate::transaction::SwapTransaction::sign) and
+//! [`JupiterClient::sign_transaction`](crate::JupiterClient::sign_transaction) like any
+//! other [`Signer`]. Every buffer the secret passes through while loading is zeroed when
+//! dropped, as is the key itself, and its `Debug` output only shows the public key.
+//!
+//! Requires the `solana` feature.
+
+use std::{fmt, fs, path::Path};
+
+use solana_keypair::Keypair;
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
+use solana_signer::{Signer, SignerError};
+use zeroize::Zeroizing;
+
+use crate::error::JupiterClientError;
+
+/// Length of a keypair: the 32-byte secret key followed by the 32-byte public key.
+pub const KEYPAIR_LENGTH: usize = 64;
+
+/// A keypair held in memory to sign transactions.
+///
+/// It can't be cloned; share it behind an [`Arc`](std::sync::Arc) instead.
+///
+/// # Example
+/// ```
+/// let signer = LocalSigner::from_file("~/.config/solana/id.json")?;
+/// let mut tx = order.decode_transaction().unwrap()?;
+/// client.sign_transaction(&mut tx, &[&signer])?;
+/// ```
+pub struct LocalSigner {
+    keypair: Keypair,
+}
+
+impl LocalSigner {
+    /// Loads a 64-byte keypair, secret key first.
+    ///
+    /// The caller's `bytes` are left as they are; zero them once loaded.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, JupiterClientError> {
+        if bytes.len() != KEYPAIR_LENGTH {
+            return Err(invalid_keypair(format!(
+                "expected {KEYPAIR_LENGTH} bytes, got {}",
+                bytes.len()
+            )));
+        }
+        let keypair = Keypair::from_bytes(bytes)
+            .map_err(|_| invalid_keypair("public key does not match secret key".to_string()))?;
+        Ok(Self { keypair })
+    }
+
+    /// Loads a base58 keypair, the format wallets export private keys in.
+    pub fn from_base58(encoded: &str) -> Result<Self, JupiterClientError> {
+        let mut bytes = Zeroizing::new([0u8; KEYPAIR_LENGTH]);
+        let len = bs58::decode(encoded.trim())
+            .onto(&mut bytes[..])
+            .map_err(|_| invalid_keypair("not a base58 keypair".to_string()))?;
+        if len != KEYPAIR_LENGTH {
+            return Err(invalid_keypair(format!(
+                "expected {KEYPAIR_LENGTH} bytes, got {len}"
+            )));
+        }
+        Self::from_bytes(&bytes[..])
+    }
+
+    /// Loads a keypair in the Solana CLI's JSON format, an array of 64 numbers.
+    pub fn from_json(json: &str) -> Result<Self, JupiterClientError> {
+        let not_json = || invalid_keypair("expected a JSON array of bytes".to_string());
+        let contents = json
+            .trim()
+            .strip_prefix('[')
+            .and_then(|json| json.strip_suffix(']'))
+            .ok_or_else(not_json)?;
+
+        let mut bytes = Zeroizing::new([0u8; KEYPAIR_LENGTH]);
+        let mut len = 0;
+        for element in contents.split(',') {
+            let byte = element.trim().parse::<u8>().map_err(|_| not_json())?;
+            if let Some(slot) = bytes.get_mut(len) {
+                *slot = byte;
+            }
+            len += 1;
+        }
+        if len != KEYPAIR_LENGTH {
+            return Err(invalid_keypair(format!(
+                "expected {KEYPAIR_LENGTH} bytes, got {len}"
+            )));
+        }
+        Self::from_bytes(&bytes[..])
+    }
+
+    /// Loads a Solana CLI keypair file, e.g. `~/.config/solana/id.json`. A leading `~/`
+    /// is resolved against `$HOME`.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, JupiterClientError> {
+        let path = path.as_ref();
+        let path = match (path.strip_prefix("~"), std::env::var_os("HOME")) {
+            (Ok(relative), Some(home)) => Path::new(&home).join(relative),
+            _ => path.to_path_buf(),
+        };
+        let contents = Zeroizing::new(fs::read_to_string(&path).map_err(|e| {
+            JupiterClientError::ValidationError(format!(
+                "failed to read keypair file {}: {e}",
+                path.display()
+            ))
+        })?);
+        Self::from_json(&contents)
+    }
+}
+
+impl Signer for LocalSigner {
+    fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+        self.keypair.try_pubkey()
+    }
+
+    fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        self.keypair.try_sign_message(message)
+    }
+
+    fn is_interactive(&self) -> bool {
+        false
+    }
+}
+
+impl From<Keypair> for LocalSigner {
+    fn from(keypair: Keypair) -> Self {
+        Self { keypair }
+    }
+}
+
+impl fmt::Debug for LocalSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalSigner")
+            .field("pubkey", &self.keypair.pubkey())
+            .finish_non_exhaustive()
+    }
+}
+
+fn invalid_keypair(reason: String) -> JupiterClientError {
+    JupiterClientError::ValidationError(format!("invalid keypair: {reason}"))
+}
@@ -0,0 +1,225 @@
+//! Persistence for long-running components, so they pick up where they left off after a
+//! restart.
+//!
+//! A [`StateStore`] keeps opaque blobs under string keys. The SDK ships [`MemoryStore`],
+//! for tests and short-lived processes, and [`FileStore`], one file per key in a
+//! directory. Implement the trait to keep state in sled, SQLite, Redis or anything else.
+//!
+//! Components namespace their keys, e.g. `order_monitor/<user>/trigger`, so one store can
+//! be shared by all of them.
+//!
+//! # Example
+//! ```
+//! use std::sync::Arc;
+//! use jup_ag_sdk::{order_monitor::OrderMonitor, state_store::FileStore};
+//!
+//! let store = Arc::new(FileStore::new("./jupiter-state")?);
+//! let mut monitor = OrderMonitor::new("YourPubKey...").state_store(store);
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{error::JupiterClientError, runtime::BoxFuture};
+
+/// The future returned by [`StateStore`] methods.
+pub type StoreFuture<'a, T> = BoxFuture<'a, Result<T, JupiterClientError>>;
+
+/// A key-value store of blobs.
+///
+/// Errors should be returned as [`JupiterClientError::StoreError`].
+pub trait StateStore: Send + Sync + fmt::Debug {
+    /// The value under `key`, `None` if there is none.
+    fn get<'a>(&'a self, key: &'a str) -> StoreFuture<'a, Option<Vec<u8>>>;
+
+    /// Stores `value` under `key`, replacing any previous value.
+    fn put<'a>(&'a self, key: &'a str, value: Vec<u8>) -> StoreFuture<'a, ()>;
+
+    /// Removes `key`; removing a missing key is not an error.
+    fn delete<'a>(&'a self, key: &'a str) -> StoreFuture<'a, ()>;
+
+    /// The keys starting with `prefix`, sorted.
+    fn list<'a>(&'a self, prefix: &'a str) -> StoreFuture<'a, Vec<String>>;
+}
+
+impl dyn StateStore {
+    /// The value under `key` decoded from JSON.
+    pub async fn get_json<T: DeserializeOwned>(
+        &self,
+        key: &str,
+    ) -> Result<Option<T>, JupiterClientError> {
+        match self.get(key).await? {
+            Some(mut bytes) => crate::json::from_slice(&mut bytes).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Stores `value` as JSON under `key`.
+    pub async fn put_json<T: Serialize>(
+        &self,
+        key: &str,
+        value: &T,
+    ) -> Result<(), JupiterClientError> {
+        let bytes =
+            serde_json::to_vec(value).map_err(|e| JupiterClientError::StoreError(Box::new(e)))?;
+        self.put(key, bytes).await
+    }
+}
+
+/// Keeps values in memory; they are lost when the process exits.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    values: Mutex<BTreeMap<String, Vec<u8>>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StateStore for MemoryStore {
+    fn get<'a>(&'a self, key: &'a str) -> StoreFuture<'a, Option<Vec<u8>>> {
+        let value = self.values.lock().unwrap().get(key).cloned();
+        Box::pin(async move { Ok(value) })
+    }
+
+    fn put<'a>(&'a self, key: &'a str, value: Vec<u8>) -> StoreFuture<'a, ()> {
+        self.values.lock().unwrap().insert(key.to_string(), value);
+        Box::pin(async { Ok(()) })
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> StoreFuture<'a, ()> {
+        self.values.lock().unwrap().remove(key);
+        Box::pin(async { Ok(()) })
+    }
+
+    fn list<'a>(&'a self, prefix: &'a str) -> StoreFuture<'a, Vec<String>> {
+        let keys = self
+            .values
+            .lock()
+            .unwrap()
+            .range(prefix.to_string()..)
+            .map(|(key, _)| key)
+            .take_while(|key| key.starts_with(prefix))
+            .cloned()
+            .collect();
+        Box::pin(async move { Ok(keys) })
+    }
+}
+
+/// Keeps each value in its own file in a directory.
+///
+/// File names are the keys with every byte other than ASCII letters, digits, `-` and `_`
+/// percent-encoded. Writes go to a temporary file that is then renamed over the old
+/// value, so a crash never leaves a half-written one. Files are read and written with
+/// blocking I/O, which suits the small values the SDK stores.
+#[derive(Debug, Clone)]
+pub struct FileStore {
+    dir: PathBuf,
+}
+
+impl FileStore {
+    /// Stores values in `dir`, creating it if needed.
+    pub fn new(dir: impl AsRef<Path>) -> Result<Self, JupiterClientError> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir).map_err(store_error)?;
+        Ok(Self { dir })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(encode_key(key))
+    }
+}
+
+impl StateStore for FileStore {
+    fn get<'a>(&'a self, key: &'a str) -> StoreFuture<'a, Option<Vec<u8>>> {
+        Box::pin(async move {
+            match fs::read(self.path(key)) {
+                Ok(value) => Ok(Some(value)),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(store_error(e)),
+            }
+        })
+    }
+
+    fn put<'a>(&'a self, key: &'a str, value: Vec<u8>) -> StoreFuture<'a, ()> {
+        Box::pin(async move {
+            let path = self.path(key);
+            let mut temporary = path.clone().into_os_string();
+            temporary.push(".tmp");
+            fs::write(&temporary, value).map_err(store_error)?;
+            fs::rename(&temporary, &path).map_err(store_error)
+        })
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> StoreFuture<'a, ()> {
+        Box::pin(async move {
+            match fs::remove_file(self.path(key)) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => Err(store_error(e)),
+                _ => Ok(()),
+            }
+        })
+    }
+
+    fn list<'a>(&'a self, prefix: &'a str) -> StoreFuture<'a, Vec<String>> {
+        Box::pin(async move {
+            let mut keys = Vec::new();
+            for entry in fs::read_dir(&self.dir).map_err(store_error)? {
+                let name = entry.map_err(store_error)?.file_name();
+                // temporary files end in `.tmp`, which no encoded key does
+                if let Some(key) = name.to_str().and_then(decode_key)
+                    && key.starts_with(prefix)
+                {
+                    keys.push(key);
+                }
+            }
+            keys.sort();
+            Ok(keys)
+        })
+    }
+}
+
+fn store_error(error: io::Error) -> JupiterClientError {
+    JupiterClientError::StoreError(Box::new(error))
+}
+
+fn encode_key(key: &str) -> String {
+    let mut encoded = String::with_capacity(key.len());
+    for byte in key.bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_' {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+fn decode_key(name: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(name.len());
+    let mut chars = name.bytes();
+    while let Some(byte) = chars.next() {
+        match byte {
+            b'%' => {
+                let hex = [chars.next()?, chars.next()?];
+                bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+            }
+            byte if byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_' => {
+                bytes.push(byte)
+            }
+            _ => return None,
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
@@ -0,0 +1,276 @@
+//! Tracking Jupiter's token lists over time.
+//!
+//! [`TokenListManager`] keeps the latest snapshot of a set of [`TokenList`]s, and each
+//! [`refresh`](TokenListManager::refresh) reports what changed since the previous one:
+//! mints added, removed, or with different tags. Changes are also broadcast as
+//! [`TokenListEvent`]s to every [`subscribe`](TokenListManager::subscribe)r, which makes it
+//! the basis for new listing alerts and for allowlists that follow the verified list.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    time::Duration,
+};
+
+use tokio::sync::broadcast;
+
+use crate::{
+    JupiterClient,
+    error::JupiterClientError,
+    poller::{self, Events, Refresh},
+    types::TokenTag,
+};
+
+/// A token list published by the Tokens API.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TokenList {
+    /// Tokens tagged `verified`.
+    Verified,
+    /// Every mint tradable through Jupiter routing. This list carries no tags and is
+    /// a large download, so refresh it sparingly.
+    Tradable,
+    /// Tokens with the given tag, e.g. `lst` or `token-2022`.
+    Tag(String),
+}
+
+/// What changed in a token list between two refreshes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenListDiff {
+    pub list: TokenList,
+    /// Mints that joined the list, sorted.
+    pub added: Vec<String>,
+    /// Mints that left the list, sorted.
+    pub removed: Vec<String>,
+    /// Mints still on the list whose tags changed, sorted by mint.
+    pub tags_changed: Vec<TagChange>,
+}
+
+impl TokenListDiff {
+    /// Returns `true` if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.tags_changed.is_empty()
+    }
+
+    /// The diff as individual events, in the order they are broadcast.
+    pub fn events(&self) -> impl Iterator<Item = TokenListEvent> + '_ {
+        let added = self.added.iter().map(|mint| TokenListEvent::Added {
+            list: self.list.clone(),
+            mint: mint.clone(),
+        });
+        let removed = self.removed.iter().map(|mint| TokenListEvent::Removed {
+            list: self.list.clone(),
+            mint: mint.clone(),
+        });
+        let tags_changed = self
+            .tags_changed
+            .iter()
+            .map(|change| TokenListEvent::TagsChanged {
+                list: self.list.clone(),
+                change: change.clone(),
+            });
+        added.chain(removed).chain(tags_changed)
+    }
+}
+
+/// The tags a token gained and lost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagChange {
+    pub mint: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/// A single change to a token list, broadcast by [`TokenListManager`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenListEvent {
+    /// `mint` joined `list`.
+    Added { list: TokenList, mint: String },
+    /// `mint` left `list`.
+    Removed { list: TokenList, mint: String },
+    /// A token on `list` gained or lost tags.
+    TagsChanged { list: TokenList, change: TagChange },
+}
+
+/// Mints of a list with their tags.
+type Snapshot = HashMap<String, BTreeSet<String>>;
+
+/// Periodically refreshes token lists and reports how they change.
+///
+/// The first refresh of a list only records it, since everything on it would otherwise
+/// show up as added; diffs and events start from the second one.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use jup_ag_sdk::token_list::{TokenList, TokenListEvent, TokenListManager};
+///
+/// let mut manager = TokenListManager::new([TokenList::Verified]);
+/// let mut events = manager.subscribe();
+///
+/// client.runtime().spawn(Box::pin(async move {
+///     while let Ok(event) = events.recv().await {
+///         if let TokenListEvent::Added { mint, .. } = event {
+///             println!("newly verified: {mint}");
+///         }
+///     }
+/// }));
+///
+/// manager.run(&client, Duration::from_secs(300)).await;
+/// ```
+#[derive(Debug)]
+pub struct TokenListManager {
+    lists: Vec<TokenList>,
+    snapshots: HashMap<TokenList, Snapshot>,
+    events: Events<TokenListEvent>,
+}
+
+impl TokenListManager {
+    /// Creates a manager tracking `lists`. Nothing is fetched until the first refresh.
+    pub fn new(lists: impl IntoIterator<Item = TokenList>) -> Self {
+        let mut tracked: Vec<TokenList> = Vec::new();
+        for list in lists {
+            if !tracked.contains(&list) {
+                tracked.push(list);
+            }
+        }
+        Self {
+            lists: tracked,
+            snapshots: HashMap::new(),
+            events: Events::new(),
+        }
+    }
+
+    /// The tracked lists.
+    pub fn lists(&self) -> &[TokenList] {
+        &self.lists
+    }
+
+    /// Receives the [`TokenListEvent`]s found by later refreshes.
+    ///
+    /// A subscriber that falls more than 1024 events behind gets a `Lagged` error and
+    /// misses the oldest ones.
+    pub fn subscribe(&self) -> broadcast::Receiver<TokenListEvent> {
+        self.events.subscribe()
+    }
+
+    /// Returns `true` if `mint` was on `list` at the last refresh.
+    pub fn contains(&self, list: &TokenList, mint: &str) -> bool {
+        self.snapshots
+            .get(list)
+            .is_some_and(|snapshot| snapshot.contains_key(mint))
+    }
+
+    /// The tags of `mint` on `list` at the last refresh, or `None` if it isn't on it.
+    pub fn tags(&self, list: &TokenList, mint: &str) -> Option<&BTreeSet<String>> {
+        self.snapshots.get(list)?.get(mint)
+    }
+
+    /// Mints on `list` at the last refresh, or `None` if it wasn't fetched yet.
+    pub fn mints(&self, list: &TokenList) -> Option<impl Iterator<Item = &str>> {
+        self.snapshots
+            .get(list)
+            .map(|snapshot| snapshot.keys().map(String::as_str))
+    }
+
+    /// Fetches every tracked list, returns the non-empty diffs against the previous
+    /// snapshots and broadcasts them as events.
+    ///
+    /// Lists are fetched in order; if one fails, the error is returned and it and the
+    /// lists after it keep their previous snapshot.
+    pub async fn refresh(
+        &mut self,
+        client: &JupiterClient,
+    ) -> Result<Vec<TokenListDiff>, JupiterClientError> {
+        let mut diffs = Vec::new();
+        for list in self.lists.clone() {
+            let snapshot = fetch(client, &list).await?;
+            let previous = self.snapshots.insert(list.clone(), snapshot);
+            let Some(previous) = previous else {
+                continue;
+            };
+
+            let diff = diff(list.clone(), &previous, &self.snapshots[&list]);
+            if !diff.is_empty() {
+                for event in diff.events() {
+                    self.events.send(event);
+                }
+                diffs.push(diff);
+            }
+        }
+        Ok(diffs)
+    }
+
+    /// Refreshes now and then every `period`, forever, sleeping on the client's
+    /// [`runtime`](JupiterClient::runtime).
+    ///
+    /// Failed refreshes are retried at the next tick; they are reported through the
+    /// client's [`on_error`](crate::JupiterClientBuilder::on_error) callback.
+    pub async fn run(&mut self, client: &JupiterClient, period: Duration) {
+        poller::run(self, client, period).await
+    }
+}
+
+impl Refresh for TokenListManager {
+    type Output = Vec<TokenListDiff>;
+
+    async fn refresh(
+        &mut self,
+        client: &JupiterClient,
+    ) -> Result<Vec<TokenListDiff>, JupiterClientError> {
+        TokenListManager::refresh(self, client).await
+    }
+}
+
+async fn fetch(client: &JupiterClient, list: &TokenList) -> Result<Snapshot, JupiterClientError> {
+    let tag = match list {
+        TokenList::Tradable => {
+            let mints = client.get_tradable_mints().await?;
+            return Ok(mints
+                .into_iter()
+                .map(|mint| (mint, BTreeSet::new()))
+                .collect());
+        }
+        TokenList::Verified => TokenTag::Verified,
+        TokenList::Tag(tag) => TokenTag::from(tag.as_str()),
+    };
+
+    let tokens = client.get_tokens_by_tag(tag).await?;
+    Ok(tokens
+        .into_iter()
+        .map(|token| (token.address, token.tags.into_iter().flatten().collect()))
+        .collect())
+}
+
+fn diff(list: TokenList, previous: &Snapshot, current: &Snapshot) -> TokenListDiff {
+    let mut added: Vec<String> = current
+        .keys()
+        .filter(|mint| !previous.contains_key(*mint))
+        .cloned()
+        .collect();
+    let mut removed: Vec<String> = previous
+        .keys()
+        .filter(|mint| !current.contains_key(*mint))
+        .cloned()
+        .collect();
+    let mut tags_changed: Vec<TagChange> = current
+        .iter()
+        .filter_map(|(mint, tags)| {
+            let old = previous.get(mint)?;
+            (old != tags).then(|| TagChange {
+                mint: mint.clone(),
+                added: tags.difference(old).cloned().collect(),
+                removed: old.difference(tags).cloned().collect(),
+            })
+        })
+        .collect();
+
+    added.sort();
+    removed.sort();
+    tags_changed.sort_by(|a, b| a.mint.cmp(&b.mint));
+
+    TokenListDiff {
+        list,
+        added,
+        removed,
+        tags_changed,
+    }
+}
@@ -0,0 +1,378 @@
+//! Decoding, signing and re-encoding the transactions returned by Jupiter.
+//!
+//! Swap API transactions are versioned (v0) by default, or legacy when requested with
+//! [`SwapRequest::as_legacy_transaction`](crate::types::SwapRequest::as_legacy_transaction).
+//! [`SwapTransaction`] decodes either, so signers that cannot handle v0 messages get a
+//! plain legacy [`Transaction`].
+//!
+//! [`estimate_size`] and [`SwapInstructions::estimate_size`] report how close a transaction
+//! composed from swap instructions comes to [`MAX_TRANSACTION_SIZE`] before it is signed.
+//!
+//! Requires the `solana` feature.
+
+use std::fmt;
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use solana_hash::Hash;
+use solana_message::{AddressLookupTableAccount, VersionedMessage, v0};
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
+use solana_signer::signers::Signers;
+
+pub use solana_transaction::{Transaction, versioned::VersionedTransaction};
+
+use crate::{
+    JupiterClient,
+    error::JupiterClientError,
+    events::LifecycleEvent,
+    types::{
+        AccountMeta, Instruction, MAX_TRANSACTION_SIZE, SwapInstructions, SwapResponse,
+        UltraOrderResponse, parse_pubkey,
+    },
+};
+
+/// A transaction built by Jupiter, in the format it was built in.
+///
+/// Its `Debug` output lists the signers and how many have signed, but not the
+/// transaction, so logging a signed one doesn't let anyone rebroadcast it.
+#[derive(Clone, PartialEq)]
+pub enum SwapTransaction {
+    /// A legacy transaction, built when `asLegacyTransaction` was requested.
+    Legacy(Transaction),
+    /// A versioned (v0) transaction, which may use address lookup tables.
+    Versioned(VersionedTransaction),
+}
+
+impl SwapTransaction {
+    /// Decodes a base64 transaction as returned by the Swap and Ultra APIs,
+    /// detecting whether it is legacy or versioned from its message.
+    pub fn from_base64(encoded: &str) -> Result<Self, JupiterClientError> {
+        let bytes = STANDARD.decode(encoded).map_err(|e| {
+            JupiterClientError::deserialization(format!("transaction is not base64: {e}"))
+        })?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Decodes a bincode-serialized transaction.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, JupiterClientError> {
+        let transaction: VersionedTransaction = bincode::deserialize(bytes).map_err(|e| {
+            JupiterClientError::deserialization(format!("invalid transaction: {e}"))
+        })?;
+
+        Ok(match transaction.message {
+            VersionedMessage::Legacy(message) => Self::Legacy(Transaction {
+                signatures: transaction.signatures,
+                message,
+            }),
+            VersionedMessage::V0(_) => Self::Versioned(transaction),
+        })
+    }
+
+    /// Returns `true` for a legacy transaction.
+    pub fn is_legacy(&self) -> bool {
+        matches!(self, Self::Legacy(_))
+    }
+
+    /// The serialized message, i.e. the bytes a signer signs.
+    ///
+    /// Use it with external or custodial signers, then attach the result with
+    /// [`add_signature`](Self::add_signature).
+    pub fn message_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Legacy(tx) => tx.message_data(),
+            Self::Versioned(tx) => tx.message.serialize(),
+        }
+    }
+
+    /// The accounts that must sign, in signature order.
+    pub fn signer_keys(&self) -> &[Pubkey] {
+        let (keys, required) = match self {
+            Self::Legacy(tx) => (
+                &tx.message.account_keys[..],
+                tx.message.header.num_required_signatures,
+            ),
+            Self::Versioned(tx) => (
+                tx.message.static_account_keys(),
+                tx.message.header().num_required_signatures,
+            ),
+        };
+        &keys[..(required as usize).min(keys.len())]
+    }
+
+    /// Signs the transaction with `signers`, leaving other signatures in place.
+    ///
+    /// Returns a `ValidationError` if a signer is not one of [`signer_keys`](Self::signer_keys).
+    ///
+    /// # Example
+    /// ```
+    /// let mut tx = swap_response.decode_transaction()?;
+    /// tx.sign(&[&keypair])?;
+    /// let signed = tx.to_base64()?;
+    /// ```
+    pub fn sign<T: Signers + ?Sized>(&mut self, signers: &T) -> Result<(), JupiterClientError> {
+        let pubkeys = signers.try_pubkeys().map_err(signer_error)?;
+        let signatures = signers
+            .try_sign_message(&self.message_bytes())
+            .map_err(signer_error)?;
+
+        for (pubkey, signature) in pubkeys.iter().zip(signatures) {
+            self.add_signature(pubkey, signature)?;
+        }
+        Ok(())
+    }
+
+    /// Sets the signature of `pubkey`, e.g. one produced by an external signer over
+    /// [`message_bytes`](Self::message_bytes).
+    pub fn add_signature(
+        &mut self,
+        pubkey: &Pubkey,
+        signature: Signature,
+    ) -> Result<(), JupiterClientError> {
+        let index = self
+            .signer_keys()
+            .iter()
+            .position(|key| key == pubkey)
+            .ok_or_else(|| {
+                JupiterClientError::ValidationError(format!(
+                    "{pubkey} is not a signer of this transaction"
+                ))
+            })?;
+
+        let signatures = match self {
+            Self::Legacy(tx) => &mut tx.signatures,
+            Self::Versioned(tx) => &mut tx.signatures,
+        };
+        if signatures.len() <= index {
+            signatures.resize(index + 1, Signature::default());
+        }
+        signatures[index] = signature;
+        Ok(())
+    }
+
+    /// Serializes the transaction to bincode bytes.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JupiterClientError> {
+        match self {
+            Self::Legacy(tx) => bincode::serialize(tx),
+            Self::Versioned(tx) => bincode::serialize(tx),
+        }
+        .map_err(|e| JupiterClientError::ValidationError(format!("invalid transaction: {e}")))
+    }
+
+    /// Serializes the transaction to base64, the encoding Ultra `/execute` and
+    /// RPC `sendTransaction` expect.
+    pub fn to_base64(&self) -> Result<String, JupiterClientError> {
+        Ok(STANDARD.encode(self.to_bytes()?))
+    }
+
+    /// The serialized size of the transaction and its margin against [`MAX_TRANSACTION_SIZE`].
+    pub fn size(&self) -> Result<SizeEstimate, JupiterClientError> {
+        Ok(SizeEstimate::new(self.to_bytes()?.len()))
+    }
+
+    /// Converts into a [`VersionedTransaction`], wrapping a legacy message if needed.
+    pub fn into_versioned(self) -> VersionedTransaction {
+        match self {
+            Self::Legacy(tx) => tx.into(),
+            Self::Versioned(tx) => tx,
+        }
+    }
+}
+
+impl fmt::Debug for SwapTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let signatures = match self {
+            Self::Legacy(tx) => &tx.signatures,
+            Self::Versioned(tx) => &tx.signatures,
+        };
+        let signed = signatures
+            .iter()
+            .filter(|signature| **signature != Signature::default())
+            .count();
+        f.debug_struct(if self.is_legacy() {
+            "Legacy"
+        } else {
+            "Versioned"
+        })
+        .field("signers", &self.signer_keys())
+        .field("signed", &signed)
+        .field("message_len", &self.message_bytes().len())
+        .finish_non_exhaustive()
+    }
+}
+
+impl SwapResponse {
+    /// Decodes `swap_transaction`, as a legacy [`Transaction`] if it was requested with
+    /// `as_legacy_transaction(true)`.
+    ///
+    /// Requires the `solana` feature.
+    pub fn decode_transaction(&self) -> Result<SwapTransaction, JupiterClientError> {
+        SwapTransaction::from_base64(&self.swap_transaction)
+    }
+}
+
+impl UltraOrderResponse {
+    /// Decodes the order transaction, or returns `None` if no `taker` was set on the order.
+    ///
+    /// Requires the `solana` feature.
+    pub fn decode_transaction(&self) -> Option<Result<SwapTransaction, JupiterClientError>> {
+        self.transaction
+            .as_deref()
+            .map(SwapTransaction::from_base64)
+    }
+}
+
+impl JupiterClient {
+    /// Signs `transaction` with `signers` like [`SwapTransaction::sign`], then emits
+    /// [`LifecycleEvent::TransactionSigned`] with the fee payer's signature.
+    ///
+    /// Requires the `solana` feature.
+    ///
+    /// # Example
+    /// ```
+    /// let mut tx = order.decode_transaction().unwrap()?;
+    /// client.sign_transaction(&mut tx, &[&keypair])?;
+    /// ```
+    pub fn sign_transaction<T: Signers + ?Sized>(
+        &self,
+        transaction: &mut SwapTransaction,
+        signers: &T,
+    ) -> Result<(), JupiterClientError> {
+        transaction.sign(signers)?;
+
+        let signatures = match transaction {
+            SwapTransaction::Legacy(tx) => &tx.signatures,
+            SwapTransaction::Versioned(tx) => &tx.signatures,
+        };
+        if let Some(signature) = signatures.first() {
+            self.emit(|| LifecycleEvent::TransactionSigned {
+                signature: signature.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// The serialized size of a transaction, compared against [`MAX_TRANSACTION_SIZE`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeEstimate {
+    /// Size in bytes, signatures included.
+    pub size: usize,
+}
+
+impl SizeEstimate {
+    fn new(size: usize) -> Self {
+        Self { size }
+    }
+
+    /// Bytes left before the limit, negative if the transaction is too large.
+    pub fn margin(&self) -> isize {
+        MAX_TRANSACTION_SIZE as isize - self.size as isize
+    }
+
+    /// Returns `true` if the transaction is within [`MAX_TRANSACTION_SIZE`].
+    pub fn fits(&self) -> bool {
+        self.size <= MAX_TRANSACTION_SIZE
+    }
+}
+
+/// Computes the size a v0 transaction with `instructions`, paid by `payer`, would have
+/// once signed, compressing accounts through `lookup_tables`.
+///
+/// The blockhash and signatures are fixed size, so placeholders give the exact size.
+pub fn estimate_size(
+    payer: &Pubkey,
+    instructions: &[solana_instruction::Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+) -> Result<SizeEstimate, JupiterClientError> {
+    let message = v0::Message::try_compile(payer, instructions, lookup_tables, Hash::default())
+        .map_err(|e| JupiterClientError::ValidationError(format!("cannot compile message: {e}")))?;
+    let transaction = VersionedTransaction {
+        signatures: vec![Signature::default(); message.header.num_required_signatures as usize],
+        message: VersionedMessage::V0(message),
+    };
+    SwapTransaction::Versioned(transaction).size()
+}
+
+impl SwapInstructions {
+    /// Estimates the size of the transaction composed from these instructions and `extra`
+    /// ones, paid by `payer`, with the swap's lookup tables resolved into `lookup_tables`
+    /// (see [`JupiterClient::resolve_address_lookup_tables`](crate::JupiterClient::resolve_address_lookup_tables)).
+    ///
+    /// Use the margin to decide whether to drop extra instructions or re-quote with a lower
+    /// `max_accounts` before signing.
+    ///
+    /// Requires the `solana` feature.
+    ///
+    /// # Example
+    /// ```
+    /// let tables = api
+    ///     .resolve_address_lookup_tables(rpc_url, &instructions.address_lookup_table_addresses)
+    ///     .await?;
+    /// let estimate = instructions.estimate_size("YourPubKey...", &[memo], &tables)?;
+    /// if !estimate.fits() {
+    ///     println!("{} bytes over the limit", -estimate.margin());
+    /// }
+    /// ```
+    pub fn estimate_size(
+        &self,
+        payer: &str,
+        extra: &[solana_instruction::Instruction],
+        lookup_tables: &[AddressLookupTableAccount],
+    ) -> Result<SizeEstimate, JupiterClientError> {
+        let mut instructions = self.to_instructions()?;
+        instructions.extend_from_slice(extra);
+
+        estimate_size(&parse_pubkey("payer", payer)?, &instructions, lookup_tables)
+    }
+
+    /// Decodes all instructions in transaction order: compute budget, setup, token
+    /// ledger, swap, cleanup, other.
+    ///
+    /// Requires the `solana` feature.
+    pub fn to_instructions(
+        &self,
+    ) -> Result<Vec<solana_instruction::Instruction>, JupiterClientError> {
+        self.compute_budget_instructions
+            .iter()
+            .flatten()
+            .chain(&self.setup_instructions)
+            .chain(&self.token_ledger_instruction)
+            .chain([&self.swap_instruction])
+            .chain(&self.cleanup_instruction)
+            .chain(self.other_instructions.iter().flatten())
+            .map(solana_instruction::Instruction::try_from)
+            .collect()
+    }
+}
+
+impl TryFrom<&Instruction> for solana_instruction::Instruction {
+    type Error = JupiterClientError;
+
+    /// Converts an instruction returned by the API, with base58 keys and base64 data.
+    fn try_from(instruction: &Instruction) -> Result<Self, Self::Error> {
+        let accounts = instruction
+            .accounts
+            .iter()
+            .map(|account: &AccountMeta| {
+                Ok(solana_instruction::AccountMeta {
+                    pubkey: parse_pubkey("account", &account.pubkey)?,
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+            })
+            .collect::<Result<_, JupiterClientError>>()?;
+        let data = STANDARD.decode(&instruction.data).map_err(|e| {
+            JupiterClientError::deserialization(format!("instruction data is not base64: {e}"))
+        })?;
+
+        Ok(Self {
+            program_id: parse_pubkey("program_id", &instruction.program_id)?,
+            accounts,
+            data,
+        })
+    }
+}
+
+fn signer_error(error: solana_signer::SignerError) -> JupiterClientError {
+    JupiterClientError::ValidationError(format!("failed to sign transaction: {error}"))
+}
@@ -0,0 +1,226 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+use std::{
+    fmt,
+    iter::Sum,
+    ops::{Add, AddAssign, Sub, SubAssign},
+    str::FromStr,
+};
+
+/// A raw token amount in the token's smallest unit (before decimals).
+///
+/// Jupiter encodes amounts as stringified integers (e.g. `"1000000000"` for 1 SOL).
+/// `Amount` deserializes from either a string or a JSON number and always serializes
+/// back to the string form, so it can be used on both request and response types.
+///
+/// # Example
+/// ```
+/// use jup_ag_sdk::types::Amount;
+///
+/// let amount: Amount = "1500000000".parse().unwrap();
+/// assert_eq!(amount.as_u64(), 1_500_000_000);
+/// assert_eq!(amount.to_ui_string(9), "1.5");
+/// assert_eq!(amount + Amount::new(500_000_000), Amount::from_ui(2.0, 9));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Amount(u64);
+
+impl Amount {
+    /// The zero amount.
+    pub const ZERO: Amount = Amount(0);
+
+    /// Creates an amount from a raw integer value.
+    pub const fn new(raw: u64) -> Self {
+        Amount(raw)
+    }
+
+    /// Converts a UI amount (e.g. `1.5` SOL) into a raw amount using the token's decimals.
+    ///
+    /// The result is rounded to the nearest raw unit and saturates at `u64::MAX`.
+    pub fn from_ui(ui_amount: f64, decimals: u8) -> Self {
+        let raw = (ui_amount * 10f64.powi(decimals as i32)).round();
+        Amount(if raw <= 0.0 { 0 } else { raw as u64 })
+    }
+
+    /// Returns the raw amount as a `u64`.
+    pub const fn as_u64(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns the raw amount as a `u128`, convenient for intermediate math that may overflow `u64`.
+    pub const fn as_u128(&self) -> u128 {
+        self.0 as u128
+    }
+
+    /// Returns `true` if the amount is zero.
+    pub const fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Adds two amounts, returning `None` on overflow.
+    pub fn checked_add(self, rhs: Amount) -> Option<Amount> {
+        self.0.checked_add(rhs.0).map(Amount)
+    }
+
+    /// Subtracts `rhs`, returning `None` if the result would be negative.
+    pub fn checked_sub(self, rhs: Amount) -> Option<Amount> {
+        self.0.checked_sub(rhs.0).map(Amount)
+    }
+
+    /// Subtracts `rhs`, clamping at zero.
+    pub fn saturating_sub(self, rhs: Amount) -> Amount {
+        Amount(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Converts the raw amount into a UI amount using the token's decimals.
+    ///
+    /// This is lossy for very large amounts; use [`Amount::to_ui_string`] for display.
+    pub fn to_ui(&self, decimals: u8) -> f64 {
+        self.0 as f64 / 10f64.powi(decimals as i32)
+    }
+
+    /// Formats the amount with the token's decimals, without any floating point rounding.
+    ///
+    /// Trailing zeros in the fractional part are trimmed, e.g. `1500000000` with 9 decimals
+    /// is rendered as `"1.5"`.
+    pub fn to_ui_string(&self, decimals: u8) -> String {
+        let raw = self.0.to_string();
+        let decimals = decimals as usize;
+        if decimals == 0 {
+            return raw;
+        }
+
+        let padded = format!("{raw:0>width$}", width = decimals + 1);
+        let (int, frac) = padded.split_at(padded.len() - decimals);
+        let frac = frac.trim_end_matches('0');
+        if frac.is_empty() {
+            int.to_string()
+        } else {
+            format!("{int}.{frac}")
+        }
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for Amount {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim().parse::<u64>().map(Amount)
+    }
+}
+
+impl From<u64> for Amount {
+    fn from(raw: u64) -> Self {
+        Amount(raw)
+    }
+}
+
+impl From<Amount> for u64 {
+    fn from(amount: Amount) -> Self {
+        amount.0
+    }
+}
+
+impl From<Amount> for u128 {
+    fn from(amount: Amount) -> Self {
+        amount.0 as u128
+    }
+}
+
+impl PartialEq<u64> for Amount {
+    fn eq(&self, other: &u64) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<str> for Amount {
+    fn eq(&self, other: &str) -> bool {
+        other.parse::<Amount>().is_ok_and(|other| *self == other)
+    }
+}
+
+impl PartialEq<&str> for Amount {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl PartialEq<String> for Amount {
+    fn eq(&self, other: &String) -> bool {
+        *self == *other.as_str()
+    }
+}
+
+impl Add for Amount {
+    type Output = Amount;
+
+    /// # Panics
+    /// Panics on overflow, like integer addition in debug builds.
+    fn add(self, rhs: Amount) -> Amount {
+        self.checked_add(rhs).expect("Amount addition overflowed")
+    }
+}
+
+impl AddAssign for Amount {
+    fn add_assign(&mut self, rhs: Amount) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Amount {
+    type Output = Amount;
+
+    /// # Panics
+    /// Panics if `rhs` is greater than `self`.
+    fn sub(self, rhs: Amount) -> Amount {
+        self.checked_sub(rhs)
+            .expect("Amount subtraction underflowed")
+    }
+}
+
+impl SubAssign for Amount {
+    fn sub_assign(&mut self, rhs: Amount) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sum for Amount {
+    fn sum<I: Iterator<Item = Amount>>(iter: I) -> Amount {
+        iter.fold(Amount::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Amount> for Amount {
+    fn sum<I: Iterator<Item = &'a Amount>>(iter: I) -> Amount {
+        iter.copied().sum()
+    }
+}
+
+impl Serialize for Amount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Number(u64),
+            String(String),
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::Number(n) => Ok(Amount(n)),
+            Raw::String(s) => s
+                .parse()
+                .map_err(|e| D::Error::custom(format!("invalid amount {s:?}: {e}"))),
+        }
+    }
+}
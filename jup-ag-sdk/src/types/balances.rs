@@ -0,0 +1,130 @@
+use std::cmp::Ordering;
+
+use super::{Amount, TokenBalance, TokenBalancesResponse, TokenPriceResponse};
+
+/// Key the Ultra balances endpoint uses for native SOL.
+pub const NATIVE_SOL: &str = "SOL";
+
+/// Wrapped SOL mint, which the Price API uses to price native SOL.
+pub const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+/// A token balance ready for display, as returned by [`TokenBalancesExt::to_display_list`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct WalletBalance {
+    /// Token mint address, or `"SOL"` for native SOL.
+    pub mint: String,
+    pub amount: Amount,
+    pub ui_amount: f64,
+    /// Value in USD, `None` if the token has no price.
+    pub usd_value: Option<f64>,
+    pub is_frozen: bool,
+}
+
+/// Post-processing helpers for [`TokenBalancesResponse`] in wallet UIs.
+///
+/// # Example
+/// ```
+/// use jup_ag_sdk::types::TokenBalancesExt;
+///
+/// let mut balances = api.get_token_balances(wallet).await?;
+/// balances.merge_wsol();
+/// let prices = api
+///     .get_token_price(&TokenPriceRequest::new(&balances.price_mints()))
+///     .await?;
+/// for balance in balances.to_display_list(&prices, 0.01) {
+///     println!("{}: {} (${:.2?})", balance.mint, balance.ui_amount, balance.usd_value);
+/// }
+/// ```
+pub trait TokenBalancesExt {
+    /// Folds the wrapped SOL balance into native SOL, so SOL shows up once.
+    fn merge_wsol(&mut self);
+
+    /// Mints to request prices for, with native SOL mapped to [`WSOL_MINT`].
+    fn price_mints(&self) -> Vec<String>;
+
+    /// Returns the balances with their USD value from `prices`, dropping non-zero balances
+    /// worth less than `min_usd_value` and zero balances, sorted by value, highest first.
+    ///
+    /// Tokens without a price are kept and listed after all priced tokens.
+    fn to_display_list(
+        &self,
+        prices: &TokenPriceResponse,
+        min_usd_value: f64,
+    ) -> Vec<WalletBalance>;
+}
+
+impl TokenBalancesExt for TokenBalancesResponse {
+    fn merge_wsol(&mut self) {
+        let Some(wsol) = self.remove(WSOL_MINT) else {
+            return;
+        };
+
+        match self.get_mut(NATIVE_SOL) {
+            Some(sol) => {
+                sol.amount += wsol.amount;
+                sol.ui_amount += wsol.ui_amount;
+                sol.slot = sol.slot.max(wsol.slot);
+            }
+            None => {
+                self.insert(NATIVE_SOL.to_string(), wsol);
+            }
+        }
+    }
+
+    fn price_mints(&self) -> Vec<String> {
+        let mut mints: Vec<String> = self
+            .keys()
+            .map(|mint| price_mint(mint).to_string())
+            .collect();
+        mints.sort();
+        mints.dedup();
+        mints
+    }
+
+    fn to_display_list(
+        &self,
+        prices: &TokenPriceResponse,
+        min_usd_value: f64,
+    ) -> Vec<WalletBalance> {
+        let mut list: Vec<WalletBalance> = self
+            .iter()
+            .filter(|(_, balance)| balance.amount != 0)
+            .map(|(mint, balance)| display_balance(mint, balance, prices))
+            .filter(|balance| balance.usd_value.is_none_or(|usd| usd >= min_usd_value))
+            .collect();
+
+        list.sort_by(|a, b| match (a.usd_value, b.usd_value) {
+            (Some(a), Some(b)) => b.partial_cmp(&a).unwrap_or(Ordering::Equal),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => b
+                .ui_amount
+                .partial_cmp(&a.ui_amount)
+                .unwrap_or(Ordering::Equal),
+        });
+        list
+    }
+}
+
+fn price_mint(mint: &str) -> &str {
+    if mint == NATIVE_SOL { WSOL_MINT } else { mint }
+}
+
+fn display_balance(
+    mint: &str,
+    balance: &TokenBalance,
+    prices: &TokenPriceResponse,
+) -> WalletBalance {
+    let price = prices
+        .data
+        .get(price_mint(mint))
+        .and_then(|price| price.price.parse::<f64>().ok());
+
+    WalletBalance {
+        mint: mint.to_string(),
+        amount: balance.amount,
+        ui_amount: balance.ui_amount,
+        usd_value: price.map(|price| price * balance.ui_amount),
+        is_frozen: balance.is_frozen,
+    }
+}
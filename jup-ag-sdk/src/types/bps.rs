@@ -0,0 +1,138 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+use super::{Amount, number_or_string};
+use crate::error::JupiterClientError;
+
+/// A value in basis points, where `10_000` bps = 100% and `1` bp = 0.01%.
+///
+/// Used for slippage tolerances and fees. Serializes to the plain integer bps value
+/// and deserializes from either an integer or a numeric string.
+///
+/// # Example
+/// ```
+/// use jup_ag_sdk::types::{Amount, Bps};
+///
+/// let slippage = Bps::from_percent(0.5).unwrap();
+/// assert_eq!(slippage.get(), 50);
+/// assert_eq!(slippage.as_percent(), 0.5);
+/// assert_eq!(slippage.apply_to(Amount::new(1_000_000)), Amount::new(5_000));
+/// assert!(Bps::new(10_001).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bps(u16);
+
+impl Bps {
+    /// Zero basis points.
+    pub const ZERO: Bps = Bps(0);
+
+    /// 100%, the largest value accepted by [`Bps::new`].
+    pub const MAX: Bps = Bps(10_000);
+
+    /// Creates a new `Bps`, returning a `ValidationError` if it is above 10,000 (100%).
+    pub fn new(bps: u16) -> Result<Self, JupiterClientError> {
+        if bps > Self::MAX.0 {
+            return Err(JupiterClientError::ValidationError(format!(
+                "{bps} bps is out of range, must be at most {}",
+                Self::MAX.0
+            )));
+        }
+        Ok(Bps(bps))
+    }
+
+    /// Creates a `Bps` from a percentage, e.g. `0.5` for 0.5% (50 bps).
+    ///
+    /// The value is rounded to the nearest basis point.
+    pub fn from_percent(percent: f64) -> Result<Self, JupiterClientError> {
+        let bps = (percent * 100.0).round();
+        if !(0.0..=Self::MAX.0 as f64).contains(&bps) {
+            return Err(JupiterClientError::ValidationError(format!(
+                "{percent}% is out of range, must be between 0% and 100%"
+            )));
+        }
+        Ok(Bps(bps as u16))
+    }
+
+    /// Wraps `bps` without the range check, for request builders that defer it to
+    /// [`Validate`](super::Validate) instead of panicking.
+    pub(crate) const fn new_unchecked(bps: u16) -> Self {
+        Bps(bps)
+    }
+
+    /// Returns the raw number of basis points.
+    pub const fn get(&self) -> u16 {
+        self.0
+    }
+
+    /// Returns the value as a percentage, e.g. `0.5` for 50 bps.
+    pub fn as_percent(&self) -> f64 {
+        self.0 as f64 / 100.0
+    }
+
+    /// Returns the value as a fraction, e.g. `0.005` for 50 bps.
+    pub fn as_fraction(&self) -> f64 {
+        self.0 as f64 / 10_000.0
+    }
+
+    /// Returns this share of `amount`, rounded down.
+    pub fn apply_to(&self, amount: Amount) -> Amount {
+        Amount::new((amount.as_u128() * self.0 as u128 / 10_000) as u64)
+    }
+}
+
+impl fmt::Display for Bps {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} bps", self.0)
+    }
+}
+
+impl PartialEq<u16> for Bps {
+    fn eq(&self, other: &u16) -> bool {
+        self.0 == *other
+    }
+}
+
+impl From<u8> for Bps {
+    fn from(bps: u8) -> Self {
+        Bps(bps as u16)
+    }
+}
+
+impl TryFrom<u16> for Bps {
+    type Error = JupiterClientError;
+
+    fn try_from(bps: u16) -> Result<Self, Self::Error> {
+        Bps::new(bps)
+    }
+}
+
+impl From<Bps> for u16 {
+    fn from(bps: Bps) -> Self {
+        bps.0
+    }
+}
+
+impl Serialize for Bps {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16(self.0)
+    }
+}
+
+/// Serializes `bps` as a numeric string, for APIs that take bps as strings.
+pub(crate) fn option_bps_as_string<S: Serializer>(
+    bps: &Option<Bps>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match bps {
+        Some(bps) => serializer.collect_str(&bps.0),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Deserialization does not enforce the 10,000 bps bound, so an unexpected value
+/// from the API is surfaced to the caller rather than failing the whole response.
+impl<'de> Deserialize<'de> for Bps {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        number_or_string(deserializer).map(Bps)
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::{BTreeMap, HashMap};
+
+use super::{QuoteRequest, Router, UltraRouting};
+use crate::error::JupiterClientError;
+
+/// How an Ultra router sources liquidity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RouterKind {
+    /// Routes through onchain liquidity, see [`UltraRouting::AGGREGATOR_ROUTERS`].
+    Aggregator,
+    /// Fills orders from market maker quotes, see [`UltraRouting::RFQ_ROUTERS`].
+    Rfq,
+    /// A router this version of the SDK doesn't know.
+    Unknown,
+}
+
+impl RouterKind {
+    /// The kind of router `id`, e.g. `metis`.
+    pub fn of(id: &str) -> Self {
+        if UltraRouting::AGGREGATOR_ROUTERS.contains(&id) {
+            Self::Aggregator
+        } else if UltraRouting::RFQ_ROUTERS.contains(&id) {
+            Self::Rfq
+        } else {
+            Self::Unknown
+        }
+    }
+}
+
+/// An Ultra router and what it can do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouterInfo {
+    pub id: String,
+    pub name: String,
+    pub icon: String,
+    pub kind: RouterKind,
+}
+
+/// The DEXes the Swap API routes through, by program id and label, and the routers of
+/// Ultra, as returned by [`dex_registry`](crate::JupiterClient::dex_registry).
+///
+/// Labels are the DEX names quotes report in `swap_info.label` and that
+/// [`QuoteRequest::dexes`](super::QuoteRequest::dexes) and `exclude_dexes` accept.
+///
+/// # Example
+/// ```
+/// let registry = client.dex_registry().await?;
+/// assert_eq!(
+///     registry.label("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo"),
+///     Some("Meteora DLMM")
+/// );
+/// assert!(registry.is_dex("Whirlpool"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DexRegistry {
+    labels: HashMap<String, String>,
+    program_ids: BTreeMap<String, Vec<String>>,
+    routers: BTreeMap<String, RouterInfo>,
+}
+
+impl DexRegistry {
+    /// Builds the registry from the Swap API `program-id-to-label` map and the Ultra
+    /// routers.
+    pub fn new(program_id_to_label: HashMap<String, String>, routers: Vec<Router>) -> Self {
+        let mut program_ids: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (program_id, label) in &program_id_to_label {
+            program_ids
+                .entry(label.clone())
+                .or_default()
+                .push(program_id.clone());
+        }
+        for ids in program_ids.values_mut() {
+            ids.sort();
+        }
+
+        let routers = routers
+            .into_iter()
+            .map(|router| {
+                let info = RouterInfo {
+                    kind: RouterKind::of(&router.id),
+                    id: router.id,
+                    name: router.name,
+                    icon: router.icon,
+                };
+                (info.id.clone(), info)
+            })
+            .collect();
+
+        Self {
+            labels: program_id_to_label,
+            program_ids,
+            routers,
+        }
+    }
+
+    /// The label of the DEX program `program_id`.
+    pub fn label(&self, program_id: &str) -> Option<&str> {
+        self.labels.get(program_id).map(String::as_str)
+    }
+
+    /// The programs of the DEX labelled `label`, sorted; some DEXes have several, e.g. one
+    /// per program version.
+    pub fn program_ids(&self, label: &str) -> &[String] {
+        self.program_ids.get(label).map_or(&[], Vec::as_slice)
+    }
+
+    /// Whether `label` is the label of a DEX.
+    pub fn is_dex(&self, label: &str) -> bool {
+        self.program_ids.contains_key(label)
+    }
+
+    /// Every DEX label, sorted.
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.program_ids.keys().map(String::as_str)
+    }
+
+    /// Fails with a `ValidationError` naming the labels of `request`'s
+    /// [`dexes`](QuoteRequest::dexes) and [`exclude_dexes`](QuoteRequest::exclude_dexes)
+    /// that aren't DEXes, which the Swap API would otherwise ignore or reject.
+    ///
+    /// Labels may be URL encoded with `+` for spaces, e.g. `Meteora+DLMM`.
+    pub fn validate_dexes(&self, request: &QuoteRequest) -> Result<(), JupiterClientError> {
+        let unknown: Vec<&str> = [&request.dexes, &request.exclude_dexes]
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(String::as_str)
+            .filter(|label| !self.is_dex(label) && !self.is_dex(&label.replace('+', " ")))
+            .collect();
+        if unknown.is_empty() {
+            return Ok(());
+        }
+        Err(JupiterClientError::ValidationError(format!(
+            "unknown dexes: {}",
+            unknown.join(", ")
+        )))
+    }
+
+    /// The Ultra router `id`, e.g. `metis`.
+    pub fn router(&self, id: &str) -> Option<&RouterInfo> {
+        self.routers.get(id)
+    }
+
+    /// Every Ultra router, sorted by id.
+    pub fn routers(&self) -> impl Iterator<Item = &RouterInfo> {
+        self.routers.values()
+    }
+}
@@ -0,0 +1,315 @@
+//! Human readable `Display` implementations for quotes and routes, meant for logs and CLI output.
+//!
+//! Well-known mints are rendered with their symbol and amounts scaled by their decimals;
+//! any other mint is shortened (e.g. `EPjF…Dt1v`) and its amounts are shown raw.
+//! [`QuoteResponse::display_with`] also looks mints up in a [`TokenMetadataCache`].
+
+use std::fmt;
+
+use crate::client::transaction_signature;
+
+use super::{
+    Amount, Quote, QuoteResponse, RouteExplanation, RoutePlanItem, RouteRestriction, SwapInfo,
+    TokenMetadataCache, UltraOrderResponse,
+    route_explanation::{compared_amount, compared_mint, route_dexes},
+};
+
+/// (mint, symbol, decimals) for tokens common enough to render by name.
+const KNOWN_TOKENS: &[(&str, &str, u8)] = &[
+    ("So11111111111111111111111111111111111111112", "SOL", 9),
+    ("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", "USDC", 6),
+    ("Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB", "USDT", 6),
+    ("JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN", "JUP", 6),
+    ("DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263", "BONK", 5),
+    ("mSoLzYCxHdYgdzU16g5QSh3i5K3z3KZK7ytfqcJm7So", "mSOL", 9),
+    ("J1toso1uCk3RLmjorhTtrVwY9HJ7X8V9yYac6Y7kGCPn", "JitoSOL", 9),
+];
+
+/// The well-known tokens as (mint, symbol, decimals).
+pub(crate) fn known_tokens() -> impl Iterator<Item = (&'static str, &'static str, u8)> {
+    KNOWN_TOKENS.iter().copied()
+}
+
+pub(crate) fn known_token(mint: &str) -> Option<(&'static str, u8)> {
+    KNOWN_TOKENS
+        .iter()
+        .find(|(m, _, _)| *m == mint)
+        .map(|(_, symbol, decimals)| (*symbol, *decimals))
+}
+
+/// The mint of the well-known token with `symbol`, ignoring case.
+pub(crate) fn known_mint(symbol: &str) -> Option<&'static str> {
+    KNOWN_TOKENS
+        .iter()
+        .find(|(_, s, _)| s.eq_ignore_ascii_case(symbol))
+        .map(|(mint, _, _)| *mint)
+}
+
+/// `Debug` output for a signed transaction: its signature and length, but not the
+/// transaction itself, which whoever reads the log could rebroadcast.
+pub(crate) struct RedactedTransaction<'a>(pub(crate) &'a str);
+
+impl fmt::Debug for RedactedTransaction<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match transaction_signature(self.0) {
+            Some(signature) => write!(
+                f,
+                "<signed transaction {signature}, {} chars>",
+                self.0.len()
+            ),
+            None => write!(f, "<transaction, {} chars>", self.0.len()),
+        }
+    }
+}
+
+/// Symbol and decimals of `mint` from `tokens`, falling back to the well-known tokens.
+fn lookup(mint: &str, tokens: Option<&TokenMetadataCache>) -> Option<(String, u8)> {
+    tokens
+        .and_then(|tokens| tokens.get(mint))
+        .map(|metadata| (metadata.symbol, metadata.decimals))
+        .or_else(|| known_token(mint).map(|(symbol, decimals)| (symbol.to_string(), decimals)))
+}
+
+/// Symbol for known mints, otherwise the mint shortened to `abcd…wxyz`.
+fn token_label(mint: &str, tokens: Option<&TokenMetadataCache>) -> String {
+    match lookup(mint, tokens) {
+        Some((symbol, _)) => symbol,
+        None if mint.is_ascii() && mint.len() > 8 => {
+            format!("{}…{}", &mint[..4], &mint[mint.len() - 4..])
+        }
+        None => mint.to_string(),
+    }
+}
+
+/// `1.5 SOL` for known mints, otherwise the raw amount followed by the shortened mint.
+fn token_amount(amount: Amount, mint: &str, tokens: Option<&TokenMetadataCache>) -> String {
+    match lookup(mint, tokens) {
+        Some((symbol, decimals)) => format!("{} {symbol}", amount.to_ui_string(decimals)),
+        None => format!("{amount} {}", token_label(mint, tokens)),
+    }
+}
+
+/// The distinct mints a route passes through, in order.
+fn route_path(input_mint: &str, output_mint: &str, route_plan: &[RoutePlanItem]) -> Vec<String> {
+    let mut path = vec![input_mint.to_string()];
+    for item in route_plan {
+        let hop = &item.swap_info.output_mint;
+        if hop != output_mint && !path.contains(hop) {
+            path.push(hop.clone());
+        }
+    }
+    if input_mint != output_mint {
+        path.push(output_mint.to_string());
+    }
+    path
+}
+
+fn write_quote(
+    f: &mut fmt::Formatter<'_>,
+    quote: &impl Quote,
+    tokens: Option<&TokenMetadataCache>,
+) -> fmt::Result {
+    let path = route_path(quote.input_mint(), quote.output_mint(), quote.route_plan())
+        .iter()
+        .map(|mint| token_label(mint, tokens))
+        .collect::<Vec<_>>()
+        .join(" → ");
+
+    write!(
+        f,
+        "{} → {}",
+        token_amount(quote.in_amount(), quote.input_mint(), tokens),
+        token_amount(quote.out_amount(), quote.output_mint(), tokens)
+    )?;
+
+    if !quote.route_plan().is_empty() {
+        let venues = quote
+            .route_plan()
+            .iter()
+            .map(|item| format!("{} {}%", item.swap_info.label, item.percent))
+            .collect::<Vec<_>>()
+            .join(" / ");
+        write!(f, " ({path} via {venues})")?;
+    }
+
+    let (bound, threshold_mint) = if quote.swap_mode().is_exact_in() {
+        ("min", quote.output_mint())
+    } else {
+        ("max in", quote.input_mint())
+    };
+    write!(
+        f,
+        ", {bound} {}, impact {}%, slippage {}",
+        token_amount(quote.other_amount_threshold(), threshold_mint, tokens),
+        quote.price_impact_pct(),
+        quote.slippage_bps()
+    )
+}
+
+fn write_swap_info(
+    f: &mut fmt::Formatter<'_>,
+    info: &SwapInfo,
+    tokens: Option<&TokenMetadataCache>,
+) -> fmt::Result {
+    write!(
+        f,
+        "{}: {} → {}",
+        info.label,
+        token_amount(info.in_amount, &info.input_mint, tokens),
+        token_amount(info.out_amount, &info.output_mint, tokens)
+    )
+}
+
+fn write_route_plan(
+    f: &mut fmt::Formatter<'_>,
+    route_plan: &[RoutePlanItem],
+    tokens: Option<&TokenMetadataCache>,
+) -> fmt::Result {
+    if f.alternate() {
+        for item in route_plan {
+            write!(f, "\n  {}% ", item.percent)?;
+            write_swap_info(f, &item.swap_info, tokens)?;
+        }
+    }
+    Ok(())
+}
+
+impl fmt::Display for SwapInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_swap_info(f, self, None)
+    }
+}
+
+/// Renders as e.g. `60% Orca: 0.6 SOL → 90.07 USDC`.
+impl fmt::Display for RoutePlanItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}% {}", self.percent, self.swap_info)
+    }
+}
+
+/// Renders as e.g.
+/// `1 SOL → 150.12 USDC (SOL → USDC via Orca 60% / Meteora DLMM 40%), min 149.37 USDC, impact 0%, slippage 50 bps`.
+///
+/// Use the alternate flag (`{:#}`) to list each route plan item on its own line.
+impl fmt::Display for QuoteResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_quote(f, self, None)?;
+        write_route_plan(f, &self.route_plan, None)
+    }
+}
+
+/// Same format as [`QuoteResponse`], followed by the Ultra swap type and request id.
+impl fmt::Display for UltraOrderResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_ultra_order(f, self, None)
+    }
+}
+
+fn write_ultra_order(
+    f: &mut fmt::Formatter<'_>,
+    order: &UltraOrderResponse,
+    tokens: Option<&TokenMetadataCache>,
+) -> fmt::Result {
+    write_quote(f, order, tokens)?;
+    write!(f, " [{:?}, request {}]", order.swap_type, order.request_id)?;
+    write_route_plan(f, &order.route_plan, tokens)
+}
+
+impl fmt::Display for RouteRestriction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RouteRestriction::Direct => f.write_str("direct routes"),
+            RouteRestriction::SingleDex(dex) => write!(f, "{dex} only"),
+        }
+    }
+}
+
+impl fmt::Display for RouteExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let quote = &self.quote;
+        write!(
+            f,
+            "{}: {}",
+            route_dexes(quote).join(" → "),
+            token_amount(compared_amount(quote), compared_mint(quote), None)
+        )?;
+        match self.best_alternative() {
+            Some(best) => write!(
+                f,
+                ", {:.1} bps better than {}",
+                best.improvement_bps.unwrap_or_default(),
+                best.restriction
+            )?,
+            None => f.write_str(", no alternative route")?,
+        }
+
+        for alternative in &self.alternatives {
+            match &alternative.quote {
+                Some(other) => write!(
+                    f,
+                    "\n  {}: {}",
+                    alternative.restriction,
+                    token_amount(compared_amount(other), compared_mint(other), None)
+                )?,
+                None => write!(f, "\n  {}: no route", alternative.restriction)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Displays a quote or order like its `Display` implementation, also rendering mints
+/// found in a [`TokenMetadataCache`] by symbol with their decimals.
+///
+/// Returned by [`QuoteResponse::display_with`] and [`UltraOrderResponse::display_with`].
+pub struct DisplayWithTokens<'a, T> {
+    value: &'a T,
+    tokens: &'a TokenMetadataCache,
+}
+
+impl QuoteResponse {
+    /// Displays the quote using symbols and decimals from `tokens` for mints
+    /// that are not well known.
+    ///
+    /// # Example
+    /// ```
+    /// client.token_metadata(&quote.output_mint).await?;
+    /// println!("{}", quote.display_with(client.token_metadata_cache()));
+    /// ```
+    pub fn display_with<'a>(
+        &'a self,
+        tokens: &'a TokenMetadataCache,
+    ) -> DisplayWithTokens<'a, Self> {
+        DisplayWithTokens {
+            value: self,
+            tokens,
+        }
+    }
+}
+
+impl UltraOrderResponse {
+    /// Displays the order using symbols and decimals from `tokens`,
+    /// see [`QuoteResponse::display_with`].
+    pub fn display_with<'a>(
+        &'a self,
+        tokens: &'a TokenMetadataCache,
+    ) -> DisplayWithTokens<'a, Self> {
+        DisplayWithTokens {
+            value: self,
+            tokens,
+        }
+    }
+}
+
+impl fmt::Display for DisplayWithTokens<'_, QuoteResponse> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_quote(f, self.value, Some(self.tokens))?;
+        write_route_plan(f, &self.value.route_plan, Some(self.tokens))
+    }
+}
+
+impl fmt::Display for DisplayWithTokens<'_, UltraOrderResponse> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_ultra_order(f, self.value, Some(self.tokens))
+    }
+}
@@ -0,0 +1,349 @@
+use std::io::{self, Write};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    Amount, QuoteResponse, RoutePlanItem, Status, UltraExecuteOrderResponse, UltraOrderResponse,
+};
+use crate::{
+    config::ApiFamily,
+    error::JupiterClientError,
+    runtime::{SystemTime, UNIX_EPOCH},
+};
+
+/// Columns written by [`ExecutionReport::write_csv`], in order.
+pub const EXECUTION_REPORT_CSV_HEADER: [&str; 19] = [
+    "api",
+    "status",
+    "request_id",
+    "signature",
+    "slot",
+    "error",
+    "input_mint",
+    "output_mint",
+    "quoted_in_amount",
+    "quoted_out_amount",
+    "executed_in_amount",
+    "executed_out_amount",
+    "slippage_bps",
+    "fee_bps",
+    "platform_fee_amount",
+    "prioritization_fee_lamports",
+    "route",
+    "quoted_at_ms",
+    "executed_at_ms",
+];
+
+/// Whether a reported swap landed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecutionStatus {
+    /// Quoted but not executed yet.
+    Pending,
+    Success,
+    Failed,
+}
+
+/// One hop of a reported route.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteHop {
+    pub amm_key: String,
+    /// The DEX, e.g. `Meteora DLMM`.
+    pub label: String,
+    /// Share of the input routed through this hop.
+    pub percent: i32,
+}
+
+/// A record of one swap, from quote to execution, for accounting and compliance.
+///
+/// Built from the responses of the Ultra flow with [`from_ultra`](Self::from_ultra) (or
+/// directly by [`JupiterClient::ultra_execute_with_report`](crate::JupiterClient::ultra_execute_with_report)),
+/// from a Swap API quote with [`from_swap`](Self::from_swap) once the transaction
+/// was sent, or read back from chain by `JupiterClient::wallet_swap_history` (`solana`
+/// feature). Export with [`to_json`](Self::to_json), [`write_json_lines`](Self::write_json_lines)
+/// or [`write_csv`](Self::write_csv).
+///
+/// Timestamps are Unix milliseconds.
+///
+/// # Example
+/// ```
+/// let report = client.ultra_execute_with_report(&order, &signed_tx).await?;
+///
+/// let mut file = std::fs::File::create("executions.csv")?;
+/// ExecutionReport::write_csv(&[report], &mut file)?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionReport {
+    /// The API the swap went through, `None` for swaps read from chain.
+    pub api: Option<ApiFamily>,
+    /// The Ultra router that filled the order, when the API reports it.
+    #[serde(default)]
+    pub router: Option<String>,
+    pub status: ExecutionStatus,
+    pub request_id: Option<String>,
+    pub signature: Option<String>,
+    pub slot: Option<u64>,
+    pub error: Option<String>,
+    pub input_mint: String,
+    pub output_mint: String,
+    /// Amounts of the quote; unknown for some swaps read from chain.
+    pub quoted_in_amount: Option<Amount>,
+    pub quoted_out_amount: Option<Amount>,
+    /// Amounts actually swapped, when the API reports them.
+    pub executed_in_amount: Option<Amount>,
+    pub executed_out_amount: Option<Amount>,
+    pub slippage_bps: Option<u16>,
+    /// Jupiter's fee, for Ultra orders.
+    pub fee_bps: Option<u16>,
+    pub platform_fee_amount: Option<Amount>,
+    pub prioritization_fee_lamports: Option<u64>,
+    pub route: Vec<RouteHop>,
+    pub quoted_at_ms: Option<u64>,
+    pub executed_at_ms: Option<u64>,
+}
+
+impl ExecutionReport {
+    /// Reports the execution of an Ultra `order`, executed at `executed_at`.
+    ///
+    /// Executed amounts come from the amount results of `execution`, or are added up from
+    /// its [swap events](UltraExecuteOrderResponse::executed_amounts) when it has none.
+    pub fn from_ultra(
+        order: &UltraOrderResponse,
+        execution: &UltraExecuteOrderResponse,
+        executed_at: SystemTime,
+    ) -> Self {
+        let succeeded = execution.status == Status::Success;
+        let from_events = execution.executed_amounts(&order.input_mint, &order.output_mint);
+        Self {
+            api: Some(ApiFamily::Ultra),
+            router: order.router.clone(),
+            status: if succeeded {
+                ExecutionStatus::Success
+            } else {
+                ExecutionStatus::Failed
+            },
+            request_id: Some(order.request_id.clone()),
+            signature: execution.signature.clone(),
+            slot: execution.slot.as_deref().and_then(|slot| slot.parse().ok()),
+            error: if succeeded {
+                None
+            } else {
+                Some(
+                    execution.error.clone().unwrap_or_else(|| {
+                        format!("execution failed with code {}", execution.code)
+                    }),
+                )
+            },
+            input_mint: order.input_mint.clone(),
+            output_mint: order.output_mint.clone(),
+            quoted_in_amount: Some(order.in_amount),
+            quoted_out_amount: Some(order.out_amount),
+            executed_in_amount: execution
+                .input_amount_result
+                .or(execution.total_input_amount)
+                .or(from_events.map(|(input, _)| input)),
+            executed_out_amount: execution
+                .output_amount_result
+                .or(execution.total_output_amount)
+                .or(from_events.map(|(_, output)| output)),
+            slippage_bps: Some(order.slippage_bps.get()),
+            fee_bps: Some(order.fee_bps.get()),
+            platform_fee_amount: order.platform_fee.as_ref().map(|fee| fee.amount),
+            prioritization_fee_lamports: Some(order.prioritization_fee_lamports),
+            route: route(&order.route_plan),
+            quoted_at_ms: None,
+            executed_at_ms: Some(unix_millis(executed_at)),
+        }
+    }
+
+    /// A [`Pending`](ExecutionStatus::Pending) report of a Swap API `quote`; record the
+    /// outcome with [`confirmed`](Self::confirmed) or [`failed`](Self::failed).
+    pub fn from_swap(quote: &QuoteResponse, quoted_at: SystemTime) -> Self {
+        Self {
+            api: Some(ApiFamily::Swap),
+            router: None,
+            status: ExecutionStatus::Pending,
+            request_id: None,
+            signature: None,
+            slot: None,
+            error: None,
+            input_mint: quote.input_mint.clone(),
+            output_mint: quote.output_mint.clone(),
+            quoted_in_amount: Some(quote.in_amount),
+            quoted_out_amount: Some(quote.out_amount),
+            executed_in_amount: None,
+            executed_out_amount: None,
+            slippage_bps: Some(quote.slippage_bps.get()),
+            fee_bps: None,
+            platform_fee_amount: quote.platform_fee.as_ref().map(|fee| fee.amount),
+            prioritization_fee_lamports: None,
+            route: route(&quote.route_plan),
+            quoted_at_ms: Some(unix_millis(quoted_at)),
+            executed_at_ms: None,
+        }
+    }
+
+    /// Marks the swap as landed in transaction `signature`, with the amounts read from
+    /// chain if known.
+    pub fn confirmed(
+        mut self,
+        signature: &str,
+        executed_in_amount: Option<Amount>,
+        executed_out_amount: Option<Amount>,
+        executed_at: SystemTime,
+    ) -> Self {
+        self.status = ExecutionStatus::Success;
+        self.signature = Some(signature.to_string());
+        self.error = None;
+        self.executed_in_amount = executed_in_amount;
+        self.executed_out_amount = executed_out_amount;
+        self.executed_at_ms = Some(unix_millis(executed_at));
+        self
+    }
+
+    /// Marks the swap as failed with `error`.
+    pub fn failed(mut self, signature: Option<&str>, error: &str, executed_at: SystemTime) -> Self {
+        self.status = ExecutionStatus::Failed;
+        self.signature = signature.map(str::to_string);
+        self.error = Some(error.to_string());
+        self.executed_at_ms = Some(unix_millis(executed_at));
+        self
+    }
+
+    /// Sets when the quote was fetched.
+    pub fn quoted_at(mut self, quoted_at: SystemTime) -> Self {
+        self.quoted_at_ms = Some(unix_millis(quoted_at));
+        self
+    }
+
+    /// Sets the slot the transaction landed in.
+    pub fn slot(mut self, slot: u64) -> Self {
+        self.slot = Some(slot);
+        self
+    }
+
+    /// How much worse the swap executed than quoted, in bps of the quoted price: positive
+    /// if it got less output per input, negative if more.
+    ///
+    /// Compares prices when both executed amounts are known, so partial fills of
+    /// `ExactIn` swaps and `ExactOut` swaps compare fairly, and the output amounts
+    /// otherwise. `None` unless the swap succeeded with a known output amount.
+    pub fn realized_slippage_bps(&self) -> Option<f64> {
+        if self.status != ExecutionStatus::Success {
+            return None;
+        }
+        let quoted_out = self.quoted_out_amount?.as_u64() as f64;
+        let executed_out = self.executed_out_amount?.as_u64() as f64;
+        if quoted_out == 0.0 {
+            return None;
+        }
+        let ratio = match (self.quoted_in_amount, self.executed_in_amount) {
+            (Some(quoted_in), Some(executed_in)) if quoted_in != 0 && executed_in != 0 => {
+                (executed_out / executed_in.as_u64() as f64)
+                    / (quoted_out / quoted_in.as_u64() as f64)
+            }
+            _ => executed_out / quoted_out,
+        };
+        Some((1.0 - ratio) * 10_000.0)
+    }
+
+    /// The report as a JSON object.
+    pub fn to_json(&self) -> Result<String, JupiterClientError> {
+        serde_json::to_string(self).map_err(|e| JupiterClientError::deserialization(e.to_string()))
+    }
+
+    /// Writes `reports` as JSON Lines, one object per line.
+    pub fn write_json_lines<W: Write>(
+        reports: &[ExecutionReport],
+        mut writer: W,
+    ) -> io::Result<()> {
+        for report in reports {
+            serde_json::to_writer(&mut writer, report)?;
+            writer.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    /// Writes `reports` as CSV with an [`EXECUTION_REPORT_CSV_HEADER`] row.
+    ///
+    /// Amounts are raw integers and the route is written as `label@amm_key:percent`
+    /// hops separated by `;`.
+    pub fn write_csv<W: Write>(reports: &[ExecutionReport], mut writer: W) -> io::Result<()> {
+        write_csv_row(&mut writer, EXECUTION_REPORT_CSV_HEADER.map(str::to_string))?;
+        for report in reports {
+            write_csv_row(&mut writer, report.csv_row())?;
+        }
+        Ok(())
+    }
+
+    fn csv_row(&self) -> [String; 19] {
+        fn opt<T: ToString>(value: &Option<T>) -> String {
+            value.as_ref().map(T::to_string).unwrap_or_default()
+        }
+        let route = self
+            .route
+            .iter()
+            .map(|hop| format!("{}@{}:{}", hop.label, hop.amm_key, hop.percent))
+            .collect::<Vec<_>>()
+            .join(";");
+
+        [
+            opt(&self.api),
+            match self.status {
+                ExecutionStatus::Pending => "pending",
+                ExecutionStatus::Success => "success",
+                ExecutionStatus::Failed => "failed",
+            }
+            .to_string(),
+            opt(&self.request_id),
+            opt(&self.signature),
+            opt(&self.slot),
+            opt(&self.error),
+            self.input_mint.clone(),
+            self.output_mint.clone(),
+            opt(&self.quoted_in_amount),
+            opt(&self.quoted_out_amount),
+            opt(&self.executed_in_amount),
+            opt(&self.executed_out_amount),
+            opt(&self.slippage_bps),
+            opt(&self.fee_bps),
+            opt(&self.platform_fee_amount),
+            opt(&self.prioritization_fee_lamports),
+            route,
+            opt(&self.quoted_at_ms),
+            opt(&self.executed_at_ms),
+        ]
+    }
+}
+
+fn route(route_plan: &[RoutePlanItem]) -> Vec<RouteHop> {
+    route_plan
+        .iter()
+        .map(|item| RouteHop {
+            amm_key: item.swap_info.amm_key.clone(),
+            label: item.swap_info.label.clone(),
+            percent: item.percent,
+        })
+        .collect()
+}
+
+pub(crate) fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_millis() as u64)
+}
+
+fn write_csv_row<W: Write>(writer: &mut W, fields: [String; 19]) -> io::Result<()> {
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            writer.write_all(b",")?;
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            write!(writer, "\"{}\"", field.replace('"', "\"\""))?;
+        } else {
+            writer.write_all(field.as_bytes())?;
+        }
+    }
+    writer.write_all(b"\n")
+}
@@ -0,0 +1,160 @@
+use std::collections::BTreeMap;
+
+use super::{ExecutionReport, ExecutionStatus};
+
+/// Execution quality of one Ultra router or DEX, aggregated by [`ExecutionStats`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VenueStats {
+    /// Swaps that landed or failed; pending ones aren't counted.
+    pub executions: u64,
+    pub successes: u64,
+    /// Successful swaps whose [realized
+    /// slippage](ExecutionReport::realized_slippage_bps) is known.
+    pub measured: u64,
+    /// Sum of the realized slippage of the measured swaps, in bps.
+    pub total_slippage_bps: f64,
+    /// The highest realized slippage of a measured swap, in bps.
+    pub worst_slippage_bps: Option<f64>,
+}
+
+impl VenueStats {
+    /// Share of the executions that landed, `None` before any.
+    pub fn fill_rate(&self) -> Option<f64> {
+        (self.executions > 0).then(|| self.successes as f64 / self.executions as f64)
+    }
+
+    /// Average realized slippage of the measured swaps, in bps; negative if they executed
+    /// better than quoted on average.
+    pub fn average_slippage_bps(&self) -> Option<f64> {
+        (self.measured > 0).then(|| self.total_slippage_bps / self.measured as f64)
+    }
+
+    fn record(&mut self, status: ExecutionStatus, slippage_bps: Option<f64>) {
+        self.executions += 1;
+        if status == ExecutionStatus::Success {
+            self.successes += 1;
+        }
+        if let Some(slippage_bps) = slippage_bps {
+            self.measured += 1;
+            self.total_slippage_bps += slippage_bps;
+            self.worst_slippage_bps = Some(
+                self.worst_slippage_bps
+                    .map_or(slippage_bps, |worst| worst.max(slippage_bps)),
+            );
+        }
+    }
+}
+
+/// Fill rate and realized slippage per Ultra router and per DEX, aggregated from
+/// [`ExecutionReport`]s, to decide which routers to pass to
+/// [`UltraOrderRequest::exclude_routers`](super::UltraOrderRequest::exclude_routers).
+///
+/// Reports are counted under their router, if known, and under every DEX of their route,
+/// so a swap split over two DEXes counts for both.
+/// [`JupiterClient::execution_stats`](crate::JupiterClient::execution_stats) aggregates
+/// the client's own executions.
+///
+/// # Example
+/// ```
+/// let stats: ExecutionStats = reports.iter().collect();
+/// for (router, venue) in stats.routers() {
+///     println!("{router}: {:?} filled, {:?} bps", venue.fill_rate(), venue.average_slippage_bps());
+/// }
+/// let request = UltraOrderRequest::new(input_mint, output_mint, amount)
+///     .exclude_routers(stats.routers_to_exclude(20, 0.9, 25.0));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExecutionStats {
+    routers: BTreeMap<String, VenueStats>,
+    dexes: BTreeMap<String, VenueStats>,
+}
+
+impl ExecutionStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `report` to the stats of its router and DEXes. Pending reports are ignored.
+    pub fn record(&mut self, report: &ExecutionReport) {
+        if report.status == ExecutionStatus::Pending {
+            return;
+        }
+        let slippage_bps = report.realized_slippage_bps();
+
+        if let Some(router) = &report.router {
+            self.routers
+                .entry(router.clone())
+                .or_default()
+                .record(report.status, slippage_bps);
+        }
+        let mut labels: Vec<&str> = report.route.iter().map(|hop| hop.label.as_str()).collect();
+        labels.sort_unstable();
+        labels.dedup();
+        for label in labels {
+            self.dexes
+                .entry(label.to_string())
+                .or_default()
+                .record(report.status, slippage_bps);
+        }
+    }
+
+    /// The stats of Ultra router `id`, e.g. `iris`.
+    pub fn router(&self, id: &str) -> Option<&VenueStats> {
+        self.routers.get(id)
+    }
+
+    /// The stats of every router, sorted by id.
+    pub fn routers(&self) -> impl Iterator<Item = (&str, &VenueStats)> {
+        self.routers.iter().map(|(id, stats)| (id.as_str(), stats))
+    }
+
+    /// The stats of the DEX labelled `label`, e.g. `Meteora DLMM`.
+    pub fn dex(&self, label: &str) -> Option<&VenueStats> {
+        self.dexes.get(label)
+    }
+
+    /// The stats of every DEX, sorted by label.
+    pub fn dexes(&self) -> impl Iterator<Item = (&str, &VenueStats)> {
+        self.dexes
+            .iter()
+            .map(|(label, stats)| (label.as_str(), stats))
+    }
+
+    /// Routers with at least `min_executions` executions that fill less than
+    /// `min_fill_rate` of them or slip more than `max_average_slippage_bps` on average,
+    /// sorted by id.
+    pub fn routers_to_exclude(
+        &self,
+        min_executions: u64,
+        min_fill_rate: f64,
+        max_average_slippage_bps: f64,
+    ) -> Vec<String> {
+        self.routers
+            .iter()
+            .filter(|(_, stats)| stats.executions >= min_executions.max(1))
+            .filter(|(_, stats)| {
+                stats.fill_rate().is_some_and(|rate| rate < min_fill_rate)
+                    || stats
+                        .average_slippage_bps()
+                        .is_some_and(|bps| bps > max_average_slippage_bps)
+            })
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+}
+
+impl<'a> Extend<&'a ExecutionReport> for ExecutionStats {
+    fn extend<I: IntoIterator<Item = &'a ExecutionReport>>(&mut self, reports: I) {
+        for report in reports {
+            self.record(report);
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a ExecutionReport> for ExecutionStats {
+    fn from_iter<I: IntoIterator<Item = &'a ExecutionReport>>(reports: I) -> Self {
+        let mut stats = Self::new();
+        stats.extend(reports);
+        stats
+    }
+}
@@ -1,20 +1,70 @@
+pub mod validation;
+pub use validation::*;
+
+pub mod serde_helpers;
+pub use serde_helpers::*;
+
+pub mod amount;
+pub use amount::*;
+
+pub mod bps;
+pub use bps::*;
+
+pub mod swap_mode;
+pub use swap_mode::*;
+
 pub mod quote_request;
 pub use quote_request::*;
 
 pub mod quote_response;
 pub use quote_response::*;
 
+pub mod quote;
+pub use quote::*;
+
+pub mod route_explanation;
+pub use route_explanation::*;
+
+pub mod dex_registry;
+pub use dex_registry::*;
+
+mod display;
+pub use display::DisplayWithTokens;
+pub(crate) use display::{RedactedTransaction, known_mint, known_tokens};
+
 pub mod swap_transaction;
 pub use swap_transaction::*;
 
 pub mod ultra;
 pub use ultra::*;
 
+pub mod balances;
+pub use balances::*;
+
 pub mod token;
 pub use token::*;
 
+pub mod token_metadata;
+pub use token_metadata::*;
+
 pub mod trigger;
 pub use trigger::*;
 
 pub mod recurring;
 pub use recurring::*;
+
+pub mod execution_report;
+pub use execution_report::*;
+pub mod execution_stats;
+pub use execution_stats::*;
+
+pub mod swap_history;
+pub use swap_history::*;
+
+pub mod shield_report;
+pub use shield_report::*;
+
+#[cfg(feature = "perps")]
+pub mod perps;
+#[cfg(feature = "perps")]
+pub use perps::*;
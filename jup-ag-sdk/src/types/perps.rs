@@ -0,0 +1,177 @@
+use serde::{Deserialize, Serialize};
+
+use super::{null_to_default, option_string_or_number};
+
+/// A market of Jupiter Perps, each backed by its custody of the traded token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PerpsMarket {
+    Sol,
+    Eth,
+    Btc,
+}
+
+impl PerpsMarket {
+    /// Every market.
+    pub const ALL: [PerpsMarket; 3] = [PerpsMarket::Sol, PerpsMarket::Eth, PerpsMarket::Btc];
+
+    /// The mint of the traded token, which the API identifies the market by.
+    pub fn mint(self) -> &'static str {
+        match self {
+            PerpsMarket::Sol => "So11111111111111111111111111111111111111112",
+            PerpsMarket::Eth => "7vfCXTUXx5WJV5JADk17DUJ4ksgau7utNKj4b963voxs",
+            PerpsMarket::Btc => "3NZ9JMVBmGAqocybic2c7LQCJScmgsAZ6vQqTDzcqmJh",
+        }
+    }
+
+    /// The market of `mint`, `None` if it isn't traded on Jupiter Perps.
+    pub fn from_mint(mint: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|market| market.mint() == mint)
+    }
+
+    /// The symbol of the traded token, e.g. `SOL`.
+    pub fn symbol(self) -> &'static str {
+        match self {
+            PerpsMarket::Sol => "SOL",
+            PerpsMarket::Eth => "ETH",
+            PerpsMarket::Btc => "BTC",
+        }
+    }
+}
+
+/// Price statistics of a market over the last 24 hours, see
+/// [`get_perps_market_stats`](crate::JupiterClient::get_perps_market_stats).
+///
+/// Prices are USD decimal strings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerpsMarketStats {
+    #[serde(default, deserialize_with = "option_string_or_number")]
+    pub price: Option<String>,
+    /// In percent.
+    #[serde(
+        default,
+        rename = "priceChange24H",
+        deserialize_with = "option_string_or_number"
+    )]
+    pub price_change_24h: Option<String>,
+    #[serde(
+        default,
+        rename = "priceHigh24H",
+        deserialize_with = "option_string_or_number"
+    )]
+    pub price_high_24h: Option<String>,
+    #[serde(
+        default,
+        rename = "priceLow24H",
+        deserialize_with = "option_string_or_number"
+    )]
+    pub price_low_24h: Option<String>,
+    /// Traded volume, in USD.
+    #[serde(default, deserialize_with = "option_string_or_number")]
+    pub volume: Option<String>,
+}
+
+/// Liquidity, utilization and fees of a market's custody, see
+/// [`get_perps_pool_info`](crate::JupiterClient::get_perps_pool_info).
+///
+/// Jupiter Perps have no funding payments between longs and shorts: positions pay an
+/// hourly borrow fee on their size instead, at the side's borrow rate. Amounts are USD
+/// and rates percents, as decimal strings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerpsPoolInfo {
+    #[serde(default, deserialize_with = "option_string_or_number")]
+    pub long_available_liquidity: Option<String>,
+    /// Hourly borrow rate of long positions.
+    #[serde(default, deserialize_with = "option_string_or_number")]
+    pub long_borrow_rate_percent: Option<String>,
+    #[serde(default, deserialize_with = "option_string_or_number")]
+    pub long_utilization_percent: Option<String>,
+    #[serde(default, deserialize_with = "option_string_or_number")]
+    pub short_available_liquidity: Option<String>,
+    /// Hourly borrow rate of short positions.
+    #[serde(default, deserialize_with = "option_string_or_number")]
+    pub short_borrow_rate_percent: Option<String>,
+    #[serde(default, deserialize_with = "option_string_or_number")]
+    pub short_utilization_percent: Option<String>,
+    #[serde(default, deserialize_with = "option_string_or_number")]
+    pub open_fee_percent: Option<String>,
+    #[serde(default, deserialize_with = "option_string_or_number")]
+    pub max_request_execution_sec: Option<String>,
+    #[serde(default, deserialize_with = "option_string_or_number")]
+    pub max_price_impact_fee_percent: Option<String>,
+}
+
+impl PerpsPoolInfo {
+    /// The hourly borrow rate of `side`, in percent.
+    pub fn borrow_rate_percent(&self, side: PerpsSide) -> Option<f64> {
+        let rate = match side {
+            PerpsSide::Long => &self.long_borrow_rate_percent,
+            PerpsSide::Short => &self.short_borrow_rate_percent,
+            PerpsSide::Unknown => return None,
+        };
+        rate.as_deref()?.parse().ok()
+    }
+}
+
+/// Whether a position gains when the price goes up or down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum PerpsSide {
+    Long,
+    Short,
+    /// A value not known to this version of the SDK.
+    #[serde(other)]
+    Unknown,
+}
+
+/// The open positions of a wallet, see
+/// [`get_perps_positions`](crate::JupiterClient::get_perps_positions).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerpsPositions {
+    #[serde(default, deserialize_with = "null_to_default")]
+    pub data_list: Vec<PerpsPosition>,
+    #[serde(default)]
+    pub count: u64,
+}
+
+/// An open position. Prices, sizes, fees and PnL are USD decimal strings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerpsPosition {
+    pub position_pubkey: String,
+    pub side: PerpsSide,
+    pub market_mint: String,
+    #[serde(default)]
+    pub collateral_mint: Option<String>,
+    #[serde(default, deserialize_with = "option_string_or_number")]
+    pub size: Option<String>,
+    #[serde(default, deserialize_with = "option_string_or_number")]
+    pub collateral: Option<String>,
+    #[serde(default, deserialize_with = "option_string_or_number")]
+    pub leverage: Option<String>,
+    #[serde(default, deserialize_with = "option_string_or_number")]
+    pub entry_price: Option<String>,
+    #[serde(default, deserialize_with = "option_string_or_number")]
+    pub mark_price: Option<String>,
+    #[serde(default, deserialize_with = "option_string_or_number")]
+    pub liquidation_price: Option<String>,
+    #[serde(default, deserialize_with = "option_string_or_number")]
+    pub pnl_after_fees: Option<String>,
+    /// Borrow fees accrued since the position was opened or last updated.
+    #[serde(default, deserialize_with = "option_string_or_number")]
+    pub borrow_fees: Option<String>,
+    #[serde(default, deserialize_with = "option_string_or_number")]
+    pub created_time: Option<String>,
+    #[serde(default, deserialize_with = "option_string_or_number")]
+    pub updated_time: Option<String>,
+}
+
+impl PerpsPosition {
+    /// The market of the position, `None` for a market unknown to this version.
+    pub fn market(&self) -> Option<PerpsMarket> {
+        PerpsMarket::from_mint(&self.market_mint)
+    }
+}
@@ -0,0 +1,178 @@
+use std::time::Duration;
+
+use super::{Amount, Bps, PlatformFee, QuoteResponse, RoutePlanItem, SwapMode, UltraOrderResponse};
+
+/// Pricing data shared by the Swap API [`QuoteResponse`] and the Ultra API [`UltraOrderResponse`].
+///
+/// Write pricing or analytics code against `Quote` and it works with either product.
+///
+/// # Example
+/// ```
+/// use jup_ag_sdk::types::{Amount, Quote};
+///
+/// fn min_received(quote: &impl Quote) -> Amount {
+///     quote.min_out_amount()
+/// }
+/// ```
+pub trait Quote {
+    /// The input token mint address.
+    fn input_mint(&self) -> &str;
+
+    /// The output token mint address.
+    fn output_mint(&self) -> &str;
+
+    /// The raw input token amount.
+    fn in_amount(&self) -> Amount;
+
+    /// The raw output token amount (excluding slippage).
+    fn out_amount(&self) -> Amount;
+
+    /// The worst-case amount after slippage: minimum output for `ExactIn`, maximum input for `ExactOut`.
+    fn other_amount_threshold(&self) -> Amount;
+
+    /// Whether the quote is `ExactIn` or `ExactOut`.
+    fn swap_mode(&self) -> SwapMode;
+
+    /// The slippage applied to the quote.
+    fn slippage_bps(&self) -> Bps;
+
+    /// Estimated price impact as returned by the API, a percentage string.
+    fn price_impact_pct(&self) -> &str;
+
+    /// The hops the swap is routed through.
+    fn route_plan(&self) -> &[RoutePlanItem];
+
+    /// Platform fee info, if any was applied.
+    fn platform_fee(&self) -> Option<&PlatformFee>;
+
+    /// How long Jupiter took to compute the quote: `time_taken` of Swap API quotes,
+    /// `total_time` of Ultra orders.
+    fn server_time(&self) -> Duration;
+
+    /// The most input the swap may take: `in_amount` for `ExactIn`, the
+    /// [`other_amount_threshold`](Self::other_amount_threshold) for `ExactOut`.
+    fn max_in_amount(&self) -> Amount {
+        if self.swap_mode().is_exact_out() {
+            self.other_amount_threshold()
+        } else {
+            self.in_amount()
+        }
+    }
+
+    /// The least output the swap may give: the
+    /// [`other_amount_threshold`](Self::other_amount_threshold) for `ExactIn`,
+    /// `out_amount` for `ExactOut`.
+    fn min_out_amount(&self) -> Amount {
+        if self.swap_mode().is_exact_out() {
+            self.out_amount()
+        } else {
+            self.other_amount_threshold()
+        }
+    }
+
+    /// Price impact parsed as a number, or `None` if the API returned something non-numeric.
+    fn price_impact(&self) -> Option<f64> {
+        self.price_impact_pct().parse().ok()
+    }
+
+    /// Output amount received per unit of input amount, in raw units.
+    fn raw_price(&self) -> Option<f64> {
+        if self.in_amount().is_zero() {
+            return None;
+        }
+        Some(self.out_amount().as_u64() as f64 / self.in_amount().as_u64() as f64)
+    }
+}
+
+impl Quote for QuoteResponse {
+    fn input_mint(&self) -> &str {
+        &self.input_mint
+    }
+
+    fn output_mint(&self) -> &str {
+        &self.output_mint
+    }
+
+    fn in_amount(&self) -> Amount {
+        self.in_amount
+    }
+
+    fn out_amount(&self) -> Amount {
+        self.out_amount
+    }
+
+    fn other_amount_threshold(&self) -> Amount {
+        self.other_amount_threshold
+    }
+
+    fn swap_mode(&self) -> SwapMode {
+        self.swap_mode
+    }
+
+    fn slippage_bps(&self) -> Bps {
+        self.slippage_bps
+    }
+
+    fn price_impact_pct(&self) -> &str {
+        &self.price_impact_pct
+    }
+
+    fn route_plan(&self) -> &[RoutePlanItem] {
+        &self.route_plan
+    }
+
+    fn platform_fee(&self) -> Option<&PlatformFee> {
+        self.platform_fee.as_ref()
+    }
+
+    fn server_time(&self) -> Duration {
+        // seconds, zero if negative or not a number
+        Duration::try_from_secs_f64(self.time_taken).unwrap_or_default()
+    }
+}
+
+impl Quote for UltraOrderResponse {
+    fn input_mint(&self) -> &str {
+        &self.input_mint
+    }
+
+    fn output_mint(&self) -> &str {
+        &self.output_mint
+    }
+
+    fn in_amount(&self) -> Amount {
+        self.in_amount
+    }
+
+    fn out_amount(&self) -> Amount {
+        self.out_amount
+    }
+
+    fn other_amount_threshold(&self) -> Amount {
+        self.other_amount_threshold
+    }
+
+    fn swap_mode(&self) -> SwapMode {
+        self.swap_mode
+    }
+
+    fn slippage_bps(&self) -> Bps {
+        self.slippage_bps
+    }
+
+    fn price_impact_pct(&self) -> &str {
+        &self.price_impact_pct
+    }
+
+    fn route_plan(&self) -> &[RoutePlanItem] {
+        &self.route_plan
+    }
+
+    fn platform_fee(&self) -> Option<&PlatformFee> {
+        self.platform_fee.as_ref()
+    }
+
+    fn server_time(&self) -> Duration {
+        Duration::from_millis(self.total_time.into())
+    }
+}
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize, Serializer};
 
+use super::{Bps, SwapMode, Validate, validate_known, validate_optional_bps, validate_pubkey};
+use crate::{JupiterClient, error::JupiterClientError};
+
 /// A request struct for fetching a quote from Jupiter's `/quote` endpoint.
 ///
 /// Use `QuoteRequest::new()` and the fluent setters to configure parameters.
@@ -29,7 +32,7 @@ pub struct QuoteRequest {
     ///
     /// Example: `100` for 1% slippage.
     /// Optional; used only if `dyanmic_slippage` is `false`.
-    pub slippage_bps: Option<u16>,
+    pub slippage_bps: Option<Bps>,
 
     /// Determines whether the amount is `ExactIn` or `ExactOut`.
     ///
@@ -37,7 +40,7 @@ pub struct QuoteRequest {
     /// - `ExactOut`: guarantees output amount, computes input.
     ///
     /// Default: `ExactIn`
-    pub swap_mode: Option<QuoteGetSwapModeEnum>,
+    pub swap_mode: Option<SwapMode>,
 
     /// A list of DEXes to exclusively include in routing.
     ///
@@ -71,7 +74,7 @@ pub struct QuoteRequest {
     /// Platform fee in basis points (bps).
     ///
     /// Used with `feeAccount` in `/swap` to apply affiliate/platform fees.
-    pub platform_fee_bps: Option<u64>,
+    pub platform_fee_bps: Option<Bps>,
 
     /// Upper bound on the number of accounts used in the quote.
     ///
@@ -84,11 +87,48 @@ pub struct QuoteRequest {
     pub dynamic_slippage: Option<bool>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-pub enum QuoteGetSwapModeEnum {
-    ExactIn,
-    ExactOut,
+/// Preset combinations of routing options for [`QuoteRequest::with_profile`].
+///
+/// | Profile        | slippage | intermediate tokens | direct routes only | max accounts |
+/// |----------------|----------|---------------------|--------------------|--------------|
+/// | `Conservative` | 0.3%     | restricted          | no                 | 64           |
+/// | `Fast`         | 1%       | restricted          | yes                | 32           |
+/// | `CheapGas`     | 0.5%     | restricted          | no                 | 20           |
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Profile {
+    /// Tight slippage over the best route through well-known intermediate tokens: the
+    /// best price, at the risk of failing when the market moves.
+    Conservative,
+    /// Simple single-hop routes with room for price movement, so the swap lands on the
+    /// first try.
+    Fast,
+    /// Routes small enough to keep the transaction, and its compute units, small.
+    CheapGas,
+}
+
+impl Profile {
+    /// The profile's slippage tolerance in basis points.
+    pub fn slippage_bps(self) -> u16 {
+        match self {
+            Profile::Conservative => 30,
+            Profile::Fast => 100,
+            Profile::CheapGas => 50,
+        }
+    }
+
+    /// The profile's cap on the accounts of a route.
+    pub fn max_accounts(self) -> u8 {
+        match self {
+            Profile::Conservative => 64,
+            Profile::Fast => 32,
+            Profile::CheapGas => 20,
+        }
+    }
+
+    /// Whether the profile only allows single-hop routes.
+    pub fn only_direct_routes(self) -> bool {
+        self == Profile::Fast
+    }
 }
 
 impl QuoteRequest {
@@ -132,6 +172,28 @@ impl QuoteRequest {
         }
     }
 
+    /// Creates an `ExactIn` request for `ui_amount` of `input` (e.g. `1.5` SOL), with the
+    /// tokens given as symbols or mint addresses.
+    ///
+    /// Mints are resolved with [`JupiterClient::resolve_mint`] and the raw amount computed
+    /// with the decimals from [`JupiterClient::decimals`].
+    ///
+    /// # Example
+    /// ```
+    /// let request = QuoteRequest::from_ui_amounts("SOL", "USDC", 1.5, &client).await?;
+    /// assert_eq!(request.amount, 1_500_000_000);
+    /// ```
+    pub async fn from_ui_amounts(
+        input: &str,
+        output: &str,
+        ui_amount: f64,
+        client: &JupiterClient,
+    ) -> Result<Self, JupiterClientError> {
+        let (input_mint, output_mint, amount) =
+            resolve_ui_amount(input, output, ui_amount, client).await?;
+        Ok(Self::new(&input_mint, &output_mint, amount))
+    }
+
     /// Sets the slippage tolerance in basis points (bps).
     ///
     /// Only used if `dynamic_slippage` is `false`. 100 bps = 1% slippage.
@@ -142,6 +204,8 @@ impl QuoteRequest {
     /// # Returns
     /// The modified `QuoteRequest` for chaining.
     ///
+    /// Values above 10,000 (100%) fail [`validate`](Validate::validate).
+    ///
     /// # Example
     /// ```
     ///
@@ -151,10 +215,10 @@ impl QuoteRequest {
     ///     1_000_000_000
     /// )
     /// .slippage_bps(100); // 1% slippage
-    /// assert_eq!(request.slippage_bps, Some(100));
+    /// assert_eq!(request.slippage_bps, Some(Bps::new(100).unwrap()));
     /// ```
     pub fn slippage_bps(mut self, slippage_bps: u16) -> Self {
-        self.slippage_bps = Some(slippage_bps);
+        self.slippage_bps = Some(Bps::new_unchecked(slippage_bps));
         self
     }
 
@@ -164,7 +228,7 @@ impl QuoteRequest {
     /// - `ExactOut`: Specifies output amount, computes input.
     ///
     /// # Arguments
-    /// * `swap_mode` - The swap mode (`QuoteGetSwapModeEnum::ExactIn` or `ExactOut`).
+    /// * `swap_mode` - The swap mode (`SwapMode::ExactIn` or `ExactOut`).
     ///
     /// # Returns
     /// The modified `QuoteRequest` for chaining.
@@ -177,10 +241,10 @@ impl QuoteRequest {
     ///     "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN",
     ///     1_000_000
     /// )
-    /// .swap_mode(QuoteGetSwapModeEnum::ExactOut); // Want exactly 1 JUP output
-    /// assert_eq!(request.swap_mode, Some(QuoteGetSwapModeEnum::ExactOut));
+    /// .swap_mode(SwapMode::ExactOut); // Want exactly 1 JUP output
+    /// assert_eq!(request.swap_mode, Some(SwapMode::ExactOut));
     /// ```
-    pub fn swap_mode(mut self, swap_mode: QuoteGetSwapModeEnum) -> Self {
+    pub fn swap_mode(mut self, swap_mode: SwapMode) -> Self {
         self.swap_mode = Some(swap_mode);
         self
     }
@@ -329,6 +393,8 @@ impl QuoteRequest {
     /// # Returns
     /// The modified `QuoteRequest` for chaining.
     ///
+    /// Values above 10,000 (100%) fail [`validate`](Validate::validate).
+    ///
     /// # Example
     /// ```
     ///
@@ -338,10 +404,10 @@ impl QuoteRequest {
     ///     1_000_000_000
     /// )
     /// .platform_fee_bps(20); // 0.2% fee
-    /// assert_eq!(request.platform_fee_bps, Some(20));
+    /// assert_eq!(request.platform_fee_bps, Some(Bps::new(20).unwrap()));
     /// ```
-    pub fn platform_fee_bps(mut self, platform_fee_bps: u64) -> Self {
-        self.platform_fee_bps = Some(platform_fee_bps);
+    pub fn platform_fee_bps(mut self, platform_fee_bps: u16) -> Self {
+        self.platform_fee_bps = Some(Bps::new_unchecked(platform_fee_bps));
         self
     }
 
@@ -400,6 +466,49 @@ impl QuoteRequest {
         self.dynamic_slippage = Some(dynamic_slippage);
         self
     }
+
+    /// Sets slippage, `restrict_intermediate_tokens`, `only_direct_routes` and
+    /// `max_accounts` from `profile`, and turns dynamic slippage off.
+    ///
+    /// Setters called afterwards override the profile's values.
+    ///
+    /// # Example
+    /// ```
+    /// let request = QuoteRequest::new(
+    ///     "So11111111111111111111111111111111111111112",
+    ///     "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN",
+    ///     1_000_000_000
+    /// )
+    /// .with_profile(Profile::Fast)
+    /// .slippage_bps(150);
+    /// assert_eq!(request.only_direct_routes, Some(true));
+    /// assert_eq!(request.slippage_bps, Some(Bps::new(150).unwrap()));
+    /// ```
+    pub fn with_profile(self, profile: Profile) -> Self {
+        self.slippage_bps(profile.slippage_bps())
+            .dynamic_slippage(false)
+            .restrict_intermediate_tokens(true)
+            .only_direct_routes(profile.only_direct_routes())
+            .max_accounts(profile.max_accounts())
+    }
+}
+
+/// The mints of `input` and `output` and the raw amount of `ui_amount` of `input`.
+pub(crate) async fn resolve_ui_amount(
+    input: &str,
+    output: &str,
+    ui_amount: f64,
+    client: &JupiterClient,
+) -> Result<(String, String, u64), JupiterClientError> {
+    if !ui_amount.is_finite() || ui_amount <= 0.0 {
+        return Err(JupiterClientError::ValidationError(format!(
+            "ui_amount must be a positive number, got {ui_amount}"
+        )));
+    }
+    let input_mint = client.resolve_mint(input)?;
+    let output_mint = client.resolve_mint(output)?;
+    let amount = client.amount_from_ui(&input_mint, ui_amount).await?;
+    Ok((input_mint, output_mint, amount.as_u64()))
 }
 
 pub fn vec_to_comma_string<S>(vec: &Option<Vec<String>>, serializer: S) -> Result<S::Ok, S::Error>
@@ -411,3 +520,15 @@ where
         None => serializer.serialize_none(),
     }
 }
+
+impl Validate for QuoteRequest {
+    fn validate(&self) -> Result<(), JupiterClientError> {
+        validate_pubkey("input_mint", &self.input_mint)?;
+        validate_pubkey("output_mint", &self.output_mint)?;
+        if let Some(swap_mode) = self.swap_mode {
+            validate_known("swap_mode", swap_mode, SwapMode::Unknown)?;
+        }
+        validate_optional_bps("slippage_bps", self.slippage_bps)?;
+        validate_optional_bps("platform_fee_bps", self.platform_fee_bps)
+    }
+}
@@ -1,44 +1,51 @@
+use std::{sync::Arc, time::Duration};
+
 use serde::{Deserialize, Serialize};
 
-use super::QuoteGetSwapModeEnum;
+use super::{
+    Amount, Bps, QuoteRequest, SwapMode, null_to_default, number_or_string, string_or_number,
+};
+use crate::runtime::Instant;
 
 /// A response returned by Jupiter’s `/quote` endpoint.
 ///
 /// Includes detailed routing, fee, and token swap info.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QuoteResponse {
     /// The input token mint address.
     pub input_mint: String,
 
     /// The raw input token amount.
-    pub in_amount: String,
+    pub in_amount: Amount,
 
     /// The output token mint address.
     pub output_mint: String,
 
     /// The raw output token amount (excluding slippage or fees).
-    pub out_amount: String,
+    pub out_amount: Amount,
 
     /// The worst-case output amount after slippage & fees.
     ///
     /// Not used by `/swap`, but useful for displaying expectations.
-    pub other_amount_threshold: String,
+    pub other_amount_threshold: Amount,
 
     /// Indicates the swap mode used (ExactIn or ExactOut).
-    pub swap_mode: QuoteGetSwapModeEnum,
+    pub swap_mode: SwapMode,
 
     /// The applied slippage in basis points.
-    pub slippage_bps: u16,
+    pub slippage_bps: Bps,
 
     /// Platform fee info (if any was applied).
     #[serde(default)]
     pub platform_fee: Option<PlatformFee>,
 
     /// Estimated price impact as a percentage string.
+    #[serde(deserialize_with = "string_or_number")]
     pub price_impact_pct: String,
 
     /// The detailed route plan (possibly multiple hops).
+    #[serde(deserialize_with = "null_to_default")]
     pub route_plan: Vec<RoutePlanItem>,
 
     /// Optional: A diagnostic or scoring report from Jupiter.
@@ -46,9 +53,12 @@ pub struct QuoteResponse {
     pub score_report: Option<serde_json::Value>,
 
     /// The Solana slot number used for this quote.
+    #[serde(deserialize_with = "number_or_string")]
     pub context_slot: u64,
 
-    /// Total time taken by Jupiter to generate this quote (in seconds).
+    /// Total time taken by Jupiter to generate this quote (in seconds), see
+    /// [`Quote::server_time`](super::Quote::server_time).
+    #[serde(deserialize_with = "number_or_string")]
     pub time_taken: f64,
 
     /// Optional: Estimated USD value of the swap.
@@ -66,36 +76,74 @@ pub struct QuoteResponse {
     /// Optional: Slippage estimated by Jupiter’s internal engine.
     #[serde(default)]
     pub use_incurred_slippage_for_quoting: Option<serde_json::Value>,
+
+    /// How the client fetched the quote, `None` for quotes it didn't fetch.
+    #[serde(skip)]
+    pub(crate) origin: Option<QuoteOrigin>,
+}
+
+/// When and for which request [`JupiterClient::get_quote`](crate::JupiterClient::get_quote)
+/// fetched a quote, so [`QuoteFreshness`](crate::config::QuoteFreshness) can age and
+/// re-quote it.
+#[derive(Debug, Clone)]
+pub(crate) struct QuoteOrigin {
+    pub(crate) fetched_at: Instant,
+    pub(crate) request: Arc<QuoteRequest>,
+    /// Served from the quote cache for another amount of the same
+    /// [`amount_precision`](crate::config::QuoteCacheConfig::amount_precision) bucket.
+    pub(crate) approximate: bool,
+}
+
+impl QuoteResponse {
+    /// Time since [`JupiterClient::get_quote`](crate::JupiterClient::get_quote) fetched
+    /// the quote, `None` if it didn't, e.g. for a deserialized quote. Quotes served from
+    /// the quote cache are as old as when they were first fetched.
+    pub fn age(&self) -> Option<Duration> {
+        self.origin
+            .as_ref()
+            .map(|origin| origin.fetched_at.elapsed())
+    }
+
+    /// Whether the quote cache served this quote for a nearby amount, see
+    /// [`QuoteCacheConfig::amount_precision`](crate::config::QuoteCacheConfig::amount_precision).
+    /// Its `inAmount`, `outAmount` and route are those of the amount it was fetched for,
+    /// not the requested one; swaps built from it are re-quoted with the requested amount.
+    pub fn is_approximate(&self) -> bool {
+        self.origin
+            .as_ref()
+            .is_some_and(|origin| origin.approximate)
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PlatformFee {
-    pub amount: String,
-    pub fee_bps: u16,
+    pub amount: Amount,
+    pub fee_bps: Bps,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RoutePlanItem {
     pub swap_info: SwapInfo,
+    #[serde(deserialize_with = "number_or_string")]
     pub percent: i32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SwapInfo {
     pub amm_key: String,
     pub label: String,
     pub input_mint: String,
     pub output_mint: String,
-    pub in_amount: String,
-    pub out_amount: String,
-    pub fee_amount: String,
+    pub in_amount: Amount,
+    pub out_amount: Amount,
+    pub fee_amount: Amount,
     pub fee_mint: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MostReliableAmmsQuoteReport {
     pub info: std::collections::HashMap<String, String>,
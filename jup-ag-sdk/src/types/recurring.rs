@@ -1,5 +1,16 @@
-use super::OrderStatus;
+use super::{
+    OrderStatus, RedactedTransaction, Validate, validate_known, validate_optional_pubkey,
+    validate_pubkey,
+};
+use crate::error::JupiterClientError;
 use serde::{Deserialize, Serialize};
+use std::{fmt, time::Duration};
+
+/// Smallest total value, in USD, the Recurring API accepts for an order.
+pub const MIN_RECURRING_ORDER_USD: f64 = 100.0;
+
+/// Decimals of the USD stablecoins (USDC, USDT) price-based orders are funded with.
+const USD_STABLECOIN_DECIMALS: i32 = 6;
 
 /// Represents a request to create a recurring order, either time-based or price-based.
 #[derive(Serialize, Deserialize, Debug)]
@@ -127,6 +138,82 @@ impl CreateRecurringOrderRequest {
         }
     }
 
+    /// Creates a value-averaging (price-based) order that grows the value of the
+    /// `output_mint` position by `increment_usd` every `interval`, for `duration`.
+    ///
+    /// Computes the API parameters from them: `increment_usdc_value`, `interval` in
+    /// seconds, and `deposit_amount` as one increment per interval in `duration`.
+    /// `input_mint` must be a 6-decimal USD stablecoin such as USDC.
+    ///
+    /// Each order buys whatever it takes to reach the target value, so if the price
+    /// drops the deposit can run out before `duration`; top it up with a [`PriceDeposit`].
+    ///
+    /// Returns a `ValidationError` if `interval` is under a second or longer than
+    /// `duration`, or if the deposit is below [`MIN_RECURRING_ORDER_USD`].
+    ///
+    /// # Example
+    /// ```
+    /// // add $50 of SOL every week for 12 weeks, funded with $600 USDC
+    /// let order = CreateRecurringOrderRequest::new_value_averaging_order(
+    ///     "YourPubKey...",
+    ///     "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+    ///     "So11111111111111111111111111111111111111112",
+    ///     50.0,
+    ///     Duration::from_secs(7 * 24 * 60 * 60),
+    ///     Duration::from_secs(12 * 7 * 24 * 60 * 60),
+    /// )?;
+    /// ```
+    pub fn new_value_averaging_order(
+        user: impl Into<String>,
+        input_mint: impl Into<String>,
+        output_mint: impl Into<String>,
+        increment_usd: f64,
+        interval: Duration,
+        duration: Duration,
+    ) -> Result<Self, JupiterClientError> {
+        let intervals = interval_count(interval, duration)?;
+        let increment_usdc_value = usd_to_raw("increment_usd", increment_usd)?;
+        let deposit_amount = increment_usdc_value.saturating_mul(intervals);
+        validate_deposit(deposit_amount)?;
+
+        Ok(Self::new_price_order(
+            user,
+            input_mint,
+            output_mint,
+            deposit_amount,
+            increment_usdc_value,
+            interval.as_secs(),
+        ))
+    }
+
+    /// Creates a value-averaging (price-based) order that spreads a `budget_usd`
+    /// deposit over `duration`, with the increment sized so that one increment per
+    /// `interval` uses the whole budget.
+    ///
+    /// See [`new_value_averaging_order`](Self::new_value_averaging_order) for the
+    /// requirements on `input_mint` and the errors returned.
+    pub fn new_value_averaging_order_with_budget(
+        user: impl Into<String>,
+        input_mint: impl Into<String>,
+        output_mint: impl Into<String>,
+        budget_usd: f64,
+        interval: Duration,
+        duration: Duration,
+    ) -> Result<Self, JupiterClientError> {
+        let intervals = interval_count(interval, duration)?;
+        let deposit_amount = usd_to_raw("budget_usd", budget_usd)?;
+        validate_deposit(deposit_amount)?;
+
+        Ok(Self::new_price_order(
+            user,
+            input_mint,
+            output_mint,
+            deposit_amount,
+            deposit_amount / intervals,
+            interval.as_secs(),
+        ))
+    }
+
     /// Sets the `start_at` Unix timestamp to delay the start of the recurring order.
     pub fn with_start_at(mut self, start_at: u64) -> Self {
         match &mut self.params {
@@ -153,6 +240,45 @@ impl CreateRecurringOrderRequest {
     }
 }
 
+/// Number of whole `interval`s in `duration`.
+fn interval_count(interval: Duration, duration: Duration) -> Result<u64, JupiterClientError> {
+    if interval.as_secs() == 0 {
+        return Err(JupiterClientError::ValidationError(
+            "interval must be at least one second".to_string(),
+        ));
+    }
+    let intervals = duration.as_secs() / interval.as_secs();
+    if intervals == 0 {
+        return Err(JupiterClientError::ValidationError(format!(
+            "duration of {}s is shorter than the {}s interval",
+            duration.as_secs(),
+            interval.as_secs()
+        )));
+    }
+    Ok(intervals)
+}
+
+/// Converts a USD value to raw stablecoin units.
+fn usd_to_raw(field: &str, usd: f64) -> Result<u64, JupiterClientError> {
+    let raw = (usd * 10f64.powi(USD_STABLECOIN_DECIMALS)).round();
+    if !raw.is_finite() || raw < 1.0 || raw > u64::MAX as f64 {
+        return Err(JupiterClientError::ValidationError(format!(
+            "{field} must be a positive USD value, got {usd}"
+        )));
+    }
+    Ok(raw as u64)
+}
+
+fn validate_deposit(deposit_amount: u64) -> Result<(), JupiterClientError> {
+    let deposit_usd = deposit_amount as f64 / 10f64.powi(USD_STABLECOIN_DECIMALS);
+    if deposit_usd < MIN_RECURRING_ORDER_USD {
+        return Err(JupiterClientError::ValidationError(format!(
+            "deposit of {deposit_usd} USD is below the {MIN_RECURRING_ORDER_USD} USD minimum"
+        )));
+    }
+    Ok(())
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CancelRecurringOrderRequest {
@@ -177,8 +303,9 @@ impl CancelRecurringOrderRequest {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
 #[serde(rename_all = "lowercase")]
+#[non_exhaustive]
 pub enum RecurringOrderType {
     Time,
     Price,
@@ -218,7 +345,7 @@ pub struct RecurringResponse {
     pub transaction: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteRecurringRequest {
     pub request_id: String,
@@ -226,6 +353,18 @@ pub struct ExecuteRecurringRequest {
     pub signed_transaction: String,
 }
 
+impl fmt::Debug for ExecuteRecurringRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExecuteRecurringRequest")
+            .field("request_id", &self.request_id)
+            .field(
+                "signed_transaction",
+                &RedactedTransaction(&self.signed_transaction),
+            )
+            .finish()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteRecurringResponse {
@@ -234,7 +373,7 @@ pub struct ExecuteRecurringResponse {
     pub status: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetRecurringOrders {
     pub recurring_type: RecurringOrderType,
@@ -294,3 +433,51 @@ pub struct RecurringOrders {
     #[serde(default)]
     pub all: Option<Vec<serde_json::Value>>,
 }
+
+impl Validate for CreateRecurringOrderRequest {
+    fn validate(&self) -> Result<(), JupiterClientError> {
+        validate_pubkey("user", &self.user)?;
+        validate_pubkey("input_mint", &self.input_mint)?;
+        validate_pubkey("output_mint", &self.output_mint)
+    }
+}
+
+impl Validate for ExecuteRecurringRequest {
+    fn validate(&self) -> Result<(), JupiterClientError> {
+        if self.signed_transaction.is_empty() || self.request_id.is_empty() {
+            return Err(JupiterClientError::ValidationError(
+                "signed_transaction and request_id are required".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Validate for CancelRecurringOrderRequest {
+    fn validate(&self) -> Result<(), JupiterClientError> {
+        validate_pubkey("order", &self.order)?;
+        validate_pubkey("user", &self.user)
+    }
+}
+
+impl Validate for PriceDeposit {
+    fn validate(&self) -> Result<(), JupiterClientError> {
+        validate_pubkey("order", &self.order)?;
+        validate_pubkey("user", &self.user)
+    }
+}
+
+impl Validate for PriceWithdraw {
+    fn validate(&self) -> Result<(), JupiterClientError> {
+        validate_pubkey("order", &self.order)?;
+        validate_pubkey("user", &self.user)
+    }
+}
+
+impl Validate for GetRecurringOrders {
+    fn validate(&self) -> Result<(), JupiterClientError> {
+        validate_pubkey("user", &self.user)?;
+        validate_known("order_status", self.order_status, OrderStatus::Unknown)?;
+        validate_optional_pubkey("mint", self.mint.as_deref())
+    }
+}
@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Amount, QuoteResponse, SwapMode};
+
+/// How a comparison quote of a [`RouteExplanation`] was restricted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RouteRestriction {
+    /// Single-hop routes only, `only_direct_routes`.
+    Direct,
+    /// Routes through this one DEX only, e.g. `Meteora DLMM`.
+    SingleDex(String),
+}
+
+/// A comparison quote of a [`RouteExplanation`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteAlternative {
+    pub restriction: RouteRestriction,
+    /// `None` if Jupiter found no route under the restriction.
+    pub quote: Option<QuoteResponse>,
+    /// How much better the chosen route is, in bps of the alternative's amount: more
+    /// output for `ExactIn` quotes, less input for `ExactOut` ones. Negative if the
+    /// alternative is better, e.g. because the market moved between the quotes.
+    pub improvement_bps: Option<f64>,
+}
+
+/// How much a quote's route improves on restricted routes for the same swap, as returned
+/// by [`explain_route`](crate::JupiterClient::explain_route).
+///
+/// Its `Display` renders one line for the quote followed by one per alternative, e.g.
+///
+/// ```text
+/// Meteora DLMM → Whirlpool: 421.337 JUP, 12.5 bps better than direct routes
+///   direct routes: 420.81 JUP
+///   Meteora DLMM only: 419.2 JUP
+///   Whirlpool only: no route
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteExplanation {
+    pub quote: QuoteResponse,
+    /// Direct routes first, then one per DEX of the route, in route order.
+    pub alternatives: Vec<RouteAlternative>,
+}
+
+impl RouteExplanation {
+    /// Compares `quote` with the quotes fetched under each restriction, `None` where no
+    /// route was found.
+    pub fn new(
+        quote: QuoteResponse,
+        alternatives: Vec<(RouteRestriction, Option<QuoteResponse>)>,
+    ) -> Self {
+        let chosen = compared_amount(&quote);
+        let alternatives = alternatives
+            .into_iter()
+            .map(|(restriction, alternative)| {
+                let improvement_bps = alternative.as_ref().and_then(|alternative| {
+                    let other = compared_amount(alternative).as_u64() as f64;
+                    if other == 0.0 {
+                        return None;
+                    }
+                    let gain = match quote.swap_mode {
+                        SwapMode::ExactOut => other - chosen.as_u64() as f64,
+                        _ => chosen.as_u64() as f64 - other,
+                    };
+                    Some(gain / other * 10_000.0)
+                });
+                RouteAlternative {
+                    restriction,
+                    quote: alternative,
+                    improvement_bps,
+                }
+            })
+            .collect();
+        Self {
+            quote,
+            alternatives,
+        }
+    }
+
+    /// The distinct DEXes of the chosen route, in route order.
+    pub fn dexes(&self) -> Vec<&str> {
+        route_dexes(&self.quote)
+    }
+
+    /// Whether the chosen route goes through more than one pool.
+    pub fn is_multi_hop(&self) -> bool {
+        self.quote.route_plan.len() > 1
+    }
+
+    /// The alternative closest to the chosen route, if any had a route.
+    pub fn best_alternative(&self) -> Option<&RouteAlternative> {
+        self.alternatives
+            .iter()
+            .filter_map(|alternative| Some((alternative, alternative.improvement_bps?)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(alternative, _)| alternative)
+    }
+
+    /// How much better the chosen route is than the [best
+    /// alternative](Self::best_alternative), in bps; `None` if none had a route.
+    pub fn improvement_bps(&self) -> Option<f64> {
+        self.best_alternative()?.improvement_bps
+    }
+
+    /// The amount the chosen route gains over the best alternative, in the output token
+    /// for `ExactIn` quotes and the input token for `ExactOut` ones. Zero if the
+    /// alternative is as good or better.
+    pub fn improvement(&self) -> Option<Amount> {
+        let alternative = compared_amount(self.best_alternative()?.quote.as_ref()?);
+        let chosen = compared_amount(&self.quote);
+        Some(match self.quote.swap_mode {
+            SwapMode::ExactOut => alternative.saturating_sub(chosen),
+            _ => chosen.saturating_sub(alternative),
+        })
+    }
+}
+
+/// The amount a route competes on: output for `ExactIn`, input for `ExactOut`.
+pub(crate) fn compared_amount(quote: &QuoteResponse) -> Amount {
+    match quote.swap_mode {
+        SwapMode::ExactOut => quote.in_amount,
+        _ => quote.out_amount,
+    }
+}
+
+/// The mint of [`compared_amount`].
+pub(crate) fn compared_mint(quote: &QuoteResponse) -> &str {
+    quote
+        .swap_mode
+        .threshold_mint(&quote.input_mint, &quote.output_mint)
+}
+
+/// The distinct DEX labels of `quote`'s route, in route order.
+pub(crate) fn route_dexes(quote: &QuoteResponse) -> Vec<&str> {
+    let mut dexes: Vec<&str> = Vec::new();
+    for item in &quote.route_plan {
+        if !dexes.contains(&item.swap_info.label.as_str()) {
+            dexes.push(&item.swap_info.label);
+        }
+    }
+    dexes
+}
@@ -0,0 +1,67 @@
+//! Lenient deserializers shared by the response types.
+//!
+//! Jupiter is not always consistent about how it encodes a field: amounts that are
+//! usually strings occasionally arrive as numbers, and lists or maps sometimes come
+//! back as `null`. These helpers accept every encoding seen in the wild so that a
+//! small change upstream produces a value (or a clean error), never a panic.
+
+use serde::{Deserialize, Deserializer, de::Error};
+use std::{fmt::Display, str::FromStr};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrNumber {
+    String(String),
+    Unsigned(u64),
+    Signed(i64),
+    Float(f64),
+}
+
+impl StringOrNumber {
+    fn into_string(self) -> String {
+        match self {
+            StringOrNumber::String(s) => s,
+            StringOrNumber::Unsigned(n) => n.to_string(),
+            StringOrNumber::Signed(n) => n.to_string(),
+            StringOrNumber::Float(n) => n.to_string(),
+        }
+    }
+}
+
+/// Deserializes a JSON string or number into a `String`.
+pub fn string_or_number<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    StringOrNumber::deserialize(deserializer).map(StringOrNumber::into_string)
+}
+
+/// Deserializes an optional JSON string or number into an `Option<String>`.
+pub fn option_string_or_number<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<StringOrNumber>::deserialize(deserializer).map(|v| v.map(StringOrNumber::into_string))
+}
+
+/// Deserializes a JSON number or a numeric string into any `FromStr` number type.
+pub fn number_or_string<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let raw = StringOrNumber::deserialize(deserializer)?.into_string();
+    raw.trim()
+        .parse::<T>()
+        .map_err(|e| D::Error::custom(format!("invalid number {raw:?}: {e}")))
+}
+
+/// Deserializes `null` as `T::default()`.
+pub fn null_to_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Option::unwrap_or_default)
+}
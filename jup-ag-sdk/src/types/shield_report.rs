@@ -0,0 +1,173 @@
+use std::{cmp::Reverse, collections::BTreeMap};
+
+use serde::{Deserialize, Serialize};
+
+use super::{NATIVE_SOL, Shield, WSOL_MINT, WalletBalance, Warning, execution_report::unix_millis};
+use crate::{error::JupiterClientError, runtime::SystemTime};
+
+/// How serious a [`Warning`] is, from least to most.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RiskLevel {
+    /// No warnings.
+    None,
+    Info,
+    Warning,
+    Critical,
+}
+
+impl RiskLevel {
+    /// The level of a warning's `severity`. Severities Shield doesn't document count as
+    /// [`Warning`](Self::Warning), so they still get flagged.
+    pub fn from_severity(severity: &str) -> Self {
+        match severity.to_ascii_lowercase().as_str() {
+            "info" => Self::Info,
+            "critical" => Self::Critical,
+            _ => Self::Warning,
+        }
+    }
+}
+
+/// A held token and the Shield warnings about it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MintRisk {
+    /// Token mint address, or `"SOL"` for native and wrapped SOL, screened as wSOL.
+    pub mint: String,
+    pub ui_amount: f64,
+    /// Value in USD, `None` if the token has no price.
+    pub usd_value: Option<f64>,
+    /// The most serious level of `warnings`.
+    pub level: RiskLevel,
+    pub warnings: Vec<Warning>,
+}
+
+/// Totals of a [`PortfolioRiskReport`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RiskSummary {
+    pub mints_screened: usize,
+    /// Mints with a warning of level [`Warning`](RiskLevel::Warning) or above.
+    pub flagged_mints: usize,
+    pub critical_mints: usize,
+    /// The most serious level across the portfolio.
+    pub level: RiskLevel,
+    /// Value of the priced holdings, in USD.
+    pub total_usd_value: f64,
+    /// Value of the priced flagged holdings, in USD.
+    pub flagged_usd_value: f64,
+    /// How many mints got each warning type.
+    pub warning_types: BTreeMap<String, usize>,
+}
+
+/// The Shield warnings for every token a wallet holds, as returned by
+/// [`screen_wallet`](crate::JupiterClient::screen_wallet).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioRiskReport {
+    pub wallet: String,
+    pub screened_at_ms: u64,
+    /// Most serious first, then by value, highest first.
+    pub mints: Vec<MintRisk>,
+    pub summary: RiskSummary,
+}
+
+impl PortfolioRiskReport {
+    /// Matches `balances`, as returned by
+    /// [`get_wallet_balances`](crate::JupiterClient::get_wallet_balances), with the
+    /// warnings in `shield`.
+    pub fn new(
+        wallet: &str,
+        balances: Vec<WalletBalance>,
+        mut shield: Shield,
+        screened_at: SystemTime,
+    ) -> Self {
+        let mut mints: Vec<MintRisk> = balances
+            .into_iter()
+            .map(|balance| {
+                let warnings = shield
+                    .warnings
+                    .remove(shield_mint(&balance.mint))
+                    .unwrap_or_default();
+                let level = warnings
+                    .iter()
+                    .map(|warning| RiskLevel::from_severity(&warning.severity))
+                    .max()
+                    .unwrap_or(RiskLevel::None);
+                MintRisk {
+                    mint: balance.mint,
+                    ui_amount: balance.ui_amount,
+                    usd_value: balance.usd_value,
+                    level,
+                    warnings,
+                }
+            })
+            .collect();
+        // stable, so mints of the same level keep their order by value
+        mints.sort_by_key(|mint| Reverse(mint.level));
+
+        let mut summary = RiskSummary {
+            mints_screened: mints.len(),
+            flagged_mints: 0,
+            critical_mints: 0,
+            level: RiskLevel::None,
+            total_usd_value: 0.0,
+            flagged_usd_value: 0.0,
+            warning_types: BTreeMap::new(),
+        };
+        for mint in &mints {
+            let usd_value = mint.usd_value.unwrap_or(0.0);
+            summary.total_usd_value += usd_value;
+            summary.level = summary.level.max(mint.level);
+            if mint.level >= RiskLevel::Warning {
+                summary.flagged_mints += 1;
+                summary.flagged_usd_value += usd_value;
+            }
+            if mint.level == RiskLevel::Critical {
+                summary.critical_mints += 1;
+            }
+            let mut types: Vec<&str> = mint
+                .warnings
+                .iter()
+                .map(|warning| warning.warning_type.as_str())
+                .collect();
+            types.sort();
+            types.dedup();
+            for warning_type in types {
+                *summary
+                    .warning_types
+                    .entry(warning_type.to_string())
+                    .or_default() += 1;
+            }
+        }
+
+        Self {
+            wallet: wallet.to_string(),
+            screened_at_ms: unix_millis(screened_at),
+            mints,
+            summary,
+        }
+    }
+
+    /// Whether no held token has a warning above [`Info`](RiskLevel::Info).
+    pub fn is_clean(&self) -> bool {
+        self.summary.level < RiskLevel::Warning
+    }
+
+    /// The held tokens with a warning of level [`Warning`](RiskLevel::Warning) or above.
+    pub fn flagged(&self) -> impl Iterator<Item = &MintRisk> {
+        self.mints
+            .iter()
+            .filter(|mint| mint.level >= RiskLevel::Warning)
+    }
+
+    /// The report as a JSON object.
+    pub fn to_json(&self) -> Result<String, JupiterClientError> {
+        serde_json::to_string(self).map_err(|e| JupiterClientError::deserialization(e.to_string()))
+    }
+}
+
+/// The mint Shield knows `mint` by, with native SOL mapped to [`WSOL_MINT`].
+pub(crate) fn shield_mint(mint: &str) -> &str {
+    if mint == NATIVE_SOL { WSOL_MINT } else { mint }
+}
@@ -0,0 +1,73 @@
+use serde::Serialize;
+
+use super::{Validate, validate_pubkey};
+use crate::error::JupiterClientError;
+
+/// The Jupiter Aggregator v6 program, which executes Swap and Ultra API routes.
+pub const JUPITER_PROGRAM_ID: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QDBMP56P4";
+
+/// Most signatures `getSignaturesForAddress` returns per call.
+pub const MAX_HISTORY_LIMIT: usize = 1000;
+
+/// Which of a wallet's transactions to scan for Jupiter swaps, newest first.
+///
+/// # Example
+/// ```
+/// let request = SwapHistoryRequest::new("YourPubKey...")
+///     .limit(200)
+///     .before("5ig...");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapHistoryRequest {
+    /// The wallet whose swaps are read.
+    #[serde(skip)]
+    pub wallet: String,
+    /// Transactions to scan, swaps or not. Default 100, at most [`MAX_HISTORY_LIMIT`].
+    pub limit: usize,
+    /// Start scanning before this transaction signature, to page back in time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// Stop scanning at this transaction signature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<String>,
+}
+
+impl SwapHistoryRequest {
+    pub fn new(wallet: &str) -> Self {
+        Self {
+            wallet: wallet.to_string(),
+            limit: 100,
+            before: None,
+            until: None,
+        }
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    pub fn before(mut self, signature: &str) -> Self {
+        self.before = Some(signature.to_string());
+        self
+    }
+
+    pub fn until(mut self, signature: &str) -> Self {
+        self.until = Some(signature.to_string());
+        self
+    }
+}
+
+impl Validate for SwapHistoryRequest {
+    fn validate(&self) -> Result<(), JupiterClientError> {
+        validate_pubkey("wallet", &self.wallet)?;
+        if self.limit == 0 || self.limit > MAX_HISTORY_LIMIT {
+            return Err(JupiterClientError::ValidationError(format!(
+                "limit must be between 1 and {MAX_HISTORY_LIMIT}, got {}",
+                self.limit
+            )));
+        }
+        Ok(())
+    }
+}
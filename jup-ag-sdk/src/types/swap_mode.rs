@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+/// Which side of a swap is fixed, shared by Swap API quotes and Ultra orders.
+///
+/// - `ExactIn`: the input amount is fixed; `other_amount_threshold` is the minimum output.
+/// - `ExactOut`: the output amount is fixed; `other_amount_threshold` is the maximum input.
+///
+/// [`Unknown`](Self::Unknown) modes are interpreted like `ExactIn`, the API default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+#[non_exhaustive]
+pub enum SwapMode {
+    #[default]
+    ExactIn,
+    ExactOut,
+    /// A value not known to this version of the SDK, only read from responses; requests
+    /// with it fail validation.
+    #[serde(other)]
+    Unknown,
+}
+
+/// The former name of [`SwapMode`].
+#[deprecated(note = "renamed to `SwapMode`")]
+pub type QuoteGetSwapModeEnum = SwapMode;
+
+impl SwapMode {
+    /// Whether the output amount is the fixed one.
+    pub fn is_exact_out(self) -> bool {
+        self == SwapMode::ExactOut
+    }
+
+    /// Whether the input amount is the fixed one; also for `Unknown` modes.
+    pub fn is_exact_in(self) -> bool {
+        !self.is_exact_out()
+    }
+
+    /// The mint a request's `amount` is in: `input_mint` for `ExactIn`, `output_mint`
+    /// for `ExactOut`.
+    pub fn amount_mint<'a>(self, input_mint: &'a str, output_mint: &'a str) -> &'a str {
+        if self.is_exact_out() {
+            output_mint
+        } else {
+            input_mint
+        }
+    }
+
+    /// The mint a quote's `other_amount_threshold` is in, the side that isn't fixed.
+    pub fn threshold_mint<'a>(self, input_mint: &'a str, output_mint: &'a str) -> &'a str {
+        self.amount_mint(output_mint, input_mint)
+    }
+}
@@ -1,13 +1,17 @@
 use serde::{Deserialize, Serialize};
 
-use super::QuoteResponse;
+use super::{QuoteRequest, QuoteResponse, Validate, validate_optional_pubkey, validate_pubkey};
+use crate::error::{JupiterClientError, QuoteErrorKind};
+
+/// Largest serialized transaction Solana accepts, in bytes (the packet data size).
+pub const MAX_TRANSACTION_SIZE: usize = 1232;
 
 /// SwapRequest is a struct that represents the request body for the swap transaction.
 ///
 /// user SwapRequest::new() and the fluent setters to configure parameters.
 ///
 /// [Official API docs](https://dev.jup.ag/docs/api/swap-api/swap)
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SwapRequest {
     /// Rquired. The public key of the user initiating the swap.
@@ -26,6 +30,13 @@ pub struct SwapRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub use_shared_accounts: Option<bool>,
 
+    /// Swap the amount held by the input token account after the preceding instructions,
+    /// instead of the quoted `inAmount`.
+    /// Useful when an instruction before the swap (e.g. a withdrawal) determines how much is swapped.
+    /// Default: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_token_ledger: Option<bool>,
+
     /// Associated Token account (must be input/output mint) to collect fees.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fee_account: Option<String>,
@@ -78,22 +89,23 @@ pub struct SwapRequest {
     pub quote_response: QuoteResponse,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PrioritizationFeeLamports {
     pub jito_tip_lamports: Option<u64>,
     pub priority_level_with_max_lamports: PriorityLevelWithMaxLamports,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PriorityLevelWithMaxLamports {
     pub max_lamports: u32,
     pub priority_level: PriorityLevel,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub enum PriorityLevel {
     Medium,
     High,
@@ -105,7 +117,178 @@ pub enum PriorityLevel {
 pub struct SwapResponse {
     pub swap_transaction: String,
     pub last_valid_block_height: u64,
+    /// Total prioritization fee: compute unit price times limit, or the Jito tip.
     pub prioritization_fee_lamports: u64,
+    /// Compute unit limit set in the transaction.
+    #[serde(default)]
+    pub compute_unit_limit: Option<u32>,
+    /// How the prioritization fee is paid.
+    #[serde(default)]
+    pub prioritization_type: Option<PrioritizationType>,
+    /// The priority level the fee was estimated at, taken from the request since Jupiter
+    /// doesn't echo it. Set by [`JupiterClient::get_swap_transaction`](crate::JupiterClient::get_swap_transaction),
+    /// `None` for a Jito tip.
+    #[serde(skip)]
+    pub priority_level: Option<PriorityLevel>,
+    /// Why simulating the swap failed, when it was simulated
+    /// (e.g. with `dynamic_compute_unit_limit`).
+    #[serde(default)]
+    pub simulation_error: Option<serde_json::Value>,
+}
+
+/// How the prioritization fee of a swap is paid, as returned in [`SwapResponse`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrioritizationType {
+    /// Set when the fee is a compute unit price.
+    #[serde(default)]
+    pub compute_budget: Option<ComputeBudgetPrioritization>,
+    /// Set when the fee is a Jito tip.
+    #[serde(default)]
+    pub jito: Option<JitoPrioritization>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComputeBudgetPrioritization {
+    /// Compute unit price set in the transaction, capped by the requested `max_lamports`.
+    pub micro_lamports: u64,
+    /// Compute unit price Jupiter estimated for the priority level, before the cap.
+    #[serde(default)]
+    pub estimated_micro_lamports: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JitoPrioritization {
+    /// Tip paid to the Jito validator.
+    pub lamports: u64,
+}
+
+/// The prioritization fee of a swap broken down, as returned by
+/// [`SwapResponse::prioritization`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrioritizationReport {
+    pub total_lamports: u64,
+    pub compute_unit_limit: Option<u32>,
+    pub compute_unit_price_micro_lamports: Option<u64>,
+    pub estimated_compute_unit_price_micro_lamports: Option<u64>,
+    pub jito_tip_lamports: Option<u64>,
+    pub priority_level: Option<PriorityLevel>,
+}
+
+impl PrioritizationReport {
+    /// Whether the compute unit price was capped below Jupiter's estimate.
+    pub fn is_capped(&self) -> bool {
+        matches!(
+            (self.compute_unit_price_micro_lamports, self.estimated_compute_unit_price_micro_lamports),
+            (Some(price), Some(estimate)) if price < estimate
+        )
+    }
+}
+
+impl SwapResponse {
+    /// The prioritization fee broken down into its parts.
+    pub fn prioritization(&self) -> PrioritizationReport {
+        let compute_budget = self
+            .prioritization_type
+            .as_ref()
+            .and_then(|kind| kind.compute_budget.as_ref());
+        PrioritizationReport {
+            total_lamports: self.prioritization_fee_lamports,
+            compute_unit_limit: self.compute_unit_limit,
+            compute_unit_price_micro_lamports: compute_budget.map(|budget| budget.micro_lamports),
+            estimated_compute_unit_price_micro_lamports: compute_budget
+                .and_then(|budget| budget.estimated_micro_lamports),
+            jito_tip_lamports: self
+                .prioritization_type
+                .as_ref()
+                .and_then(|kind| kind.jito.as_ref())
+                .map(|jito| jito.lamports),
+            priority_level: self.priority_level,
+        }
+    }
+
+    /// Size of the serialized transaction in bytes, signature slots included.
+    pub fn transaction_size(&self) -> usize {
+        let encoded = self.swap_transaction.trim_end_matches('=');
+        encoded.len() * 3 / 4
+    }
+
+    /// A [`SlippageExceeded`](JupiterClientError::SlippageExceeded) error with the amounts
+    /// of `quote`, if simulating the swap failed because the price moved past its
+    /// slippage tolerance.
+    pub fn slippage_error(&self, quote: &QuoteResponse) -> Option<JupiterClientError> {
+        let error = self.simulation_error.as_ref()?.to_string();
+        (QuoteErrorKind::from_message(&error) == Some(QuoteErrorKind::SlippageToleranceExceeded))
+            .then(|| JupiterClientError::slippage_exceeded(quote, None, None))
+    }
+
+    /// Returns `true` if the transaction is over [`MAX_TRANSACTION_SIZE`] or its
+    /// simulation failed because it locks too many accounts.
+    pub fn exceeds_transaction_limits(&self) -> bool {
+        self.transaction_size() > MAX_TRANSACTION_SIZE
+            || self.simulation_error.as_ref().is_some_and(|error| {
+                QuoteErrorKind::from_message(&error.to_string())
+                    == Some(QuoteErrorKind::TransactionTooLarge)
+            })
+    }
+}
+
+/// The routing restriction [`JupiterClient::get_fitted_swap_transaction`] applied
+/// to make the swap transaction fit.
+///
+/// [`JupiterClient::get_fitted_swap_transaction`]: crate::JupiterClient::get_fitted_swap_transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountConstraint {
+    /// The quote request was used as given.
+    None,
+    /// The quote was requested again with a lower `maxAccounts`.
+    MaxAccounts(u8),
+    /// The quote was requested again with `onlyDirectRoutes`.
+    OnlyDirectRoutes,
+}
+
+impl AccountConstraint {
+    /// `maxAccounts` values tried, in order, before falling back to direct routes.
+    const MAX_ACCOUNTS_STEPS: [u8; 3] = [48, 32, 20];
+
+    /// Constraints to try after `request` produced a transaction that is too large,
+    /// from least to most restrictive.
+    pub(crate) fn fallbacks(request: &QuoteRequest) -> Vec<Self> {
+        // Jupiter uses up to 64 accounts when maxAccounts isn't set
+        let current = request.max_accounts.unwrap_or(64);
+        let mut fallbacks: Vec<_> = Self::MAX_ACCOUNTS_STEPS
+            .into_iter()
+            .filter(|&max| max < current)
+            .map(AccountConstraint::MaxAccounts)
+            .collect();
+        if request.only_direct_routes != Some(true) {
+            fallbacks.push(AccountConstraint::OnlyDirectRoutes);
+        }
+        fallbacks
+    }
+
+    /// Applies the constraint to `request`.
+    pub(crate) fn apply(self, request: QuoteRequest) -> QuoteRequest {
+        match self {
+            AccountConstraint::None => request,
+            AccountConstraint::MaxAccounts(max) => request.max_accounts(max),
+            AccountConstraint::OnlyDirectRoutes => request.only_direct_routes(true),
+        }
+    }
+}
+
+/// A swap transaction that fits Solana's limits, returned by
+/// [`JupiterClient::get_fitted_swap_transaction`](crate::JupiterClient::get_fitted_swap_transaction).
+#[derive(Debug)]
+pub struct FittedSwap {
+    pub response: SwapResponse,
+    /// The restriction that was needed to make the transaction fit.
+    pub constraint: AccountConstraint,
+    /// Number of quote and build rounds, 1 if the first transaction fit.
+    pub attempts: u32,
 }
 
 impl SwapRequest {
@@ -127,6 +310,7 @@ impl SwapRequest {
             user_public_key: input_wallet.to_string(),
             wrap_and_unwrap_sol: None,
             use_shared_accounts: None,
+            use_token_ledger: None,
             fee_account: None,
             tracking_account: None,
             prioritization_fee_lamports: None,
@@ -158,6 +342,15 @@ impl SwapRequest {
         self
     }
 
+    /// Sets whether to swap the input token account's balance increase instead of the quoted amount.
+    ///
+    /// `/swap-instructions` then returns a `token_ledger_instruction`, which must run before
+    /// the instructions that fund the input account; see [`SwapInstructions::with_token_ledger`].
+    pub fn use_token_ledger(mut self, use_token_ledger: bool) -> Self {
+        self.use_token_ledger = Some(use_token_ledger);
+        self
+    }
+
     /// Set An token account that will be used to collect fees
     ///
     /// The mint of the token account can only be either the input or output mint of the swap
@@ -166,6 +359,33 @@ impl SwapRequest {
         self
     }
 
+    /// Sets `fee_account` to the referral token account of `referral_account` for the
+    /// mint the quote's platform fee is taken in (see [`crate::referral::fee_mint`]).
+    ///
+    /// Does nothing if the quote has no platform fee. The account must exist before the
+    /// swap; check with [`JupiterClient::referral_fee_account`](crate::JupiterClient::referral_fee_account).
+    ///
+    /// Requires the `solana` feature.
+    #[cfg(feature = "solana")]
+    pub fn referral_fee_account(
+        mut self,
+        referral_account: &str,
+    ) -> Result<Self, JupiterClientError> {
+        let has_fee = self
+            .quote_response
+            .platform_fee
+            .as_ref()
+            .is_some_and(|fee| fee.fee_bps.get() > 0);
+        if has_fee {
+            let mint = crate::referral::fee_mint(&self.quote_response);
+            self.fee_account = Some(crate::referral::referral_token_account(
+                referral_account,
+                mint,
+            )?);
+        }
+        Ok(self)
+    }
+
     /// Specify any public key that belongs to you to track the transactions
     ///
     /// Useful for integrators to get all the swap transactions from this public key. Query the data using a block explorer like Solscan/SolanaFM or query like Dune/Flipside
@@ -204,6 +424,9 @@ impl SwapRequest {
     }
 
     /// Forces the transaction to be built as a legacy (non-versioned) transaction.
+    ///
+    /// With the `solana` feature, `SwapResponse::decode_transaction` returns it as a legacy
+    /// `Transaction` for signers that cannot sign v0 messages.
     pub fn as_legacy_transaction(mut self, legacy: bool) -> Self {
         self.as_legacy_transaction = Some(legacy);
         self
@@ -275,6 +498,8 @@ pub struct Instruction {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SwapInstructions {
+    /// Records the input token account balance; only returned with `useTokenLedger`.
+    pub token_ledger_instruction: Option<Instruction>,
     pub other_instructions: Option<Vec<Instruction>>,
     pub compute_budget_instructions: Option<Vec<Instruction>>,
     pub setup_instructions: Vec<Instruction>,
@@ -282,3 +507,66 @@ pub struct SwapInstructions {
     pub cleanup_instruction: Option<Instruction>,
     pub address_lookup_table_addresses: Vec<String>,
 }
+
+impl SwapInstructions {
+    /// The token ledger instruction and the swap instruction it pairs with, if the
+    /// instructions were requested with [`SwapRequest::use_token_ledger`].
+    ///
+    /// Instructions that fund the input token account go between the two.
+    pub fn token_ledger_pair(&self) -> Option<(&Instruction, &Instruction)> {
+        self.token_ledger_instruction
+            .as_ref()
+            .map(|ledger| (ledger, &self.swap_instruction))
+    }
+
+    /// Orders all instructions for a token ledger swap, placing `amount_instructions`
+    /// (the ones that determine the input amount) between the token ledger instruction
+    /// and the swap:
+    ///
+    /// compute budget, setup, token ledger, `amount_instructions`, swap, cleanup, other.
+    ///
+    /// Returns a `ValidationError` if the instructions were not requested with
+    /// [`SwapRequest::use_token_ledger`].
+    ///
+    /// # Example
+    /// ```
+    /// let payload = SwapRequest::new("YourPubKey...", quote).use_token_ledger(true);
+    /// let instructions = api
+    ///     .get_swap_instructions(&payload)
+    ///     .await?
+    ///     .with_token_ledger(vec![withdraw_instruction])?;
+    /// ```
+    pub fn with_token_ledger(
+        self,
+        amount_instructions: Vec<Instruction>,
+    ) -> Result<Vec<Instruction>, JupiterClientError> {
+        let token_ledger = self.token_ledger_instruction.ok_or_else(|| {
+            JupiterClientError::ValidationError(
+                "swap instructions have no token ledger instruction, request them with use_token_ledger(true)"
+                    .to_string(),
+            )
+        })?;
+
+        let mut instructions = Vec::new();
+        instructions.extend(self.compute_budget_instructions.unwrap_or_default());
+        instructions.extend(self.setup_instructions);
+        instructions.push(token_ledger);
+        instructions.extend(amount_instructions);
+        instructions.push(self.swap_instruction);
+        instructions.extend(self.cleanup_instruction);
+        instructions.extend(self.other_instructions.unwrap_or_default());
+        Ok(instructions)
+    }
+}
+
+impl Validate for SwapRequest {
+    fn validate(&self) -> Result<(), JupiterClientError> {
+        validate_pubkey("user_public_key", &self.user_public_key)?;
+        validate_optional_pubkey("fee_account", self.fee_account.as_deref())?;
+        validate_optional_pubkey("tracking_account", self.tracking_account.as_deref())?;
+        validate_optional_pubkey(
+            "destination_token_account",
+            self.destination_token_account.as_deref(),
+        )
+    }
+}
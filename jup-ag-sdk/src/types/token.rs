@@ -1,5 +1,16 @@
+use super::{
+    Validate, null_to_default, number_or_string, string_or_number, validate_optional_pubkey,
+    validate_pubkey,
+};
+use crate::{
+    error::JupiterClientError,
+    runtime::{SystemTime, UNIX_EPOCH},
+};
 use serde::{Deserialize, Serialize, Serializer};
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -48,6 +59,7 @@ pub struct TokenPrice {
     #[serde(rename = "type")]
     pub data_type: String,
 
+    #[serde(deserialize_with = "string_or_number")]
     pub price: String,
 
     #[serde(default)]
@@ -58,9 +70,35 @@ pub struct TokenPrice {
 #[serde(rename_all = "camelCase")]
 pub struct TokenPriceResponse {
     pub data: HashMap<String, TokenPrice>,
+    /// Seconds, see [`server_time`](Self::server_time).
     pub time_taken: f64,
 }
 
+impl TokenPriceResponse {
+    /// How long Jupiter took to price the tokens.
+    pub fn server_time(&self) -> Duration {
+        Duration::try_from_secs_f64(self.time_taken).unwrap_or_default()
+    }
+}
+
+/// A token price from the Price API v3, see
+/// [`get_prices_v3`](crate::JupiterClient::get_prices_v3).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenPriceV3 {
+    pub usd_price: f64,
+    /// Slot of the block the price was computed at.
+    #[serde(default)]
+    pub block_id: Option<u64>,
+    pub decimals: u8,
+    /// Price change over the last 24 hours, in percent.
+    #[serde(default)]
+    pub price_change_24h: Option<f64>,
+}
+
+/// Prices from the Price API v3 by mint. Mints without a reliable price are left out.
+pub type TokenPricesV3 = HashMap<String, TokenPriceV3>;
+
 pub fn to_comma_string<S>(vec: &[String], serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -73,9 +111,11 @@ pub struct TokenInfoResponse {
     pub address: String,
     pub name: String,
     pub symbol: String,
+    #[serde(deserialize_with = "number_or_string")]
     pub decimals: i32,
     #[serde(rename = "logoURI")]
     pub logo_uri: Option<String>,
+    #[serde(default, deserialize_with = "null_to_default")]
     pub tags: Vec<Option<String>>,
     pub daily_volume: Option<f64>,
     pub created_at: String,
@@ -83,19 +123,193 @@ pub struct TokenInfoResponse {
     pub mint_authority: Option<String>,
     pub permanent_delegate: Option<String>,
     pub minted_at: Option<String>,
+    #[serde(default, deserialize_with = "null_to_default")]
     pub extensions: HashMap<String, String>,
 }
 
+/// A tag of the Tokens API, selecting a curated token list with
+/// [`get_tokens_by_tag`](crate::JupiterClient::get_tokens_by_tag).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TokenTag {
+    /// Tokens verified by Jupiter, the usual default for token pickers.
+    Verified,
+    /// Liquid staking tokens.
+    Lst,
+    /// Tokens of the Token-2022 program.
+    Token2022,
+    /// Tokens launched on pump.fun.
+    Pump,
+    /// Tokens launched on Moonshot.
+    Moonshot,
+    /// A tag not known to this version of the SDK.
+    Other(String),
+}
+
+impl TokenTag {
+    /// The tag as written in the API, e.g. `token-2022`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            TokenTag::Verified => "verified",
+            TokenTag::Lst => "lst",
+            TokenTag::Token2022 => "token-2022",
+            TokenTag::Pump => "pump",
+            TokenTag::Moonshot => "moonshot",
+            TokenTag::Other(tag) => tag,
+        }
+    }
+}
+
+impl From<&str> for TokenTag {
+    fn from(tag: &str) -> Self {
+        match tag {
+            "verified" => TokenTag::Verified,
+            "lst" => TokenTag::Lst,
+            "token-2022" => TokenTag::Token2022,
+            "pump" => TokenTag::Pump,
+            "moonshot" => TokenTag::Moonshot,
+            other => TokenTag::Other(other.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for TokenTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Validate for TokenTag {
+    fn validate(&self) -> Result<(), JupiterClientError> {
+        let tag = self.as_str();
+        // the tag is a path segment, so nothing that would change the path
+        let valid = tag
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if tag.is_empty() || !valid {
+            return Err(JupiterClientError::ValidationError(format!(
+                "invalid token tag: {tag:?}"
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl TokenInfoResponse {
+    /// Whether the token carries `tag`.
+    pub fn has_tag(&self, tag: &TokenTag) -> bool {
+        self.tags.iter().flatten().any(|t| t == tag.as_str())
+    }
+}
+
+/// The set of mints Jupiter can route, from
+/// [`get_tradable_mint_set`](crate::JupiterClient::get_tradable_mint_set).
+///
+/// Fetch it once and refresh it now and then, as it is a large download, then check user
+/// input against it before quoting to fail fast instead of on a certain "no route".
+///
+/// # Example
+/// ```
+/// let tradable = client.get_tradable_mint_set().await?;
+/// tradable.check_pair(&input_mint, &output_mint)?;
+/// let quote = client.get_quote(&QuoteRequest::new(&input_mint, &output_mint, amount)).await?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TradableMints {
+    mints: HashSet<String>,
+}
+
+impl TradableMints {
+    pub fn contains(&self, mint: &str) -> bool {
+        self.mints.contains(mint)
+    }
+
+    pub fn len(&self) -> usize {
+        self.mints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mints.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.mints.iter().map(String::as_str)
+    }
+
+    /// Fails with a `ValidationError` naming the first of `input_mint` and `output_mint`
+    /// that Jupiter can't route.
+    pub fn check_pair(
+        &self,
+        input_mint: &str,
+        output_mint: &str,
+    ) -> Result<(), JupiterClientError> {
+        for (field, mint) in [("input_mint", input_mint), ("output_mint", output_mint)] {
+            if !self.contains(mint) {
+                return Err(JupiterClientError::ValidationError(format!(
+                    "{field} {mint} is not tradable on Jupiter"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromIterator<String> for TradableMints {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+        Self {
+            mints: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// A token from [`get_new_tokens`](crate::JupiterClient::get_new_tokens), newest first.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct NewTokens {
     pub mint: String,
+    /// Unix seconds the mint was created at, see [`created_at_time`](Self::created_at_time).
     pub created_at: String,
+    /// Unix seconds the metadata was last updated at.
     pub metadata_updated_at: u64,
     pub name: String,
     pub symbol: String,
     pub decimals: u32,
     pub logo_uri: Option<String>,
+    /// Markets the token trades on, empty until one is indexed.
+    #[serde(default, deserialize_with = "null_to_default")]
     pub known_markets: Vec<String>,
     pub mint_authority: Option<String>,
     pub freeze_authority: Option<String>,
 }
+
+impl NewTokens {
+    /// When the mint was created, `None` if `created_at` isn't a Unix timestamp.
+    pub fn created_at_time(&self) -> Option<SystemTime> {
+        let seconds = self.created_at.parse().ok()?;
+        UNIX_EPOCH.checked_add(Duration::from_secs(seconds))
+    }
+
+    /// When the token's metadata was last updated.
+    pub fn metadata_updated_time(&self) -> Option<SystemTime> {
+        UNIX_EPOCH.checked_add(Duration::from_secs(self.metadata_updated_at))
+    }
+
+    /// How long ago the mint was created, zero if in the future.
+    pub fn age(&self) -> Option<Duration> {
+        let created = self.created_at_time()?;
+        Some(
+            SystemTime::now()
+                .duration_since(created)
+                .unwrap_or_default(),
+        )
+    }
+}
+
+impl Validate for TokenPriceRequest {
+    fn validate(&self) -> Result<(), JupiterClientError> {
+        self.token_mints
+            .iter()
+            .try_for_each(|mint| validate_pubkey("token_mints", mint))?;
+        validate_optional_pubkey("vs_token", self.vs_token.as_deref())
+    }
+}
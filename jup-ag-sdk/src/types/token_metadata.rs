@@ -0,0 +1,136 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use super::TokenInfoResponse;
+use crate::error::JupiterClientError;
+
+/// Capacity of the token metadata cache of a client built without
+/// [`token_metadata_cache`](crate::JupiterClientBuilder::token_metadata_cache).
+pub const DEFAULT_TOKEN_METADATA_CAPACITY: usize = 1024;
+
+/// The subset of a token's metadata needed to convert and display its amounts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenMetadata {
+    pub mint: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub logo_uri: Option<String>,
+}
+
+impl TryFrom<&TokenInfoResponse> for TokenMetadata {
+    type Error = JupiterClientError;
+
+    fn try_from(info: &TokenInfoResponse) -> Result<Self, Self::Error> {
+        let decimals = u8::try_from(info.decimals).map_err(|_| {
+            JupiterClientError::deserialization(format!(
+                "token {} has invalid decimals {}",
+                info.address, info.decimals
+            ))
+        })?;
+
+        Ok(Self {
+            mint: info.address.clone(),
+            symbol: info.symbol.clone(),
+            decimals,
+            logo_uri: info.logo_uri.clone(),
+        })
+    }
+}
+
+/// An in-memory, least recently used cache of [`TokenMetadata`] keyed by mint.
+///
+/// Every [`JupiterClient`](crate::JupiterClient) owns one, filled on demand by
+/// [`token_metadata`](crate::JupiterClient::token_metadata) and read by the
+/// decimal-aware amount helpers and [`QuoteResponse::display_with`](super::QuoteResponse::display_with).
+/// When full, inserting a new mint evicts the one used least recently.
+#[derive(Debug)]
+pub struct TokenMetadataCache {
+    capacity: usize,
+    clock: AtomicU64,
+    entries: Mutex<HashMap<String, (u64, TokenMetadata)>>,
+}
+
+impl TokenMetadataCache {
+    /// Creates a cache holding up to `capacity` tokens. A capacity of `0` disables caching.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            clock: AtomicU64::new(0),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Maximum number of tokens kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of tokens currently cached.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Returns `true` if nothing is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the cached metadata for `mint`, marking it as recently used.
+    pub fn get(&self, mint: &str) -> Option<TokenMetadata> {
+        let mut entries = self.entries.lock().unwrap();
+        let (used_at, metadata) = entries.get_mut(mint)?;
+        *used_at = self.tick();
+        Some(metadata.clone())
+    }
+
+    /// Caches `metadata`, evicting the least recently used token if the cache is full.
+    pub fn insert(&self, metadata: TokenMetadata) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= self.capacity && !entries.contains_key(&metadata.mint) {
+            let least_recent = entries
+                .iter()
+                .min_by_key(|(_, (used_at, _))| *used_at)
+                .map(|(mint, _)| mint.clone());
+            if let Some(least_recent) = least_recent {
+                entries.remove(&least_recent);
+            }
+        }
+        entries.insert(metadata.mint.clone(), (self.tick(), metadata));
+    }
+
+    /// The mint of the only cached token with `symbol`, ignoring case. `None` if no
+    /// cached token or more than one has it.
+    pub fn mint_for_symbol(&self, symbol: &str) -> Option<String> {
+        let entries = self.entries.lock().unwrap();
+        let mut matches = entries
+            .values()
+            .filter(|(_, metadata)| metadata.symbol.eq_ignore_ascii_case(symbol));
+        match (matches.next(), matches.next()) {
+            (Some((_, metadata)), None) => Some(metadata.mint.clone()),
+            _ => None,
+        }
+    }
+
+    /// Removes every cached token.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+impl Default for TokenMetadataCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_TOKEN_METADATA_CAPACITY)
+    }
+}
@@ -1,5 +1,11 @@
-use crate::types::to_comma_string;
+use crate::error::JupiterClientError;
+use crate::types::{
+    Amount, Bps, RedactedTransaction, Validate, null_to_default, number_or_string,
+    option_bps_as_string, to_comma_string, validate_known, validate_optional_bps,
+    validate_optional_pubkey, validate_pubkey,
+};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// Request for a base64-encoded unsigned trigger order creation transaction
 ///
@@ -45,22 +51,28 @@ pub struct CreateTriggerOrder {
 #[serde(rename_all = "camelCase")]
 pub struct Params {
     /// Amount of input mint to swap
-    pub making_amount: String,
+    pub making_amount: Amount,
 
     /// Amount of output mint to receive
-    pub taking_amount: String,
+    pub taking_amount: Amount,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expired_at: Option<String>,
 
     /// Amount of slippage the order can be executed with
     /// Default value: 0
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub slippage_bps: Option<String>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "option_bps_as_string"
+    )]
+    pub slippage_bps: Option<Bps>,
 
     /// Requires the feeAccount parameter, the amount of fees in bps that will be sent to the fee account
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub fee_bps: Option<String>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "option_bps_as_string"
+    )]
+    pub fee_bps: Option<Bps>,
 }
 
 impl CreateTriggerOrder {
@@ -112,14 +124,18 @@ impl CreateTriggerOrder {
 
     /// Sets the slippage in basis points
     /// Default value: 0
-    pub fn slippage_bps(mut self, slippage: &str) -> Self {
-        self.params.slippage_bps = Some(slippage.to_string());
+    ///
+    /// Values above 10,000 (100%) fail [`validate`](Validate::validate).
+    pub fn slippage_bps(mut self, slippage: u16) -> Self {
+        self.params.slippage_bps = Some(Bps::new_unchecked(slippage));
         self
     }
 
     /// Sets the fee in basis points (requires fee_account to be set)
-    pub fn fee_bps(mut self, fee: &str) -> Self {
-        self.params.fee_bps = Some(fee.to_string());
+    ///
+    /// Values above 10,000 (100%) fail [`validate`](Validate::validate).
+    pub fn fee_bps(mut self, fee: u16) -> Self {
+        self.params.fee_bps = Some(Bps::new_unchecked(fee));
         self
     }
 }
@@ -128,8 +144,8 @@ impl Params {
     /// Creates new parameters with required amounts
     pub fn new(making_amount: u64, taking_amount: u64) -> Self {
         Self {
-            making_amount: making_amount.to_string(),
-            taking_amount: taking_amount.to_string(),
+            making_amount: Amount::new(making_amount),
+            taking_amount: Amount::new(taking_amount),
             expired_at: None,
             slippage_bps: None,
             fee_bps: None,
@@ -143,14 +159,14 @@ impl Params {
     }
 
     /// Sets slippage tolerance in basis points
-    pub fn slippage_bps(mut self, slippage: &str) -> Self {
-        self.slippage_bps = Some(slippage.to_string());
+    pub fn slippage_bps(mut self, slippage: u16) -> Self {
+        self.slippage_bps = Some(Bps::new_unchecked(slippage));
         self
     }
 
     /// Sets fee in basis points
-    pub fn fee_bps(mut self, fee: &str) -> Self {
-        self.fee_bps = Some(fee.to_string());
+    pub fn fee_bps(mut self, fee: u16) -> Self {
+        self.fee_bps = Some(Bps::new_unchecked(fee));
         self
     }
 }
@@ -167,10 +183,45 @@ pub struct TriggerResponse {
     /// Base-58 account which is the Trigger Order account
     pub order: String,
 
+    #[serde(deserialize_with = "number_or_string")]
     pub code: u8,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// The cancel and create transactions that move an existing trigger order to new
+/// parameters, returned by
+/// [`JupiterClient::replace_trigger_order`](crate::JupiterClient::replace_trigger_order).
+///
+/// Sign both, then execute the cancel before the create: the new order is funded
+/// with the tokens the cancel returns.
+#[derive(Debug)]
+pub struct TriggerOrderReplacement {
+    /// Cancels the old order.
+    pub cancel: TriggerResponse,
+    /// Creates the new order.
+    pub create: TriggerResponse,
+}
+
+impl TriggerOrderReplacement {
+    /// The unsigned transactions in the order they must be signed and executed.
+    pub fn transactions(&self) -> [&str; 2] {
+        [&self.cancel.transaction, &self.create.transaction]
+    }
+
+    /// Pairs the signed transactions with their request ids, ready for
+    /// [`execute_trigger_order`](crate::JupiterClient::execute_trigger_order), in execution order.
+    pub fn execute_requests(
+        &self,
+        signed_cancel: &str,
+        signed_create: &str,
+    ) -> [ExecuteTriggerOrder; 2] {
+        [
+            ExecuteTriggerOrder::new(&self.cancel.request_id, signed_cancel),
+            ExecuteTriggerOrder::new(&self.create.request_id, signed_create),
+        ]
+    }
+}
+
+#[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteTriggerOrder {
     /// The request ID  
@@ -180,6 +231,18 @@ pub struct ExecuteTriggerOrder {
     pub signed_transaction: String,
 }
 
+impl fmt::Debug for ExecuteTriggerOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExecuteTriggerOrder")
+            .field("request_id", &self.request_id)
+            .field(
+                "signed_transaction",
+                &RedactedTransaction(&self.signed_transaction),
+            )
+            .finish()
+    }
+}
+
 impl ExecuteTriggerOrder {
     pub fn new(request_id: &str, signed_transaction: &str) -> Self {
         Self {
@@ -228,7 +291,7 @@ pub struct CancelTriggerOrders {
     pub compute_unit_price: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetTriggerOrders {
     /// user wallet address to retrive orders for
@@ -252,11 +315,16 @@ pub struct GetTriggerOrders {
     pub output_mint: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub enum OrderStatus {
     Active,
     History,
+    /// A value not known to this version of the SDK, only read from responses; requests
+    /// with it fail validation.
+    #[serde(other)]
+    Unknown,
 }
 
 impl GetTriggerOrders {
@@ -309,8 +377,11 @@ impl GetTriggerOrders {
 pub struct OrderResponse {
     pub user: String,
     pub order_status: String,
+    #[serde(deserialize_with = "null_to_default")]
     pub orders: Vec<Order>,
+    #[serde(deserialize_with = "number_or_string")]
     pub total_pages: u32,
+    #[serde(deserialize_with = "number_or_string")]
     pub page: u32,
 }
 
@@ -325,11 +396,11 @@ pub struct Order {
     pub taking_amount: String,
     pub remaining_making_amount: String,
     pub remaining_taking_amount: String,
-    pub raw_making_amount: String,
-    pub raw_taking_amount: String,
-    pub raw_remaining_making_amount: String,
-    pub raw_remaining_taking_amount: String,
-    pub slippage_bps: String,
+    pub raw_making_amount: Amount,
+    pub raw_taking_amount: Amount,
+    pub raw_remaining_making_amount: Amount,
+    pub raw_remaining_taking_amount: Amount,
+    pub slippage_bps: Bps,
     #[serde(default)]
     pub expired_at: Option<String>,
     pub created_at: String,
@@ -338,9 +409,66 @@ pub struct Order {
     pub open_tx: String,
     pub close_tx: String,
     pub program_version: String,
+    #[serde(default, deserialize_with = "null_to_default")]
     pub trades: Vec<Trade>,
 }
 
+impl Order {
+    /// The order's [`status`](Self::status) as a state.
+    pub fn state(&self) -> TriggerOrderState {
+        TriggerOrderState::from_status(&self.status)
+    }
+
+    /// The raw input sold and output bought by the order's trades so far.
+    pub fn filled_amounts(&self) -> (Amount, Amount) {
+        self.trades
+            .iter()
+            .fold((Amount::ZERO, Amount::ZERO), |(input, output), trade| {
+                (
+                    input + trade.raw_input_amount,
+                    output + trade.raw_output_amount,
+                )
+            })
+    }
+}
+
+/// Where a trigger [`Order`] is in its life, from its `status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TriggerOrderState {
+    /// Waiting for its price, possibly partially filled.
+    Open,
+    /// Filled in full.
+    Completed,
+    Cancelled,
+    Expired,
+    /// A status not known to this version of the SDK.
+    Unknown,
+}
+
+impl TriggerOrderState {
+    /// Maps a `status` such as `"Open"`, ignoring case.
+    pub fn from_status(status: &str) -> Self {
+        match status.to_ascii_lowercase().as_str() {
+            "open" => TriggerOrderState::Open,
+            "completed" => TriggerOrderState::Completed,
+            "cancelled" | "canceled" => TriggerOrderState::Cancelled,
+            "expired" => TriggerOrderState::Expired,
+            _ => TriggerOrderState::Unknown,
+        }
+    }
+
+    /// Whether the order can't be filled anymore.
+    pub fn is_closed(self) -> bool {
+        matches!(
+            self,
+            TriggerOrderState::Completed
+                | TriggerOrderState::Cancelled
+                | TriggerOrderState::Expired
+        )
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Trade {
@@ -350,14 +478,62 @@ pub struct Trade {
     pub output_mint: String,
     pub input_amount: String,
     pub output_amount: String,
-    pub raw_input_amount: String,
-    pub raw_output_amount: String,
+    pub raw_input_amount: Amount,
+    pub raw_output_amount: Amount,
     pub fee_mint: String,
     pub fee_amount: String,
-    pub raw_fee_amount: String,
+    pub raw_fee_amount: Amount,
     pub tx_id: String,
     pub confirmed_at: String,
     pub action: String,
     #[serde(default)]
     pub product_meta: Option<serde_json::Value>, // Flexible for null or arbitrary JSON
 }
+
+impl Validate for CreateTriggerOrder {
+    fn validate(&self) -> Result<(), JupiterClientError> {
+        validate_pubkey("input_mint", &self.input_mint)?;
+        validate_pubkey("output_mint", &self.output_mint)?;
+        validate_pubkey("maker", &self.maker)?;
+        validate_pubkey("payer", &self.payer)?;
+        validate_optional_pubkey("fee_account", self.fee_account.as_deref())?;
+        validate_optional_bps("slippage_bps", self.params.slippage_bps)?;
+        validate_optional_bps("fee_bps", self.params.fee_bps)
+    }
+}
+
+impl Validate for ExecuteTriggerOrder {
+    fn validate(&self) -> Result<(), JupiterClientError> {
+        if self.signed_transaction.is_empty() || self.request_id.is_empty() {
+            return Err(JupiterClientError::ValidationError(
+                "signed_transaction and request_id are required".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Validate for CancelTriggerOrder {
+    fn validate(&self) -> Result<(), JupiterClientError> {
+        validate_pubkey("maker", &self.maker)?;
+        validate_pubkey("order", &self.order)
+    }
+}
+
+impl Validate for CancelTriggerOrders {
+    fn validate(&self) -> Result<(), JupiterClientError> {
+        validate_pubkey("maker", &self.maker)?;
+        self.order
+            .iter()
+            .try_for_each(|order| validate_pubkey("order", order))
+    }
+}
+
+impl Validate for GetTriggerOrders {
+    fn validate(&self) -> Result<(), JupiterClientError> {
+        validate_pubkey("user", &self.user)?;
+        validate_known("order_status", self.order_status, OrderStatus::Unknown)?;
+        validate_optional_pubkey("input_mint", self.input_mint.as_deref())?;
+        validate_optional_pubkey("output_mint", self.output_mint.as_deref())
+    }
+}
@@ -1,6 +1,14 @@
-use super::{PlatformFee, QuoteGetSwapModeEnum, RoutePlanItem, vec_to_comma_string};
+use super::{
+    Amount, Bps, PlatformFee, RedactedTransaction, RoutePlanItem, SwapMode, Validate,
+    null_to_default, number_or_string, option_string_or_number, quote_request::resolve_ui_amount,
+    string_or_number, validate_optional_pubkey, validate_pubkey, vec_to_comma_string,
+};
+use crate::{
+    JupiterClient,
+    error::{JupiterClientError, QuoteErrorKind},
+};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
 
 /// Request for a base64-encoded unsigned swap transaction to be used in POST
 ///
@@ -32,7 +40,7 @@ pub struct UltraOrderRequest {
     /// referral fee in basis points (bps)
     ///
     /// Possible values: >= 50 and <= 255
-    pub referral_fee: Option<u8>,
+    pub referral_fee: Option<Bps>,
 
     /// A list of Routers to exclude from routing.
     ///
@@ -71,6 +79,26 @@ impl UltraOrderRequest {
         }
     }
 
+    /// Creates an order for `ui_amount` of `input` (e.g. `1.5` SOL), with the tokens given
+    /// as symbols or mint addresses, see [`QuoteRequest::from_ui_amounts`](super::QuoteRequest::from_ui_amounts).
+    ///
+    /// # Example
+    /// ```
+    /// let request = UltraOrderRequest::from_ui("SOL", "JUP", 0.25, &client)
+    ///     .await?
+    ///     .add_taker("taker wallet address");
+    /// ```
+    pub async fn from_ui(
+        input: &str,
+        output: &str,
+        ui_amount: f64,
+        client: &JupiterClient,
+    ) -> Result<Self, JupiterClientError> {
+        let (input_mint, output_mint, amount) =
+            resolve_ui_amount(input, output, ui_amount, client).await?;
+        Ok(Self::new(&input_mint, &output_mint, amount))
+    }
+
     /// add the taker account to the UltraOrder
     ///
     /// # Arguments
@@ -119,6 +147,9 @@ impl UltraOrderRequest {
     /// # Panics
     /// Panics if fee is less than 50 or greater than 255
     ///
+    /// The fee is only collected with a referral account set through
+    /// [`add_referral_account`](Self::add_referral_account).
+    ///
     /// # Example
     /// ```
     /// let request = UltraOrderRequest::new(
@@ -128,7 +159,7 @@ impl UltraOrderRequest {
     /// ).add_referral_fee(100); // 1% fee (100 bps)
     pub fn add_referral_fee(mut self, fee: u8) -> Self {
         assert!(fee >= 50, "Referral fee must be between 50 and 255 bps");
-        self.referral_fee = Some(fee);
+        self.referral_fee = Some(Bps::from(fee));
         self
     }
 
@@ -154,6 +185,48 @@ impl UltraOrderRequest {
         self.exclude_routers = Some(exclude_routers);
         self
     }
+
+    /// Restricts the order to aggregator routes or to RFQ (market maker) quotes,
+    /// replacing any routers set with [`exclude_routers`](Self::exclude_routers).
+    ///
+    /// # Example
+    /// ```
+    /// let request = UltraOrderRequest::new(
+    ///     "So11111111111111111111111111111111111111112",
+    ///     "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN",
+    ///     1_000_000_000
+    /// )
+    /// .routing(UltraRouting::AggregatorOnly);
+    /// ```
+    pub fn routing(mut self, routing: UltraRouting) -> Self {
+        let excluded = match routing {
+            UltraRouting::All => None,
+            UltraRouting::AggregatorOnly => Some(UltraRouting::RFQ_ROUTERS),
+            UltraRouting::RfqOnly => Some(UltraRouting::AGGREGATOR_ROUTERS),
+        };
+        self.exclude_routers =
+            excluded.map(|routers| routers.iter().map(|r| r.to_string()).collect());
+        self
+    }
+}
+
+/// Which kind of Ultra routers may fill an order, see [`UltraOrderRequest::routing`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UltraRouting {
+    /// Any router (the default).
+    All,
+    /// Only onchain aggregator routes, no RFQ market makers.
+    AggregatorOnly,
+    /// Only RFQ quotes from market makers.
+    RfqOnly,
+}
+
+impl UltraRouting {
+    /// Routers that route through onchain liquidity.
+    pub const AGGREGATOR_ROUTERS: &'static [&'static str] = &["metis", "dflow", "okx"];
+
+    /// Routers that fill orders from market maker quotes.
+    pub const RFQ_ROUTERS: &'static [&'static str] = &["jupiterz", "hashflow", "pyth"];
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -166,37 +239,44 @@ pub struct UltraOrderResponse {
     pub output_mint: String,
 
     /// The raw input token amount.
-    pub in_amount: String,
+    pub in_amount: Amount,
 
     /// The raw output token amount (excluding slippage or fees).
-    pub out_amount: String,
+    pub out_amount: Amount,
 
     /// The worst-case output amount after slippage & fees.
     ///
     /// Not used by `/swap`, but useful for displaying expectations.
-    pub other_amount_threshold: String,
+    pub other_amount_threshold: Amount,
 
     /// Indicates the swap mode used (ExactIn or ExactOut).
-    pub swap_mode: QuoteGetSwapModeEnum,
+    pub swap_mode: SwapMode,
 
     /// The applied slippage in basis points.
-    pub slippage_bps: i32,
+    pub slippage_bps: Bps,
 
     /// Estimated price impact as a percentage string.
+    #[serde(deserialize_with = "string_or_number")]
     pub price_impact_pct: String,
 
     /// The detailed route plan (possibly multiple hops).
+    #[serde(deserialize_with = "null_to_default")]
     pub route_plan: Vec<RoutePlanItem>,
 
     #[serde(default)]
     pub fee_mint: Option<String>,
 
-    pub fee_bps: u8,
+    pub fee_bps: Bps,
 
+    #[serde(deserialize_with = "number_or_string")]
     pub prioritization_fee_lamports: u64,
 
     pub swap_type: SwapType,
 
+    /// The router that quoted the order, e.g. `iris` or `jupiterz`.
+    #[serde(default)]
+    pub router: Option<String>,
+
     #[serde(default)]
     pub transaction: Option<String>,
 
@@ -204,6 +284,7 @@ pub struct UltraOrderResponse {
 
     pub request_id: String,
 
+    #[serde(deserialize_with = "number_or_string")]
     pub total_time: u16,
 
     #[serde(default)]
@@ -225,13 +306,17 @@ pub struct UltraOrderResponse {
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
 #[serde(rename_all = "lowercase")]
+#[non_exhaustive]
 pub enum SwapType {
     Aggregator,
     Rfq,
     Hashflow,
+    /// A value not known to this version of the SDK.
+    #[serde(other)]
+    Unknown,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UltraExecuteOrderRequest {
     /// The signed transaction to execute
@@ -241,6 +326,18 @@ pub struct UltraExecuteOrderRequest {
     pub request_id: String,
 }
 
+impl fmt::Debug for UltraExecuteOrderRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UltraExecuteOrderRequest")
+            .field(
+                "signed_transaction",
+                &RedactedTransaction(&self.signed_transaction),
+            )
+            .field("request_id", &self.request_id)
+            .finish()
+    }
+}
+
 impl UltraExecuteOrderRequest {
     // function to construct a new UltraExecuteOrderRequest
     //
@@ -263,62 +360,204 @@ pub struct UltraExecuteOrderResponse {
     #[serde(default)]
     pub signature: Option<String>,
 
-    #[serde(default)]
+    #[serde(default, deserialize_with = "option_string_or_number")]
     pub slot: Option<String>,
 
     #[serde(default)]
     pub error: Option<String>,
 
-    pub code: u32,
+    #[serde(deserialize_with = "number_or_string")]
+    pub code: i32,
 
     #[serde(default)]
-    pub total_input_amount: Option<String>,
+    pub total_input_amount: Option<Amount>,
 
     #[serde(default)]
-    pub total_output_amount: Option<String>,
+    pub total_output_amount: Option<Amount>,
 
     #[serde(default)]
-    pub input_amount_result: Option<String>,
+    pub input_amount_result: Option<Amount>,
 
     #[serde(default)]
-    pub output_amount_result: Option<String>,
+    pub output_amount_result: Option<Amount>,
 
+    /// One entry per swap the transaction made, across hops and split routes.
     #[serde(default)]
     pub swap_events: Option<Vec<SwapEvent>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl UltraExecuteOrderResponse {
+    /// The swap events, empty if the response has none.
+    pub fn events(&self) -> &[SwapEvent] {
+        self.swap_events.as_deref().unwrap_or_default()
+    }
+
+    /// The executed amounts of a swap of `input_mint` to `output_mint` added up from the
+    /// swap events: what the events took of `input_mint` and gave of `output_mint`, so
+    /// intermediate hops of a multi-hop route cancel out.
+    ///
+    /// `None` if no event takes `input_mint` or none gives `output_mint`.
+    pub fn executed_amounts(
+        &self,
+        input_mint: &str,
+        output_mint: &str,
+    ) -> Option<(Amount, Amount)> {
+        let input = total(
+            self.events()
+                .iter()
+                .filter(|event| event.input_mint.as_deref() == Some(input_mint))
+                .map(|event| event.input_amount),
+        );
+        let output = total(
+            self.events()
+                .iter()
+                .filter(|event| event.output_mint.as_deref() == Some(output_mint))
+                .map(|event| event.output_amount),
+        );
+        input.zip(output)
+    }
+
+    /// A [`SlippageExceeded`](JupiterClientError::SlippageExceeded) error with the amounts
+    /// of `order`, if the execution failed because the price moved past its slippage
+    /// tolerance. `None` for successful executions and other failures.
+    ///
+    /// # Example
+    /// ```
+    /// let execution = api.ultra_execute_order(&UltraExecuteOrderRequest::new(signed_tx, &order.request_id)).await?;
+    /// if let Some(JupiterClientError::SlippageExceeded { min_out_amount, actual_out_amount, .. }) = execution.slippage_error(&order) {
+    ///     println!("wanted at least {min_out_amount}, got {actual_out_amount:?}; re-quoting");
+    /// }
+    /// ```
+    pub fn slippage_error(&self, order: &UltraOrderResponse) -> Option<JupiterClientError> {
+        if self.status == Status::Success {
+            return None;
+        }
+        let kind = QuoteErrorKind::from_code(&self.code.to_string())
+            .or_else(|| self.error.as_deref().and_then(QuoteErrorKind::from_message));
+        (kind == Some(QuoteErrorKind::SlippageToleranceExceeded)).then(|| {
+            JupiterClientError::slippage_exceeded(
+                order,
+                self.output_amount_result.or(self.total_output_amount),
+                self.signature.clone(),
+            )
+        })
+    }
+}
+
+/// The sum of `amounts`, `None` if there are none or one is missing.
+fn total(mut amounts: impl Iterator<Item = Option<Amount>>) -> Option<Amount> {
+    let first = amounts.next()??;
+    amounts.try_fold(first, |sum, amount| sum.checked_add(amount?))
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
 pub enum Status {
     Success,
     Failed,
+    /// A value not known to this version of the SDK.
+    #[serde(other)]
+    Unknown,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A single swap made by an executed Ultra order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SwapEvent {
     pub input_mint: Option<String>,
-    pub input_amount: Option<String>,
+    pub input_amount: Option<Amount>,
     pub output_mint: Option<String>,
-    pub output_amount: Option<String>,
+    pub output_amount: Option<Amount>,
+    /// Address of the AMM the swap went through.
+    #[serde(default)]
+    pub amm: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TokenBalance {
-    pub amount: String,
+    pub amount: Amount,
     pub ui_amount: f64,
+    #[serde(deserialize_with = "number_or_string")]
     pub slot: u64,
     pub is_frozen: bool,
 }
 
 pub type TokenBalancesResponse = HashMap<String, TokenBalance>;
 
+/// Everything a wallet holds, from [`get_holdings`](crate::JupiterClient::get_holdings):
+/// its SOL and each of its token accounts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Holdings {
+    /// Native SOL, in lamports.
+    pub amount: Amount,
+    pub ui_amount: f64,
+    pub ui_amount_string: String,
+    /// Token accounts by mint. A mint may be held in several accounts.
+    #[serde(default, deserialize_with = "null_to_default")]
+    pub tokens: HashMap<String, Vec<TokenAccountHolding>>,
+}
+
+impl Holdings {
+    /// The total held of `mint` across its accounts, zero if none.
+    pub fn token_amount(&self, mint: &str) -> Amount {
+        self.accounts(mint)
+            .iter()
+            .map(|account| account.amount)
+            .sum()
+    }
+
+    /// The accounts holding `mint`.
+    pub fn accounts(&self, mint: &str) -> &[TokenAccountHolding] {
+        self.tokens.get(mint).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Mints held in at least one frozen account, sorted. Frozen tokens can't be swapped.
+    pub fn frozen_mints(&self) -> Vec<&str> {
+        let mut mints: Vec<&str> = self
+            .tokens
+            .iter()
+            .filter(|(_, accounts)| accounts.iter().any(|account| account.is_frozen))
+            .map(|(mint, _)| mint.as_str())
+            .collect();
+        mints.sort_unstable();
+        mints
+    }
+}
+
+/// The SPL Token-2022 program, as `referral::TOKEN_2022_PROGRAM_ID` without the
+/// `solana` feature.
+const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+/// A token account of [`Holdings`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenAccountHolding {
+    pub account: String,
+    pub amount: Amount,
+    pub ui_amount: f64,
+    pub ui_amount_string: String,
+    pub is_frozen: bool,
+    /// Whether this is the wallet's associated token account for the mint.
+    pub is_associated_token_account: bool,
+    pub decimals: u8,
+    /// The token program owning the account, SPL Token or Token-2022.
+    pub program_id: String,
+}
+
+impl TokenAccountHolding {
+    pub fn is_token_2022(&self) -> bool {
+        self.program_id == TOKEN_2022_PROGRAM_ID
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Shield {
     pub warnings: HashMap<String, Vec<Warning>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Warning {
     #[serde(rename = "type")]
     pub warning_type: String,
@@ -326,9 +565,70 @@ pub struct Warning {
     pub severity: String,
 }
 
+/// A token matching a [`search_tokens`](crate::JupiterClient::search_tokens) query.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenSearchResult {
+    #[serde(rename = "id")]
+    pub mint: String,
+    pub name: String,
+    pub symbol: String,
+    pub icon: Option<String>,
+    pub decimals: u8,
+    /// Whether Jupiter verified the token; unverified tokens may impersonate others.
+    #[serde(default)]
+    pub is_verified: Option<bool>,
+    #[serde(default, deserialize_with = "null_to_default")]
+    pub tags: Vec<String>,
+    /// Jupiter's score of genuine trading activity, 0 to 100.
+    #[serde(default)]
+    pub organic_score: Option<f64>,
+    #[serde(default)]
+    pub usd_price: Option<f64>,
+    #[serde(default)]
+    pub liquidity: Option<f64>,
+    #[serde(default)]
+    pub mcap: Option<f64>,
+    #[serde(default)]
+    pub holder_count: Option<u64>,
+}
+
+impl TokenSearchResult {
+    pub fn is_verified(&self) -> bool {
+        self.is_verified.unwrap_or(false)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Router {
     pub id: String,
     pub name: String,
     pub icon: String,
 }
+
+impl Validate for UltraOrderRequest {
+    fn validate(&self) -> Result<(), JupiterClientError> {
+        validate_pubkey("input_mint", &self.input_mint)?;
+        validate_pubkey("output_mint", &self.output_mint)?;
+        validate_optional_pubkey("taker", self.taker.as_deref())?;
+        validate_optional_pubkey("referral_account", self.referral_account.as_deref())?;
+        // without a referral account the fee would silently go uncollected
+        if self.referral_fee.is_some() && self.referral_account.is_none() {
+            return Err(JupiterClientError::ValidationError(
+                "referral_fee requires a referral_account".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Validate for UltraExecuteOrderRequest {
+    fn validate(&self) -> Result<(), JupiterClientError> {
+        if self.signed_transaction.is_empty() || self.request_id.is_empty() {
+            return Err(JupiterClientError::ValidationError(
+                "signed_transaction and request_id are required".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
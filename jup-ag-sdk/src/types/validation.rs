@@ -0,0 +1,110 @@
+use crate::error::JupiterClientError;
+
+/// Client-side checks run on a request before it is sent to Jupiter.
+///
+/// Every API method calls `validate` first, so malformed input fails fast with a
+/// [`JupiterClientError::ValidationError`] instead of a less helpful HTTP 400. End a
+/// builder chain with [`build`](Self::build) to fail where the request is built instead.
+pub trait Validate {
+    fn validate(&self) -> Result<(), JupiterClientError>;
+
+    /// Returns the request if it is valid, e.g. to check one built from user input
+    /// before keeping it.
+    ///
+    /// # Example
+    /// ```
+    /// use jup_ag_sdk::types::{QuoteRequest, Validate};
+    ///
+    /// let request = QuoteRequest::new(
+    ///     "So11111111111111111111111111111111111111112",
+    ///     "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN",
+    ///     1_000_000_000,
+    /// )
+    /// .slippage_bps(50)
+    /// .build()
+    /// .unwrap();
+    ///
+    /// assert!(QuoteRequest::new("not-a-mint", &request.output_mint, 1).build().is_err());
+    /// ```
+    fn build(self) -> Result<Self, JupiterClientError>
+    where
+        Self: Sized,
+    {
+        self.validate()?;
+        Ok(self)
+    }
+}
+
+/// Checks that `value` is a base58 encoded 32-byte Solana public key.
+///
+/// `field` is the name reported in the error, e.g. `"input_mint"`.
+///
+/// # Example
+/// ```
+/// use jup_ag_sdk::types::validate_pubkey;
+///
+/// assert!(validate_pubkey("input_mint", "So11111111111111111111111111111111111111112").is_ok());
+/// assert!(validate_pubkey("input_mint", "not-a-mint").is_err());
+/// ```
+pub fn validate_pubkey(field: &str, value: &str) -> Result<(), JupiterClientError> {
+    // a 32-byte key is at most 44 base58 characters
+    if value.is_empty() || value.len() > 44 {
+        return Err(invalid_pubkey(field, value));
+    }
+
+    match bs58::decode(value).into_vec() {
+        Ok(bytes) if bytes.len() == 32 => Ok(()),
+        _ => Err(invalid_pubkey(field, value)),
+    }
+}
+
+/// Validates an optional address, accepting `None`.
+pub fn validate_optional_pubkey(
+    field: &str,
+    value: Option<&str>,
+) -> Result<(), JupiterClientError> {
+    value.map_or(Ok(()), |v| validate_pubkey(field, v))
+}
+
+/// Checks that `value`, if set, is at most [`Bps::MAX`](super::Bps::MAX).
+pub(crate) fn validate_optional_bps(
+    field: &str,
+    value: Option<super::Bps>,
+) -> Result<(), JupiterClientError> {
+    match value {
+        Some(bps) => super::Bps::new(bps.get())
+            .map(|_| ())
+            .map_err(|e| JupiterClientError::ValidationError(format!("{field}: {e}"))),
+        None => Ok(()),
+    }
+}
+
+/// Fails if `value` is the `Unknown` fallback of an enum also read from responses,
+/// which Jupiter wouldn't accept in a request.
+pub(crate) fn validate_known<T: PartialEq>(
+    field: &str,
+    value: T,
+    unknown: T,
+) -> Result<(), JupiterClientError> {
+    if value == unknown {
+        return Err(JupiterClientError::ValidationError(format!(
+            "{field}: Unknown can't be sent to Jupiter"
+        )));
+    }
+    Ok(())
+}
+
+/// Parses an address into a [`Pubkey`](solana_pubkey::Pubkey).
+#[cfg(feature = "solana")]
+pub(crate) fn parse_pubkey(
+    field: &str,
+    value: &str,
+) -> Result<solana_pubkey::Pubkey, JupiterClientError> {
+    value.parse().map_err(|_| invalid_pubkey(field, value))
+}
+
+fn invalid_pubkey(field: &str, value: &str) -> JupiterClientError {
+    JupiterClientError::ValidationError(format!(
+        "{field} is not a valid base58 public key: {value:?}"
+    ))
+}
@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod amm_blacklist_tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use jup_ag_sdk::{
+        JupiterClient,
+        amm_blacklist::{AmmBlacklist, AmmBlacklistConfig},
+        types::{QuoteRequest, QuoteResponse},
+    };
+    use serde_json::Value;
+
+    use crate::{
+        common::{JUP_MINT, SOL_MINT, TEST_AMOUNT, quote_json, route_hop},
+        mock::{MockResponse, MockServer},
+    };
+
+    const POOL: &str = "5BKxfWMbmYBAEWvyPZS9esPducUba9GqyMjtLCfbaqyF";
+    const OTHER_POOL: &str = "HcoJqG325TTifs6jyWvRJ9ET4pDu12Xrt2EQKZGFmuKX";
+
+    fn routed_quote_json(hops: &[(&str, &str)]) -> Value {
+        let mut quote = quote_json();
+        quote["routePlan"] = hops
+            .iter()
+            .map(|(amm_key, label)| route_hop(amm_key, label))
+            .collect();
+        quote
+    }
+
+    fn quote(hops: &[(&str, &str)]) -> QuoteResponse {
+        serde_json::from_value(routed_quote_json(hops)).unwrap()
+    }
+
+    fn fail(blacklist: &AmmBlacklist, quote: &QuoteResponse, times: usize) {
+        for _ in 0..times {
+            blacklist.record_execution(quote, false);
+        }
+    }
+
+    #[test]
+    fn test_bans_after_threshold() {
+        let blacklist = AmmBlacklist::default();
+        let failing = quote(&[(POOL, "Meteora DLMM")]);
+        let healthy = quote(&[(OTHER_POOL, "Orca V2")]);
+
+        fail(&blacklist, &failing, 2);
+        assert!(!blacklist.is_blacklisted(POOL));
+
+        fail(&blacklist, &failing, 1);
+        assert!(blacklist.is_blacklisted(POOL));
+        assert_eq!(blacklist.excluded_dexes(), vec!["Meteora DLMM"]);
+        assert!(!blacklist.allows(&failing));
+        assert!(blacklist.allows(&healthy));
+
+        let banned = blacklist.blacklisted();
+        assert_eq!(banned.len(), 1);
+        assert_eq!(banned[0].amm_key, POOL);
+        assert!(banned[0].remaining <= AmmBlacklistConfig::default().ban);
+    }
+
+    #[test]
+    fn test_success_clears_failures() {
+        let blacklist = AmmBlacklist::default();
+        let route = quote(&[(POOL, "Meteora DLMM")]);
+
+        fail(&blacklist, &route, 2);
+        blacklist.record_execution(&route, true);
+        fail(&blacklist, &route, 2);
+
+        assert!(!blacklist.is_blacklisted(POOL));
+    }
+
+    #[test]
+    fn test_bans_expire_and_double() {
+        let ban = Duration::from_millis(40);
+        let blacklist = AmmBlacklist::new(AmmBlacklistConfig::default().ban(ban));
+        let route = quote(&[(POOL, "Meteora DLMM")]);
+
+        fail(&blacklist, &route, 3);
+        assert!(blacklist.is_blacklisted(POOL));
+        std::thread::sleep(ban + Duration::from_millis(10));
+        assert!(!blacklist.is_blacklisted(POOL));
+        assert!(blacklist.excluded_dexes().is_empty());
+
+        fail(&blacklist, &route, 3);
+        assert!(blacklist.blacklisted()[0].remaining > ban);
+    }
+
+    #[test]
+    fn test_apply_merges_exclusions() {
+        let blacklist = AmmBlacklist::default();
+        fail(&blacklist, &quote(&[(POOL, "Meteora DLMM")]), 3);
+        fail(&blacklist, &quote(&[(OTHER_POOL, "Orca V2")]), 3);
+
+        let request = blacklist.apply(
+            QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+                .exclude_dexes(vec!["Raydium".to_string(), "Orca V2".to_string()]),
+        );
+        assert_eq!(
+            request.exclude_dexes.unwrap(),
+            vec!["Raydium", "Orca V2", "Meteora DLMM"]
+        );
+
+        let pinned = blacklist.apply(
+            QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+                .dexes(vec!["Meteora DLMM".to_string()]),
+        );
+        assert_eq!(pinned.exclude_dexes.unwrap(), vec!["Orca V2"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_quote_excludes_blacklisted_dexes() {
+        let queries = Arc::new(Mutex::new(Vec::new()));
+        let seen = queries.clone();
+        let server = MockServer::start(move |req| {
+            let seen = seen.clone();
+            async move {
+                seen.lock().unwrap().push(req.path.clone());
+                MockResponse::json(&routed_quote_json(&[(POOL, "Meteora DLMM")]).to_string())
+            }
+        })
+        .await;
+        let blacklist = Arc::new(AmmBlacklist::default());
+        let client = JupiterClient::builder(&server.url)
+            .amm_blacklist(blacklist.clone())
+            .build()
+            .unwrap();
+        let request = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT);
+
+        let quote = client.get_quote(&request).await.unwrap();
+        fail(&blacklist, &quote, 3);
+        client.get_quote(&request).await.unwrap();
+
+        let queries = queries.lock().unwrap();
+        assert!(!queries[0].contains("excludeDexes"), "{}", queries[0]);
+        assert!(
+            queries[1].contains("excludeDexes=Meteora+DLMM"),
+            "{}",
+            queries[1]
+        );
+        assert!(client.amm_blacklist().unwrap().is_blacklisted(POOL));
+    }
+}
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod balances_tests {
+    use jup_ag_sdk::{
+        JupiterClient,
+        types::{
+            NATIVE_SOL, TokenBalancesExt, TokenBalancesResponse, TokenPriceResponse, WSOL_MINT,
+        },
+    };
+    use serde_json::json;
+
+    use crate::{
+        common::{JUP_MINT, TEST_USER_PUBKEY, USDC_MINT},
+        mock::{MockResponse, MockServer},
+    };
+
+    const DUST_MINT: &str = "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263";
+    const UNPRICED_MINT: &str = "EKpQGSJtjMFqKZ9KQanSqYXRcF8fBopzLHYxdM65zcjm";
+
+    fn balances_json() -> serde_json::Value {
+        let balance = |amount: &str, ui_amount: f64| json!({ "amount": amount, "uiAmount": ui_amount, "slot": 1, "isFrozen": false });
+        json!({
+            "SOL": balance("1500000000", 1.5),
+            WSOL_MINT: balance("500000000", 0.5),
+            USDC_MINT: balance("10000000", 10.0),
+            JUP_MINT: balance("1000000", 1.0),
+            DUST_MINT: balance("100", 0.001),
+            UNPRICED_MINT: balance("42", 42.0),
+            "4k3Dyjzvzp8eMZWUXbBCjEvwSkkk59S5iCNLY3QrkX6R": balance("0", 0.0)
+        })
+    }
+
+    fn prices_json() -> serde_json::Value {
+        let price =
+            |mint: &str, price: &str| json!({ "id": mint, "type": "derivedPrice", "price": price });
+        json!({
+            "data": {
+                WSOL_MINT: price(WSOL_MINT, "150"),
+                USDC_MINT: price(USDC_MINT, "1"),
+                JUP_MINT: price(JUP_MINT, "0.5"),
+                DUST_MINT: price(DUST_MINT, "0.00002")
+            },
+            "timeTaken": 0.001
+        })
+    }
+
+    #[test]
+    fn test_merge_wsol() {
+        let mut balances: TokenBalancesResponse = serde_json::from_value(balances_json()).unwrap();
+        balances.merge_wsol();
+
+        assert!(!balances.contains_key(WSOL_MINT));
+        let sol = &balances[NATIVE_SOL];
+        assert_eq!(sol.amount, 2_000_000_000);
+        assert_eq!(sol.ui_amount, 2.0);
+
+        let mints = balances.price_mints();
+        assert!(mints.contains(&WSOL_MINT.to_string()));
+        assert!(!mints.contains(&NATIVE_SOL.to_string()));
+    }
+
+    #[test]
+    fn test_display_list_sorts_and_filters_dust() {
+        let mut balances: TokenBalancesResponse = serde_json::from_value(balances_json()).unwrap();
+        balances.merge_wsol();
+        let prices: TokenPriceResponse = serde_json::from_value(prices_json()).unwrap();
+
+        let list = balances.to_display_list(&prices, 0.01);
+        let mints: Vec<_> = list.iter().map(|b| b.mint.as_str()).collect();
+
+        // priced by value, then unpriced; dust and empty balances dropped
+        assert_eq!(mints, [NATIVE_SOL, USDC_MINT, JUP_MINT, UNPRICED_MINT]);
+        assert_eq!(list[0].usd_value, Some(300.0));
+        assert_eq!(list[3].usd_value, None);
+    }
+
+    #[tokio::test]
+    async fn test_get_wallet_balances() {
+        let server = MockServer::start(|req| async move {
+            if req.path.starts_with("/ultra/v1/balances/") {
+                MockResponse::json(&balances_json().to_string())
+            } else {
+                assert!(req.path.starts_with("/price/v2"), "{}", req.path);
+                MockResponse::json(&prices_json().to_string())
+            }
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+
+        let list = client
+            .get_wallet_balances(TEST_USER_PUBKEY, 0.01)
+            .await
+            .unwrap();
+
+        assert_eq!(list.len(), 4);
+        assert_eq!(list[0].mint, NATIVE_SOL);
+        assert_eq!(server.hits(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_holdings() {
+        let server = MockServer::start(|req| async move {
+            assert_eq!(req.path, format!("/ultra/v1/holdings/{TEST_USER_PUBKEY}"));
+            let account = |account: &str, amount: &str, frozen: bool, ata: bool, program: &str| {
+                json!({
+                    "account": account,
+                    "amount": amount,
+                    "uiAmount": 0.0,
+                    "uiAmountString": "0",
+                    "isFrozen": frozen,
+                    "isAssociatedTokenAccount": ata,
+                    "decimals": 6,
+                    "programId": program
+                })
+            };
+            let token = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+            let token_2022 = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+            MockResponse::json(
+                &json!({
+                    "amount": "1500000000",
+                    "uiAmount": 1.5,
+                    "uiAmountString": "1.5",
+                    "tokens": {
+                        USDC_MINT: [
+                            account("ata-usdc", "10000000", false, true, token),
+                            account("aux-usdc", "2500000", false, false, token)
+                        ],
+                        JUP_MINT: [account("ata-jup", "1000000", true, true, token_2022)]
+                    }
+                })
+                .to_string(),
+            )
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+
+        let holdings = client.get_holdings(TEST_USER_PUBKEY).await.unwrap();
+        assert_eq!(holdings.amount, 1_500_000_000);
+        assert_eq!(holdings.token_amount(USDC_MINT), 12_500_000);
+        assert_eq!(holdings.token_amount(DUST_MINT), 0);
+        assert_eq!(holdings.accounts(USDC_MINT).len(), 2);
+        assert!(!holdings.accounts(USDC_MINT)[1].is_associated_token_account);
+        assert!(holdings.accounts(JUP_MINT)[0].is_token_2022());
+        assert_eq!(holdings.frozen_mints(), [JUP_MINT]);
+
+        assert!(client.get_holdings("not an address").await.is_err());
+        assert_eq!(server.hits(), 1);
+    }
+}
@@ -0,0 +1,163 @@
+#[cfg(test)]
+mod blockhash_tests {
+    use std::time::Duration;
+
+    use base64::{Engine, engine::general_purpose::STANDARD};
+    use jup_ag_sdk::{JupiterClient, JupiterClientError, blockhash::BlockhashManager};
+    use serde_json::json;
+    use solana_hash::Hash;
+    use solana_instruction::AccountMeta;
+    use solana_pubkey::Pubkey;
+
+    use crate::mock::{MockResponse, MockServer};
+
+    fn rpc_result(result: serde_json::Value) -> MockResponse {
+        MockResponse::json(&json!({ "jsonrpc": "2.0", "id": 1, "result": result }).to_string())
+    }
+
+    /// A Solana RPC answering `getLatestBlockhash` with a new blockhash each time.
+    async fn blockhash_rpc() -> MockServer {
+        let height = std::sync::Arc::new(std::sync::atomic::AtomicU64::new(100));
+        MockServer::start(move |req| {
+            let height = height.clone();
+            async move {
+                let request: serde_json::Value = serde_json::from_str(&req.body).unwrap();
+                assert_eq!(request["method"], "getLatestBlockhash");
+                assert_eq!(request["params"][0]["commitment"], "confirmed");
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                let height = height.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                rpc_result(json!({
+                    "context": { "slot": 1 },
+                    "value": {
+                        "blockhash": Hash::new_from_array([height as u8; 32]).to_string(),
+                        "lastValidBlockHeight": height,
+                    }
+                }))
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_gets_share_one_fetch() {
+        let server = blockhash_rpc().await;
+        let client = JupiterClient::new("https://lite-api.jup.ag");
+        let manager = BlockhashManager::new(&server.url);
+        assert!(manager.current().is_none());
+
+        let (a, b, c) = tokio::join!(
+            manager.get(&client),
+            manager.get(&client),
+            manager.get(&client)
+        );
+        let (a, b, c) = (a.unwrap(), b.unwrap(), c.unwrap());
+        assert_eq!(server.hits(), 1);
+        assert_eq!((a, b), (c, c));
+        assert_eq!(c.last_valid_block_height, 100);
+        assert_eq!(c.blockhash, Hash::new_from_array([100; 32]));
+
+        assert_eq!(manager.get(&client).await.unwrap(), c);
+        assert_eq!(server.hits(), 1);
+
+        let refreshed = manager.refresh(&client).await.unwrap();
+        assert_eq!(refreshed.last_valid_block_height, 101);
+        assert_eq!(manager.current(), Some(refreshed));
+        assert_eq!(server.hits(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_refetches_old_blockhash() {
+        let server = blockhash_rpc().await;
+        let client = JupiterClient::new("https://lite-api.jup.ag");
+        let manager = BlockhashManager::new(&server.url).max_age(Duration::from_millis(20));
+
+        let first = manager.get(&client).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(manager.current(), Some(first));
+
+        let second = manager.get(&client).await.unwrap();
+        assert_eq!(second.last_valid_block_height, 101);
+        assert_eq!(server.hits(), 2);
+    }
+
+    fn nonce_account(state: u32, authority: &Pubkey, nonce: &Hash) -> serde_json::Value {
+        let mut data = Vec::new();
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&state.to_le_bytes());
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(nonce.as_ref());
+        data.extend_from_slice(&5000u64.to_le_bytes());
+        json!({
+            "owner": "11111111111111111111111111111111",
+            "data": [STANDARD.encode(&data), "base64"]
+        })
+    }
+
+    #[tokio::test]
+    async fn test_nonce_pool() {
+        let (first, second, authority) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let nonce = Hash::new_from_array([7; 32]);
+        let uninitialized = second.to_string();
+        let server = MockServer::start(move |req| {
+            let uninitialized = uninitialized.clone();
+            async move {
+                let request: serde_json::Value = serde_json::from_str(&req.body).unwrap();
+                assert_eq!(request["method"], "getMultipleAccounts");
+                let state = u32::from(request["params"][0][0] != uninitialized.as_str());
+                rpc_result(json!({
+                    "context": { "slot": 1 },
+                    "value": [nonce_account(state, &authority, &nonce)]
+                }))
+            }
+        })
+        .await;
+        let client = JupiterClient::new("https://lite-api.jup.ag");
+        let manager = BlockhashManager::new(&server.url)
+            .nonce_accounts(&[&first.to_string(), &second.to_string()])
+            .unwrap();
+        assert_eq!(manager.available_nonces(), 2);
+
+        let lease = manager.acquire_nonce(&client).await.unwrap().unwrap();
+        assert_eq!(lease.account, first);
+        assert_eq!(lease.authority, authority);
+        assert_eq!(lease.nonce, nonce);
+        assert_eq!(manager.available_nonces(), 1);
+
+        let advance = lease.advance_instruction();
+        assert_eq!(advance.program_id, Pubkey::default());
+        assert_eq!(advance.data, [4, 0, 0, 0]);
+        assert_eq!(
+            advance.accounts,
+            [
+                AccountMeta::new(first, false),
+                AccountMeta::new_readonly(
+                    Pubkey::from_str_const("SysvarRecentB1ockHashes11111111111111111111"),
+                    false
+                ),
+                AccountMeta::new_readonly(authority, true),
+            ]
+        );
+
+        // the second account isn't initialized, so it goes back to the pool
+        let err = manager.acquire_nonce(&client).await.unwrap_err();
+        assert!(
+            matches!(err, JupiterClientError::ValidationError(_)),
+            "{err}"
+        );
+        assert_eq!(manager.available_nonces(), 1);
+
+        manager.release_nonce(lease);
+        assert_eq!(manager.available_nonces(), 2);
+
+        let empty = BlockhashManager::new(&server.url);
+        assert_eq!(empty.acquire_nonce(&client).await.unwrap(), None);
+        assert!(matches!(
+            empty.nonce_accounts(&["not-a-key"]),
+            Err(JupiterClientError::ValidationError(_))
+        ));
+    }
+}
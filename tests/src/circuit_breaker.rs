@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod circuit_breaker_tests {
+    use std::{
+        sync::{
+            Arc, Mutex,
+            atomic::{AtomicU16, Ordering},
+        },
+        time::Duration,
+    };
+
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError,
+        config::{ApiFamily, CircuitBreakerConfig},
+        events::LifecycleEvent,
+    };
+
+    use crate::mock::{MockResponse, MockServer};
+
+    /// Answers every request with the status in `status`.
+    async fn server(status: Arc<AtomicU16>) -> MockServer {
+        MockServer::start(move |_| {
+            let status = status.load(Ordering::SeqCst);
+            async move {
+                match status {
+                    200 => MockResponse::json("[]"),
+                    status => MockResponse::status(status, "unavailable"),
+                }
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_breaker_opens_and_recovers() {
+        let status = Arc::new(AtomicU16::new(503));
+        let server = server(status.clone()).await;
+        let opened = Arc::new(Mutex::new(Vec::new()));
+        let sink = opened.clone();
+        let client = JupiterClient::builder(&server.url)
+            .circuit_breaker(CircuitBreakerConfig::new(2, Duration::from_millis(100)))
+            .event_subscriber(move |event: &LifecycleEvent| {
+                if let LifecycleEvent::CircuitOpened { path, .. } = event {
+                    sink.lock().unwrap().push(path.clone());
+                }
+            })
+            .build()
+            .unwrap();
+
+        for _ in 0..2 {
+            assert!(matches!(
+                client.routers().await,
+                Err(JupiterClientError::ApiError(..))
+            ));
+        }
+        match client.routers().await {
+            Err(JupiterClientError::CircuitOpen {
+                family,
+                endpoint,
+                retry_in,
+            }) => {
+                assert_eq!(family, ApiFamily::Ultra);
+                assert_eq!(endpoint, "/ultra/v1/order/routers");
+                assert!(retry_in <= Duration::from_millis(100));
+            }
+            other => panic!("expected CircuitOpen, got {other:?}"),
+        }
+        assert_eq!(server.hits(), 2);
+        assert_eq!(client.stats().circuit_breaker_trips, 1);
+        assert_eq!(*opened.lock().unwrap(), ["/ultra/v1/order/routers"]);
+
+        // other endpoints keep going
+        assert!(client.get_program_id_to_label().await.is_err());
+        assert_eq!(server.hits(), 3);
+
+        // a failed probe opens it again
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        assert!(client.routers().await.is_err());
+        assert_eq!(server.hits(), 4);
+        assert!(matches!(
+            client.routers().await,
+            Err(JupiterClientError::CircuitOpen { .. })
+        ));
+        assert_eq!(server.hits(), 4);
+        assert_eq!(client.stats().circuit_breaker_trips, 1);
+
+        // a successful one closes it
+        status.store(200, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        client.routers().await.expect("probe should succeed");
+        client.routers().await.expect("breaker should be closed");
+        assert_eq!(server.hits(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_requests_do_not_trip() {
+        let server = server(Arc::new(AtomicU16::new(400))).await;
+        let client = JupiterClient::builder(&server.url)
+            .circuit_breaker(CircuitBreakerConfig::new(1, Duration::from_secs(60)))
+            .build()
+            .unwrap();
+
+        for _ in 0..3 {
+            assert!(matches!(
+                client.routers().await,
+                Err(JupiterClientError::ApiError(..))
+            ));
+        }
+        assert_eq!(server.hits(), 3);
+        assert_eq!(client.stats().circuit_breaker_trips, 0);
+    }
+}
@@ -1,5 +1,7 @@
 #[cfg(test)]
 use jup_ag_sdk::JupiterClient;
+#[cfg(test)]
+use serde_json::{Value, json};
 
 #[cfg(test)]
 pub const BASE_URL: &str = "https://lite-api.jup.ag";
@@ -20,3 +22,61 @@ pub const DEFAULT_SLIPPAGE_BPS: u16 = 100;
 pub fn create_test_client() -> JupiterClient {
     JupiterClient::new("https://lite-api.jup.ag")
 }
+
+/// A Swap API quote of 1 SOL for 421.337 JUP computed at slot 100, without a route.
+/// Tests override the fields they check.
+#[cfg(test)]
+pub fn quote_json() -> Value {
+    json!({
+        "inputMint": SOL_MINT,
+        "inAmount": "1000000000",
+        "outputMint": JUP_MINT,
+        "outAmount": "421337000",
+        "otherAmountThreshold": "419230315",
+        "swapMode": "ExactIn",
+        "slippageBps": 50,
+        "priceImpactPct": "0",
+        "routePlan": [],
+        "contextSlot": 100,
+        "timeTaken": 0.0123
+    })
+}
+
+/// A `routePlan` item swapping all of the [`quote_json`] SOL for its JUP through `amm_key`.
+#[cfg(test)]
+pub fn route_hop(amm_key: &str, label: &str) -> Value {
+    json!({
+        "swapInfo": {
+            "ammKey": amm_key,
+            "label": label,
+            "inputMint": SOL_MINT,
+            "outputMint": JUP_MINT,
+            "inAmount": "1000000000",
+            "outAmount": "421337000",
+            "feeAmount": "0",
+            "feeMint": SOL_MINT
+        },
+        "percent": 100
+    })
+}
+
+/// An Ultra order for the [`quote_json`] swap, without a transaction.
+#[cfg(test)]
+pub fn ultra_order_json() -> Value {
+    let mut order = quote_json();
+    let fields = order.as_object_mut().unwrap();
+    fields.remove("contextSlot");
+    fields.remove("timeTaken");
+    if let Value::Object(ultra) = json!({
+        "feeBps": 5,
+        "prioritizationFeeLamports": 0,
+        "swapType": "aggregator",
+        "transaction": null,
+        "gasless": false,
+        "requestId": "0196d1b8-6d49-73ec-a0f0-6f9e7c2b1a2d",
+        "totalTime": 410
+    }) {
+        fields.extend(ultra);
+    }
+    order
+}
@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod concurrency_tests {
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Duration,
+    };
+
+    use futures::future::join_all;
+    use jup_ag_sdk::{JupiterClient, config::ApiFamily};
+
+    use crate::mock::{MockResponse, MockServer};
+
+    /// Starts a server that answers slowly and records the peak number of
+    /// requests it was handling at once.
+    async fn slow_server() -> (MockServer, Arc<AtomicUsize>) {
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let (current, max) = (in_flight.clone(), peak.clone());
+        let server = MockServer::start(move |_| {
+            let (current, max) = (current.clone(), max.clone());
+            async move {
+                let now = current.fetch_add(1, Ordering::SeqCst) + 1;
+                max.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                current.fetch_sub(1, Ordering::SeqCst);
+                MockResponse::json(r#"[{"id": "metis", "name": "Metis", "icon": ""}]"#)
+            }
+        })
+        .await;
+
+        (server, peak)
+    }
+
+    async fn fire_routers(client: &JupiterClient, count: usize) {
+        let results = join_all((0..count).map(|_| client.routers())).await;
+        for result in results {
+            assert_eq!(result.expect("request should succeed").len(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_family_limit() {
+        let (server, peak) = slow_server().await;
+        let client = JupiterClient::builder(&server.url)
+            .max_concurrent_requests_for(ApiFamily::Ultra, 2)
+            .build()
+            .unwrap();
+
+        assert_eq!(client.available_request_slots(ApiFamily::Ultra), Some(2));
+        assert_eq!(client.available_request_slots(ApiFamily::Swap), None);
+
+        fire_routers(&client, 6).await;
+
+        assert_eq!(server.hits(), 6);
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+        assert_eq!(client.available_request_slots(ApiFamily::Ultra), Some(2));
+
+        let stats = client.stats();
+        assert_eq!(stats.requests, 6);
+        assert!(stats.concurrency_waits >= 4);
+    }
+
+    #[tokio::test]
+    async fn test_global_limit() {
+        let (server, peak) = slow_server().await;
+        let client = JupiterClient::builder(&server.url)
+            .max_concurrent_requests(1)
+            .max_concurrent_requests_for(ApiFamily::Ultra, 4)
+            .build()
+            .unwrap();
+
+        assert_eq!(client.available_request_slots(ApiFamily::Ultra), Some(1));
+
+        fire_routers(&client, 4).await;
+
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unlimited_by_default() {
+        let (server, peak) = slow_server().await;
+        let client = JupiterClient::new(&server.url);
+
+        fire_routers(&client, 4).await;
+
+        assert!(peak.load(Ordering::SeqCst) > 1);
+        assert_eq!(client.stats().concurrency_waits, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "greater than zero")]
+    fn test_zero_limit_panics() {
+        let _ = JupiterClient::builder("http://localhost").max_concurrent_requests(0);
+    }
+}
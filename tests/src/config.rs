@@ -0,0 +1,340 @@
+#[cfg(test)]
+mod config_tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientBuilder, JupiterClientError,
+        config::{
+            API_KEY_ENV, ApiFamily, ApiPaths, BASE_URL_ENV, FailoverConfig, HedgeConfig,
+            LITE_API_URL, PRO_API_URL, USER_AGENT,
+        },
+    };
+
+    use crate::{
+        common::BASE_URL,
+        mock::{MockResponse, MockServer},
+    };
+
+    #[test]
+    fn test_default_paths() {
+        let client = JupiterClient::new(BASE_URL);
+
+        assert_eq!(
+            client.endpoint(ApiFamily::Swap, "/quote"),
+            format!("{BASE_URL}/swap/v1/quote")
+        );
+        assert_eq!(
+            client.endpoint(ApiFamily::Ultra, "/order/routers"),
+            format!("{BASE_URL}/ultra/v1/order/routers")
+        );
+        assert_eq!(
+            client.endpoint(ApiFamily::Price, ""),
+            format!("{BASE_URL}/price/v2")
+        );
+        assert_eq!(
+            client.endpoint(ApiFamily::Recurring, "/execute"),
+            format!("{BASE_URL}/recurring/v1/execute")
+        );
+    }
+
+    #[test]
+    fn test_custom_paths() {
+        let paths = ApiPaths::default()
+            .swap("jupiter/swap/")
+            .tokens("/")
+            .set(ApiFamily::Trigger, "/limit/v2");
+
+        let client = JupiterClient::new("https://gateway.example.com/")
+            .with_api_key("key")
+            .with_paths(paths);
+
+        assert_eq!(
+            client.endpoint(ApiFamily::Swap, "/quote"),
+            "https://gateway.example.com/jupiter/swap/quote"
+        );
+        assert_eq!(
+            client.endpoint(ApiFamily::Tokens, "/all"),
+            "https://gateway.example.com/all"
+        );
+        assert_eq!(
+            client.endpoint(ApiFamily::Trigger, "/execute"),
+            "https://gateway.example.com/limit/v2/execute"
+        );
+        // untouched families keep their defaults
+        assert_eq!(client.paths.get(ApiFamily::Ultra), "/ultra/v1");
+    }
+
+    #[test]
+    fn test_with_api_key_keeps_paths() {
+        let client = JupiterClient::new(BASE_URL)
+            .with_paths(ApiPaths::default().ultra("/u"))
+            .with_api_key("key");
+
+        assert_eq!(client.paths.ultra, "/u");
+    }
+
+    #[test]
+    fn test_builder_keep_alive() {
+        let client = JupiterClient::builder(BASE_URL)
+            .tcp_keepalive(Duration::from_secs(30))
+            .http2_keep_alive_interval(Duration::from_secs(20))
+            .http2_keep_alive_timeout(Duration::from_secs(5))
+            .http2_keep_alive_while_idle(true)
+            .http2_adaptive_window(true)
+            .paths(ApiPaths::default().price("/price/v3"))
+            .build()
+            .expect("builder should succeed");
+
+        assert_eq!(client.base_url, BASE_URL);
+        assert_eq!(
+            client.endpoint(ApiFamily::Price, ""),
+            format!("{BASE_URL}/price/v3")
+        );
+
+        // rebuilding with an api key keeps the builder settings
+        let client = client.with_api_key("key");
+        assert_eq!(client.paths.price, "/price/v3");
+    }
+
+    #[test]
+    fn test_builder_invalid_api_key() {
+        let result = JupiterClient::builder(BASE_URL).api_key("bad\nkey").build();
+
+        assert!(matches!(result, Err(JupiterClientError::HeaderError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_builder_http_client_and_default_headers() {
+        let server = MockServer::start(|req| async move {
+            assert_eq!(req.header("x-gateway-token"), Some("token"));
+            assert_eq!(req.header("x-api-key"), Some("key"));
+            assert_eq!(req.header("user-agent"), Some("corp-http"));
+            MockResponse::json("[]")
+        })
+        .await;
+
+        let http = reqwest::Client::builder()
+            .user_agent("corp-http")
+            .build()
+            .unwrap();
+        let client = JupiterClient::builder(&server.url)
+            .http_client(http)
+            .default_header("x-gateway-token", "token")
+            .api_key("key")
+            .build()
+            .expect("builder should succeed");
+
+        client.routers().await.expect("request should succeed");
+        assert_eq!(server.hits(), 1);
+
+        let result = JupiterClient::builder(BASE_URL)
+            .default_header("bad header", "value")
+            .build();
+        assert!(matches!(
+            result,
+            Err(JupiterClientError::ValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_user_agent() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let server = MockServer::start(move |req| {
+            sink.lock()
+                .unwrap()
+                .push(req.header("user-agent").map(str::to_string));
+            async move { MockResponse::json("[]") }
+        })
+        .await;
+
+        let builders = [
+            JupiterClient::builder(&server.url),
+            JupiterClient::builder(&server.url).user_agent_suffix("my-wallet/2.1"),
+            JupiterClient::builder(&server.url)
+                .user_agent_suffix("my-wallet/2.1")
+                .default_header("user-agent", "gateway"),
+        ];
+        for builder in builders {
+            let client = builder.build().expect("builder should succeed");
+            client.routers().await.expect("request should succeed");
+        }
+
+        assert!(USER_AGENT.starts_with("jup-ag-sdk/"));
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen[0].as_deref(), Some(USER_AGENT));
+        assert_eq!(seen[1], Some(format!("{USER_AGENT} my-wallet/2.1")));
+        assert_eq!(seen[2].as_deref(), Some("gateway"));
+
+        let result = JupiterClient::builder(BASE_URL)
+            .user_agent_suffix("bad\nsuffix")
+            .build();
+        assert!(matches!(result, Err(JupiterClientError::HeaderError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_builder_timeout() {
+        let server = MockServer::start(|_| async move {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            MockResponse::json("[]")
+        })
+        .await;
+
+        let client = JupiterClient::builder(&server.url)
+            .connect_timeout(Duration::from_secs(1))
+            .timeout(Duration::from_millis(50))
+            .build()
+            .expect("builder should succeed");
+
+        match client.routers().await {
+            Err(JupiterClientError::RequestError(e)) => assert!(e.is_timeout()),
+            other => panic!("expected a timeout, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_client_from_env() {
+        let server = MockServer::start(|req| async move {
+            assert_eq!(req.header("x-api-key"), Some("env-key"));
+            MockResponse::json("[]")
+        })
+        .await;
+
+        // the only test touching these variables
+        unsafe {
+            std::env::remove_var(BASE_URL_ENV);
+            std::env::remove_var(API_KEY_ENV);
+        }
+        assert_eq!(JupiterClient::default().base_url, LITE_API_URL);
+
+        unsafe { std::env::set_var(API_KEY_ENV, "env-key") };
+        assert_eq!(JupiterClient::default().base_url, PRO_API_URL);
+
+        unsafe { std::env::set_var(BASE_URL_ENV, &server.url) };
+        let client = JupiterClientBuilder::from_env().build().unwrap();
+        unsafe {
+            std::env::remove_var(BASE_URL_ENV);
+            std::env::remove_var(API_KEY_ENV);
+        }
+        assert_eq!(client.base_url, server.url);
+        client.routers().await.expect("request should succeed");
+        assert_eq!(server.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_requests_use_configured_paths() {
+        let server = MockServer::start(|req| async move {
+            assert_eq!(req.method, "GET");
+            assert_eq!(req.path, "/gw/ultra/order/routers");
+            assert_eq!(req.header("x-api-key"), Some("key"));
+            MockResponse::json("[]")
+        })
+        .await;
+
+        let client = JupiterClient::new(&format!("{}/gw", server.url))
+            .with_paths(ApiPaths::default().ultra("ultra"))
+            .with_api_key("key");
+
+        let routers = client.routers().await.expect("request should succeed");
+        assert!(routers.is_empty());
+        assert_eq!(server.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_reuses_connection() {
+        let server = MockServer::start(|req| async move {
+            if req.method == "HEAD" {
+                MockResponse::status(404, "")
+            } else {
+                MockResponse::json("[]")
+            }
+        })
+        .await;
+        // perps requests go to their own host, the same server here
+        let client = JupiterClient::builder(&server.url)
+            .perps_base_url(&server.url)
+            .build()
+            .unwrap();
+
+        client.warm_up().await.expect("warm up should succeed");
+        assert_eq!(server.connections(), 1);
+
+        client.routers().await.expect("request should succeed");
+        assert_eq!(server.hits(), 2);
+        assert_eq!(server.connections(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_builder_pool_settings() {
+        let server = MockServer::json("[]").await;
+
+        let client = JupiterClient::builder(&server.url)
+            .pool_max_idle_per_host(0)
+            .build()
+            .unwrap();
+        client.routers().await.expect("request should succeed");
+        client.routers().await.expect("request should succeed");
+        assert_eq!(server.connections(), 2);
+
+        let client = JupiterClient::builder(&server.url)
+            .pool_idle_timeout(Some(Duration::from_millis(50)))
+            .build()
+            .unwrap();
+        client.routers().await.expect("request should succeed");
+        client.routers().await.expect("request should succeed");
+        assert_eq!(server.connections(), 3);
+        // the idle connection is closed and replaced
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        client.routers().await.expect("request should succeed");
+        assert_eq!(server.connections(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_clones_share_state() {
+        let server = MockServer::json("[]").await;
+        let client = JupiterClient::new(&server.url);
+
+        let handles: Vec<_> = (0..3)
+            .map(|_| {
+                let client = client.clone();
+                tokio::spawn(async move { client.routers().await })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap().expect("request should succeed");
+        }
+
+        assert_eq!(client.stats().requests, 3);
+        assert_eq!(client.clone().stats().requests, 3);
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_every_host() {
+        let head = |_| async { MockResponse::status(404, "") };
+        let primary = MockServer::start(head).await;
+        let failover = MockServer::start(head).await;
+        let hedge = MockServer::start(head).await;
+        let client = JupiterClient::builder(&primary.url)
+            .failover(FailoverConfig::new(&failover.url))
+            .hedge(HedgeConfig::new(&hedge.url, Duration::from_millis(50)))
+            // same host as the base URL, warmed once
+            .perps_base_url(&format!("{}/perps", primary.url))
+            .build()
+            .unwrap();
+
+        client.warm_up().await.expect("warm up should succeed");
+        assert_eq!(primary.hits(), 1);
+        assert_eq!(failover.hits(), 1);
+        assert_eq!(hedge.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_unreachable() {
+        // nothing listens on port 9 of localhost
+        let client = JupiterClient::new("http://127.0.0.1:9");
+        assert!(client.warm_up().await.is_err());
+    }
+}
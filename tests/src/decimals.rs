@@ -0,0 +1,174 @@
+#[cfg(test)]
+mod decimals_tests {
+    use base64::{Engine, engine::general_purpose::STANDARD};
+    use jup_ag_sdk::{
+        JupiterClient,
+        client::{DecimalsResolver, RpcDecimals, StaticDecimals, TokensApiDecimals},
+        referral::{SYSTEM_PROGRAM_ID, TOKEN_2022_PROGRAM_ID},
+        types::{Amount, QuoteRequest, UltraOrderRequest},
+    };
+    use serde_json::json;
+
+    use crate::{
+        common::{JUP_MINT, SOL_MINT, TEST_USER_PUBKEY, USDC_MINT},
+        mock::{MockResponse, MockServer},
+    };
+
+    const PENGU_MINT: &str = "2zMMhcVQEXDtdE6vsFS7S7D5oUodfJHE8vd1gnBouauv";
+
+    /// Serves every token from the Tokens API with 3 decimals.
+    async fn tokens_server() -> MockServer {
+        MockServer::start(|req| async move {
+            let mint = req.path.rsplit('/').next().unwrap_or_default().to_string();
+            MockResponse::json(
+                &json!({
+                    "address": mint,
+                    "name": "Test Token",
+                    "symbol": "TEST",
+                    "decimals": 3,
+                    "logoURI": null,
+                    "tags": [],
+                    "daily_volume": null,
+                    "created_at": "2024-01-01T00:00:00Z",
+                    "freeze_authority": null,
+                    "mint_authority": null,
+                    "permanent_delegate": null,
+                    "minted_at": null,
+                    "extensions": {}
+                })
+                .to_string(),
+            )
+        })
+        .await
+    }
+
+    /// Serves `getMultipleAccounts` with a single mint account owned by `owner`.
+    async fn rpc_server(owner: &'static str, decimals: u8) -> MockServer {
+        MockServer::start(move |_| async move {
+            let mut mint = vec![0u8; 82];
+            mint[44] = decimals;
+            MockResponse::json(
+                &json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "result": {
+                        "context": { "slot": 1 },
+                        "value": [{ "owner": owner, "data": [STANDARD.encode(mint), "base64"] }]
+                    }
+                })
+                .to_string(),
+            )
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_default_resolver_knows_common_tokens() {
+        let server = tokens_server().await;
+        let client = JupiterClient::new(&server.url);
+
+        assert_eq!(client.decimals(SOL_MINT).await.unwrap(), 9);
+        assert_eq!(client.decimals(USDC_MINT).await.unwrap(), 6);
+        assert_eq!(server.hits(), 0, "well-known tokens need no request");
+
+        assert_eq!(client.decimals(PENGU_MINT).await.unwrap(), 3);
+        assert_eq!(server.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_static_resolver() {
+        let server = tokens_server().await;
+        let client = JupiterClient::builder(&server.url)
+            .decimals_resolver(StaticDecimals::new().with(PENGU_MINT, 6))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            client.amount_from_ui(PENGU_MINT, 1.5).await.unwrap(),
+            Amount::new(1_500_000)
+        );
+        assert!(client.decimals(JUP_MINT).await.is_err());
+        assert_eq!(server.hits(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_chained_resolvers() {
+        let server = tokens_server().await;
+        let client = JupiterClient::builder(&server.url)
+            .decimals_resolver(
+                StaticDecimals::new()
+                    .with(JUP_MINT, 6)
+                    .or(TokensApiDecimals),
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(client.decimals(JUP_MINT).await.unwrap(), 6);
+        assert_eq!(
+            client
+                .amount_to_ui_string(PENGU_MINT, Amount::new(1_250))
+                .await
+                .unwrap(),
+            "1.25"
+        );
+        assert_eq!(server.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rpc_resolver_reads_mint_account() {
+        let rpc = rpc_server(TOKEN_2022_PROGRAM_ID, 7).await;
+        let client = JupiterClient::builder("https://lite-api.jup.ag")
+            .decimals_resolver(RpcDecimals::new(&rpc.url))
+            .build()
+            .unwrap();
+
+        assert_eq!(client.decimals(PENGU_MINT).await.unwrap(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_rpc_resolver_rejects_non_mint_accounts() {
+        let rpc = rpc_server(SYSTEM_PROGRAM_ID, 7).await;
+        let client = JupiterClient::new("https://lite-api.jup.ag");
+
+        let result = RpcDecimals::new(&rpc.url)
+            .decimals(&client, TEST_USER_PUBKEY)
+            .await;
+        assert!(result.unwrap_err().to_string().contains("not a token mint"));
+    }
+
+    #[tokio::test]
+    async fn test_requests_from_ui_amounts() {
+        let server = tokens_server().await;
+        let client = JupiterClient::new(&server.url);
+
+        let quote = QuoteRequest::from_ui_amounts("SOL", "usdc", 1.5, &client)
+            .await
+            .unwrap();
+        assert_eq!(
+            (quote.input_mint.as_str(), quote.output_mint.as_str()),
+            (SOL_MINT, USDC_MINT)
+        );
+        assert_eq!(quote.amount, 1_500_000_000);
+        assert_eq!(server.hits(), 0);
+
+        // an unknown mint is looked up, and its symbol is known from then on
+        let order = UltraOrderRequest::from_ui(PENGU_MINT, "JUP", 2.5, &client)
+            .await
+            .unwrap();
+        assert_eq!(order.input_mint, PENGU_MINT);
+        assert_eq!(order.output_mint, JUP_MINT);
+        assert_eq!(order.amount, 2_500);
+        assert_eq!(client.resolve_mint("test").unwrap(), PENGU_MINT);
+
+        assert!(
+            QuoteRequest::from_ui_amounts("NOPE", "USDC", 1.0, &client)
+                .await
+                .is_err()
+        );
+        assert!(
+            QuoteRequest::from_ui_amounts("SOL", "USDC", -1.0, &client)
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod dex_registry_tests {
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError,
+        types::{QuoteRequest, RouterKind},
+    };
+
+    use crate::{
+        common::{JUP_MINT, SOL_MINT, TEST_AMOUNT},
+        mock::{MockResponse, MockServer},
+    };
+
+    const DLMM: &str = "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo";
+    const WHIRLPOOL: &str = "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc";
+    const ORCA_V1: &str = "DjVE6JNiYqPL2QXyCUUh8rNjHrbz9hXHNYt99MQ59qw1";
+    const ORCA_V2: &str = "9W959DqEETiGZocYWCQPaJ6sBmUzgfxXfqGeTEdp3aQP";
+
+    async fn registry_server(fail_labels: bool) -> MockServer {
+        MockServer::start(move |req| async move {
+            if req.path.ends_with("/program-id-to-label") {
+                if fail_labels {
+                    return MockResponse::status(503, "unavailable");
+                }
+                MockResponse::json(&format!(
+                    r#"{{"{DLMM}": "Meteora DLMM", "{WHIRLPOOL}": "Whirlpool", "{ORCA_V2}": "Orca", "{ORCA_V1}": "Orca"}}"#
+                ))
+            } else {
+                MockResponse::json(
+                    r#"[
+                        {"id": "metis", "name": "Metis", "icon": "https://jup.ag/metis.svg"},
+                        {"id": "jupiterz", "name": "JupiterZ", "icon": "https://jup.ag/jupiterz.svg"},
+                        {"id": "newrouter", "name": "New", "icon": ""}
+                    ]"#,
+                )
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_dex_registry_lookups() {
+        let server = registry_server(false).await;
+        let client = JupiterClient::new(&server.url);
+
+        let (first, second) = tokio::join!(client.dex_registry(), client.dex_registry());
+        let registry = first.unwrap();
+        assert_eq!(registry, second.unwrap());
+        assert_eq!(server.hits(), 2);
+
+        assert_eq!(registry.label(DLMM), Some("Meteora DLMM"));
+        assert_eq!(registry.label("unknown"), None);
+        assert_eq!(registry.program_ids("Orca"), [ORCA_V2, ORCA_V1]);
+        assert!(registry.program_ids("Raydium").is_empty());
+        assert!(registry.is_dex("Whirlpool"));
+        assert_eq!(
+            registry.labels().collect::<Vec<_>>(),
+            ["Meteora DLMM", "Orca", "Whirlpool"]
+        );
+
+        let kinds: Vec<_> = registry
+            .routers()
+            .map(|router| (router.id.as_str(), router.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ("jupiterz", RouterKind::Rfq),
+                ("metis", RouterKind::Aggregator),
+                ("newrouter", RouterKind::Unknown),
+            ]
+        );
+        assert_eq!(registry.router("metis").unwrap().name, "Metis");
+
+        client.dex_registry().await.unwrap();
+        assert_eq!(server.hits(), 2);
+        client.refresh_dex_registry().await.unwrap();
+        assert_eq!(server.hits(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_dex_registry_failure_is_not_cached() {
+        let server = registry_server(true).await;
+        let client = JupiterClient::new(&server.url);
+
+        assert!(client.dex_registry().await.is_err());
+        assert!(client.dex_registry().await.is_err());
+        assert_eq!(server.hits(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_validate_dexes() {
+        let server = registry_server(false).await;
+        let client = JupiterClient::new(&server.url);
+        let request = || QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT);
+
+        client.validate_dexes(&request()).await.unwrap();
+        let known = request()
+            .dexes(vec!["Orca".to_string(), "Meteora+DLMM".to_string()])
+            .exclude_dexes(vec!["Whirlpool".to_string()]);
+        client.validate_dexes(&known).await.unwrap();
+
+        let unknown = request()
+            .dexes(vec!["Orca".to_string(), "Raydium".to_string()])
+            .exclude_dexes(vec!["Lifinity".to_string()]);
+        match client.validate_dexes(&unknown).await {
+            Err(JupiterClientError::ValidationError(message)) => {
+                assert_eq!(message, "unknown dexes: Raydium, Lifinity")
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+        // the registry is fetched once
+        assert_eq!(server.hits(), 2);
+    }
+}
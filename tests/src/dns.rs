@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod dns_tests {
+    use std::{
+        net::SocketAddr,
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+    };
+
+    use jup_ag_sdk::{
+        JupiterClient,
+        client::dns::{Name, Resolve, Resolving},
+    };
+
+    use crate::mock::MockServer;
+
+    fn server_addr(server: &MockServer) -> SocketAddr {
+        server.url.trim_start_matches("http://").parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_resolve_override() {
+        let server = MockServer::json("[]").await;
+        let addr = server_addr(&server);
+
+        let client = JupiterClient::builder(&format!("http://jupiter.invalid:{}", addr.port()))
+            .resolve("jupiter.invalid", addr)
+            .build()
+            .unwrap();
+
+        client
+            .routers()
+            .await
+            .expect("request should reach the pinned address");
+        assert_eq!(server.hits(), 1);
+    }
+
+    struct FixedResolver {
+        addr: SocketAddr,
+        lookups: AtomicUsize,
+    }
+
+    impl Resolve for FixedResolver {
+        fn resolve(&self, name: Name) -> Resolving {
+            assert_eq!(name.as_str(), "jupiter.invalid");
+            self.lookups.fetch_add(1, Ordering::SeqCst);
+            let addrs: Vec<SocketAddr> = vec![self.addr];
+            Box::pin(async move { Ok(Box::new(addrs.into_iter()) as _) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_resolver() {
+        let server = MockServer::json("[]").await;
+        let addr = server_addr(&server);
+        let resolver = Arc::new(FixedResolver {
+            addr,
+            lookups: AtomicUsize::new(0),
+        });
+
+        let client = JupiterClient::builder(&format!("http://jupiter.invalid:{}", addr.port()))
+            .dns_resolver(resolver.clone())
+            .build()
+            .unwrap();
+
+        client
+            .routers()
+            .await
+            .expect("request should use the custom resolver");
+        assert_eq!(resolver.lookups.load(Ordering::SeqCst), 1);
+        assert_eq!(server.hits(), 1);
+    }
+}
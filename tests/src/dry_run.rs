@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod dry_run_tests {
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError,
+        client::{DEFAULT_SIGNATURE_HEADER, DryRun, RequestOptions, RequestSigner},
+        types::{QuoteRequest, UltraExecuteOrderRequest},
+    };
+    use reqwest::Method;
+
+    use crate::{
+        common::{JUP_MINT, SOL_MINT, TEST_AMOUNT},
+        mock::MockServer,
+    };
+
+    #[tokio::test]
+    async fn test_dry_run_returns_prepared_request() {
+        let server = MockServer::json("[]").await;
+        let client = JupiterClient::builder(&server.url)
+            .default_header("x-tenant-id", "acme")
+            .request_signer(RequestSigner::hmac_sha256("gateway-secret"))
+            .build()
+            .unwrap();
+
+        let quote = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT);
+        let options = RequestOptions::new().query("traceId", "4bf92f35");
+        let request = DryRun::new(client.get_quote_with(&quote, options))
+            .await
+            .unwrap()
+            .expect("a quote is requested");
+        assert_eq!(request.method(), Method::GET);
+        assert_eq!(request.url().path(), "/swap/v1/quote");
+        let query = request.url().query().unwrap();
+        assert!(query.contains(&format!("inputMint={SOL_MINT}")), "{query}");
+        assert!(query.ends_with("traceId=4bf92f35"), "{query}");
+        assert_eq!(request.headers()["x-tenant-id"], "acme");
+        assert!(request.headers().contains_key(DEFAULT_SIGNATURE_HEADER));
+
+        let execute = UltraExecuteOrderRequest::new("c2lnbmVkLXR4", "request-1");
+        let request = DryRun::new(client.ultra_execute_order(&execute))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(request.method(), Method::POST);
+        let body: serde_json::Value =
+            serde_json::from_slice(request.body().unwrap().as_bytes().unwrap()).unwrap();
+        assert_eq!(body["requestId"], "request-1");
+        assert_eq!(body["signedTransaction"], "c2lnbmVkLXR4");
+
+        assert_eq!(server.hits(), 0);
+        assert_eq!(client.stats().requests, 0);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_keeps_validation_errors() {
+        let server = MockServer::json("[]").await;
+        let client = JupiterClient::new(&server.url);
+
+        let invalid = QuoteRequest::new("not a mint", JUP_MINT, TEST_AMOUNT);
+        let result = DryRun::new(client.get_quote(&invalid)).await;
+        assert!(matches!(
+            result,
+            Err(JupiterClientError::ValidationError(_))
+        ));
+        assert_eq!(server.hits(), 0);
+    }
+}
@@ -0,0 +1,368 @@
+#[cfg(test)]
+mod errors_tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError,
+        config::ApiFamily,
+        error::{BODY_EXCERPT_LEN, ErrorKind, JupiterApiError, QuoteErrorKind},
+        types::{QuoteRequest, UltraExecuteOrderRequest},
+    };
+    use reqwest::StatusCode;
+
+    use crate::{
+        common::{JUP_MINT, SOL_MINT, TEST_AMOUNT},
+        mock::{MockResponse, MockServer},
+    };
+
+    fn api_error(body: &str) -> JupiterClientError {
+        JupiterClientError::ApiError(Box::new(JupiterApiError::new(
+            StatusCode::BAD_REQUEST,
+            "/swap/v1/quote",
+            body.to_string(),
+        )))
+    }
+
+    #[test]
+    fn test_quote_error_kinds() {
+        let cases = [
+            (
+                r#"{"error": "Could not find any route", "errorCode": "COULD_NOT_FIND_ANY_ROUTE"}"#,
+                Some(QuoteErrorKind::NoRouteFound),
+            ),
+            (
+                r#"{"error": "The token So11111111111111111111111111111111111111112 is not tradable", "errorCode": "TOKEN_NOT_TRADABLE"}"#,
+                Some(QuoteErrorKind::TokenNotTradable),
+            ),
+            // message only, no code
+            (
+                r#"{"error": "The value is too small"}"#,
+                Some(QuoteErrorKind::AmountTooSmall),
+            ),
+            (
+                r#"{"error": "Circular arbitrage is disabled"}"#,
+                Some(QuoteErrorKind::CircularArbitrageDisabled),
+            ),
+            (
+                r#"{"error": "Insufficient funds", "code": -1}"#,
+                Some(QuoteErrorKind::InsufficientFunds),
+            ),
+            // plain text body
+            ("No routes found", Some(QuoteErrorKind::NoRouteFound)),
+            (r#"{"error": "Internal server error"}"#, None),
+            ("", None),
+        ];
+
+        for (body, expected) in cases {
+            assert_eq!(api_error(body).quote_error_kind(), expected, "{body}");
+        }
+
+        assert!(api_error("Could not find any route").is_no_route());
+        assert!(!api_error("The value is too small").is_no_route());
+        assert_eq!(
+            JupiterClientError::ValidationError("Could not find any route".to_string())
+                .quote_error_kind(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_route_from_api() {
+        let server = MockServer::start(|_| async {
+            MockResponse::status(
+                400,
+                r#"{"error": "Could not find any route", "errorCode": "COULD_NOT_FIND_ANY_ROUTE"}"#,
+            )
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+
+        let err = client
+            .get_quote(&QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT))
+            .await
+            .unwrap_err();
+
+        assert!(err.is_no_route());
+        let api = err.api_error().unwrap();
+        assert_eq!(api.status, StatusCode::BAD_REQUEST);
+        assert_eq!(api.code.as_deref(), Some("COULD_NOT_FIND_ANY_ROUTE"));
+        assert_eq!(api.message, "Could not find any route");
+        assert_eq!(api.endpoint, "/swap/v1/quote");
+        assert_eq!(
+            err.to_string(),
+            "API returned error: 400 Bad Request from GET /swap/v1/quote: Could not find any route (COULD_NOT_FIND_ANY_ROUTE)"
+        );
+    }
+
+    #[test]
+    fn test_api_error_payloads() {
+        let parse = |body: &str| JupiterApiError::new(StatusCode::BAD_REQUEST, "/", body.into());
+
+        // Ultra
+        let error = parse(r#"{"error": "Insufficient funds", "code": -1, "status": "Failed"}"#);
+        assert_eq!(error.code.as_deref(), Some("-1"));
+        assert_eq!(error.message, "Insufficient funds");
+        // Trigger
+        let error = parse(r#"{"message": "Order not found"}"#);
+        assert_eq!(
+            (error.code, error.message.as_str()),
+            (None, "Order not found")
+        );
+        let error = parse(r#"{"errorMessage": "taker has insufficient balance"}"#);
+        assert_eq!(
+            error.quote_error_kind(),
+            Some(QuoteErrorKind::InsufficientFunds)
+        );
+        // not JSON, or no message
+        assert_eq!(parse(" Bad Gateway\n").message, "Bad Gateway");
+        assert_eq!(parse(r#"{"ok": false}"#).message, r#"{"ok": false}"#);
+    }
+
+    #[tokio::test]
+    async fn test_error_endpoint_method_and_request_id() {
+        let server = MockServer::start(|req| async move {
+            if req.path.starts_with("/ultra/v1/execute") {
+                MockResponse::status(400, r#"{"error": "Insufficient funds", "code": -1}"#)
+                    .with_header("x-request-id", "req-execute")
+            } else {
+                MockResponse::json(r#"{"routers": "moved"}"#)
+                    .with_header("cf-ray", "8f1c2e3d4a5b-AMS")
+            }
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+
+        let err = client
+            .ultra_execute_order(&UltraExecuteOrderRequest::new("c2lnbmVkLXR4", "request-1"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.method(), Some("POST"));
+        assert_eq!(err.endpoint(), Some("/ultra/v1/execute"));
+        assert_eq!(err.request_id(), Some("req-execute"));
+        assert!(
+            err.to_string().ends_with("(request id req-execute)"),
+            "{err}"
+        );
+
+        let err = client.routers().await.unwrap_err();
+        assert_eq!(err.endpoint(), Some("/ultra/v1/order/routers"));
+        assert_eq!(err.request_id(), Some("8f1c2e3d4a5b-AMS"));
+        assert_eq!(err.method(), Some("GET"));
+
+        // with request context, the method of every error is known
+        let client = JupiterClient::builder(&server.url)
+            .request_context_in_errors(true)
+            .build()
+            .unwrap();
+        let err = client.routers().await.unwrap_err();
+        assert_eq!(err.method(), Some("GET"));
+        assert_eq!(err.request_id(), Some("8f1c2e3d4a5b-AMS"));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_error_endpoint_and_method() {
+        let server = MockServer::start(|_| async {
+            MockResponse::status(429, "Rate limit exceeded").with_header("Retry-After", "5")
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+
+        let err = client.routers().await.unwrap_err();
+        assert!(
+            matches!(err, JupiterClientError::RateLimited { .. }),
+            "{err}"
+        );
+        assert_eq!(err.method(), Some("GET"));
+        assert_eq!(err.endpoint(), Some("/ultra/v1/order/routers"));
+    }
+
+    #[tokio::test]
+    async fn test_on_error_context() {
+        let server = MockServer::start(|_| async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            MockResponse::status(
+                400,
+                r#"{"error": "Could not find any route", "errorCode": "COULD_NOT_FIND_ANY_ROUTE"}"#,
+            )
+        })
+        .await;
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let recorded = seen.clone();
+        let client = JupiterClient::builder(&server.url)
+            .request_context_in_errors(true)
+            .on_error(move |ctx| {
+                recorded.lock().unwrap().push((
+                    ctx.family,
+                    ctx.method.to_string(),
+                    ctx.endpoint.to_string(),
+                    ctx.duration,
+                    ctx.status,
+                    ctx.code.clone(),
+                    ctx.kind,
+                ))
+            })
+            .build()
+            .unwrap();
+
+        let err = client
+            .get_quote(&QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Api);
+        assert_eq!(err.kind().to_string(), "api");
+
+        let seen = seen.lock().unwrap();
+        let [(family, method, endpoint, duration, status, code, kind)] = &seen[..] else {
+            panic!("expected one failure, got {seen:?}");
+        };
+        assert_eq!(*family, ApiFamily::Swap);
+        assert_eq!(
+            (method.as_str(), endpoint.as_str()),
+            ("GET", "/swap/v1/quote")
+        );
+        assert!(*duration >= Duration::from_millis(50));
+        assert_eq!(*status, Some(StatusCode::BAD_REQUEST));
+        assert_eq!(code.as_deref(), Some("COULD_NOT_FIND_ANY_ROUTE"));
+        assert_eq!(*kind, ErrorKind::Api);
+    }
+
+    async fn no_route_server() -> MockServer {
+        MockServer::start(|_| async {
+            MockResponse::status(400, r#"{"error": "Could not find any route"}"#)
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_request_context_in_errors() {
+        let server = no_route_server().await;
+        let client = JupiterClient::builder(&server.url)
+            .request_context_in_errors(true)
+            .build()
+            .unwrap();
+
+        let request = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+            .slippage_bps(50)
+            .only_direct_routes(true);
+        let err = client.get_quote(&request).await.unwrap_err();
+
+        let summary = err.request().expect("error should carry the request");
+        assert_eq!(summary.method, "GET");
+        assert_eq!(summary.endpoint, "/swap/v1/quote");
+        assert!(
+            summary
+                .params
+                .contains(&("inputMint".into(), SOL_MINT.into()))
+        );
+        assert!(
+            summary
+                .params
+                .contains(&("amount".into(), TEST_AMOUNT.to_string()))
+        );
+        assert!(
+            summary
+                .params
+                .contains(&("slippageBps".into(), "50".into()))
+        );
+
+        assert!(matches!(err.inner(), JupiterClientError::ApiError(..)));
+        assert!(err.is_no_route());
+
+        let message = err.to_string();
+        assert!(message.contains("GET /swap/v1/quote"), "{message}");
+        assert!(message.contains("inputMint=So11…1112"), "{message}");
+        assert!(message.contains("amount=1000000000"), "{message}");
+    }
+
+    #[tokio::test]
+    async fn test_request_context_omits_transactions() {
+        let server = no_route_server().await;
+        let client = JupiterClient::builder(&server.url)
+            .request_context_in_errors(true)
+            .build()
+            .unwrap();
+
+        let request = UltraExecuteOrderRequest::new("c2lnbmVkLXR4", "request-1");
+        let err = client.ultra_execute_order(&request).await.unwrap_err();
+
+        let summary = err.request().unwrap();
+        assert_eq!(summary.method, "POST");
+        assert_eq!(
+            summary.params,
+            vec![("requestId".into(), "request-1".into())]
+        );
+        assert!(!err.to_string().contains("c2lnbmVkLXR4"));
+    }
+
+    #[tokio::test]
+    async fn test_request_context_off_by_default() {
+        let server = no_route_server().await;
+        let client = JupiterClient::new(&server.url);
+
+        let err = client
+            .get_quote(&QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT))
+            .await
+            .unwrap_err();
+
+        assert!(err.request().is_none());
+        assert!(matches!(err, JupiterClientError::ApiError(..)));
+    }
+
+    #[tokio::test]
+    async fn test_strict_deserialization() {
+        let server = MockServer::json(
+            r#"[{"id": "iris", "name": "Iris", "icon": "https://jup.ag/iris.svg", "weight": 3}]"#,
+        )
+        .await;
+
+        let tolerant = JupiterClient::new(&server.url);
+        assert_eq!(tolerant.routers().await.unwrap()[0].id, "iris");
+
+        let strict = JupiterClient::builder(&server.url)
+            .strict_deserialization(true)
+            .build()
+            .unwrap();
+        let err = strict.routers().await.unwrap_err();
+        assert!(
+            matches!(&err, JupiterClientError::DeserializationError { message, .. } if message == "unknown fields: [0].weight"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deserialization_error_keeps_body() {
+        let server = MockServer::json(r#"{"routers": "moved"}"#).await;
+        let client = JupiterClient::new(&server.url);
+
+        match client.routers().await.unwrap_err() {
+            JupiterClientError::DeserializationError { endpoint, body, .. } => {
+                assert_eq!(endpoint.as_deref(), Some("/ultra/v1/order/routers"));
+                assert_eq!(body.as_deref(), Some(r#"{"routers": "moved"}"#));
+            }
+            other => panic!("expected a deserialization error, got {other:?}"),
+        }
+
+        let long = format!(r#"{{"data": "{}"}}"#, "x".repeat(2_000));
+        let server = MockServer::start(move |_| {
+            let long = long.clone();
+            async move { MockResponse::json(&long) }
+        })
+        .await;
+        let err = JupiterClient::new(&server.url).routers().await.unwrap_err();
+        let JupiterClientError::DeserializationError {
+            body: Some(body), ..
+        } = &err
+        else {
+            panic!("expected a deserialization error, got {err:?}");
+        };
+        assert_eq!(body.len(), BODY_EXCERPT_LEN + "... (2012 bytes)".len());
+        assert!(body.ends_with("xxx... (2012 bytes)"), "{body}");
+        assert!(
+            err.to_string().contains("from /ultra/v1/order/routers"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,197 @@
+#[cfg(test)]
+mod events_tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use jup_ag_sdk::{
+        JupiterClient,
+        config::ApiFamily,
+        events::LifecycleEvent,
+        transaction::{SwapTransaction, Transaction},
+        types::{Amount, QuoteRequest, UltraExecuteOrderRequest, UltraOrderRequest},
+    };
+    use serde_json::json;
+    use solana_hash::Hash;
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_keypair::Keypair;
+    use solana_message::Message;
+    use solana_pubkey::Pubkey;
+    use solana_signer::Signer;
+
+    use crate::{
+        common::{JUP_MINT, SOL_MINT, TEST_AMOUNT, quote_json, ultra_order_json},
+        mock::{MockResponse, MockServer},
+    };
+
+    const REQUEST_ID: &str = "request-1";
+
+    /// A client whose events are collected into the returned list.
+    fn recording_client(url: &str) -> (JupiterClient, Arc<Mutex<Vec<LifecycleEvent>>>) {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        let client = JupiterClient::builder(url)
+            .event_subscriber(move |event: &LifecycleEvent| {
+                recorded.lock().unwrap().push(event.clone())
+            })
+            .build()
+            .unwrap();
+        (client, events)
+    }
+
+    fn order_body() -> String {
+        let mut order = ultra_order_json();
+        order["transaction"] = json!("dW5zaWduZWQ=");
+        order["requestId"] = json!(REQUEST_ID);
+        order.to_string()
+    }
+
+    /// Serves Ultra orders, answering `/execute` with `execute_body`.
+    async fn ultra_server(execute_body: &'static str) -> MockServer {
+        MockServer::start(move |req| async move {
+            if req.path.starts_with("/ultra/v1/order") {
+                MockResponse::json(&order_body())
+            } else {
+                MockResponse::json(execute_body)
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_ultra_flow_events() {
+        let server = ultra_server(r#"{"status": "Success", "signature": "5ig", "code": 0}"#).await;
+        let (client, events) = recording_client(&server.url);
+
+        let order = client
+            .get_ultra_order(&UltraOrderRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT))
+            .await
+            .unwrap();
+        client
+            .ultra_execute_order(&UltraExecuteOrderRequest::new(
+                "c2lnbmVk",
+                &order.request_id,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                LifecycleEvent::QuoteFetched {
+                    family: ApiFamily::Ultra,
+                    input_mint: SOL_MINT.to_string(),
+                    output_mint: JUP_MINT.to_string(),
+                    in_amount: Amount::new(1_000_000_000),
+                    out_amount: Amount::new(421_337_000),
+                    server_time: Duration::from_millis(410),
+                },
+                LifecycleEvent::OrderCreated {
+                    family: ApiFamily::Ultra,
+                    request_id: REQUEST_ID.to_string(),
+                },
+                LifecycleEvent::ExecutionSubmitted {
+                    family: ApiFamily::Ultra,
+                    request_id: REQUEST_ID.to_string(),
+                },
+                LifecycleEvent::ExecutionConfirmed {
+                    family: ApiFamily::Ultra,
+                    request_id: REQUEST_ID.to_string(),
+                    signature: Some("5ig".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_execution_event() {
+        let server = ultra_server(
+            r#"{"status": "Failed", "signature": "5ig", "error": "slippage exceeded", "code": 1}"#,
+        )
+        .await;
+        let (client, events) = recording_client(&server.url);
+
+        client
+            .ultra_execute_order(&UltraExecuteOrderRequest::new("c2lnbmVk", REQUEST_ID))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            events.lock().unwrap().last(),
+            Some(&LifecycleEvent::ExecutionFailed {
+                family: ApiFamily::Ultra,
+                request_id: REQUEST_ID.to_string(),
+                error: "slippage exceeded".to_string(),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_error_is_failed_execution() {
+        let server = MockServer::start(|_| async { MockResponse::status(500, "down") }).await;
+        let (client, events) = recording_client(&server.url);
+
+        client
+            .ultra_execute_order(&UltraExecuteOrderRequest::new("c2lnbmVk", REQUEST_ID))
+            .await
+            .unwrap_err();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(
+            matches!(&events[1], LifecycleEvent::ExecutionFailed { error, .. } if error.contains("down")),
+            "{:?}",
+            events[1]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_quote_fetched_event() {
+        let server =
+            MockServer::start(|_| async move { MockResponse::json(&quote_json().to_string()) })
+                .await;
+        let (client, events) = recording_client(&server.url);
+
+        client
+            .get_quote(&QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT))
+            .await
+            .unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0],
+            LifecycleEvent::QuoteFetched {
+                family: ApiFamily::Swap,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_sign_transaction_event() {
+        let (client, events) = recording_client("http://localhost");
+        let user = Keypair::new();
+        let instruction = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[1],
+            vec![AccountMeta::new(user.pubkey(), true)],
+        );
+        let message =
+            Message::new_with_blockhash(&[instruction], Some(&user.pubkey()), &Hash::default());
+        let mut tx = SwapTransaction::Legacy(Transaction::new_unsigned(message));
+
+        client.sign_transaction(&mut tx, &[&user]).unwrap();
+
+        let SwapTransaction::Legacy(signed) = &tx else {
+            unreachable!()
+        };
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![LifecycleEvent::TransactionSigned {
+                signature: signed.signatures[0].to_string(),
+            }]
+        );
+    }
+}
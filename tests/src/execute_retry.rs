@@ -0,0 +1,204 @@
+#[cfg(test)]
+mod execute_retry_tests {
+    use base64::{Engine, engine::general_purpose::STANDARD};
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError,
+        types::{ExecuteRecurringRequest, ExecuteTriggerOrder, Status, UltraExecuteOrderRequest},
+    };
+
+    use crate::mock::{MockResponse, MockServer};
+
+    /// A signed transaction whose fee payer signature is 64 `fill` bytes.
+    fn signed_transaction(fill: u8) -> (String, String) {
+        let mut bytes = vec![1];
+        bytes.extend([fill; 64]);
+        bytes.extend([0x80, 1, 0, 1, 2, 3, 4, 5, 6, 7]);
+        (
+            STANDARD.encode(bytes),
+            bs58::encode([fill; 64]).into_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_retried_execute_returns_prior_success() {
+        let server = MockServer::start(|_| async {
+            MockResponse::json(
+                r#"{"status": "Success", "signature": "5ig", "slot": "321", "code": 0, "outputAmountResult": "42"}"#,
+            )
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+        let (transaction, _) = signed_transaction(7);
+        let request = UltraExecuteOrderRequest::new(&transaction, "request-1");
+
+        let first = client.ultra_execute_order(&request).await.unwrap();
+        let retried = client.ultra_execute_order(&request).await.unwrap();
+
+        assert_eq!(server.hits(), 1);
+        assert_eq!(retried.signature, first.signature);
+        assert_eq!(retried.output_amount_result, first.output_amount_result);
+
+        // a different transaction under the same request id is sent
+        let (other, _) = signed_transaction(8);
+        client
+            .ultra_execute_order(&UltraExecuteOrderRequest::new(&other, "request-1"))
+            .await
+            .unwrap();
+        assert_eq!(server.hits(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_already_processed_becomes_success() {
+        let server = MockServer::start(|_| async {
+            MockResponse::json(
+                r#"{"status": "Failed", "error": "Transaction already processed", "code": -1}"#,
+            )
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+        let (transaction, signature) = signed_transaction(7);
+        let request = UltraExecuteOrderRequest::new(&transaction, "request-1");
+
+        let response = client.ultra_execute_order(&request).await.unwrap();
+
+        assert_eq!(response.status, Status::Success);
+        assert_eq!(response.signature, Some(signature));
+        assert_eq!(response.error, None);
+    }
+
+    #[tokio::test]
+    async fn test_already_processed_api_error_on_recurring() {
+        let server = MockServer::start(|_| async {
+            MockResponse::status(
+                400,
+                r#"{"error": "This transaction has already been processed"}"#,
+            )
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+        let (transaction, signature) = signed_transaction(3);
+
+        let response = client
+            .execute_recurring_order(&ExecuteRecurringRequest {
+                request_id: "request-2".to_string(),
+                signed_transaction: transaction,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(response.signature, signature);
+        assert_eq!(response.status, "Success");
+    }
+
+    #[tokio::test]
+    async fn test_failed_execute_is_sent_again() {
+        let server = MockServer::start(|_| async {
+            MockResponse::json(
+                r#"{"status": "Failed", "error": "slippage exceeded", "code": 6001}"#,
+            )
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+        let (transaction, _) = signed_transaction(7);
+        let request = UltraExecuteOrderRequest::new(&transaction, "request-1");
+
+        for _ in 0..2 {
+            let response = client.ultra_execute_order(&request).await.unwrap();
+            assert_eq!(response.status, Status::Failed);
+        }
+        assert_eq!(server.hits(), 2);
+
+        // an unsigned transaction has no signature to report
+        let unsigned = STANDARD.encode([[1].as_slice(), &[0; 74]].concat());
+        let server = MockServer::start(|_| async {
+            MockResponse::status(400, r#"{"error": "already processed"}"#)
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+        assert!(
+            client
+                .ultra_execute_order(&UltraExecuteOrderRequest::new(&unsigned, "request-3"))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_paused_execution() {
+        let server = MockServer::start(|req| async move {
+            if req.path.ends_with("/execute") {
+                MockResponse::json(r#"{"status": "Success", "signature": "5ig", "code": 0}"#)
+            } else {
+                MockResponse::json("[]")
+            }
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+        let (transaction, _) = signed_transaction(7);
+        let request = UltraExecuteOrderRequest::new(&transaction, "request-1");
+
+        client.pause_execution();
+        assert!(client.is_execution_paused());
+        let paused = |result: Result<_, JupiterClientError>| {
+            matches!(result, Err(JupiterClientError::ExecutionPaused))
+        };
+        assert!(paused(client.ultra_execute_order(&request).await.map(drop)));
+        assert!(paused(
+            client
+                .execute_trigger_order(&ExecuteTriggerOrder::new("request-2", &transaction))
+                .await
+                .map(drop)
+        ));
+        assert!(paused(
+            client
+                .execute_recurring_order(&ExecuteRecurringRequest {
+                    request_id: "request-3".to_string(),
+                    signed_transaction: transaction.clone(),
+                })
+                .await
+                .map(drop)
+        ));
+        assert_eq!(server.hits(), 0);
+
+        // read-only endpoints keep working
+        assert!(client.routers().await.unwrap().is_empty());
+        assert_eq!(server.hits(), 1);
+
+        client.resume_execution();
+        assert!(!client.is_execution_paused());
+        client.ultra_execute_order(&request).await.unwrap();
+        assert_eq!(server.hits(), 2);
+    }
+
+    #[test]
+    fn test_debug_redacts_signed_transactions_and_api_key() {
+        let (transaction, signature) = signed_transaction(7);
+        let requests = [
+            format!(
+                "{:?}",
+                UltraExecuteOrderRequest::new(&transaction, "request-1")
+            ),
+            format!("{:?}", ExecuteTriggerOrder::new("request-1", &transaction)),
+            format!(
+                "{:?}",
+                ExecuteRecurringRequest {
+                    request_id: "request-1".to_string(),
+                    signed_transaction: transaction.clone(),
+                }
+            ),
+        ];
+        for debug in requests {
+            assert!(!debug.contains(&transaction), "{debug}");
+            assert!(debug.contains("request-1"));
+            assert!(debug.contains(&format!(
+                "<signed transaction {signature}, {} chars>",
+                transaction.len()
+            )));
+        }
+
+        let builder = JupiterClient::builder("https://api.jup.ag").api_key("my-secret-key");
+        assert!(!format!("{builder:?}").contains("my-secret-key"));
+        let client = builder.build().unwrap();
+        assert!(!format!("{client:?}").contains("my-secret-key"));
+    }
+}
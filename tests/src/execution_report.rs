@@ -0,0 +1,381 @@
+#[cfg(test)]
+mod execution_report_tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError,
+        config::ApiFamily,
+        types::{
+            Amount, EXECUTION_REPORT_CSV_HEADER, ExecutionReport, ExecutionStats, ExecutionStatus,
+            QuoteResponse, RouteHop, SwapResponse, UltraExecuteOrderResponse, UltraOrderRequest,
+            UltraOrderResponse,
+        },
+    };
+    use serde_json::{Value, json};
+
+    use crate::{
+        common::{JUP_MINT, SOL_MINT, TEST_AMOUNT, quote_json, route_hop, ultra_order_json},
+        mock::{MockResponse, MockServer},
+    };
+
+    const POOL: &str = "5BKxfWMbmYBAEWvyPZS9esPducUba9GqyMjtLCfbaqyF";
+
+    fn route_plan() -> Value {
+        json!([route_hop(POOL, "Meteora DLMM")])
+    }
+
+    fn order_body() -> String {
+        let mut order = ultra_order_json();
+        order["routePlan"] = route_plan();
+        order["prioritizationFeeLamports"] = json!(5000);
+        order["router"] = json!("iris");
+        order["transaction"] = json!("dW5zaWduZWQ=");
+        order["requestId"] = json!("request-1");
+        order.to_string()
+    }
+
+    fn quote() -> QuoteResponse {
+        let mut quote = quote_json();
+        quote["routePlan"] = route_plan();
+        serde_json::from_value(quote).unwrap()
+    }
+
+    async fn report_for(execute_body: &'static str) -> ExecutionReport {
+        let server = MockServer::start(move |req| async move {
+            if req.path.starts_with("/ultra/v1/order") {
+                MockResponse::json(&order_body())
+            } else {
+                MockResponse::json(execute_body)
+            }
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+
+        let order = client
+            .get_ultra_order(&UltraOrderRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT))
+            .await
+            .unwrap();
+        client
+            .ultra_execute_with_report(&order, "c2lnbmVk")
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_ultra_success_report() {
+        let report = report_for(
+            r#"{"status": "Success", "signature": "5ig", "slot": "321", "code": 0,
+                "inputAmountResult": "1000000000", "outputAmountResult": "420000000"}"#,
+        )
+        .await;
+
+        assert_eq!(report.api, Some(ApiFamily::Ultra));
+        assert_eq!(report.router.as_deref(), Some("iris"));
+        assert_eq!(report.status, ExecutionStatus::Success);
+        assert_eq!(report.request_id.as_deref(), Some("request-1"));
+        assert_eq!(report.signature.as_deref(), Some("5ig"));
+        assert_eq!(report.slot, Some(321));
+        assert_eq!(report.error, None);
+        assert_eq!(report.quoted_out_amount, Some(Amount::new(421_337_000)));
+        assert_eq!(report.executed_out_amount, Some(Amount::new(420_000_000)));
+        assert_eq!(report.fee_bps, Some(5));
+        assert_eq!(report.prioritization_fee_lamports, Some(5000));
+        assert_eq!(
+            report.route,
+            vec![RouteHop {
+                amm_key: POOL.to_string(),
+                label: "Meteora DLMM".to_string(),
+                percent: 100,
+            }]
+        );
+        assert!(report.executed_at_ms.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_ultra_report_from_swap_events() {
+        // SOL -> USDC -> JUP, with the first hop split over two pools
+        let report = report_for(
+            r#"{"status": "Success", "signature": "5ig", "code": 0, "swapEvents": [
+                {"inputMint": "So11111111111111111111111111111111111111112", "inputAmount": "600000000",
+                 "outputMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", "outputAmount": "90000000",
+                 "amm": "5BKxfWMbmYBAEWvyPZS9esPducUba9GqyMjtLCfbaqyF"},
+                {"inputMint": "So11111111111111111111111111111111111111112", "inputAmount": "400000000",
+                 "outputMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", "outputAmount": "60000000"},
+                {"inputMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", "inputAmount": "150000000",
+                 "outputMint": "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN", "outputAmount": "420500000"}
+            ]}"#,
+        )
+        .await;
+
+        assert_eq!(report.executed_in_amount, Some(Amount::new(1_000_000_000)));
+        assert_eq!(report.executed_out_amount, Some(Amount::new(420_500_000)));
+    }
+
+    #[test]
+    fn test_swap_event_amounts() {
+        let execution: UltraExecuteOrderResponse = serde_json::from_value(json!({
+            "status": "Success",
+            "code": 0,
+            "swapEvents": [
+                { "inputMint": SOL_MINT, "inputAmount": "1000", "outputMint": JUP_MINT, "outputAmount": "42", "amm": POOL },
+                { "inputMint": SOL_MINT, "outputMint": JUP_MINT, "outputAmount": "1" }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(execution.events().len(), 2);
+        assert_eq!(execution.events()[0].amm.as_deref(), Some(POOL));
+        // the second event has no input amount
+        assert_eq!(execution.executed_amounts(SOL_MINT, JUP_MINT), None);
+        assert_eq!(execution.executed_amounts(JUP_MINT, SOL_MINT), None);
+
+        let execution: UltraExecuteOrderResponse =
+            serde_json::from_value(json!({ "status": "Success", "code": 0 })).unwrap();
+        assert!(execution.events().is_empty());
+        assert_eq!(execution.executed_amounts(SOL_MINT, JUP_MINT), None);
+    }
+
+    #[tokio::test]
+    async fn test_ultra_failed_report() {
+        let report =
+            report_for(r#"{"status": "Failed", "error": "slippage exceeded", "code": 6001}"#).await;
+
+        assert_eq!(report.status, ExecutionStatus::Failed);
+        assert_eq!(report.error.as_deref(), Some("slippage exceeded"));
+        assert_eq!(report.signature, None);
+        assert_eq!(report.executed_out_amount, None);
+    }
+
+    #[test]
+    fn test_slippage_errors() {
+        let order: UltraOrderResponse = serde_json::from_str(&order_body()).unwrap();
+        let execution =
+            |body: Value| serde_json::from_value::<UltraExecuteOrderResponse>(body).unwrap();
+
+        let failed = execution(json!({
+            "status": "Failed",
+            "signature": "5ig",
+            "error": "custom program error: 0x1771",
+            "code": 6001,
+            "outputAmountResult": "418000000"
+        }));
+        match failed.slippage_error(&order) {
+            Some(JupiterClientError::SlippageExceeded {
+                quoted_out_amount,
+                min_out_amount,
+                actual_out_amount,
+                signature,
+            }) => {
+                assert_eq!(quoted_out_amount, Amount::new(421_337_000));
+                assert_eq!(min_out_amount, Amount::new(419_230_315));
+                assert_eq!(actual_out_amount, Some(Amount::new(418_000_000)));
+                assert_eq!(signature.as_deref(), Some("5ig"));
+            }
+            other => panic!("expected a slippage error, got {other:?}"),
+        }
+        // recognized by the message alone
+        let failed = execution(
+            json!({"status": "Failed", "error": "Slippage tolerance exceeded", "code": -1000}),
+        );
+        assert!(failed.slippage_error(&order).is_some());
+        let failed =
+            execution(json!({"status": "Failed", "error": "Transaction expired", "code": -1000}));
+        assert!(failed.slippage_error(&order).is_none());
+        let landed = execution(json!({"status": "Success", "signature": "5ig", "code": 0}));
+        assert!(landed.slippage_error(&order).is_none());
+
+        let swap: SwapResponse = serde_json::from_value(json!({
+            "swapTransaction": "dW5zaWduZWQ=",
+            "lastValidBlockHeight": 1,
+            "prioritizationFeeLamports": 0,
+            "simulationError": {"error": "Error processing Instruction 3: custom program error: 0x1771"}
+        }))
+        .unwrap();
+        let error = swap.slippage_error(&quote()).unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Slippage tolerance exceeded: quoted 421337000, minimum 419230315"
+        );
+    }
+
+    #[test]
+    fn test_swap_report_lifecycle() {
+        let quoted_at = UNIX_EPOCH + Duration::from_millis(1_700_000_000_000);
+        let report = ExecutionReport::from_swap(&quote(), quoted_at);
+        assert_eq!(report.status, ExecutionStatus::Pending);
+        assert_eq!(report.quoted_at_ms, Some(1_700_000_000_000));
+
+        let report = report.confirmed(
+            "5ig",
+            Some(Amount::new(1_000_000_000)),
+            Some(Amount::new(421_000_000)),
+            quoted_at + Duration::from_secs(2),
+        );
+        assert_eq!(report.status, ExecutionStatus::Success);
+        assert_eq!(report.signature.as_deref(), Some("5ig"));
+        assert_eq!(report.executed_at_ms, Some(1_700_000_002_000));
+    }
+
+    #[test]
+    fn test_realized_slippage() {
+        let confirmed = |input: u64, output: u64| {
+            ExecutionReport::from_swap(&quote(), UNIX_EPOCH).confirmed(
+                "5ig",
+                Some(Amount::new(input)),
+                Some(Amount::new(output)),
+                UNIX_EPOCH,
+            )
+        };
+
+        let bps = confirmed(1_000_000_000, 420_000_000)
+            .realized_slippage_bps()
+            .unwrap();
+        assert!((bps - 31.73).abs() < 0.01, "{bps}");
+        // half filled at the quoted price
+        assert_eq!(
+            confirmed(500_000_000, 210_668_500).realized_slippage_bps(),
+            Some(0.0)
+        );
+        assert!(
+            confirmed(1_000_000_000, 422_000_000)
+                .realized_slippage_bps()
+                .unwrap()
+                < 0.0
+        );
+        assert_eq!(
+            ExecutionReport::from_swap(&quote(), UNIX_EPOCH).realized_slippage_bps(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_execution_stats() {
+        let with_router = |report: ExecutionReport, router: &str| ExecutionReport {
+            router: Some(router.to_string()),
+            ..report
+        };
+        let confirmed = |output: u64| {
+            ExecutionReport::from_swap(&quote(), UNIX_EPOCH).confirmed(
+                "5ig",
+                Some(Amount::new(1_000_000_000)),
+                Some(Amount::new(output)),
+                UNIX_EPOCH,
+            )
+        };
+        let pending = ExecutionReport::from_swap(&quote(), UNIX_EPOCH);
+        let failed = pending
+            .clone()
+            .failed(None, "slippage exceeded", UNIX_EPOCH);
+        let reports = [
+            with_router(confirmed(420_000_000), "iris"),
+            with_router(confirmed(421_337_000), "iris"),
+            with_router(failed, "jupiterz"),
+            with_router(pending, "jupiterz"),
+        ];
+
+        let stats: ExecutionStats = reports.iter().collect();
+        let iris = stats.router("iris").unwrap();
+        assert_eq!((iris.executions, iris.successes, iris.measured), (2, 2, 2));
+        assert_eq!(iris.fill_rate(), Some(1.0));
+        let average = iris.average_slippage_bps().unwrap();
+        assert!((average - 15.87).abs() < 0.01, "{average}");
+        assert!((iris.worst_slippage_bps.unwrap() - 31.73).abs() < 0.01);
+
+        let jupiterz = stats.router("jupiterz").unwrap();
+        assert_eq!(jupiterz.executions, 1);
+        assert_eq!(jupiterz.fill_rate(), Some(0.0));
+        assert_eq!(jupiterz.average_slippage_bps(), None);
+
+        let dexes: Vec<_> = stats
+            .dexes()
+            .map(|(label, dex)| (label, dex.executions, dex.successes))
+            .collect();
+        assert_eq!(dexes, [("Meteora DLMM", 3, 2)]);
+
+        assert_eq!(stats.routers_to_exclude(1, 0.5, 10.0), ["iris", "jupiterz"]);
+        assert_eq!(stats.routers_to_exclude(1, 0.5, 20.0), ["jupiterz"]);
+        assert!(stats.routers_to_exclude(2, 0.5, 20.0).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_client_records_ultra_executions() {
+        let server = MockServer::start(|req| async move {
+            if req.path.starts_with("/ultra/v1/order") {
+                MockResponse::json(&order_body())
+            } else {
+                MockResponse::json(
+                    r#"{"status": "Success", "signature": "5ig", "code": 0,
+                        "inputAmountResult": "1000000000", "outputAmountResult": "421337000"}"#,
+                )
+            }
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+        let order = client
+            .get_ultra_order(&UltraOrderRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT))
+            .await
+            .unwrap();
+        client
+            .ultra_execute_with_report(&order, "c2lnbmVk")
+            .await
+            .unwrap();
+        client.record_execution(&ExecutionReport::from_swap(&quote(), UNIX_EPOCH).failed(
+            None,
+            "blockhash expired",
+            UNIX_EPOCH,
+        ));
+
+        let stats = client.execution_stats();
+        let iris = stats.router("iris").unwrap();
+        assert_eq!((iris.executions, iris.successes), (1, 1));
+        assert_eq!(iris.average_slippage_bps(), Some(0.0));
+        assert_eq!(stats.dex("Meteora DLMM").unwrap().executions, 2);
+    }
+
+    #[test]
+    fn test_json_lines_round_trip() {
+        let report = ExecutionReport::from_swap(&quote(), UNIX_EPOCH).failed(
+            None,
+            "blockhash expired",
+            UNIX_EPOCH,
+        );
+
+        let value: Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(value["api"], "swap");
+        assert_eq!(value["status"], "failed");
+        assert_eq!(value["quotedOutAmount"], "421337000");
+
+        let mut out = Vec::new();
+        ExecutionReport::write_json_lines(&[report.clone(), report.clone()], &mut out).unwrap();
+        let lines: Vec<ExecutionReport> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines, vec![report.clone(), report]);
+    }
+
+    #[test]
+    fn test_csv_export() {
+        let report = ExecutionReport::from_swap(&quote(), UNIX_EPOCH).failed(
+            Some("5ig"),
+            "failed, \"custom\" error",
+            UNIX_EPOCH,
+        );
+
+        let mut out = Vec::new();
+        ExecutionReport::write_csv(&[report], &mut out).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], EXECUTION_REPORT_CSV_HEADER.join(","));
+        assert_eq!(
+            lines[1],
+            format!(
+                "swap,failed,,5ig,,\"failed, \"\"custom\"\" error\",{SOL_MINT},{JUP_MINT},\
+                 1000000000,421337000,,,50,,,,Meteora DLMM@{POOL}:100,0,0"
+            )
+        );
+    }
+}
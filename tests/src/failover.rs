@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod failover_tests {
+    use std::sync::{Arc, Mutex};
+
+    use jup_ag_sdk::{
+        JupiterClient,
+        client::ResponseMeta,
+        config::{ApiFamily, FailoverConfig},
+        events::LifecycleEvent,
+        types::UltraExecuteOrderRequest,
+    };
+
+    use crate::mock::{MockResponse, MockServer};
+
+    const LABELS: &str = r#"{"JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4": "Jupiter"}"#;
+
+    fn failover_client(primary: &str, failover: FailoverConfig) -> JupiterClient {
+        JupiterClient::builder(primary)
+            .failover(failover)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_failover_on_server_error() {
+        let primary = MockServer::start(|_| async { MockResponse::status(503, "down") }).await;
+        let secondary = MockServer::json(LABELS).await;
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let seen = events.clone();
+        let client = JupiterClient::builder(&primary.url)
+            .failover(FailoverConfig::new(&secondary.url))
+            .event_subscriber(move |event: &LifecycleEvent| {
+                seen.lock().unwrap().push(event.clone())
+            })
+            .build()
+            .unwrap();
+
+        let (labels, meta) = ResponseMeta::capture(client.get_program_id_to_label()).await;
+        assert_eq!(labels.unwrap().len(), 1);
+        assert_eq!((primary.hits(), secondary.hits()), (1, 1));
+        assert_eq!(
+            meta,
+            ResponseMeta {
+                base_url: Some(secondary.url.clone()),
+                failed_over: true,
+            }
+        );
+        assert_eq!(client.stats().failovers, 1);
+        assert!(matches!(
+            &events.lock().unwrap()[..],
+            [LifecycleEvent::FailedOver { family: ApiFamily::Swap, base_url, .. }]
+                if *base_url == secondary.url
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_failover_on_connection_error() {
+        let secondary = MockServer::json(LABELS).await;
+        // nothing listens on port 1
+        let client = failover_client("http://127.0.0.1:1", FailoverConfig::new(&secondary.url));
+
+        assert_eq!(client.get_program_id_to_label().await.unwrap().len(), 1);
+        assert_eq!(client.stats().failovers, 1);
+    }
+
+    #[tokio::test]
+    async fn test_no_failover_when_primary_answers() {
+        let primary = MockServer::json(LABELS).await;
+        let secondary = MockServer::json(LABELS).await;
+        let client = failover_client(&primary.url, FailoverConfig::new(&secondary.url));
+
+        let (labels, meta) = ResponseMeta::capture(client.get_program_id_to_label()).await;
+        assert!(labels.is_ok());
+        assert_eq!(meta.base_url.as_deref(), Some(primary.url.as_str()));
+        assert!(!meta.failed_over);
+
+        // client errors are the caller's, the other host would answer the same
+        let primary = MockServer::start(|_| async { MockResponse::status(400, "bad") }).await;
+        let client = failover_client(&primary.url, FailoverConfig::new(&secondary.url));
+        assert!(client.get_program_id_to_label().await.is_err());
+        assert_eq!(secondary.hits(), 0);
+        assert_eq!(client.stats().failovers, 0);
+    }
+
+    #[tokio::test]
+    async fn test_failover_skips_non_idempotent_and_other_families() {
+        let primary = MockServer::start(|_| async { MockResponse::status(503, "down") }).await;
+        let secondary = MockServer::json(LABELS).await;
+
+        let client = failover_client(&primary.url, FailoverConfig::new(&secondary.url));
+        let request = UltraExecuteOrderRequest::new("c2lnbmVkLXR4", "request-1");
+        assert!(client.ultra_execute_order(&request).await.is_err());
+        assert_eq!(secondary.hits(), 0);
+
+        let client = failover_client(
+            &primary.url,
+            FailoverConfig::new(&secondary.url).families(&[ApiFamily::Ultra]),
+        );
+        assert!(client.get_program_id_to_label().await.is_err());
+        assert_eq!(secondary.hits(), 0);
+        assert_eq!(client.stats().failovers, 0);
+    }
+}
@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod fallback_tests {
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Duration,
+    };
+
+    use jup_ag_sdk::{JupiterClient, JupiterClientError, client::Freshness};
+
+    use crate::mock::{MockResponse, MockServer};
+
+    const ROUTERS: &str = r#"[{"id": "metis", "name": "Metis", "icon": ""}]"#;
+
+    /// Answers the first request successfully and every later one with `status`.
+    async fn flaky_server(status: u16) -> MockServer {
+        let calls = Arc::new(AtomicUsize::new(0));
+        MockServer::start(move |_| {
+            let calls = calls.clone();
+            async move {
+                if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                    MockResponse::json(ROUTERS)
+                } else {
+                    MockResponse::status(status, "upstream unavailable")
+                }
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_serves_stale_on_outage() {
+        let server = flaky_server(503).await;
+        let client = JupiterClient::builder(&server.url)
+            .offline_fallback(Duration::from_secs(60))
+            .build()
+            .unwrap();
+
+        let fresh = client.routers_or_cached().await.unwrap();
+        assert!(!fresh.is_stale());
+        assert_eq!(fresh.data[0].id, "metis");
+
+        let stale = client.routers_or_cached().await.unwrap();
+        assert!(stale.is_stale());
+        assert_eq!(stale.data[0].id, "metis");
+        match stale.freshness {
+            Freshness::Stale { age, cause } => {
+                assert!(age < Duration::from_secs(60));
+                assert!(
+                    matches!(cause, JupiterClientError::ApiError(e) if e.status.as_u16() == 503)
+                );
+            }
+            Freshness::Fresh => unreachable!(),
+        }
+
+        // the plain method never serves cached data
+        assert!(client.routers().await.is_err());
+
+        let stats = client.stats();
+        assert_eq!(stats.requests, 3);
+        assert_eq!(stats.stale_responses, 1);
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_are_not_masked() {
+        let server = flaky_server(400).await;
+        let client = JupiterClient::builder(&server.url)
+            .offline_fallback(Duration::from_secs(60))
+            .build()
+            .unwrap();
+
+        client.routers_or_cached().await.unwrap();
+        assert!(client.routers_or_cached().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_expired_entries_are_not_served() {
+        let server = flaky_server(503).await;
+        let client = JupiterClient::builder(&server.url)
+            .offline_fallback(Duration::ZERO)
+            .build()
+            .unwrap();
+
+        client.routers_or_cached().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert!(client.routers_or_cached().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_disabled_by_default() {
+        let server = flaky_server(503).await;
+        let client = JupiterClient::new(&server.url);
+
+        assert!(!client.routers_or_cached().await.unwrap().is_stale());
+        assert!(client.routers_or_cached().await.is_err());
+    }
+}
@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod hedging_tests {
+    use std::time::{Duration, Instant};
+
+    use jup_ag_sdk::{JupiterClient, config::HedgeConfig};
+
+    use crate::mock::{MockResponse, MockServer};
+
+    const LABELS: &str = r#"{"JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4": "Jupiter"}"#;
+
+    /// Answers after `delay` with `response`.
+    async fn server(delay: Duration, response: MockResponse) -> MockServer {
+        MockServer::start(move |_| {
+            let response = response.clone();
+            async move {
+                tokio::time::sleep(delay).await;
+                response
+            }
+        })
+        .await
+    }
+
+    fn client(primary: &MockServer, secondary: &MockServer) -> JupiterClient {
+        JupiterClient::builder(&primary.url)
+            .hedge(HedgeConfig::new(&secondary.url, Duration::from_millis(50)))
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_hedge_returns_first_success() {
+        let primary = server(Duration::from_secs(2), MockResponse::json(LABELS)).await;
+        let secondary = server(Duration::ZERO, MockResponse::json(LABELS)).await;
+        let client = client(&primary, &secondary);
+
+        let started = Instant::now();
+        let labels = client.get_program_id_to_label().await.unwrap();
+        assert_eq!(labels.len(), 1);
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(secondary.hits(), 1);
+        let stats = client.stats();
+        assert_eq!((stats.hedges, stats.hedge_wins), (1, 1));
+
+        // not a hedged family
+        let primary = server(Duration::from_millis(200), MockResponse::json("[]")).await;
+        let client = JupiterClient::builder(&primary.url)
+            .hedge(HedgeConfig::new(&secondary.url, Duration::from_millis(50)))
+            .build()
+            .unwrap();
+        assert!(client.routers().await.unwrap().is_empty());
+        assert_eq!(secondary.hits(), 1);
+        assert_eq!(client.stats().hedges, 0);
+    }
+
+    #[tokio::test]
+    async fn test_hedge_not_sent_for_fast_responses() {
+        let primary = server(Duration::ZERO, MockResponse::json(LABELS)).await;
+        let secondary = server(Duration::ZERO, MockResponse::json(LABELS)).await;
+        let client = client(&primary, &secondary);
+
+        client.get_program_id_to_label().await.unwrap();
+        assert_eq!(secondary.hits(), 0);
+        assert_eq!(client.stats().hedges, 0);
+    }
+
+    #[tokio::test]
+    async fn test_hedge_failure_waits_for_primary() {
+        let primary = server(Duration::from_millis(200), MockResponse::json(LABELS)).await;
+        let secondary = server(Duration::ZERO, MockResponse::status(503, "down")).await;
+        let client = client(&primary, &secondary);
+
+        assert_eq!(client.get_program_id_to_label().await.unwrap().len(), 1);
+        assert_eq!(secondary.hits(), 1);
+        let stats = client.stats();
+        assert_eq!((stats.hedges, stats.hedge_wins), (1, 0));
+    }
+}
@@ -0,0 +1,343 @@
+#[cfg(test)]
+mod hooks_tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::{Duration, Instant},
+    };
+
+    use reqwest::Method;
+
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError,
+        client::{
+            CancellationToken, DEFAULT_SIGNATURE_HEADER, DEFAULT_TIMESTAMP_HEADER, RequestOptions,
+            RequestSigner, SigningInput,
+        },
+        config::ApiFamily,
+        types::{QuoteRequest, UltraExecuteOrderRequest},
+    };
+
+    use crate::{
+        common::{JUP_MINT, SOL_MINT, TEST_AMOUNT},
+        mock::{MockResponse, MockServer},
+    };
+
+    #[tokio::test]
+    async fn test_query_hook() {
+        let server = MockServer::start(|req| async move {
+            assert!(req.path.starts_with("/swap/v1/quote?"), "{}", req.path);
+            assert!(req.path.contains("platformFeeBps=20"), "{}", req.path);
+            assert!(req.path.contains("onlyDirectRoutes=true"), "{}", req.path);
+            // error out after checking, the quote body itself doesn't matter here
+            MockResponse::status(400, "checked")
+        })
+        .await;
+
+        let client = JupiterClient::builder(&server.url)
+            .request_hook(|req| {
+                assert_eq!(req.family, ApiFamily::Swap);
+                assert_eq!(req.query_param("inputMint"), Some(SOL_MINT));
+                req.default_query_param("platformFeeBps", 20);
+            })
+            .request_hook(|req| req.set_query_param("onlyDirectRoutes", true))
+            .build()
+            .unwrap();
+
+        let request = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT).only_direct_routes(false);
+        let err = client.get_quote(&request).await.unwrap_err();
+        assert!(err.to_string().contains("checked"), "{err}");
+        assert_eq!(server.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_body_hook() {
+        let server = MockServer::start(|req| async move {
+            assert_eq!(req.method, "POST");
+            let body: serde_json::Value = serde_json::from_str(&req.body).unwrap();
+            assert_eq!(body["signedTransaction"], "tx");
+            assert_eq!(body["requestId"], "overridden");
+            assert_eq!(body["integrator"], "my-app");
+            MockResponse::json(r#"{"status": "Success", "code": 0}"#)
+        })
+        .await;
+
+        let client = JupiterClient::builder(&server.url)
+            .request_hook(|req| {
+                req.default_body_field("integrator", "my-app");
+                req.default_body_field("signedTransaction", "ignored, already set");
+                req.set_body_field("requestId", "overridden");
+            })
+            .build()
+            .unwrap();
+
+        let request = UltraExecuteOrderRequest::new("tx", "id");
+        let response = client.ultra_execute_order(&request).await.unwrap();
+        assert_eq!(response.code, 0);
+    }
+
+    #[tokio::test]
+    async fn test_on_error() {
+        let server = MockServer::start(|_| async {
+            MockResponse::status(
+                400,
+                r#"{"error": "Could not find any route", "errorCode": "COULD_NOT_FIND_ANY_ROUTE"}"#,
+            )
+        })
+        .await;
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let client = JupiterClient::builder(&server.url)
+            .on_error(move |ctx| {
+                sink.lock().unwrap().push((
+                    ctx.family,
+                    ctx.method.to_string(),
+                    ctx.endpoint.to_string(),
+                    ctx.attempt,
+                    ctx.status.map(|s| s.as_u16()),
+                    ctx.code.clone(),
+                ));
+            })
+            .build()
+            .unwrap();
+
+        let request = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT);
+        assert!(client.get_quote(&request).await.is_err());
+
+        // validation errors are raised before sending and not reported
+        let invalid = QuoteRequest::new("not a mint", JUP_MINT, TEST_AMOUNT);
+        assert!(client.get_quote(&invalid).await.is_err());
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(
+            *seen,
+            vec![(
+                ApiFamily::Swap,
+                "GET".to_string(),
+                "/swap/v1/quote".to_string(),
+                1,
+                Some(400),
+                Some("COULD_NOT_FIND_ANY_ROUTE".to_string()),
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_on_error_deserialization() {
+        let server = MockServer::json("not json").await;
+
+        let codes = Arc::new(Mutex::new(Vec::new()));
+        let sink = codes.clone();
+        let client = JupiterClient::builder(&server.url)
+            .on_error(move |ctx| {
+                sink.lock()
+                    .unwrap()
+                    .push((ctx.status, ctx.error.to_string()))
+            })
+            .build()
+            .unwrap();
+
+        assert!(client.routers().await.is_err());
+
+        let codes = codes.lock().unwrap();
+        assert_eq!(codes.len(), 1);
+        assert_eq!(codes[0].0, None);
+        assert!(codes[0].1.contains("deserialize"), "{}", codes[0].1);
+    }
+
+    #[tokio::test]
+    async fn test_request_signer() {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let sink = requests.clone();
+        let server = MockServer::start(move |req| {
+            sink.lock().unwrap().push(req);
+            async { MockResponse::status(400, "checked") }
+        })
+        .await;
+
+        let signer = RequestSigner::hmac_sha256("gateway-secret").prefix("sha256=");
+        let client = JupiterClient::builder(&server.url)
+            .request_hook(|req| req.set_body_field("requestId", "hooked"))
+            .request_signer(signer.clone())
+            .build()
+            .unwrap();
+        let request = UltraExecuteOrderRequest::new("signed-tx", "request-1");
+        assert!(client.ultra_execute_order(&request).await.is_err());
+        assert!(client.routers().await.is_err());
+
+        let seen = requests.lock().unwrap().clone();
+        assert_eq!(seen.len(), 2);
+        for req in &seen {
+            let timestamp: u64 = req
+                .header(DEFAULT_TIMESTAMP_HEADER)
+                .unwrap()
+                .parse()
+                .unwrap();
+            let (path, query) = match req.path.split_once('?') {
+                Some((path, query)) => (path, Some(query)),
+                None => (req.path.as_str(), None),
+            };
+            let expected = signer.sign(&SigningInput {
+                method: &req.method.parse().unwrap(),
+                path,
+                query,
+                timestamp: Some(timestamp),
+                body: req.body.as_bytes(),
+            });
+            assert!(expected.starts_with("sha256="));
+            assert_eq!(
+                req.header(DEFAULT_SIGNATURE_HEADER),
+                Some(expected.as_str())
+            );
+        }
+        // signed after hooks, over the body that was sent
+        assert!(seen[0].body.contains("hooked"), "{}", seen[0].body);
+
+        // a custom scheme without a timestamp
+        let signer = RequestSigner::hmac_sha512("gateway-secret")
+            .header("x-gateway-signature")
+            .without_timestamp()
+            .message(|input| format!("{} {}", input.method, input.path).into_bytes());
+        assert!(!format!("{signer:?}").contains("gateway-secret"));
+        let client = JupiterClient::builder(&server.url)
+            .request_signer(signer.clone())
+            .build()
+            .unwrap();
+        assert!(client.routers().await.is_err());
+
+        let requests = requests.lock().unwrap();
+        let req = requests.last().unwrap();
+        assert!(req.header(DEFAULT_TIMESTAMP_HEADER).is_none());
+        let expected = signer.sign(&SigningInput {
+            method: &Method::GET,
+            path: "/ultra/v1/order/routers",
+            query: None,
+            timestamp: None,
+            body: &[],
+        });
+        assert_eq!(expected.len(), 128);
+        assert_eq!(req.header("x-gateway-signature"), Some(expected.as_str()));
+    }
+
+    #[tokio::test]
+    async fn test_request_options() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let server = MockServer::start(move |req| {
+            sink.lock().unwrap().push((
+                req.path.clone(),
+                req.header("x-tenant-id").map(str::to_string),
+            ));
+            async move { MockResponse::status(400, "checked") }
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+        let tenant = |tenant: &str| {
+            RequestOptions::new()
+                .header("x-tenant-id", tenant)
+                .query("traceId", "abc")
+        };
+
+        let request = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT);
+        let _ = client.get_quote_with(&request, tenant("acme")).await;
+        let _ = tenant("globex").scope(client.routers()).await;
+        let _ = client.routers().await;
+        // the inner scope wins
+        let _ = tenant("acme")
+            .scope(async { tenant("initech").scope(client.routers()).await })
+            .await;
+
+        let invalid = RequestOptions::new().header("bad header", "value");
+        assert!(matches!(
+            invalid.scope(client.routers()).await,
+            Err(JupiterClientError::ValidationError(_))
+        ));
+
+        let seen = seen.lock().unwrap();
+        assert!(seen[0].0.starts_with("/swap/v1/quote?"), "{}", seen[0].0);
+        assert!(seen[0].0.ends_with("&traceId=abc"), "{}", seen[0].0);
+        assert_eq!(seen[0].1.as_deref(), Some("acme"));
+        assert_eq!(seen[1].0, "/ultra/v1/order/routers?traceId=abc");
+        assert_eq!(seen[1].1.as_deref(), Some("globex"));
+        assert_eq!(seen[2], ("/ultra/v1/order/routers".to_string(), None));
+        assert_eq!(seen[3].1.as_deref(), Some("initech"));
+    }
+
+    #[tokio::test]
+    async fn test_request_options_timeout() {
+        let server = MockServer::start(|_| async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            MockResponse::json("[]")
+        })
+        .await;
+        let client = JupiterClient::builder(&server.url)
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+        let is_timeout = |result: Result<_, JupiterClientError>| matches!(result, Err(JupiterClientError::RequestError(e)) if e.is_timeout());
+
+        let fast = RequestOptions::new().timeout(Duration::from_millis(50));
+        assert!(is_timeout(fast.scope(client.routers()).await));
+
+        let expired = RequestOptions::new().deadline(Instant::now());
+        assert!(is_timeout(expired.scope(client.routers()).await));
+
+        // the sooner of the timeout and the deadline applies
+        let slow = RequestOptions::new()
+            .timeout(Duration::from_millis(50))
+            .deadline(Instant::now() + Duration::from_secs(5));
+        assert!(is_timeout(slow.scope(client.routers()).await));
+
+        // a looser timeout than the client's
+        let client = JupiterClient::builder(&server.url)
+            .timeout(Duration::from_millis(50))
+            .build()
+            .unwrap();
+        let patient = RequestOptions::new().timeout(Duration::from_secs(5));
+        patient
+            .scope(client.routers())
+            .await
+            .expect("request should complete within its own timeout");
+    }
+
+    #[tokio::test]
+    async fn test_request_options_cancellation() {
+        let server = MockServer::start(|_| async move {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            MockResponse::json("[]")
+        })
+        .await;
+        let client = JupiterClient::builder(&server.url)
+            .max_concurrent_requests(1)
+            .build()
+            .unwrap();
+
+        let token = CancellationToken::new();
+        let call = RequestOptions::new()
+            .cancellation_token(token.clone())
+            .scope(client.routers());
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            canceller.cancel();
+        });
+        let started = Instant::now();
+        assert!(matches!(call.await, Err(JupiterClientError::Cancelled)));
+        assert!(started.elapsed() < Duration::from_millis(400));
+        assert!(token.is_cancelled());
+
+        // a call waiting for a concurrency slot gives up at its deadline without being sent
+        let busy = tokio::spawn({
+            let client = client.clone();
+            async move { client.routers().await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let hits = server.hits();
+        let waiting = RequestOptions::new().deadline(Instant::now() + Duration::from_millis(50));
+        let result = waiting.scope(client.routers()).await;
+        assert!(matches!(result, Err(JupiterClientError::Cancelled)));
+        assert_eq!(result.unwrap_err().to_string(), "Call cancelled");
+        assert_eq!(server.hits(), hits);
+        assert!(busy.await.unwrap().is_ok());
+    }
+}
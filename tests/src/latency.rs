@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod latency_tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use jup_ag_sdk::{
+        JupiterClient, client::LatencyHistogram, config::ApiFamily, events::LifecycleEvent,
+    };
+
+    use crate::{
+        common::JUP_MINT,
+        mock::{MockResponse, MockServer},
+    };
+
+    #[test]
+    fn test_latency_histogram() {
+        let mut histogram = LatencyHistogram::new();
+        assert_eq!(histogram.percentile(0.5), None);
+        assert_eq!(histogram.mean(), None);
+
+        for millis in [3, 8, 40, 45, 60, 70, 80, 90, 95, 700] {
+            histogram.record(Duration::from_millis(millis));
+        }
+        assert_eq!(histogram.count(), 10);
+        assert_eq!(histogram.mean(), Some(Duration::from_micros(119_100)));
+        assert_eq!(histogram.max(), Some(Duration::from_millis(700)));
+        assert_eq!(histogram.percentile(0.1), Some(Duration::from_millis(5)));
+        assert_eq!(histogram.percentile(0.5), Some(Duration::from_millis(100)));
+        assert_eq!(histogram.percentile(0.9), Some(Duration::from_millis(100)));
+        assert_eq!(histogram.percentile(0.99), Some(Duration::from_millis(700)));
+
+        histogram.record(Duration::from_secs(60));
+        let (bound, count) = histogram.buckets().last().unwrap();
+        assert_eq!((bound, count), (None, 1));
+        assert_eq!(histogram.percentile(1.0), Some(Duration::from_secs(60)));
+    }
+
+    #[tokio::test]
+    async fn test_latencies_and_slow_calls() {
+        let server = MockServer::start(|req| async move {
+            if req.path.starts_with("/ultra") {
+                tokio::time::sleep(Duration::from_millis(150)).await;
+                MockResponse::json("[]")
+            } else {
+                MockResponse::status(404, "not found")
+            }
+        })
+        .await;
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let client = JupiterClient::builder(&server.url)
+            .slow_call_threshold(Duration::from_secs(5))
+            .slow_call_threshold_for(ApiFamily::Ultra, Duration::from_millis(100))
+            .event_subscriber(move |event: &LifecycleEvent| {
+                if let LifecycleEvent::SlowCall {
+                    path, threshold, ..
+                } = event
+                {
+                    sink.lock().unwrap().push((path.clone(), *threshold));
+                }
+            })
+            .build()
+            .unwrap();
+
+        client.routers().await.unwrap();
+        assert!(client.get_token_info(JUP_MINT).await.is_err());
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            [(
+                "/ultra/v1/order/routers".to_string(),
+                Duration::from_millis(100)
+            )]
+        );
+
+        let routers = client
+            .latency(ApiFamily::Ultra, "/ultra/v1/order/routers")
+            .unwrap();
+        assert_eq!(routers.count(), 1);
+        assert!(routers.max().unwrap() >= Duration::from_millis(150));
+
+        let paths: Vec<_> = client
+            .latencies()
+            .into_iter()
+            .map(|endpoint| (endpoint.family, endpoint.path, endpoint.histogram.count()))
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                (ApiFamily::Tokens, "/tokens/v1/token/:pubkey".to_string(), 1),
+                (ApiFamily::Ultra, "/ultra/v1/order/routers".to_string(), 1),
+            ]
+        );
+        assert!(
+            client
+                .latency(ApiFamily::Tokens, &format!("/tokens/v1/token/{JUP_MINT}"))
+                .is_some()
+        );
+    }
+}
@@ -1,6 +1,54 @@
+mod amm_blacklist;
+mod balances;
+mod blockhash;
+mod circuit_breaker;
 mod common;
+mod concurrency;
+mod config;
+mod decimals;
+mod dex_registry;
+mod dns;
+mod dry_run;
+mod errors;
+mod events;
+mod execute_retry;
+mod execution_report;
+mod failover;
+mod fallback;
+mod hedging;
+mod hooks;
+mod latency;
+mod mock;
+mod notifier;
+mod order_monitor;
+mod otel;
+mod pagination;
+mod parsing;
+mod perps;
+mod portfolio;
+mod prelude;
+mod price;
+mod price_alert;
+mod program_test;
+mod proxy;
+mod quote_cache;
+mod quote_freshness;
+mod rate_limit;
 mod recurring;
+mod referral;
+mod route_explanation;
+mod runtime;
+mod shield_screening;
+mod signer;
+mod state_store;
 mod swap;
+mod swap_history;
 mod token;
+mod token_list;
+mod token_metadata;
+mod tower;
+mod transaction;
+mod transport;
 mod trigger;
 mod ultra;
+mod validation;
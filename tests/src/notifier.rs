@@ -0,0 +1,141 @@
+#[cfg(test)]
+mod notifier_tests {
+    use std::{
+        sync::{
+            Arc, Mutex,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Duration,
+    };
+
+    use jup_ag_sdk::{
+        JupiterClient,
+        config::ApiFamily,
+        error::JupiterClientError,
+        notifier::{Notifier, SIGNATURE_HEADER, TIMESTAMP_HEADER, Webhook, signature},
+        order_monitor::OrderEvent,
+    };
+    use serde_json::Value;
+
+    use crate::mock::{MockRequest, MockResponse, MockServer};
+
+    const SECRET: &str = "whsec_test";
+
+    fn event() -> OrderEvent {
+        OrderEvent::Closed {
+            family: ApiFamily::Trigger,
+            order_key: "EyZ8SCWqKR7V6WzU8N3qNgbU5eTsMCAVf5Ldg2kP4Pq2".to_string(),
+        }
+    }
+
+    /// A webhook receiver answering with `statuses` in turn, then 200.
+    async fn receiver(statuses: Vec<u16>) -> (MockServer, Arc<Mutex<Vec<MockRequest>>>) {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let server = MockServer::start(move |req| {
+            recorded.lock().unwrap().push(req);
+            let status = statuses
+                .get(calls.fetch_add(1, Ordering::SeqCst))
+                .copied()
+                .unwrap_or(200);
+            async move { MockResponse::status(status, "{}") }
+        })
+        .await;
+        (server, requests)
+    }
+
+    #[tokio::test]
+    async fn test_signed_webhook_delivery() {
+        let (server, requests) = receiver(vec![]).await;
+        let client = JupiterClient::new(&server.url);
+        let notifier = Notifier::new().webhook(Webhook::new(&server.url).secret(SECRET));
+
+        notifier.notify(&client, &event()).await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        let request = &requests[0];
+        assert_eq!(request.method, "POST");
+        let body: Value = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(body["type"], "closed");
+        assert_eq!(body["family"], "trigger");
+
+        let timestamp: u64 = request.header(TIMESTAMP_HEADER).unwrap().parse().unwrap();
+        assert_eq!(
+            request.header(SIGNATURE_HEADER),
+            Some(signature(SECRET, timestamp, request.body.as_bytes()).as_str())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_webhook_delivery_omits_jupiter_headers() {
+        let (server, requests) = receiver(vec![]).await;
+        let client = JupiterClient::builder("https://api.jup.ag")
+            .api_key("secret-key")
+            .default_header("x-gateway-token", "gateway-secret")
+            .build()
+            .unwrap();
+        let notifier = Notifier::new().webhook(Webhook::new(&server.url));
+
+        notifier.notify(&client, &event()).await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].header("x-api-key"), None);
+        assert_eq!(requests[0].header("x-gateway-token"), None);
+        assert_eq!(requests[0].header("content-type"), Some("application/json"));
+    }
+
+    #[test]
+    fn test_signature_format() {
+        let signed = signature(SECRET, 1_700_000_000, b"{}");
+        assert!(signed.starts_with("sha256="));
+        assert_eq!(signed.len(), 7 + 64);
+        assert_ne!(signed, signature("other", 1_700_000_000, b"{}"));
+        assert_ne!(signed, signature(SECRET, 1_700_000_001, b"{}"));
+
+        let webhook = format!("{:?}", Webhook::new("https://example.com").secret(SECRET));
+        assert!(!webhook.contains(SECRET));
+    }
+
+    #[tokio::test]
+    async fn test_retries_server_errors() {
+        let (server, _) = receiver(vec![500, 503]).await;
+        let client = JupiterClient::new(&server.url);
+        let notifier =
+            Notifier::new().webhook(Webhook::new(&server.url).backoff(Duration::from_millis(1)));
+
+        notifier.notify(&client, &event()).await.unwrap();
+        assert_eq!(server.hits(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_on_client_errors() {
+        let (server, _) = receiver(vec![400]).await;
+        let client = JupiterClient::new(&server.url);
+        let notifier =
+            Notifier::new().webhook(Webhook::new(&server.url).backoff(Duration::from_millis(1)));
+
+        let error = notifier.notify(&client, &event()).await.unwrap_err();
+        assert!(matches!(error, JupiterClientError::ApiError(e) if e.status == 400));
+        assert_eq!(server.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_callbacks_and_run() {
+        let (server, _) = receiver(vec![]).await;
+        let client = JupiterClient::new(&server.url);
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let recorded = received.clone();
+        let notifier = Notifier::new()
+            .callback(move |event: &OrderEvent| recorded.lock().unwrap().push(event.clone()));
+
+        let (sender, events) = tokio::sync::broadcast::channel(8);
+        sender.send(event()).unwrap();
+        sender.send(event()).unwrap();
+        drop(sender);
+        notifier.run(&client, events).await;
+
+        assert_eq!(*received.lock().unwrap(), vec![event(), event()]);
+        assert_eq!(server.hits(), 0);
+    }
+}
@@ -0,0 +1,328 @@
+#[cfg(test)]
+mod order_monitor_tests {
+    use std::{
+        pin::pin,
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Duration,
+    };
+
+    use futures::StreamExt;
+    use jup_ag_sdk::{
+        JupiterClient,
+        config::ApiFamily,
+        order_monitor::{Fill, OrderEvent, OrderFill, OrderMonitor},
+        types::Amount,
+    };
+    use serde_json::{Value, json};
+
+    use crate::{
+        common::{SOL_MINT, TEST_USER_PUBKEY, USDC_MINT},
+        mock::{MockResponse, MockServer},
+    };
+
+    const ORDER: &str = "EyZ8SCWqKR7V6WzU8N3qNgbU5eTsMCAVf5Ldg2kP4Pq2";
+    const OTHER_ORDER: &str = "HcoJqG325TTifs6jyWvRJ9ET4pDu12Xrt2EQKZGFmuKX";
+
+    fn trade(tx_id: &str) -> Value {
+        json!({
+            "orderKey": ORDER,
+            "keeper": "keeper",
+            "inputMint": SOL_MINT,
+            "outputMint": USDC_MINT,
+            "inputAmount": "0.5",
+            "outputAmount": "75",
+            "rawInputAmount": "500000000",
+            "rawOutputAmount": "75000000",
+            "feeMint": USDC_MINT,
+            "feeAmount": "0",
+            "rawFeeAmount": "0",
+            "txId": tx_id,
+            "confirmedAt": "2025-06-01T00:00:00Z",
+            "action": "Fill"
+        })
+    }
+
+    fn trigger_order(order_key: &str, trades: Vec<Value>) -> Value {
+        json!({
+            "userPubkey": TEST_USER_PUBKEY,
+            "orderKey": order_key,
+            "inputMint": SOL_MINT,
+            "outputMint": USDC_MINT,
+            "makingAmount": "1",
+            "takingAmount": "150",
+            "remainingMakingAmount": "1",
+            "remainingTakingAmount": "150",
+            "rawMakingAmount": "1000000000",
+            "rawTakingAmount": "150000000",
+            "rawRemainingMakingAmount": "1000000000",
+            "rawRemainingTakingAmount": "150000000",
+            "slippageBps": "0",
+            "createdAt": "2025-06-01T00:00:00Z",
+            "updatedAt": "2025-06-01T00:00:00Z",
+            "status": "Open",
+            "openTx": "",
+            "closeTx": "",
+            "programVersion": "j1o2qRpjcyUwEvwtcfhEQefh773ZgjxcVRry7LDqg5X",
+            "trades": trades
+        })
+    }
+
+    /// Serves `rounds[n]` as the active orders on the n-th request, repeating the last.
+    async fn server(rounds: Vec<Value>) -> MockServer {
+        let round = Arc::new(AtomicUsize::new(0));
+        let rounds = Arc::new(rounds);
+        MockServer::start(move |_| {
+            let n = round.fetch_add(1, Ordering::SeqCst);
+            let body = rounds[n.min(rounds.len() - 1)].to_string();
+            async move { MockResponse::json(&body) }
+        })
+        .await
+    }
+
+    fn trigger_page(orders: Vec<Value>) -> Value {
+        json!({
+            "user": TEST_USER_PUBKEY,
+            "orderStatus": "active",
+            "orders": orders,
+            "totalPages": 1,
+            "page": 1
+        })
+    }
+
+    #[tokio::test]
+    async fn test_trigger_fills_and_closes() {
+        let server = server(vec![
+            trigger_page(vec![
+                trigger_order(ORDER, vec![trade("tx-1")]),
+                trigger_order(OTHER_ORDER, vec![]),
+            ]),
+            trigger_page(vec![trigger_order(
+                ORDER,
+                vec![trade("tx-1"), trade("tx-2")],
+            )]),
+        ])
+        .await;
+        let client = JupiterClient::new(&server.url);
+        let mut monitor = OrderMonitor::new(TEST_USER_PUBKEY).trigger_only();
+        let mut subscriber = monitor.subscribe();
+
+        assert!(monitor.refresh(&client).await.unwrap().is_empty());
+        assert_eq!(monitor.active_orders(), vec![ORDER, OTHER_ORDER]);
+
+        let events = monitor.refresh(&client).await.unwrap();
+        assert_eq!(
+            events,
+            vec![
+                OrderEvent::Filled {
+                    family: ApiFamily::Trigger,
+                    order_key: ORDER.to_string(),
+                    fill: OrderFill {
+                        tx_id: "tx-2".to_string(),
+                        input_mint: SOL_MINT.to_string(),
+                        output_mint: USDC_MINT.to_string(),
+                        input_amount: Some(Amount::new(500_000_000)),
+                        output_amount: Some(Amount::new(75_000_000)),
+                        confirmed_at: Some("2025-06-01T00:00:00Z".to_string()),
+                    },
+                },
+                OrderEvent::Closed {
+                    family: ApiFamily::Trigger,
+                    order_key: OTHER_ORDER.to_string(),
+                },
+            ]
+        );
+        assert_eq!(subscriber.recv().await.unwrap(), events[0]);
+        assert_eq!(subscriber.recv().await.unwrap(), events[1]);
+        assert_eq!(monitor.active_orders(), vec![ORDER]);
+    }
+
+    #[tokio::test]
+    async fn test_order_filled_in_one_step_reports_its_fill() {
+        let round = Arc::new(AtomicUsize::new(0));
+        let server = MockServer::start(move |req| {
+            let body = if req.path.contains("orderStatus=history") {
+                let mut page = trigger_page(vec![trigger_order(ORDER, vec![trade("tx-1")])]);
+                page["orderStatus"] = json!("history");
+                page
+            } else if round.fetch_add(1, Ordering::SeqCst) == 0 {
+                trigger_page(vec![trigger_order(ORDER, vec![])])
+            } else {
+                trigger_page(vec![])
+            };
+            async move { MockResponse::json(&body.to_string()) }
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+        let mut monitor = OrderMonitor::new(TEST_USER_PUBKEY).trigger_only();
+
+        monitor.refresh(&client).await.unwrap();
+        let events = monitor.refresh(&client).await.unwrap();
+
+        assert!(matches!(
+            &events[..],
+            [
+                OrderEvent::Filled { order_key, fill, .. },
+                OrderEvent::Closed { order_key: closed, .. },
+            ] if order_key == ORDER && fill.tx_id == "tx-1" && closed == ORDER
+        ));
+        assert_eq!(server.hits(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_orders_past_many_pages_stay_active() {
+        let server = MockServer::start(|req| async move {
+            let page: u64 = req
+                .path
+                .split("page=")
+                .nth(1)
+                .and_then(|page| page.split('&').next())
+                .unwrap()
+                .parse()
+                .unwrap();
+            let mut body = trigger_page(vec![trigger_order(&format!("order-{page}"), vec![])]);
+            body["totalPages"] = json!(25);
+            body["page"] = json!(page);
+            MockResponse::json(&body.to_string())
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+        let mut monitor = OrderMonitor::new(TEST_USER_PUBKEY).trigger_only();
+
+        monitor.refresh(&client).await.unwrap();
+        assert_eq!(monitor.active_orders().len(), 25);
+        assert!(monitor.refresh(&client).await.unwrap().is_empty());
+        assert_eq!(server.hits(), 50);
+    }
+
+    #[tokio::test]
+    async fn test_recurring_fills() {
+        let page = |trades: Vec<Value>| {
+            json!({
+                "user": TEST_USER_PUBKEY,
+                "orderStatus": "active",
+                "all": [{ "orderKey": ORDER, "trades": trades }],
+                "totalPages": 1,
+                "page": 1
+            })
+        };
+        let server = server(vec![page(vec![]), page(vec![trade("tx-1")])]).await;
+        let client = JupiterClient::new(&server.url);
+        let mut monitor = OrderMonitor::new(TEST_USER_PUBKEY).recurring_only();
+
+        monitor.refresh(&client).await.unwrap();
+        let events = monitor.refresh(&client).await.unwrap();
+
+        assert!(matches!(
+            &events[..],
+            [OrderEvent::Filled { family: ApiFamily::Recurring, fill, .. }] if fill.tx_id == "tx-1"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_failed_family_keeps_every_snapshot() {
+        let trigger_round = Arc::new(AtomicUsize::new(0));
+        let recurring_round = Arc::new(AtomicUsize::new(0));
+        let server = MockServer::start(move |req| {
+            let response = if req.path.contains("/getTriggerOrders") {
+                let trades = match trigger_round.fetch_add(1, Ordering::SeqCst) {
+                    0 => vec![],
+                    _ => vec![trade("tx-1")],
+                };
+                MockResponse::json(&trigger_page(vec![trigger_order(ORDER, trades)]).to_string())
+            } else if recurring_round.fetch_add(1, Ordering::SeqCst) == 1 {
+                MockResponse::status(400, r#"{"error":"bad request"}"#)
+            } else {
+                MockResponse::json(
+                    &json!({
+                        "user": TEST_USER_PUBKEY,
+                        "orderStatus": "active",
+                        "all": [],
+                        "totalPages": 1,
+                        "page": 1
+                    })
+                    .to_string(),
+                )
+            };
+            async move { response }
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+        let mut monitor = OrderMonitor::new(TEST_USER_PUBKEY);
+
+        assert!(monitor.refresh(&client).await.unwrap().is_empty());
+        assert!(monitor.refresh(&client).await.is_err());
+
+        // the trigger fill found by the failed refresh is reported by the next one
+        let events = monitor.refresh(&client).await.unwrap();
+        assert!(matches!(
+            &events[..],
+            [OrderEvent::Filled { family: ApiFamily::Trigger, fill, .. }] if fill.tx_id == "tx-1"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_fills_merges_families() {
+        let trigger_round = Arc::new(AtomicUsize::new(0));
+        let recurring_round = Arc::new(AtomicUsize::new(0));
+        let server = MockServer::start(move |req| {
+            let confirmed = |mut trade: Value, at: &str| {
+                trade["confirmedAt"] = json!(at);
+                trade
+            };
+            let body = if req.path.contains("/getTriggerOrders") {
+                let trades = match trigger_round.fetch_add(1, Ordering::SeqCst) {
+                    0 => vec![],
+                    _ => vec![confirmed(trade("tx-trigger"), "2025-06-01T00:00:05Z")],
+                };
+                trigger_page(vec![trigger_order(ORDER, trades)])
+            } else {
+                let trades = match recurring_round.fetch_add(1, Ordering::SeqCst) {
+                    0 => vec![],
+                    _ => vec![confirmed(trade("tx-recurring"), "2025-06-01T00:00:01Z")],
+                };
+                json!({
+                    "user": TEST_USER_PUBKEY,
+                    "orderStatus": "active",
+                    "all": [{ "orderKey": OTHER_ORDER, "trades": trades }],
+                    "totalPages": 1,
+                    "page": 1
+                })
+            };
+            async move { MockResponse::json(&body.to_string()) }
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+
+        let fills: Vec<Fill> = pin!(client.subscribe_fills(TEST_USER_PUBKEY, Duration::ZERO))
+            .take(2)
+            .collect()
+            .await;
+        assert_eq!(server.hits(), 4);
+        assert_eq!(
+            fills
+                .iter()
+                .map(|fill| (fill.family, fill.order_key.as_str(), fill.tx_id.as_str()))
+                .collect::<Vec<_>>(),
+            [
+                (ApiFamily::Recurring, OTHER_ORDER, "tx-recurring"),
+                (ApiFamily::Trigger, ORDER, "tx-trigger"),
+            ]
+        );
+        assert_eq!(fills[1].output_amount, Some(Amount::new(75_000_000)));
+    }
+
+    #[test]
+    fn test_event_json() {
+        let event = OrderEvent::Closed {
+            family: ApiFamily::Recurring,
+            order_key: ORDER.to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            json!({ "type": "closed", "family": "recurring", "orderKey": ORDER })
+        );
+    }
+}
@@ -0,0 +1,134 @@
+#[cfg(test)]
+mod otel_tests {
+    use std::sync::{Arc, Mutex, OnceLock};
+
+    use jup_ag_sdk::JupiterClient;
+    use opentelemetry::{
+        Context, Value, global,
+        trace::{FutureExt, Span, SpanKind, Status, TraceContextExt, Tracer},
+    };
+    use opentelemetry_sdk::{
+        propagation::TraceContextPropagator,
+        trace::{InMemorySpanExporter, SdkTracerProvider, SpanData},
+    };
+
+    use crate::mock::{MockResponse, MockServer};
+
+    /// Installs an in-memory exporter and the W3C propagator globally, once for all tests.
+    fn exporter() -> &'static InMemorySpanExporter {
+        static EXPORTER: OnceLock<InMemorySpanExporter> = OnceLock::new();
+        EXPORTER.get_or_init(|| {
+            let exporter = InMemorySpanExporter::default();
+            global::set_tracer_provider(
+                SdkTracerProvider::builder()
+                    .with_simple_exporter(exporter.clone())
+                    .build(),
+            );
+            global::set_text_map_propagator(TraceContextPropagator::new());
+            exporter
+        })
+    }
+
+    /// Finished spans of calls to `server`; other tests record spans concurrently.
+    fn spans_for(server: &MockServer) -> Vec<SpanData> {
+        exporter()
+            .get_finished_spans()
+            .unwrap()
+            .into_iter()
+            .filter(|span| {
+                attribute(span, "url.full").is_some_and(|url| url.as_str().starts_with(&server.url))
+            })
+            .collect()
+    }
+
+    fn attribute(span: &SpanData, key: &str) -> Option<Value> {
+        span.attributes
+            .iter()
+            .find(|kv| kv.key.as_str() == key)
+            .map(|kv| kv.value.clone())
+    }
+
+    /// Serves `status` and records the `traceparent` header of each request.
+    async fn traced_server(status: u16) -> (MockServer, Arc<Mutex<Vec<String>>>) {
+        let headers = Arc::new(Mutex::new(Vec::new()));
+        let seen = headers.clone();
+        let server = MockServer::start(move |req| {
+            let seen = seen.clone();
+            async move {
+                seen.lock()
+                    .unwrap()
+                    .push(req.header("traceparent").unwrap_or_default().to_string());
+                MockResponse::status(status, "[]")
+            }
+        })
+        .await;
+        (server, headers)
+    }
+
+    #[tokio::test]
+    async fn test_call_span_and_trace_context_header() {
+        exporter();
+        let (server, headers) = traced_server(200).await;
+        let client = JupiterClient::new(&server.url);
+
+        client.routers().await.unwrap();
+
+        let spans = spans_for(&server);
+        assert_eq!(spans.len(), 1);
+        let span = &spans[0];
+        assert_eq!(span.name, "GET /ultra/v1/order/routers");
+        assert_eq!(span.span_kind, SpanKind::Client);
+        assert_eq!(span.status, Status::Unset);
+        assert_eq!(
+            attribute(span, "http.response.status_code"),
+            Some(Value::I64(200))
+        );
+        assert_eq!(
+            attribute(span, "jupiter.api_family"),
+            Some(Value::from("ultra"))
+        );
+
+        let context = span.span_context.clone();
+        assert_eq!(
+            *headers.lock().unwrap(),
+            vec![format!(
+                "00-{}-{}-01",
+                context.trace_id(),
+                context.span_id()
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_call_marks_span_as_error() {
+        exporter();
+        let (server, _) = traced_server(503).await;
+        let client = JupiterClient::new(&server.url);
+
+        client.routers().await.unwrap_err();
+
+        let spans = spans_for(&server);
+        assert_eq!(spans.len(), 1);
+        assert!(matches!(spans[0].status, Status::Error { .. }));
+        assert_eq!(attribute(&spans[0], "error.type"), Some(Value::from("503")));
+    }
+
+    #[tokio::test]
+    async fn test_span_is_child_of_current_context() {
+        exporter();
+        let (server, _) = traced_server(200).await;
+        let client = JupiterClient::new(&server.url);
+
+        let tracer = global::tracer("otel-tests");
+        let parent = tracer.start("swap flow");
+        let parent_context = parent.span_context().clone();
+        let cx = Context::current_with_span(parent);
+
+        client.routers().with_context(cx).await.unwrap();
+
+        let spans = spans_for(&server);
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].span_context.trace_id(), parent_context.trace_id());
+        assert_eq!(spans[0].parent_span_id, parent_context.span_id());
+    }
+}
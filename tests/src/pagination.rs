@@ -0,0 +1,300 @@
+#[cfg(test)]
+mod pagination_tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError,
+        types::{
+            GetRecurringOrders, GetTriggerOrders, OrderStatus, RecurringOrderType,
+            TriggerOrderState,
+        },
+    };
+    use serde_json::{Value, json};
+
+    use crate::{
+        common::{JUP_MINT, SOL_MINT, TEST_USER_PUBKEY, USDC_MINT},
+        mock::{MockResponse, MockServer},
+    };
+
+    /// The value of query parameter `name` in `path`.
+    fn query(path: &str, name: &str) -> Option<u64> {
+        let (_, query) = path.split_once('?')?;
+        query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix(&format!("{name}=")))
+            .and_then(|value| value.parse().ok())
+    }
+
+    fn trigger_order(order_key: &str) -> Value {
+        json!({
+            "userPubkey": TEST_USER_PUBKEY,
+            "orderKey": order_key,
+            "inputMint": SOL_MINT,
+            "outputMint": USDC_MINT,
+            "makingAmount": "1",
+            "takingAmount": "150",
+            "remainingMakingAmount": "1",
+            "remainingTakingAmount": "150",
+            "rawMakingAmount": "1000000000",
+            "rawTakingAmount": "150000000",
+            "rawRemainingMakingAmount": "1000000000",
+            "rawRemainingTakingAmount": "150000000",
+            "slippageBps": "0",
+            "createdAt": "2025-06-01T00:00:00Z",
+            "updatedAt": "2025-06-01T00:00:00Z",
+            "status": "Open",
+            "openTx": "",
+            "closeTx": "",
+            "programVersion": "j1o2qRpjcyUwEvwtcfhEQefh773ZgjxcVRry7LDqg5X",
+            "trades": []
+        })
+    }
+
+    /// Serves 5 pages of one trigger or recurring order each.
+    async fn orders_server() -> MockServer {
+        MockServer::start(|req| async move {
+            let page = query(&req.path, "page").unwrap_or(1);
+            let order_key = format!("order-{page}");
+            let mut body = json!({
+                "user": TEST_USER_PUBKEY,
+                "orderStatus": "history",
+                "totalPages": 5,
+                "page": page
+            });
+            if req.path.contains("/getTriggerOrders") {
+                body["orders"] = json!([trigger_order(&order_key)]);
+            } else {
+                body["all"] = json!([{ "orderKey": order_key }]);
+            }
+            MockResponse::json(&body.to_string())
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_trigger_orders_all() {
+        let server = orders_server().await;
+        let client = JupiterClient::new(&server.url);
+        let request = GetTriggerOrders::new(TEST_USER_PUBKEY, OrderStatus::History).page("4");
+
+        let orders = client.get_trigger_orders_all(&request, None).await.unwrap();
+        let keys: Vec<_> = orders
+            .iter()
+            .map(|order| order.order_key.as_str())
+            .collect();
+        assert_eq!(
+            keys,
+            ["order-1", "order-2", "order-3", "order-4", "order-5"]
+        );
+        assert_eq!(server.hits(), 5);
+
+        let orders = client
+            .get_trigger_orders_all(&request, Some(2))
+            .await
+            .unwrap();
+        assert_eq!(orders.len(), 2);
+        assert_eq!(server.hits(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_recurring_orders_all() {
+        let server = orders_server().await;
+        let client = JupiterClient::new(&server.url);
+        let request = GetRecurringOrders::new(
+            RecurringOrderType::All,
+            OrderStatus::History,
+            TEST_USER_PUBKEY,
+        );
+
+        let orders = client
+            .get_recurring_orders_all(&request, Some(3))
+            .await
+            .unwrap();
+        let keys: Vec<_> = orders
+            .iter()
+            .map(|order| order["orderKey"].clone())
+            .collect();
+        assert_eq!(keys, [json!("order-1"), json!("order-2"), json!("order-3")]);
+        assert_eq!(server.hits(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_new_tokens_all() {
+        let server = MockServer::start(|req| async move {
+            let limit = query(&req.path, "limit").unwrap();
+            let offset = query(&req.path, "offset").unwrap();
+            let tokens: Vec<Value> = (offset..(offset + limit).min(7))
+                .map(|n| {
+                    json!({
+                        "mint": format!("mint-{n}"),
+                        "created_at": "1749000000",
+                        "metadata_updated_at": 1749000000,
+                        "name": "Token",
+                        "symbol": "TKN",
+                        "decimals": 6,
+                        "logo_uri": null,
+                        "known_markets": [],
+                        "mint_authority": null,
+                        "freeze_authority": null
+                    })
+                })
+                .collect();
+            MockResponse::json(&Value::from(tokens).to_string())
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+
+        let tokens = client.get_new_tokens_all(2, None).await.unwrap();
+        let mints: Vec<_> = tokens.iter().map(|token| token.mint.as_str()).collect();
+        assert_eq!(
+            mints,
+            [
+                "mint-0", "mint-1", "mint-2", "mint-3", "mint-4", "mint-5", "mint-6"
+            ]
+        );
+        assert_eq!(server.hits(), 4);
+        assert_eq!(
+            tokens[0].created_at_time(),
+            UNIX_EPOCH.checked_add(Duration::from_secs(1749000000))
+        );
+
+        assert_eq!(
+            client.get_new_tokens_all(2, Some(2)).await.unwrap().len(),
+            4
+        );
+        assert!(matches!(
+            client.get_new_tokens_all(0, None).await,
+            Err(JupiterClientError::ValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_zero_max_pages_is_rejected() {
+        let server = orders_server().await;
+        let client = JupiterClient::new(&server.url);
+        let trigger = GetTriggerOrders::new(TEST_USER_PUBKEY, OrderStatus::History);
+        let recurring = GetRecurringOrders::new(
+            RecurringOrderType::All,
+            OrderStatus::History,
+            TEST_USER_PUBKEY,
+        );
+
+        assert!(matches!(
+            client.get_trigger_orders_all(&trigger, Some(0)).await,
+            Err(JupiterClientError::ValidationError(_))
+        ));
+        assert!(matches!(
+            client.get_recurring_orders_all(&recurring, Some(0)).await,
+            Err(JupiterClientError::ValidationError(_))
+        ));
+        assert!(matches!(
+            client.get_new_tokens_all(2, Some(0)).await,
+            Err(JupiterClientError::ValidationError(_))
+        ));
+        assert_eq!(server.hits(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_new_tokens_query() {
+        let paths = Arc::new(Mutex::new(Vec::new()));
+        let seen = paths.clone();
+        let server = MockServer::start(move |req| {
+            seen.lock().unwrap().push(req.path);
+            async { MockResponse::json("[]") }
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+
+        client.get_new_tokens(None, None).await.unwrap();
+        client.get_new_tokens(None, Some(20)).await.unwrap();
+        client.get_new_tokens(Some(10), Some(20)).await.unwrap();
+        assert!(matches!(
+            client.get_new_tokens(Some(0), None).await,
+            Err(JupiterClientError::ValidationError(_))
+        ));
+        assert_eq!(
+            *paths.lock().unwrap(),
+            [
+                "/tokens/v1/new",
+                "/tokens/v1/new?offset=20",
+                "/tokens/v1/new?limit=10&offset=20",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_trigger_order() {
+        // two pages of active orders, then the history with a filled order on page 2
+        let server = MockServer::start(|req| async move {
+            let page = query(&req.path, "page").unwrap_or(1);
+            let history = req.path.contains("orderStatus=history");
+            let mut order = trigger_order(if history { JUP_MINT } else { SOL_MINT });
+            if history && page == 2 {
+                order = trigger_order(USDC_MINT);
+                order["status"] = json!("Completed");
+                let trade = json!({
+                    "orderKey": USDC_MINT,
+                    "keeper": TEST_USER_PUBKEY,
+                    "inputMint": SOL_MINT,
+                    "outputMint": USDC_MINT,
+                    "inputAmount": "0.5",
+                    "outputAmount": "75",
+                    "rawInputAmount": "500000000",
+                    "rawOutputAmount": "75000000",
+                    "feeMint": USDC_MINT,
+                    "feeAmount": "0",
+                    "rawFeeAmount": "0",
+                    "txId": "tx",
+                    "confirmedAt": "2025-06-02T00:00:00Z",
+                    "action": "Fill",
+                    "productMeta": null
+                });
+                order["trades"] = json!([trade.clone(), trade]);
+            }
+            let body = json!({
+                "user": TEST_USER_PUBKEY,
+                "orderStatus": if history { "history" } else { "active" },
+                "orders": [order],
+                "totalPages": 2,
+                "page": page
+            });
+            MockResponse::json(&body.to_string())
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+
+        let order = client
+            .get_trigger_order(TEST_USER_PUBKEY, USDC_MINT)
+            .await
+            .unwrap()
+            .expect("the order is on the second page of the history");
+        assert_eq!(order.state(), TriggerOrderState::Completed);
+        assert!(order.state().is_closed());
+        assert_eq!(
+            order.filled_amounts(),
+            (1_000_000_000.into(), 150_000_000.into())
+        );
+        assert_eq!(server.hits(), 4);
+
+        let active = client
+            .get_trigger_order(TEST_USER_PUBKEY, SOL_MINT)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(active.state(), TriggerOrderState::Open);
+        assert_eq!(server.hits(), 5);
+
+        let missing = client
+            .get_trigger_order(
+                TEST_USER_PUBKEY,
+                "4k3Dyjzvzp8eMZWUXbBCjEvwSkkk59S5iCNLY3QrkX6R",
+            )
+            .await
+            .unwrap();
+        assert!(missing.is_none());
+        assert_eq!(server.hits(), 9);
+    }
+}
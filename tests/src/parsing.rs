@@ -0,0 +1,305 @@
+#[cfg(test)]
+mod parsing_tests {
+    use std::time::Duration;
+
+    use jup_ag_sdk::{
+        JupiterClientError, json,
+        types::{
+            Amount, Bps, OrderResponse, Quote, QuoteResponse, Status, SwapMode,
+            TokenBalancesResponse, TokenInfoResponse, UltraExecuteOrderResponse,
+            UltraOrderResponse,
+        },
+    };
+    use serde::de::DeserializeOwned;
+    use serde_json::{Value, json};
+
+    use crate::common::{quote_json, route_hop, ultra_order_json};
+
+    const POOL: &str = "5BKxfWMbmYBAEWvyPZS9esPducUba9GqyMjtLCfbaqyF";
+
+    fn quote_body() -> Value {
+        let mut quote = quote_json();
+        quote["platformFee"] = Value::Null;
+        quote["routePlan"] = json!([route_hop(POOL, "Meteora DLMM")]);
+        quote["routePlan"][0]["swapInfo"]["feeAmount"] = json!("24825");
+        quote["contextSlot"] = json!(338000000);
+        quote
+    }
+
+    fn ultra_order_body() -> Value {
+        let mut order = ultra_order_json();
+        order["priceImpactPct"] = json!("0.0012");
+        order
+    }
+
+    fn parse<T: DeserializeOwned>(body: &str) -> Result<T, JupiterClientError> {
+        json::from_slice(&mut body.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_quote_response_parses() {
+        let quote: QuoteResponse = parse(&quote_body().to_string()).expect("quote should parse");
+        assert_eq!(quote.out_amount, "421337000");
+        assert_eq!(quote.route_plan.len(), 1);
+    }
+
+    #[test]
+    fn test_quote_response_accepts_numbers_as_strings() {
+        let mut body = quote_body();
+        body["inAmount"] = json!(1000000000);
+        body["slippageBps"] = json!("50");
+        body["contextSlot"] = json!("338000000");
+        body["routePlan"][0]["percent"] = json!("100");
+
+        let quote: QuoteResponse = parse(&body.to_string()).expect("quote should parse");
+        assert_eq!(quote.in_amount, "1000000000");
+        assert_eq!(quote.slippage_bps, 50);
+        assert_eq!(quote.context_slot, 338_000_000);
+        assert_eq!(quote.route_plan[0].percent, 100);
+    }
+
+    #[test]
+    fn test_quote_response_null_route_plan() {
+        let mut body = quote_body();
+        body["routePlan"] = Value::Null;
+
+        let quote: QuoteResponse = parse(&body.to_string()).expect("quote should parse");
+        assert!(quote.route_plan.is_empty());
+    }
+
+    #[test]
+    fn test_truncated_bodies_return_errors() {
+        let body = serde_json::to_string_pretty(&quote_body()).unwrap();
+        for end in 0..body.len() - 1 {
+            if !body.is_char_boundary(end) {
+                continue;
+            }
+            match parse::<QuoteResponse>(&body[..end]) {
+                Err(JupiterClientError::DeserializationError { .. }) => {}
+                other => panic!("truncated body at {end} should fail cleanly, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_non_numeric_string_is_an_error() {
+        let mut body = quote_body();
+        body["slippageBps"] = json!("fifty");
+        match parse::<QuoteResponse>(&body.to_string()) {
+            Err(JupiterClientError::DeserializationError { message: msg, .. }) => {
+                assert!(
+                    msg.contains("fifty"),
+                    "error should mention the bad value: {msg}"
+                )
+            }
+            other => panic!("expected deserialization error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_token_info_null_collections() {
+        let body = r#"[{
+            "address": "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN",
+            "name": "Jupiter",
+            "symbol": "JUP",
+            "decimals": "6",
+            "logoURI": null,
+            "tags": null,
+            "daily_volume": null,
+            "created_at": "2024-04-26T10:56:58.893768Z",
+            "freeze_authority": null,
+            "mint_authority": null,
+            "permanent_delegate": null,
+            "minted_at": null,
+            "extensions": null
+        }]"#;
+
+        let tokens: Vec<TokenInfoResponse> = parse(body).expect("token list should parse");
+        assert_eq!(tokens[0].decimals, 6);
+        assert!(tokens[0].tags.is_empty());
+        assert!(tokens[0].extensions.is_empty());
+    }
+
+    #[test]
+    fn test_balances_and_orders_reject_garbage() {
+        for body in ["", "null", "[]", "{", "\"text\"", "{\"orders\": 1}"] {
+            assert!(
+                parse::<TokenBalancesResponse>(body).is_err(),
+                "{body:?} should not parse"
+            );
+            assert!(
+                parse::<OrderResponse>(body).is_err(),
+                "{body:?} should not parse"
+            );
+        }
+    }
+
+    #[test]
+    fn test_unknown_enum_values_fall_back() {
+        let mut body = quote_body();
+        body["swapMode"] = json!("ExactBoth");
+        let quote: QuoteResponse = parse(&body.to_string()).expect("quote should parse");
+        assert_eq!(quote.swap_mode, SwapMode::Unknown);
+
+        let body = r#"{"status": "Pending", "code": 0}"#;
+        let execute: UltraExecuteOrderResponse = parse(body).expect("execute should parse");
+        assert_eq!(execute.status, Status::Unknown);
+    }
+
+    #[test]
+    fn test_strict_parsing_rejects_unknown_fields() {
+        fn strict<T: DeserializeOwned>(body: &str) -> Result<T, JupiterClientError> {
+            json::from_slice_strict(&mut body.as_bytes().to_vec())
+        }
+
+        let quote: QuoteResponse = strict(&quote_body().to_string()).expect("quote should parse");
+        assert_eq!(quote.route_plan[0].swap_info.label, "Meteora DLMM");
+        strict::<UltraOrderResponse>(&ultra_order_body().to_string()).expect("order should parse");
+        let mut body = quote_body();
+        body["swapMode"] = json!("ExactBoth");
+        strict::<QuoteResponse>(&body.to_string())
+            .expect("unknown enum values are not unknown fields");
+
+        let mut body = quote_body();
+        body["routePlan"][0]["bps"] = json!(10000);
+        body["routePlan"][0]["swapInfo"]["feeVault"] = json!({"a": 1});
+        body["newField"] = json!("150");
+        let body = body.to_string();
+        parse::<QuoteResponse>(&body).expect("tolerant parsing ignores new fields");
+        let err = strict::<QuoteResponse>(&body).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Failed to deserialize response: unknown fields: newField, routePlan[0].bps, \
+             routePlan[0].swapInfo.feeVault"
+        );
+    }
+
+    #[test]
+    fn test_amount_parsing_and_formatting() {
+        let quote: QuoteResponse = parse(&quote_body().to_string()).expect("quote should parse");
+        assert_eq!(quote.in_amount.as_u64(), 1_000_000_000);
+        assert_eq!(quote.in_amount.to_ui_string(9), "1");
+        assert_eq!(quote.out_amount.to_ui_string(6), "421.337");
+        assert_eq!(
+            quote.out_amount - quote.other_amount_threshold,
+            Amount::new(2_106_685)
+        );
+
+        assert_eq!(Amount::new(5).to_ui_string(6), "0.000005");
+        assert_eq!(Amount::from_ui(0.000005, 6), Amount::new(5));
+        assert_eq!(Amount::new(u64::MAX).as_u128(), u64::MAX as u128);
+        assert!(Amount::new(1).checked_sub(Amount::new(2)).is_none());
+        assert!(parse::<Amount>("\"-1\"").is_err());
+        assert!(parse::<Amount>("\"1.5\"").is_err());
+    }
+
+    #[test]
+    fn test_bps_conversions_and_bounds() {
+        let quote: QuoteResponse = parse(&quote_body().to_string()).expect("quote should parse");
+        assert_eq!(quote.slippage_bps, Bps::from_percent(0.5).unwrap());
+
+        assert_eq!(Bps::new(250).unwrap().as_percent(), 2.5);
+        assert_eq!(Bps::new(250).unwrap().as_fraction(), 0.025);
+        assert!(Bps::new(10_000).is_ok());
+        assert!(matches!(
+            Bps::new(10_001),
+            Err(JupiterClientError::ValidationError(_))
+        ));
+        assert!(Bps::from_percent(-1.0).is_err());
+        assert_eq!(parse::<Bps>("\"75\"").unwrap(), 75);
+    }
+
+    #[test]
+    fn test_quote_trait_across_products() {
+        fn summary(quote: &impl Quote) -> (Amount, Amount, Bps, Option<f64>) {
+            (
+                quote.in_amount(),
+                quote.other_amount_threshold(),
+                quote.slippage_bps(),
+                quote.price_impact(),
+            )
+        }
+
+        let swap: QuoteResponse = parse(&quote_body().to_string()).expect("quote should parse");
+        let ultra: UltraOrderResponse =
+            parse(&ultra_order_body().to_string()).expect("order should parse");
+
+        let (swap_in, swap_min, swap_slippage, _) = summary(&swap);
+        let (ultra_in, ultra_min, ultra_slippage, ultra_impact) = summary(&ultra);
+        assert_eq!(swap_in, ultra_in);
+        assert_eq!(swap_min, ultra_min);
+        assert_eq!(swap_slippage, ultra_slippage);
+        assert_eq!(ultra_impact, Some(0.0012));
+        assert_eq!(swap.raw_price(), ultra.raw_price());
+        assert_eq!(swap.server_time(), Duration::from_micros(12_300));
+        assert_eq!(ultra.server_time(), Duration::from_millis(410));
+    }
+
+    #[test]
+    fn test_swap_mode_amounts() {
+        let mut quote: QuoteResponse =
+            parse(&quote_body().to_string()).expect("quote should parse");
+        assert_eq!(quote.swap_mode, SwapMode::ExactIn);
+        assert_eq!(quote.max_in_amount(), quote.in_amount);
+        assert_eq!(quote.min_out_amount(), quote.other_amount_threshold);
+        assert_eq!(
+            quote
+                .swap_mode
+                .amount_mint(&quote.input_mint, &quote.output_mint),
+            quote.input_mint
+        );
+
+        quote.swap_mode = SwapMode::ExactOut;
+        assert_eq!(quote.max_in_amount(), quote.other_amount_threshold);
+        assert_eq!(quote.min_out_amount(), quote.out_amount);
+        assert_eq!(
+            quote
+                .swap_mode
+                .threshold_mint(&quote.input_mint, &quote.output_mint),
+            quote.input_mint
+        );
+
+        assert_eq!(
+            parse::<SwapMode>("\"ExactOut\"").unwrap(),
+            SwapMode::ExactOut
+        );
+        assert_eq!(
+            serde_json::to_string(&SwapMode::ExactIn).unwrap(),
+            "\"ExactIn\""
+        );
+        assert!(SwapMode::Unknown.is_exact_in());
+    }
+
+    #[test]
+    fn test_display_quotes_and_routes() {
+        let quote: QuoteResponse = parse(&quote_body().to_string()).expect("quote should parse");
+        assert_eq!(
+            quote.to_string(),
+            "1 SOL → 421.337 JUP (SOL → JUP via Meteora DLMM 100%), \
+             min 419.230315 JUP, impact 0%, slippage 50 bps"
+        );
+        assert_eq!(
+            quote.route_plan[0].to_string(),
+            "100% Meteora DLMM: 1 SOL → 421.337 JUP"
+        );
+        assert_eq!(format!("{quote:#}").lines().count(), 2);
+
+        let mut exact_out = quote.clone();
+        exact_out.swap_mode = SwapMode::ExactOut;
+        exact_out.other_amount_threshold = 1_010_000_000.into();
+        assert_eq!(
+            exact_out.to_string(),
+            "1 SOL → 421.337 JUP (SOL → JUP via Meteora DLMM 100%), \
+             max in 1.01 SOL, impact 0%, slippage 50 bps"
+        );
+
+        let ultra: UltraOrderResponse =
+            parse(&ultra_order_body().to_string()).expect("order should parse");
+        assert!(ultra.to_string().starts_with("1 SOL → 421.337 JUP, min"));
+        assert!(
+            ultra
+                .to_string()
+                .ends_with("[Aggregator, request 0196d1b8-6d49-73ec-a0f0-6f9e7c2b1a2d]")
+        );
+    }
+}
@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod perps_tests {
+    use jup_ag_sdk::{
+        JupiterClient,
+        config::{ApiFamily, LITE_API_URL},
+        types::{PerpsMarket, PerpsSide},
+    };
+    use serde_json::json;
+
+    use crate::{
+        common::{SOL_MINT, TEST_USER_PUBKEY},
+        mock::{MockResponse, MockServer},
+    };
+
+    async fn perps_server() -> MockServer {
+        MockServer::start(|req| async move {
+            let body = if req.path.starts_with("/v1/positions?walletAddress=") {
+                json!({
+                    "dataList": [{
+                        "positionPubkey": "5BQzStdMtDJmQ2yFXmHjzXkMvQDkPZSPsM6NuLyz8ZS1",
+                        "side": "long",
+                        "marketMint": SOL_MINT,
+                        "collateralMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+                        "size": "1500.00",
+                        "collateral": "150.00",
+                        "leverage": "10.0",
+                        "entryPrice": "140.25",
+                        "markPrice": 147.48,
+                        "liquidationPrice": "127.1",
+                        "pnlAfterFees": "75.3",
+                        "createdTime": 1749000000
+                    }],
+                    "count": 1
+                })
+            } else if req.path == format!("/v1/market-stats?mint={SOL_MINT}") {
+                json!({
+                    "price": "147.48",
+                    "priceChange24H": "1.29",
+                    "priceHigh24H": "150.1",
+                    "priceLow24H": "141.9",
+                    "volume": "250000000"
+                })
+            } else if req.path == format!("/v1/pool-info?mint={SOL_MINT}") {
+                json!({
+                    "longAvailableLiquidity": "42000000",
+                    "longBorrowRatePercent": "0.0068",
+                    "longUtilizationPercent": "61.2",
+                    "shortAvailableLiquidity": "90000000",
+                    "shortBorrowRatePercent": "0.0021",
+                    "shortUtilizationPercent": "19.5",
+                    "openFeePercent": "0.06",
+                    "maxRequestExecutionSec": 45
+                })
+            } else {
+                return MockResponse::status(404, &req.path);
+            };
+            MockResponse::json(&body.to_string())
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_perps_endpoints() {
+        let server = perps_server().await;
+        let client = JupiterClient::builder(LITE_API_URL)
+            .perps_base_url(&server.url)
+            .build()
+            .unwrap();
+        assert_eq!(
+            client.endpoint(ApiFamily::Perps, "/positions"),
+            format!("{}/v1/positions", server.url)
+        );
+
+        let positions = client.get_perps_positions(TEST_USER_PUBKEY).await.unwrap();
+        assert_eq!(positions.count, 1);
+        let position = &positions.data_list[0];
+        assert_eq!(position.side, PerpsSide::Long);
+        assert_eq!(position.market(), Some(PerpsMarket::Sol));
+        assert_eq!(position.mark_price.as_deref(), Some("147.48"));
+        assert_eq!(position.borrow_fees, None);
+
+        let stats = client
+            .get_perps_market_stats(PerpsMarket::Sol)
+            .await
+            .unwrap();
+        assert_eq!(stats.price_change_24h.as_deref(), Some("1.29"));
+
+        let pool = client.get_perps_pool_info(PerpsMarket::Sol).await.unwrap();
+        assert_eq!(pool.borrow_rate_percent(PerpsSide::Long), Some(0.0068));
+        assert_eq!(pool.borrow_rate_percent(PerpsSide::Short), Some(0.0021));
+        assert_eq!(pool.max_request_execution_sec.as_deref(), Some("45"));
+
+        assert!(client.get_perps_positions("not a wallet").await.is_err());
+        assert_eq!(server.hits(), 3);
+        assert_eq!(client.stats().requests, 3);
+    }
+
+    #[test]
+    fn test_perps_markets() {
+        assert_eq!(PerpsMarket::from_mint(SOL_MINT), Some(PerpsMarket::Sol));
+        assert_eq!(PerpsMarket::from_mint(TEST_USER_PUBKEY), None);
+        let symbols: Vec<_> = PerpsMarket::ALL.iter().map(|m| m.symbol()).collect();
+        assert_eq!(symbols, ["SOL", "ETH", "BTC"]);
+    }
+}
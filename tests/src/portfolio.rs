@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod portfolio_tests {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use jup_ag_sdk::{
+        JupiterClient,
+        portfolio::{PortfolioEvent, PortfolioService},
+        types::{Amount, NATIVE_SOL, WSOL_MINT},
+    };
+    use serde_json::{Value, json};
+
+    use crate::{
+        common::{JUP_MINT, TEST_USER_PUBKEY, USDC_MINT},
+        mock::{MockResponse, MockServer},
+    };
+
+    const OTHER_WALLET: &str = "3X2LFoTQecbpqCR7G5tL1kczqBKurjKPHhKSZrJ4wgWc";
+
+    fn balance(amount: u64, decimals: i32) -> Value {
+        let ui_amount = amount as f64 / 10f64.powi(decimals);
+        json!({ "amount": amount.to_string(), "uiAmount": ui_amount, "slot": 1, "isFrozen": false })
+    }
+
+    /// Serves balances and prices of round 0 or 1, as set in the returned counter.
+    async fn server() -> (MockServer, Arc<AtomicUsize>) {
+        let round = Arc::new(AtomicUsize::new(0));
+        let server_round = round.clone();
+        let server = MockServer::start(move |req| {
+            let second = server_round.load(Ordering::SeqCst) == 1;
+            async move {
+                let body = if req.path.ends_with(TEST_USER_PUBKEY) {
+                    let sol = if second { 2_000_000_000 } else { 1_000_000_000 };
+                    json!({ "SOL": balance(sol, 9), USDC_MINT: balance(10_000_000, 6) })
+                } else if req.path.ends_with(OTHER_WALLET) {
+                    let mut balances = json!({ USDC_MINT: balance(100_000_000, 6) });
+                    if second {
+                        balances[JUP_MINT] = balance(2_000_000, 6);
+                    }
+                    balances
+                } else {
+                    let price = |mint: &str, price: &str| json!({ "id": mint, "type": "derivedPrice", "price": price });
+                    json!({
+                        "data": {
+                            WSOL_MINT: price(WSOL_MINT, "150"),
+                            USDC_MINT: price(USDC_MINT, "1"),
+                            JUP_MINT: price(JUP_MINT, "0.5")
+                        },
+                        "timeTaken": 0.001
+                    })
+                };
+                MockResponse::json(&body.to_string())
+            }
+        })
+        .await;
+        (server, round)
+    }
+
+    #[tokio::test]
+    async fn test_portfolio_refresh() {
+        let (server, round) = server().await;
+        let client = JupiterClient::new(&server.url);
+        let mut portfolio = PortfolioService::new().concurrency(2);
+        portfolio.add_wallet(TEST_USER_PUBKEY).unwrap();
+        portfolio.add_wallet(OTHER_WALLET).unwrap();
+        portfolio.add_wallet(OTHER_WALLET).unwrap();
+        assert!(portfolio.add_wallet("not-a-wallet").is_err());
+        assert_eq!(portfolio.wallets(), [TEST_USER_PUBKEY, OTHER_WALLET]);
+        let mut subscriber = portfolio.subscribe();
+
+        assert!(portfolio.snapshot().is_none());
+        assert!(portfolio.refresh(&client).await.unwrap().is_empty());
+        assert_eq!(server.hits(), 3);
+        let snapshot = portfolio.snapshot().unwrap();
+        assert_eq!(snapshot.wallet(TEST_USER_PUBKEY).unwrap().usd_value, 160.0);
+        assert_eq!(snapshot.usd_value(), 260.0);
+
+        round.store(1, Ordering::SeqCst);
+        let events = portfolio.refresh(&client).await.unwrap();
+        assert_eq!(
+            events,
+            [
+                PortfolioEvent::BalanceChanged {
+                    wallet: OTHER_WALLET.to_string(),
+                    mint: JUP_MINT.to_string(),
+                    previous: Amount::default(),
+                    current: Amount::new(2_000_000),
+                },
+                PortfolioEvent::BalanceChanged {
+                    wallet: TEST_USER_PUBKEY.to_string(),
+                    mint: NATIVE_SOL.to_string(),
+                    previous: Amount::new(1_000_000_000),
+                    current: Amount::new(2_000_000_000),
+                },
+                PortfolioEvent::ValueChanged {
+                    wallet: TEST_USER_PUBKEY.to_string(),
+                    previous_usd: 160.0,
+                    current_usd: 310.0,
+                },
+            ]
+        );
+        assert_eq!(subscriber.recv().await.unwrap(), events[0]);
+
+        let holdings: Vec<_> = portfolio
+            .snapshot()
+            .unwrap()
+            .holdings()
+            .into_iter()
+            .map(|holding| (holding.mint, holding.usd_value, holding.wallets))
+            .collect();
+        assert_eq!(
+            holdings,
+            [
+                (NATIVE_SOL.to_string(), Some(300.0), 1),
+                (USDC_MINT.to_string(), Some(110.0), 2),
+                (JUP_MINT.to_string(), Some(1.0), 1),
+            ]
+        );
+
+        assert!(portfolio.remove_wallet(OTHER_WALLET));
+        assert!(!portfolio.remove_wallet(OTHER_WALLET));
+        assert_eq!(portfolio.snapshot().unwrap().usd_value(), 310.0);
+    }
+
+    #[tokio::test]
+    async fn test_portfolio_failed_refresh_keeps_snapshot() {
+        let (server, _) = server().await;
+        let client = JupiterClient::new(&server.url);
+        let mut portfolio = PortfolioService::new();
+        portfolio.add_wallet(TEST_USER_PUBKEY).unwrap();
+        portfolio.refresh(&client).await.unwrap();
+
+        let failing = MockServer::start(|_| async { MockResponse::status(500, "down") }).await;
+        let failing_client = JupiterClient::new(&failing.url);
+        assert!(portfolio.refresh(&failing_client).await.is_err());
+        let wallet = portfolio
+            .snapshot()
+            .unwrap()
+            .wallet(TEST_USER_PUBKEY)
+            .unwrap();
+        assert_eq!(wallet.balance(NATIVE_SOL).unwrap().amount, 1_000_000_000);
+    }
+}
@@ -0,0 +1,27 @@
+#[cfg(test)]
+mod prelude_tests {
+    use jup_ag_sdk::prelude::*;
+
+    use crate::common::{JUP_MINT, SOL_MINT, TEST_AMOUNT, TEST_USER_PUBKEY, quote_json};
+
+    #[test]
+    fn test_prelude_covers_a_swap() {
+        let client = JupiterClient::builder("https://lite-api.jup.ag")
+            .quote_freshness(QuoteFreshness::default().max_slot_lag(20))
+            .build()
+            .unwrap();
+        assert_eq!(
+            client.endpoint(ApiFamily::Swap, "/quote"),
+            "https://lite-api.jup.ag/swap/v1/quote"
+        );
+
+        let request =
+            QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT).swap_mode(SwapMode::ExactIn);
+        assert_eq!(request.amount, TEST_AMOUNT);
+
+        let quote: QuoteResponse = serde_json::from_value(quote_json()).unwrap();
+        assert_eq!(quote.in_amount(), Amount::new(TEST_AMOUNT));
+        let swap = SwapRequest::new(TEST_USER_PUBKEY, quote);
+        assert_eq!(swap.quote_response.slippage_bps, Bps::new(50).unwrap());
+    }
+}
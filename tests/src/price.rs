@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod price_tests {
+    use jup_ag_sdk::{JupiterClient, JupiterClientError, config::ApiPaths};
+    use serde_json::json;
+
+    use crate::{
+        common::{JUP_MINT, SOL_MINT, USDC_MINT},
+        mock::{MockResponse, MockServer},
+    };
+
+    #[tokio::test]
+    async fn test_get_prices_v3() {
+        let server = MockServer::start(|req| async move {
+            assert_eq!(req.path, format!("/price/v3?ids={SOL_MINT}%2C{JUP_MINT}"));
+            MockResponse::json(
+                &json!({
+                    SOL_MINT: {
+                        "usdPrice": 147.48,
+                        "blockId": 348004023,
+                        "decimals": 9,
+                        "priceChange24h": 1.29
+                    },
+                    JUP_MINT: { "usdPrice": 0.42, "decimals": 6 }
+                })
+                .to_string(),
+            )
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+
+        let prices = client
+            .get_prices_v3(&[SOL_MINT.to_string(), JUP_MINT.to_string()])
+            .await
+            .unwrap();
+        let sol = &prices[SOL_MINT];
+        assert_eq!(sol.usd_price, 147.48);
+        assert_eq!(sol.block_id, Some(348004023));
+        assert_eq!(sol.price_change_24h, Some(1.29));
+        assert_eq!(prices[JUP_MINT].block_id, None);
+        assert!(!prices.contains_key(USDC_MINT));
+
+        assert!(matches!(
+            client.get_prices_v3(&[]).await,
+            Err(JupiterClientError::ValidationError(_))
+        ));
+        let too_many = vec![SOL_MINT.to_string(); 51];
+        assert!(matches!(
+            client.get_prices_v3(&too_many).await,
+            Err(JupiterClientError::ValidationError(_))
+        ));
+        assert_eq!(server.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_price_v3_path() {
+        let server = MockServer::start(|req| async move {
+            assert!(req.path.starts_with("/gateway/price?ids="), "{}", req.path);
+            MockResponse::json("{}")
+        })
+        .await;
+        let client = JupiterClient::new(&server.url)
+            .with_paths(ApiPaths::default().price_v3("gateway/price/"));
+
+        let prices = client.get_prices_v3(&[SOL_MINT.to_string()]).await.unwrap();
+        assert!(prices.is_empty());
+        assert_eq!(server.hits(), 1);
+    }
+}
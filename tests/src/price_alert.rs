@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod price_alert_tests {
+    use std::sync::{Arc, Mutex};
+
+    use jup_ag_sdk::{
+        JupiterClient,
+        price_alert::{PriceAlert, PriceAlertEvent, PriceAlertManager},
+    };
+    use serde_json::json;
+
+    use crate::{
+        common::{JUP_MINT, SOL_MINT, USDC_MINT},
+        mock::{MockResponse, MockServer},
+    };
+
+    /// A Price API whose prices are set by the test, recording the requested paths.
+    async fn price_server(
+        prices: Arc<Mutex<Vec<(&'static str, f64)>>>,
+    ) -> (MockServer, Arc<Mutex<Vec<String>>>) {
+        let paths = Arc::new(Mutex::new(Vec::new()));
+        let recorded = paths.clone();
+        let server = MockServer::start(move |req| {
+            let prices = prices.clone();
+            let recorded = recorded.clone();
+            async move {
+                assert!(req.path.starts_with("/price/v2"), "{}", req.path);
+                recorded.lock().unwrap().push(req.path.clone());
+                let data: serde_json::Map<String, serde_json::Value> = prices
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .map(|(mint, price)| {
+                        let price = json!({ "id": mint, "type": "derivedPrice", "price": price.to_string() });
+                        (mint.to_string(), price)
+                    })
+                    .collect();
+                MockResponse::json(&json!({ "data": data, "timeTaken": 0.001 }).to_string())
+            }
+        })
+        .await;
+        (server, paths)
+    }
+
+    #[tokio::test]
+    async fn test_alerts_fire_once_with_hysteresis() {
+        let prices = Arc::new(Mutex::new(vec![(SOL_MINT, 150.0), (JUP_MINT, 0.9)]));
+        let (server, paths) = price_server(prices.clone()).await;
+        let client = JupiterClient::new(&server.url);
+
+        let mut manager = PriceAlertManager::new();
+        let sol = manager
+            .add(PriceAlert::above(SOL_MINT, 160.0).hysteresis(0.05))
+            .unwrap();
+        let jup = manager.add(PriceAlert::below(JUP_MINT, 1.0)).unwrap();
+        let mut events = manager.subscribe();
+
+        // JUP is already below its threshold
+        let fired = manager.refresh(&client).await.unwrap();
+        assert_eq!(fired.len(), 1);
+        assert!(
+            matches!(fired[0], PriceAlertEvent::Triggered { id, price, .. } if id == jup && price == 0.9)
+        );
+        assert_eq!(events.try_recv().unwrap(), fired[0]);
+        assert_eq!(manager.price(SOL_MINT), Some(150.0));
+
+        // SOL crosses, JUP stays below without firing again
+        *prices.lock().unwrap() = vec![(SOL_MINT, 161.0), (JUP_MINT, 0.8)];
+        let fired = manager.refresh(&client).await.unwrap();
+        assert_eq!(fired.len(), 1);
+        assert!(matches!(fired[0], PriceAlertEvent::Triggered { id, .. } if id == sol));
+        assert!(manager.is_triggered(sol));
+
+        // dipping under the threshold but not past the 5% hysteresis keeps SOL fired
+        *prices.lock().unwrap() = vec![(SOL_MINT, 155.0), (JUP_MINT, 0.8)];
+        assert!(manager.refresh(&client).await.unwrap().is_empty());
+        *prices.lock().unwrap() = vec![(SOL_MINT, 161.0), (JUP_MINT, 0.8)];
+        assert!(manager.refresh(&client).await.unwrap().is_empty());
+
+        *prices.lock().unwrap() = vec![(SOL_MINT, 151.0), (JUP_MINT, 0.8)];
+        let fired = manager.refresh(&client).await.unwrap();
+        assert!(matches!(fired[..], [PriceAlertEvent::Rearmed { id, .. }] if id == sol));
+        assert!(!manager.is_triggered(sol));
+
+        *prices.lock().unwrap() = vec![(SOL_MINT, 170.0), (JUP_MINT, 0.8)];
+        let fired = manager.refresh(&client).await.unwrap();
+        assert!(matches!(fired[..], [PriceAlertEvent::Triggered { id, .. }] if id == sol));
+
+        // both mints in one request
+        let paths = paths.lock().unwrap();
+        assert_eq!(paths.len(), 6);
+        assert!(paths[0].contains(JUP_MINT) && paths[0].contains(SOL_MINT));
+    }
+
+    #[tokio::test]
+    async fn test_vs_token_and_remove() {
+        let prices = Arc::new(Mutex::new(vec![(JUP_MINT, 0.004)]));
+        let (server, paths) = price_server(prices).await;
+        let client = JupiterClient::new(&server.url);
+
+        let mut manager = PriceAlertManager::new().vs_token(SOL_MINT).unwrap();
+        let jup = manager.add(PriceAlert::above(JUP_MINT, 0.003)).unwrap();
+        let usdc = manager.add(PriceAlert::below(USDC_MINT, 0.99)).unwrap();
+        assert_eq!(manager.alerts().count(), 2);
+
+        // USDC has no price: skipped
+        let fired = manager.refresh(&client).await.unwrap();
+        assert!(matches!(fired[..], [PriceAlertEvent::Triggered { id, .. }] if id == jup));
+        assert!(paths.lock().unwrap()[0].contains(&format!("vsToken={SOL_MINT}")));
+
+        assert_eq!(manager.remove(usdc).unwrap().mint, USDC_MINT);
+        assert!(manager.remove(usdc).is_none());
+        manager.refresh(&client).await.unwrap();
+        assert!(!paths.lock().unwrap()[1].contains(USDC_MINT));
+    }
+
+    #[test]
+    fn test_alert_validation() {
+        let mut manager = PriceAlertManager::new();
+        assert!(manager.add(PriceAlert::above("not-a-mint", 1.0)).is_err());
+        assert!(manager.add(PriceAlert::above(JUP_MINT, 0.0)).is_err());
+        assert!(manager.add(PriceAlert::above(JUP_MINT, f64::NAN)).is_err());
+        assert!(
+            manager
+                .add(PriceAlert::below(JUP_MINT, 1.0).hysteresis(1.0))
+                .is_err()
+        );
+        assert!(PriceAlertManager::new().vs_token("not-a-mint").is_err());
+        assert_eq!(manager.alerts().count(), 0);
+    }
+}
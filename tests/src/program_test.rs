@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod program_test_tests {
+    use base64::{Engine, engine::general_purpose::STANDARD};
+    use jup_ag_sdk::{
+        JupiterClient,
+        program_test::{AccountFixtures, FixtureIssue},
+    };
+    use serde_json::{Value, json};
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_pubkey::Pubkey;
+
+    use crate::mock::{MockResponse, MockServer};
+
+    const UPGRADEABLE_LOADER: &str = "BPFLoaderUpgradeab1e11111111111111111111111";
+    const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGqPK9ZjRR9Ph6xpW1ATZYo";
+
+    fn account(owner: &str, data: &[u8], executable: bool) -> Value {
+        json!({
+            "lamports": 1_000_000,
+            "data": [STANDARD.encode(data), "base64"],
+            "owner": owner,
+            "executable": executable,
+            "rentEpoch": u64::MAX,
+            "space": data.len(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_clone_accounts() {
+        let program = Pubkey::new_unique();
+        let program_data = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+        let user_ata = Pubkey::new_unique();
+
+        let server = MockServer::start(move |req| async move {
+            let request: Value = serde_json::from_str(&req.body).unwrap();
+            assert_eq!(request["method"], "getMultipleAccounts");
+            let values: Vec<Value> = request["params"][0]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|address| {
+                    let address: Pubkey = address.as_str().unwrap().parse().unwrap();
+                    if address == program {
+                        let mut data = vec![2, 0, 0, 0];
+                        data.extend_from_slice(program_data.as_ref());
+                        account(UPGRADEABLE_LOADER, &data, true)
+                    } else if address == program_data {
+                        account(UPGRADEABLE_LOADER, &[3; 64], false)
+                    } else if address == pool {
+                        account(&program.to_string(), &[7; 16], false)
+                    } else {
+                        Value::Null
+                    }
+                })
+                .collect();
+            MockResponse::json(
+                &json!({ "jsonrpc": "2.0", "id": 1, "result": { "value": values } }).to_string(),
+            )
+        })
+        .await;
+        let client = JupiterClient::new("https://lite-api.jup.ag");
+
+        let instructions = [Instruction {
+            program_id: program,
+            accounts: vec![
+                AccountMeta::new(pool, false),
+                AccountMeta::new(user_ata, false),
+            ],
+            data: vec![1],
+        }];
+        let fixtures = client
+            .clone_accounts(&server.url, &instructions)
+            .await
+            .unwrap();
+        assert_eq!(server.hits(), 2);
+        assert_eq!(fixtures.len(), 3);
+        assert_eq!(fixtures.missing(), [user_ata]);
+        assert_eq!(fixtures.get(&pool).unwrap().data, [7; 16]);
+        assert_eq!(fixtures.get(&pool).unwrap().owner, program);
+        assert_eq!(
+            fixtures.get(&program).unwrap().program_data_address(),
+            Some(program_data)
+        );
+        assert!(fixtures.check(&instructions).is_empty());
+
+        let json = serde_json::to_string(&fixtures).unwrap();
+        let loaded: AccountFixtures = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            loaded.iter().collect::<Vec<_>>(),
+            fixtures.iter().collect::<Vec<_>>()
+        );
+        assert!(loaded.missing().is_empty());
+
+        let file: Value = serde_json::from_str(&fixtures.account_json(&pool).unwrap()).unwrap();
+        assert_eq!(file["pubkey"], pool.to_string());
+        assert_eq!(file["account"]["owner"], program.to_string());
+        assert_eq!(file["account"]["space"], 16);
+        assert_eq!(file["account"]["rentEpoch"], u64::MAX);
+    }
+
+    #[test]
+    fn test_fixture_check() {
+        let program = Pubkey::new_unique();
+        let program_data = Pubkey::new_unique();
+        let not_a_program = Pubkey::new_unique();
+        let token_program: Pubkey = TOKEN_PROGRAM.parse().unwrap();
+
+        let mut data = vec![2, 0, 0, 0];
+        data.extend_from_slice(program_data.as_ref());
+        let fixtures: AccountFixtures = serde_json::from_value(json!([
+            { "pubkey": program.to_string(), "account": account(UPGRADEABLE_LOADER, &data, true) },
+            { "pubkey": not_a_program.to_string(), "account": account(TOKEN_PROGRAM, &[], false) },
+        ]))
+        .unwrap();
+
+        let call = |program_id| Instruction::new_with_bytes(program_id, &[], vec![]);
+        let issues = fixtures.check(&[
+            call(program),
+            call(not_a_program),
+            call(token_program),
+            call(program),
+        ]);
+        assert_eq!(
+            issues,
+            [
+                FixtureIssue::MissingProgramData {
+                    program,
+                    program_data
+                },
+                FixtureIssue::NotExecutable(not_a_program),
+                FixtureIssue::MissingProgram(token_program),
+            ]
+        );
+        assert_eq!(
+            issues[2].to_string(),
+            format!("program {TOKEN_PROGRAM} is not cloned")
+        );
+    }
+}
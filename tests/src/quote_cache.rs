@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod quote_cache_tests {
+    use std::time::Duration;
+
+    use jup_ag_sdk::{
+        JupiterClient,
+        config::QuoteCacheConfig,
+        types::{QuoteRequest, SwapRequest},
+    };
+    use serde_json::{Value, json};
+
+    use crate::{
+        common::{JUP_MINT, SOL_MINT, TEST_AMOUNT, TEST_USER_PUBKEY, quote_json},
+        mock::{MockResponse, MockServer},
+    };
+
+    /// Serves quotes computed at slot 100.
+    async fn quote_server() -> MockServer {
+        MockServer::start(|_| async { MockResponse::json(&quote_json().to_string()) }).await
+    }
+
+    fn cached_client(url: &str, config: QuoteCacheConfig) -> JupiterClient {
+        JupiterClient::builder(url)
+            .quote_cache(config)
+            .build()
+            .unwrap()
+    }
+
+    fn request(amount: u64) -> QuoteRequest {
+        QuoteRequest::new(SOL_MINT, JUP_MINT, amount)
+    }
+
+    #[tokio::test]
+    async fn test_identical_requests_served_from_cache() {
+        let server = quote_server().await;
+        let client = cached_client(&server.url, QuoteCacheConfig::default());
+
+        let first = client.get_quote(&request(TEST_AMOUNT)).await.unwrap();
+        let second = client.get_quote(&request(TEST_AMOUNT)).await.unwrap();
+        client
+            .get_quote(&request(TEST_AMOUNT).slippage_bps(100))
+            .await
+            .unwrap();
+
+        assert_eq!(first.out_amount, second.out_amount);
+        assert_eq!(server.hits(), 2);
+        assert_eq!(client.stats().quote_cache_hits, 1);
+    }
+
+    #[tokio::test]
+    async fn test_slot_advancement_invalidates() {
+        let server = quote_server().await;
+        let client = cached_client(&server.url, QuoteCacheConfig::default().max_slot_lag(2));
+
+        client.get_quote(&request(TEST_AMOUNT)).await.unwrap();
+        client.observe_slot(102);
+        client.get_quote(&request(TEST_AMOUNT)).await.unwrap();
+        assert_eq!(server.hits(), 1);
+
+        client.observe_slot(103);
+        client.get_quote(&request(TEST_AMOUNT)).await.unwrap();
+        assert_eq!(server.hits(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_ttl_expiry_and_clear() {
+        let server = quote_server().await;
+        let client = cached_client(
+            &server.url,
+            QuoteCacheConfig::default().ttl(Duration::from_millis(50)),
+        );
+
+        client.get_quote(&request(TEST_AMOUNT)).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        client.get_quote(&request(TEST_AMOUNT)).await.unwrap();
+        assert_eq!(server.hits(), 2);
+
+        client.clear_quote_cache();
+        client.get_quote(&request(TEST_AMOUNT)).await.unwrap();
+        assert_eq!(server.hits(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_amount_buckets() {
+        let server = quote_server().await;
+        let client = cached_client(&server.url, QuoteCacheConfig::default().amount_precision(4));
+
+        let exact = client.get_quote(&request(1_234_567)).await.unwrap();
+        let nearby = client.get_quote(&request(1_234_999)).await.unwrap();
+        assert_eq!(server.hits(), 1);
+        assert!(!exact.is_approximate());
+        assert!(nearby.is_approximate());
+
+        let again = client.get_quote(&request(1_234_567)).await.unwrap();
+        assert!(!again.is_approximate());
+
+        client.get_quote(&request(1_235_000)).await.unwrap();
+        assert_eq!(server.hits(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_swap_from_approximate_quote_is_requoted() {
+        // quotes echo the requested amount, swaps the amount of the quote they were built from
+        let server = MockServer::start(|req| async move {
+            if req.path.starts_with("/swap/v1/quote") {
+                let amount = req.path.split("amount=").nth(1).unwrap();
+                let amount = amount.split('&').next().unwrap();
+                let mut quote = quote_json();
+                quote["inAmount"] = json!(amount);
+                return MockResponse::json(&quote.to_string());
+            }
+            let body: Value = serde_json::from_str(&req.body).unwrap();
+            MockResponse::json(
+                &json!({
+                    "swapTransaction": body["quoteResponse"]["inAmount"],
+                    "lastValidBlockHeight": 1,
+                    "prioritizationFeeLamports": 0
+                })
+                .to_string(),
+            )
+        })
+        .await;
+        let client = cached_client(&server.url, QuoteCacheConfig::default().amount_precision(4));
+
+        client.get_quote(&request(1_234_567)).await.unwrap();
+        let nearby = client.get_quote(&request(1_234_999)).await.unwrap();
+        assert!(nearby.is_approximate());
+        assert_eq!(nearby.in_amount, 1_234_567);
+
+        let swap = SwapRequest::new(TEST_USER_PUBKEY, nearby);
+        let transaction = client.get_swap_transaction(&swap).await.unwrap();
+        assert_eq!(transaction.swap_transaction, "1234999");
+        assert_eq!(server.hits(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_exact_amounts_without_precision() {
+        let server = quote_server().await;
+        let client = cached_client(&server.url, QuoteCacheConfig::default());
+
+        client.get_quote(&request(1_234_567)).await.unwrap();
+        client.get_quote(&request(1_234_568)).await.unwrap();
+
+        assert_eq!(server.hits(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_no_cache_by_default() {
+        let server = quote_server().await;
+        let client = JupiterClient::new(&server.url);
+
+        client.get_quote(&request(TEST_AMOUNT)).await.unwrap();
+        client.get_quote(&request(TEST_AMOUNT)).await.unwrap();
+
+        assert_eq!(server.hits(), 2);
+        assert_eq!(client.stats().quote_cache_hits, 0);
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod quote_freshness_tests {
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicU64, Ordering},
+        },
+        time::Duration,
+    };
+
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError,
+        config::{QuoteFreshness, StaleQuotePolicy},
+        types::{QuoteRequest, QuoteResponse, SwapRequest},
+    };
+    use serde_json::{Value, json};
+
+    use crate::{
+        common::{JUP_MINT, SOL_MINT, TEST_AMOUNT, TEST_USER_PUBKEY, quote_json},
+        mock::{MockResponse, MockServer},
+    };
+
+    fn quote_at(slot: u64) -> Value {
+        let mut quote = quote_json();
+        quote["contextSlot"] = json!(slot);
+        quote
+    }
+
+    /// Serves quotes at slots 100, 101, ... and swaps whose transaction is the slot of
+    /// the quote they were built from.
+    async fn swap_server() -> MockServer {
+        let slot = Arc::new(AtomicU64::new(100));
+        MockServer::start(move |req| {
+            let slot = slot.clone();
+            async move {
+                if req.path.starts_with("/swap/v1/quote") {
+                    let slot = slot.fetch_add(1, Ordering::SeqCst);
+                    return MockResponse::json(&quote_at(slot).to_string());
+                }
+                let body: Value = serde_json::from_str(&req.body).unwrap();
+                MockResponse::json(
+                    &json!({
+                        "swapTransaction": body["quoteResponse"]["contextSlot"].to_string(),
+                        "lastValidBlockHeight": 1,
+                        "prioritizationFeeLamports": 0
+                    })
+                    .to_string(),
+                )
+            }
+        })
+        .await
+    }
+
+    fn client(url: &str, freshness: QuoteFreshness) -> JupiterClient {
+        JupiterClient::builder(url)
+            .quote_freshness(freshness)
+            .build()
+            .unwrap()
+    }
+
+    fn request() -> QuoteRequest {
+        QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+    }
+
+    #[tokio::test]
+    async fn test_stale_quote_is_requoted() {
+        let server = swap_server().await;
+        let client = client(
+            &server.url,
+            QuoteFreshness::default()
+                .max_age(Duration::from_millis(50))
+                .max_slot_lag(5),
+        );
+
+        let quote = client.get_quote(&request()).await.unwrap();
+        assert!(quote.age().unwrap() < Duration::from_millis(50));
+        let swap = SwapRequest::new(TEST_USER_PUBKEY, quote);
+        let fresh = client.get_swap_transaction(&swap).await.unwrap();
+        assert_eq!(fresh.swap_transaction, "100");
+
+        client.observe_slot(106);
+        let requoted = client.get_swap_transaction(&swap).await.unwrap();
+        assert_eq!(requoted.swap_transaction, "101");
+
+        let quote = client.get_quote(&request()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        let swap = SwapRequest::new(TEST_USER_PUBKEY, quote);
+        let requoted = client.get_swap_transaction(&swap).await.unwrap();
+        assert_eq!(requoted.swap_transaction, "103");
+        assert_eq!(server.hits(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_stale_quote_rejected() {
+        let server = swap_server().await;
+        let client = client(
+            &server.url,
+            QuoteFreshness::default()
+                .max_slot_lag(5)
+                .on_stale(StaleQuotePolicy::Error),
+        );
+
+        let quote = client.get_quote(&request()).await.unwrap();
+        client.observe_slot(110);
+        let swap = SwapRequest::new(TEST_USER_PUBKEY, quote);
+        let error = client.get_swap_transaction(&swap).await.unwrap_err();
+        assert!(matches!(
+            error,
+            JupiterClientError::StaleQuote {
+                age: Some(_),
+                slot_lag: 10
+            }
+        ));
+        assert_eq!(server.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_quote_not_fetched_by_client() {
+        let server = swap_server().await;
+        let client = client(
+            &server.url,
+            QuoteFreshness::default()
+                .max_age(Duration::from_millis(1))
+                .max_slot_lag(5),
+        );
+
+        // no known age, so only its slot is checked
+        let quote: QuoteResponse = serde_json::from_value(quote_at(98)).unwrap();
+        assert_eq!(quote.age(), None);
+        let swap = SwapRequest::new(TEST_USER_PUBKEY, quote);
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        client.get_swap_transaction(&swap).await.unwrap();
+
+        // and it can't be re-quoted
+        client.observe_slot(110);
+        let error = client.get_swap_transaction(&swap).await.unwrap_err();
+        assert!(matches!(
+            error,
+            JupiterClientError::StaleQuote {
+                age: None,
+                slot_lag: 12
+            }
+        ));
+        assert_eq!(server.hits(), 1);
+    }
+}
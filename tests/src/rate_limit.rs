@@ -0,0 +1,256 @@
+#[cfg(test)]
+mod rate_limit_tests {
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::{Duration, Instant},
+    };
+
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError,
+        client::RateLimitInfo,
+        config::{ApiFamily, RateLimitPlan},
+    };
+    use reqwest::header::{HeaderMap, HeaderValue};
+
+    use crate::mock::{MockResponse, MockServer};
+
+    #[test]
+    fn test_presets() {
+        assert_eq!(RateLimitPlan::Lite.requests(), 60);
+        assert_eq!(RateLimitPlan::Lite.window(), Duration::from_secs(60));
+        assert_eq!(RateLimitPlan::ProII.requests(), 500);
+        assert_eq!(RateLimitPlan::ProII.window(), Duration::from_secs(10));
+
+        let client = JupiterClient::builder("http://localhost")
+            .rate_limit(RateLimitPlan::ProI)
+            .build()
+            .unwrap();
+        assert_eq!(client.rate_limit(), Some(RateLimitPlan::ProI));
+        assert_eq!(JupiterClient::new("http://localhost").rate_limit(), None);
+    }
+
+    #[tokio::test]
+    async fn test_requests_are_throttled() {
+        let server = MockServer::json("[]").await;
+        let client = JupiterClient::builder(&server.url)
+            .rate_limit(RateLimitPlan::Custom {
+                requests: 2,
+                window: Duration::from_millis(200),
+            })
+            .build()
+            .unwrap();
+
+        let start = Instant::now();
+        for _ in 0..4 {
+            client.routers().await.expect("request should succeed");
+        }
+
+        // the first two go out immediately, the next two wait 100ms each
+        assert!(start.elapsed() >= Duration::from_millis(180));
+        assert_eq!(server.hits(), 4);
+
+        let stats = client.stats();
+        assert_eq!(stats.requests, 4);
+        assert_eq!(stats.rate_limit_waits, 2);
+        assert!(stats.rate_limit_wait_time >= Duration::from_millis(150));
+        assert_eq!(stats.retries, 0);
+    }
+
+    #[tokio::test]
+    async fn test_adjusts_to_reported_limit() {
+        let server = MockServer::start(|_| async {
+            MockResponse::json("[]").with_header("x-ratelimit-limit", "250")
+        })
+        .await;
+        let client = JupiterClient::builder(&server.url)
+            .rate_limit(RateLimitPlan::ProI)
+            .build()
+            .unwrap();
+
+        client.routers().await.expect("request should succeed");
+
+        assert_eq!(
+            client.rate_limit(),
+            Some(RateLimitPlan::Custom {
+                requests: 250,
+                window: Duration::from_secs(10),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_info() {
+        let server = MockServer::start(|_| async {
+            MockResponse::json("[]")
+                .with_header("x-ratelimit-limit", "600")
+                .with_header("x-ratelimit-remaining", "598")
+                .with_header("x-ratelimit-reset", "42")
+        })
+        .await;
+        // tracked without a client-side rate limit
+        let client = JupiterClient::new(&server.url);
+        assert_eq!(client.rate_limit_info(ApiFamily::Ultra), None);
+
+        client.routers().await.unwrap();
+        let info = client.rate_limit_info(ApiFamily::Ultra).unwrap();
+        assert_eq!(info.limit, Some(600));
+        assert_eq!(info.remaining, Some(598));
+        assert_eq!(info.reset, Some(Duration::from_secs(42)));
+        assert_eq!(
+            info.resets_at(),
+            Some(info.observed_at + Duration::from_secs(42))
+        );
+        assert_eq!(client.rate_limit_info(ApiFamily::Swap), None);
+    }
+
+    #[test]
+    fn test_rate_limit_info_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(RateLimitInfo::from_headers(&headers), None);
+
+        headers.insert("ratelimit-remaining", HeaderValue::from_static("7"));
+        let reset_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            + Duration::from_secs(30);
+        headers.insert(
+            "x-ratelimit-reset",
+            HeaderValue::from_str(&reset_at.as_secs().to_string()).unwrap(),
+        );
+        let info = RateLimitInfo::from_headers(&headers).unwrap();
+        assert_eq!((info.limit, info.remaining), (None, Some(7)));
+        let reset = info.reset.unwrap();
+        assert!(reset > Duration::from_secs(28) && reset <= Duration::from_secs(30));
+    }
+
+    /// Answers with a 429 and `retry_after` until `limited` requests were sent.
+    async fn limited_server(limited: usize, retry_after: Option<&'static str>) -> MockServer {
+        let count = Arc::new(AtomicUsize::new(0));
+        MockServer::start(move |_| {
+            let count = count.clone();
+            async move {
+                if count.fetch_add(1, Ordering::SeqCst) >= limited {
+                    return MockResponse::json("[]");
+                }
+                let response = MockResponse::status(429, r#"{"message":"Rate limit exceeded"}"#);
+                match retry_after {
+                    Some(value) => response.with_header("retry-after", value),
+                    None => response,
+                }
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_surfaces_as_rate_limited() {
+        let server = limited_server(usize::MAX, Some("7")).await;
+        let client = JupiterClient::new(&server.url);
+        match client.routers().await {
+            Err(JupiterClientError::RateLimited {
+                retry_after, body, ..
+            }) => {
+                assert_eq!(retry_after, Duration::from_secs(7));
+                assert!(body.contains("Rate limit exceeded"));
+            }
+            other => panic!("expected RateLimited, got {other:?}"),
+        }
+
+        // an HTTP date in the past means no wait
+        let server = limited_server(usize::MAX, Some("Wed, 21 Oct 2015 07:28:00 GMT")).await;
+        let client = JupiterClient::new(&server.url);
+        assert!(matches!(
+            client.routers().await,
+            Err(JupiterClientError::RateLimited { retry_after, .. }) if retry_after.is_zero()
+        ));
+
+        // without the header it stays a plain API error
+        let server = limited_server(usize::MAX, None).await;
+        let client = JupiterClient::new(&server.url);
+        assert!(matches!(
+            client.routers().await,
+            Err(JupiterClientError::ApiError(e)) if e.status.as_u16() == 429
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_requests_are_retried() {
+        let server = limited_server(2, Some("0")).await;
+        let client = JupiterClient::builder(&server.url)
+            .retry_rate_limited(3, Duration::from_secs(1))
+            .build()
+            .unwrap();
+
+        client.routers().await.expect("request should succeed");
+        assert_eq!(server.hits(), 3);
+        assert_eq!(client.stats().retries, 2);
+
+        // out of retries
+        let server = limited_server(2, Some("0")).await;
+        let client = JupiterClient::builder(&server.url)
+            .retry_rate_limited(1, Duration::from_secs(1))
+            .build()
+            .unwrap();
+        assert!(matches!(
+            client.routers().await,
+            Err(JupiterClientError::RateLimited { .. })
+        ));
+        assert_eq!(server.hits(), 2);
+
+        // asked to wait longer than allowed
+        let server = limited_server(2, Some("120")).await;
+        let client = JupiterClient::builder(&server.url)
+            .retry_rate_limited(3, Duration::from_secs(1))
+            .build()
+            .unwrap();
+        assert!(matches!(
+            client.routers().await,
+            Err(JupiterClientError::RateLimited { .. })
+        ));
+        assert_eq!(server.hits(), 1);
+        assert_eq!(client.stats().retries, 0);
+    }
+
+    #[tokio::test]
+    async fn test_on_error_reports_attempt_after_rate_limit_retry() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let server = MockServer::start(move |_| {
+            let first = calls.fetch_add(1, Ordering::SeqCst) == 0;
+            async move {
+                if first {
+                    MockResponse::status(429, "Rate limit exceeded").with_header("Retry-After", "0")
+                } else {
+                    MockResponse::status(500, "Internal error")
+                }
+            }
+        })
+        .await;
+        let attempts = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = attempts.clone();
+        let client = JupiterClient::builder(&server.url)
+            .retry_rate_limited(3, Duration::from_secs(1))
+            .on_error(move |ctx| {
+                sink.lock()
+                    .unwrap()
+                    .push((ctx.attempt, ctx.status.map(|s| s.as_u16())))
+            })
+            .build()
+            .unwrap();
+
+        assert!(client.routers().await.is_err());
+        assert_eq!(server.hits(), 2);
+        assert_eq!(*attempts.lock().unwrap(), vec![(2, Some(500))]);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one request")]
+    fn test_zero_rate_limit_panics() {
+        let _ = JupiterClient::builder("http://localhost").rate_limit(RateLimitPlan::Custom {
+            requests: 0,
+            window: Duration::from_secs(1),
+        });
+    }
+}
@@ -1,7 +1,10 @@
 #[cfg(test)]
 mod recurring_tests {
+    use std::time::Duration;
+
     use jup_ag_sdk::types::{
-        CreateRecurringOrderRequest, GetRecurringOrders, OrderStatus, RecurringOrderType,
+        CreateRecurringOrderRequest, GetRecurringOrders, OrderParams, OrderStatus,
+        RecurringOrderType,
     };
 
     use crate::common::{SOL_MINT, TEST_USER_PUBKEY, USDC_MINT, create_test_client};
@@ -37,6 +40,80 @@ mod recurring_tests {
         );
     }
 
+    const WEEK: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+    fn price_params(order: &CreateRecurringOrderRequest) -> (u64, u64, u64) {
+        match &order.params {
+            OrderParams::PriceWrapper { price } => (
+                price.deposit_amount,
+                price.increment_usdc_value,
+                price.interval,
+            ),
+            OrderParams::TimeWrapper { .. } => panic!("expected a price-based order"),
+        }
+    }
+
+    #[test]
+    fn test_value_averaging_order_from_increment() {
+        let order = CreateRecurringOrderRequest::new_value_averaging_order(
+            TEST_USER_PUBKEY,
+            USDC_MINT,
+            SOL_MINT,
+            50.0,
+            WEEK,
+            WEEK * 12,
+        )
+        .unwrap();
+
+        assert_eq!(
+            price_params(&order),
+            (600_000_000, 50_000_000, 604_800),
+            "deposit should cover one increment per week"
+        );
+    }
+
+    #[test]
+    fn test_value_averaging_order_from_budget() {
+        let order = CreateRecurringOrderRequest::new_value_averaging_order_with_budget(
+            TEST_USER_PUBKEY,
+            USDC_MINT,
+            SOL_MINT,
+            1_000.0,
+            WEEK,
+            WEEK * 3 + Duration::from_secs(60),
+        )
+        .unwrap();
+
+        assert_eq!(price_params(&order), (1_000_000_000, 333_333_333, 604_800));
+    }
+
+    #[test]
+    fn test_value_averaging_order_validation() {
+        let order = |increment_usd, interval, duration| {
+            CreateRecurringOrderRequest::new_value_averaging_order(
+                TEST_USER_PUBKEY,
+                USDC_MINT,
+                SOL_MINT,
+                increment_usd,
+                interval,
+                duration,
+            )
+        };
+
+        assert!(
+            order(10.0, WEEK, WEEK * 5).is_err(),
+            "50 USD is below the minimum"
+        );
+        assert!(
+            order(50.0, WEEK, WEEK * 2).is_ok(),
+            "100 USD is the minimum"
+        );
+        assert!(order(500.0, WEEK * 2, WEEK).is_err());
+        assert!(order(500.0, Duration::from_millis(500), WEEK).is_err());
+        assert!(order(f64::NAN, WEEK, WEEK * 4).is_err());
+        assert!(order(-50.0, WEEK, WEEK * 4).is_err());
+    }
+
     #[tokio::test]
     async fn test_create_recurring_price_order() {
         let client = create_test_client();
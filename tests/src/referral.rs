@@ -0,0 +1,329 @@
+#[cfg(test)]
+mod referral_tests {
+    use jup_ag_sdk::{
+        JupiterClient,
+        referral::{
+            self, JUPITER_PROJECT, REFERRAL_PROGRAM_ID, SYSTEM_PROGRAM_ID, TOKEN_2022_PROGRAM_ID,
+            TOKEN_PROGRAM_ID, referral_account, referral_token_account,
+        },
+        types::{QuoteResponse, SwapRequest, UltraOrderRequest, Validate},
+    };
+    use serde_json::{Value, json};
+    use solana_hash::Hash;
+
+    use crate::{
+        common::{JUP_MINT, SOL_MINT, TEST_USER_PUBKEY, USDC_MINT, quote_json},
+        mock::{MockResponse, MockServer},
+    };
+
+    const REFERRAL_ACCOUNT: &str = "45ruCyfdRkWpRNGEqWzjCiXRHkZs8WXCLQ67Pnpye7Hp";
+
+    fn quote(swap_mode: &str, platform_fee: Value) -> QuoteResponse {
+        let mut quote = quote_json();
+        quote["swapMode"] = json!(swap_mode);
+        quote["platformFee"] = platform_fee;
+        serde_json::from_value(quote).unwrap()
+    }
+
+    #[test]
+    fn test_referral_token_account_derivation() {
+        let jup = referral_token_account(REFERRAL_ACCOUNT, JUP_MINT).unwrap();
+        let usdc = referral_token_account(REFERRAL_ACCOUNT, USDC_MINT).unwrap();
+
+        assert_eq!(bs58::decode(&jup).into_vec().unwrap().len(), 32);
+        assert_eq!(
+            jup,
+            referral_token_account(REFERRAL_ACCOUNT, JUP_MINT).unwrap()
+        );
+        assert_ne!(jup, usdc);
+        assert!(referral_token_account("not-a-key", JUP_MINT).is_err());
+    }
+
+    #[test]
+    fn test_swap_request_sets_fee_account_from_platform_fee() {
+        let fee = json!({ "amount": "2106685", "feeBps": 50 });
+
+        let exact_in = SwapRequest::new(TEST_USER_PUBKEY, quote("ExactIn", fee.clone()))
+            .referral_fee_account(REFERRAL_ACCOUNT)
+            .unwrap();
+        assert_eq!(
+            exact_in.fee_account,
+            Some(referral_token_account(REFERRAL_ACCOUNT, JUP_MINT).unwrap())
+        );
+
+        let exact_out = SwapRequest::new(TEST_USER_PUBKEY, quote("ExactOut", fee))
+            .referral_fee_account(REFERRAL_ACCOUNT)
+            .unwrap();
+        assert_eq!(
+            exact_out.fee_account,
+            Some(referral_token_account(REFERRAL_ACCOUNT, SOL_MINT).unwrap())
+        );
+
+        let no_fee = SwapRequest::new(TEST_USER_PUBKEY, quote("ExactIn", Value::Null))
+            .referral_fee_account(REFERRAL_ACCOUNT)
+            .unwrap();
+        assert_eq!(no_fee.fee_account, None);
+    }
+
+    #[test]
+    fn test_create_referral_token_account_instruction() {
+        let ix = referral::create_referral_token_account_instruction(
+            TEST_USER_PUBKEY,
+            REFERRAL_ACCOUNT,
+            JUP_MINT,
+            TOKEN_PROGRAM_ID,
+        )
+        .unwrap();
+
+        assert_eq!(ix.program_id, REFERRAL_PROGRAM_ID);
+        let keys: Vec<_> = ix.accounts.iter().map(|a| a.pubkey.as_str()).collect();
+        assert_eq!(keys[0], TEST_USER_PUBKEY);
+        assert_eq!(keys[1], JUPITER_PROJECT);
+        assert_eq!(keys[2], REFERRAL_ACCOUNT);
+        assert_eq!(
+            keys[3],
+            referral_token_account(REFERRAL_ACCOUNT, JUP_MINT).unwrap()
+        );
+        assert_eq!(keys[6], TOKEN_PROGRAM_ID);
+        assert!(ix.accounts[0].is_signer && ix.accounts[0].is_writable);
+        assert!(ix.accounts[3].is_writable && !ix.accounts[3].is_signer);
+        assert_eq!(ix.data, "fRJGX1az3b4=");
+    }
+
+    async fn rpc_server(fee_account_exists: bool, mint_owner: &'static str) -> MockServer {
+        MockServer::start(move |req| async move {
+            let request: Value = serde_json::from_str(&req.body).unwrap();
+            assert_eq!(request["method"], "getMultipleAccounts");
+            let fee_account = fee_account_exists
+                .then(|| json!({ "owner": REFERRAL_PROGRAM_ID, "data": ["", "base64"] }));
+            MockResponse::json(
+                &json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "result": {
+                        "context": { "slot": 1 },
+                        "value": [fee_account, { "owner": mint_owner, "data": ["", "base64"] }]
+                    }
+                })
+                .to_string(),
+            )
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_referral_fee_account_exists() {
+        let server = rpc_server(true, TOKEN_PROGRAM_ID).await;
+        let client = JupiterClient::new("https://lite-api.jup.ag");
+
+        let fee_account = client
+            .referral_fee_account(&server.url, REFERRAL_ACCOUNT, JUP_MINT, TEST_USER_PUBKEY)
+            .await
+            .unwrap();
+
+        assert!(fee_account.exists());
+        assert_eq!(
+            fee_account.address,
+            referral_token_account(REFERRAL_ACCOUNT, JUP_MINT).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_referral_fee_account_missing_uses_mint_token_program() {
+        let server = rpc_server(false, TOKEN_2022_PROGRAM_ID).await;
+        let client = JupiterClient::new("https://lite-api.jup.ag");
+
+        let fee_account = client
+            .referral_fee_account(&server.url, REFERRAL_ACCOUNT, JUP_MINT, TEST_USER_PUBKEY)
+            .await
+            .unwrap();
+
+        let create = fee_account.create_instruction.expect("account is missing");
+        assert_eq!(create.accounts[6].pubkey, TOKEN_2022_PROGRAM_ID);
+    }
+
+    #[tokio::test]
+    async fn test_rpc_requests_omit_jupiter_headers() {
+        let server = MockServer::start(|req| async move {
+            assert_eq!(req.header("x-api-key"), None);
+            assert_eq!(req.header("x-gateway-token"), None);
+            MockResponse::json(
+                &json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "result": { "context": { "slot": 1 }, "value": [null, null] }
+                })
+                .to_string(),
+            )
+        })
+        .await;
+        let client = JupiterClient::builder("https://api.jup.ag")
+            .api_key("secret-key")
+            .default_header("x-gateway-token", "gateway-secret")
+            .build()
+            .unwrap();
+
+        client
+            .referral_fee_account(&server.url, REFERRAL_ACCOUNT, JUP_MINT, TEST_USER_PUBKEY)
+            .await
+            .unwrap();
+
+        assert_eq!(server.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_referral_fee_account_rpc_error() {
+        let server = MockServer::start(|_| async {
+            MockResponse::json(
+                r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32602,"message":"Invalid params"}}"#,
+            )
+        })
+        .await;
+        let client = JupiterClient::new("https://lite-api.jup.ag");
+
+        let err = client
+            .referral_fee_account(&server.url, REFERRAL_ACCOUNT, JUP_MINT, TEST_USER_PUBKEY)
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("Invalid params"), "{err}");
+    }
+
+    #[test]
+    fn test_create_referral_account_instruction() {
+        let address = referral_account("my-app").unwrap();
+        assert_eq!(bs58::decode(&address).into_vec().unwrap().len(), 32);
+        assert_ne!(address, referral_account("other-app").unwrap());
+        assert!(referral_account("").is_err());
+        assert!(referral_account(&"a".repeat(33)).is_err());
+
+        let ix = referral::create_referral_account_instruction(
+            TEST_USER_PUBKEY,
+            TEST_USER_PUBKEY,
+            "my-app",
+        )
+        .unwrap();
+
+        assert_eq!(ix.program_id, REFERRAL_PROGRAM_ID);
+        let keys: Vec<_> = ix.accounts.iter().map(|a| a.pubkey.as_str()).collect();
+        assert_eq!(
+            keys,
+            [
+                TEST_USER_PUBKEY,
+                TEST_USER_PUBKEY,
+                JUPITER_PROJECT,
+                address.as_str(),
+                SYSTEM_PROGRAM_ID
+            ]
+        );
+        assert!(ix.accounts[3].is_writable && !ix.accounts[3].is_signer);
+
+        let data =
+            base64::Engine::decode(&base64::engine::general_purpose::STANDARD, &ix.data).unwrap();
+        assert_eq!(data[..8], [241, 190, 107, 26, 244, 236, 119, 229]);
+        assert_eq!(data[8..12], 6u32.to_le_bytes());
+        assert_eq!(&data[12..], b"my-app");
+    }
+
+    #[test]
+    fn test_creation_transaction() {
+        let create_account = referral::create_referral_account_instruction(
+            TEST_USER_PUBKEY,
+            TEST_USER_PUBKEY,
+            "my-app",
+        )
+        .unwrap();
+        let create_token_account = referral::create_referral_token_account_instruction(
+            TEST_USER_PUBKEY,
+            &referral_account("my-app").unwrap(),
+            JUP_MINT,
+            TOKEN_PROGRAM_ID,
+        )
+        .unwrap();
+        let blockhash = Hash::new_from_array([9; 32]);
+
+        let tx = referral::creation_transaction(
+            TEST_USER_PUBKEY,
+            &[create_account, create_token_account],
+            blockhash,
+        )
+        .unwrap();
+
+        assert_eq!(tx.message.instructions.len(), 2);
+        assert_eq!(tx.message.recent_blockhash, blockhash);
+        assert_eq!(tx.message.header.num_required_signatures, 1);
+        assert_eq!(tx.message.account_keys[0].to_string(), TEST_USER_PUBKEY);
+        assert!(referral::creation_transaction("not-a-key", &[], blockhash).is_err());
+    }
+
+    #[test]
+    fn test_ultra_referral_fee_requires_referral_account() {
+        let order = UltraOrderRequest::new(SOL_MINT, JUP_MINT, 1_000_000_000);
+
+        assert!(order.add_referral_fee(100).validate().is_err());
+        assert!(
+            UltraOrderRequest::new(SOL_MINT, JUP_MINT, 1_000_000_000)
+                .add_referral_account(REFERRAL_ACCOUNT)
+                .add_referral_fee(100)
+                .validate()
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ultra_referral_fee_accounts_checks_both_mints() {
+        let server = MockServer::start(|req| async move {
+            let request: Value = serde_json::from_str(&req.body).unwrap();
+            let keys = request["params"][0].as_array().unwrap();
+            assert_eq!(keys.len(), 4);
+            assert_eq!(keys[2], SOL_MINT);
+            assert_eq!(keys[3], JUP_MINT);
+            let account = |owner| json!({ "owner": owner, "data": ["", "base64"] });
+            MockResponse::json(
+                &json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "result": {
+                        "context": { "slot": 1 },
+                        "value": [
+                            account(REFERRAL_PROGRAM_ID),
+                            null,
+                            account(TOKEN_PROGRAM_ID),
+                            account(TOKEN_2022_PROGRAM_ID)
+                        ]
+                    }
+                })
+                .to_string(),
+            )
+        })
+        .await;
+        let client = JupiterClient::new("https://lite-api.jup.ag");
+        let request = UltraOrderRequest::new(SOL_MINT, JUP_MINT, 1_000_000_000)
+            .add_referral_account(REFERRAL_ACCOUNT)
+            .add_referral_fee(100);
+
+        let accounts = client
+            .ultra_referral_fee_accounts(&server.url, &request, TEST_USER_PUBKEY)
+            .await
+            .unwrap();
+
+        assert_eq!(accounts.len(), 2);
+        assert!(accounts[0].exists());
+        assert_eq!(
+            accounts[0].address,
+            referral_token_account(REFERRAL_ACCOUNT, SOL_MINT).unwrap()
+        );
+        let create = accounts[1].create_instruction.as_ref().unwrap();
+        assert_eq!(create.accounts[4].pubkey, JUP_MINT);
+        assert_eq!(create.accounts[6].pubkey, TOKEN_2022_PROGRAM_ID);
+
+        let no_referral = UltraOrderRequest::new(SOL_MINT, JUP_MINT, 1_000_000_000);
+        assert!(
+            client
+                .ultra_referral_fee_accounts(&server.url, &no_referral, TEST_USER_PUBKEY)
+                .await
+                .is_err()
+        );
+        assert_eq!(server.hits(), 1);
+    }
+}
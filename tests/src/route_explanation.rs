@@ -0,0 +1,135 @@
+#[cfg(test)]
+mod route_explanation_tests {
+    use jup_ag_sdk::{
+        JupiterClient,
+        error::QuoteErrorKind,
+        types::{Amount, QuoteRequest, QuoteResponse, RouteRestriction},
+    };
+    use serde_json::{Value, json};
+
+    use crate::{
+        common::{JUP_MINT, SOL_MINT, TEST_AMOUNT, USDC_MINT, quote_json, route_hop},
+        mock::{MockResponse, MockServer},
+    };
+
+    const NO_ROUTE: &str =
+        r#"{"error": "Could not find any route", "errorCode": "COULD_NOT_FIND_ANY_ROUTE"}"#;
+
+    fn routed_quote_json(hops: &[(&str, &str, &str)], out_amount: &str) -> Value {
+        let mut quote = quote_json();
+        quote["outAmount"] = json!(out_amount);
+        quote["otherAmountThreshold"] = json!(out_amount);
+        quote["routePlan"] = hops
+            .iter()
+            .map(|(label, input_mint, output_mint)| {
+                let mut hop = route_hop("5BKxfWMbmYBAEWvyPZS9esPducUba9GqyMjtLCfbaqyF", label);
+                hop["swapInfo"]["inputMint"] = json!(input_mint);
+                hop["swapInfo"]["outputMint"] = json!(output_mint);
+                hop["swapInfo"]["outAmount"] = json!(out_amount);
+                hop
+            })
+            .collect();
+        quote
+    }
+
+    fn chosen() -> QuoteResponse {
+        serde_json::from_value(routed_quote_json(
+            &[
+                ("Meteora DLMM", SOL_MINT, USDC_MINT),
+                ("Whirlpool", USDC_MINT, JUP_MINT),
+            ],
+            "421337000",
+        ))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_explain_route() {
+        let server = MockServer::start(|req| async move {
+            let body = if req.path.contains("onlyDirectRoutes=true") {
+                routed_quote_json(&[("Raydium", SOL_MINT, JUP_MINT)], "420000000")
+            } else if req.path.contains("dexes=Meteora+DLMM") {
+                routed_quote_json(&[("Meteora DLMM", SOL_MINT, JUP_MINT)], "419000000")
+            } else {
+                return MockResponse::status(400, NO_ROUTE);
+            };
+            MockResponse::json(&body.to_string())
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+        let request = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT).max_accounts(40);
+
+        let explanation = client.explain_route(&request, &chosen()).await.unwrap();
+        assert_eq!(server.hits(), 3);
+        assert_eq!(explanation.dexes(), ["Meteora DLMM", "Whirlpool"]);
+        assert!(explanation.is_multi_hop());
+
+        let restrictions: Vec<_> = explanation
+            .alternatives
+            .iter()
+            .map(|alternative| {
+                (
+                    alternative.restriction.clone(),
+                    alternative.quote.as_ref().map(|quote| quote.out_amount),
+                )
+            })
+            .collect();
+        assert_eq!(
+            restrictions,
+            [
+                (RouteRestriction::Direct, Some(Amount::new(420_000_000))),
+                (
+                    RouteRestriction::SingleDex("Meteora DLMM".to_string()),
+                    Some(Amount::new(419_000_000))
+                ),
+                (RouteRestriction::SingleDex("Whirlpool".to_string()), None),
+            ]
+        );
+
+        let best = explanation.best_alternative().unwrap();
+        assert_eq!(best.restriction, RouteRestriction::Direct);
+        let bps = explanation.improvement_bps().unwrap();
+        assert!((bps - 31.83).abs() < 0.01, "{bps}");
+        assert_eq!(explanation.improvement(), Some(Amount::new(1_337_000)));
+
+        assert_eq!(
+            explanation.to_string(),
+            "Meteora DLMM → Whirlpool: 421.337 JUP, 31.8 bps better than direct routes\n  \
+             direct routes: 420 JUP\n  \
+             Meteora DLMM only: 419 JUP\n  \
+             Whirlpool only: no route"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_explain_route_fails_on_other_errors() {
+        let server =
+            MockServer::start(|_| async { MockResponse::status(401, "unauthorized") }).await;
+        let client = JupiterClient::new(&server.url);
+        let request = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT);
+
+        assert!(client.explain_route(&request, &chosen()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_explain_route_fails_on_other_quote_errors() {
+        let server = MockServer::start(|req| async move {
+            if req.path.contains("onlyDirectRoutes=true") {
+                return MockResponse::status(
+                    400,
+                    r#"{"error": "Amount too small", "errorCode": "AMOUNT_TOO_SMALL"}"#,
+                );
+            }
+            MockResponse::status(400, NO_ROUTE)
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+        let request = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT);
+
+        let error = client.explain_route(&request, &chosen()).await.unwrap_err();
+        assert_eq!(
+            error.quote_error_kind(),
+            Some(QuoteErrorKind::AmountTooSmall)
+        );
+    }
+}
@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod runtime_tests {
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Duration,
+    };
+
+    use jup_ag_sdk::{
+        JupiterClient,
+        config::RateLimitPlan,
+        runtime::{Runtime, Sleep, Task, TokioRuntime},
+    };
+    use tokio::sync::oneshot;
+
+    use crate::mock::MockServer;
+
+    /// Delegates to tokio, counting the calls made through it.
+    #[derive(Debug, Clone, Default)]
+    struct CountingRuntime {
+        spawns: Arc<AtomicUsize>,
+        sleeps: Arc<AtomicUsize>,
+    }
+
+    impl Runtime for CountingRuntime {
+        fn spawn(&self, task: Task) {
+            self.spawns.fetch_add(1, Ordering::SeqCst);
+            TokioRuntime.spawn(task);
+        }
+
+        fn sleep(&self, duration: Duration) -> Sleep {
+            self.sleeps.fetch_add(1, Ordering::SeqCst);
+            TokioRuntime.sleep(duration)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_waits_use_client_runtime() {
+        let server = MockServer::json("[]").await;
+        let runtime = CountingRuntime::default();
+        let client = JupiterClient::builder(&server.url)
+            .rate_limit(RateLimitPlan::Custom {
+                requests: 1,
+                window: Duration::from_millis(50),
+            })
+            .runtime(runtime.clone())
+            .build()
+            .unwrap();
+
+        client.routers().await.unwrap();
+        client.routers().await.unwrap();
+
+        assert!(runtime.sleeps.load(Ordering::SeqCst) >= 1);
+        assert_eq!(client.stats().rate_limit_waits, 1);
+    }
+
+    #[tokio::test]
+    async fn test_spawn_through_client_runtime() {
+        let runtime = CountingRuntime::default();
+        let client = JupiterClient::builder("http://localhost")
+            .runtime(runtime.clone())
+            .build()
+            .unwrap();
+
+        let (done, finished) = oneshot::channel();
+        client.runtime().spawn(Box::pin(async move {
+            let _ = done.send(());
+        }));
+
+        finished.await.unwrap();
+        assert_eq!(runtime.spawns.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_runtime_survives_rebuild() {
+        let runtime = CountingRuntime::default();
+        let client = JupiterClient::builder("http://localhost")
+            .runtime(runtime.clone())
+            .build()
+            .unwrap()
+            .with_api_key("test-key");
+
+        client.runtime().sleep(Duration::ZERO).await;
+        assert_eq!(runtime.sleeps.load(Ordering::SeqCst), 1);
+    }
+}
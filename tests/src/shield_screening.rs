@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod shield_screening_tests {
+    use std::time::UNIX_EPOCH;
+
+    use jup_ag_sdk::{
+        JupiterClient,
+        types::{NATIVE_SOL, PortfolioRiskReport, RiskLevel, Shield, WSOL_MINT, WalletBalance},
+    };
+    use serde_json::json;
+
+    use crate::{
+        common::{JUP_MINT, TEST_USER_PUBKEY, USDC_MINT},
+        mock::{MockResponse, MockServer},
+    };
+
+    const SCAM_MINT: &str = "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263";
+
+    fn balance(mint: &str, ui_amount: f64, usd_value: Option<f64>) -> WalletBalance {
+        WalletBalance {
+            mint: mint.to_string(),
+            amount: Default::default(),
+            ui_amount,
+            usd_value,
+            is_frozen: false,
+        }
+    }
+
+    fn shield_json() -> serde_json::Value {
+        let warning = |warning_type: &str, severity: &str| json!({ "type": warning_type, "message": "...", "severity": severity });
+        json!({
+            "warnings": {
+                WSOL_MINT: [],
+                JUP_MINT: [warning("NOT_VERIFIED", "info")],
+                SCAM_MINT: [
+                    warning("HAS_FREEZE_AUTHORITY", "warning"),
+                    warning("HAS_FREEZE_AUTHORITY", "warning"),
+                    warning("NOT_SELLABLE", "critical")
+                ],
+                USDC_MINT: [warning("HAS_FREEZE_AUTHORITY", "WARNING")]
+            }
+        })
+    }
+
+    #[test]
+    fn test_report_levels_and_summary() {
+        let balances = vec![
+            balance(NATIVE_SOL, 2.0, Some(300.0)),
+            balance(USDC_MINT, 10.0, Some(10.0)),
+            balance(JUP_MINT, 1.0, Some(0.5)),
+            balance(SCAM_MINT, 1000.0, None),
+        ];
+        let shield: Shield = serde_json::from_value(shield_json()).unwrap();
+
+        let report = PortfolioRiskReport::new(TEST_USER_PUBKEY, balances, shield, UNIX_EPOCH);
+
+        let order: Vec<(&str, RiskLevel)> = report
+            .mints
+            .iter()
+            .map(|mint| (mint.mint.as_str(), mint.level))
+            .collect();
+        assert_eq!(
+            order,
+            [
+                (SCAM_MINT, RiskLevel::Critical),
+                (USDC_MINT, RiskLevel::Warning),
+                (JUP_MINT, RiskLevel::Info),
+                (NATIVE_SOL, RiskLevel::None),
+            ]
+        );
+
+        let summary = &report.summary;
+        assert_eq!(summary.mints_screened, 4);
+        assert_eq!(summary.flagged_mints, 2);
+        assert_eq!(summary.critical_mints, 1);
+        assert_eq!(summary.level, RiskLevel::Critical);
+        assert_eq!(summary.total_usd_value, 310.5);
+        assert_eq!(summary.flagged_usd_value, 10.0);
+        // counted once per mint
+        assert_eq!(summary.warning_types["HAS_FREEZE_AUTHORITY"], 2);
+        assert_eq!(summary.warning_types["NOT_SELLABLE"], 1);
+        assert!(!report.is_clean());
+        assert_eq!(report.flagged().count(), 2);
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["summary"]["level"], "critical");
+        assert_eq!(json["mints"][0]["warnings"][2]["type"], "NOT_SELLABLE");
+    }
+
+    #[test]
+    fn test_clean_report() {
+        let shield: Shield = serde_json::from_value(json!({ "warnings": {} })).unwrap();
+        let report = PortfolioRiskReport::new(
+            TEST_USER_PUBKEY,
+            vec![balance(NATIVE_SOL, 1.0, Some(150.0))],
+            shield,
+            UNIX_EPOCH,
+        );
+
+        assert!(report.is_clean());
+        assert_eq!(report.summary.level, RiskLevel::None);
+        assert!(report.mints[0].warnings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_screen_wallet() {
+        let server = MockServer::start(|req| async move {
+            if req.path.starts_with("/ultra/v1/balances/") {
+                let balance = |amount: &str, ui_amount: f64| json!({ "amount": amount, "uiAmount": ui_amount, "slot": 1, "isFrozen": false });
+                MockResponse::json(
+                    &json!({
+                        "SOL": balance("1000000000", 1.0),
+                        SCAM_MINT: balance("1000", 1000.0),
+                        JUP_MINT: balance("0", 0.0)
+                    })
+                    .to_string(),
+                )
+            } else if req.path.starts_with("/ultra/v1/shield") {
+                let mints = req.path.split("mints=").nth(1).unwrap().replace("%2C", ",");
+                let mut mints: Vec<&str> = mints.split(',').collect();
+                mints.sort();
+                let mut expected = [SCAM_MINT, WSOL_MINT];
+                expected.sort();
+                assert_eq!(mints, expected);
+                MockResponse::json(&shield_json().to_string())
+            } else {
+                assert!(req.path.starts_with("/price/v2"), "{}", req.path);
+                MockResponse::json(
+                    &json!({
+                        "data": { WSOL_MINT: { "id": WSOL_MINT, "type": "derivedPrice", "price": "150" } },
+                        "timeTaken": 0.001
+                    })
+                    .to_string(),
+                )
+            }
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+
+        let report = client.screen_wallet(TEST_USER_PUBKEY).await.unwrap();
+
+        assert_eq!(server.hits(), 3);
+        assert_eq!(report.wallet, TEST_USER_PUBKEY);
+        assert_eq!(report.mints.len(), 2);
+        assert_eq!(report.mints[0].mint, SCAM_MINT);
+        assert_eq!(report.mints[1].mint, NATIVE_SOL);
+        assert_eq!(report.summary.flagged_usd_value, 0.0);
+        assert_eq!(report.summary.total_usd_value, 150.0);
+        assert!(report.screened_at_ms > 0);
+    }
+}
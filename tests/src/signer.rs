@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod signer_tests {
+    use jup_ag_sdk::{JupiterClientError, signer::LocalSigner};
+    use solana_keypair::Keypair;
+    use solana_signer::Signer;
+
+    #[test]
+    fn test_local_signer_formats() {
+        let keypair = Keypair::new();
+        let bytes = keypair.to_bytes();
+        let json = format!("[{}]\n", bytes.map(|byte| byte.to_string()).join(", "));
+
+        let path = std::env::temp_dir().join(format!("jup-ag-sdk-{}.json", keypair.pubkey()));
+        std::fs::write(&path, &json).unwrap();
+        let from_file = LocalSigner::from_file(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        for signer in [
+            LocalSigner::from_bytes(&bytes).unwrap(),
+            LocalSigner::from_base58(&keypair.to_base58_string()).unwrap(),
+            LocalSigner::from_json(&json).unwrap(),
+            from_file.unwrap(),
+        ] {
+            assert_eq!(signer.pubkey(), keypair.pubkey());
+            let signature = signer.sign_message(b"message");
+            assert!(signature.verify(keypair.pubkey().as_ref(), b"message"));
+
+            let debug = format!("{signer:?}");
+            assert!(debug.contains(&keypair.pubkey().to_string()), "{debug}");
+            assert!(!debug.contains(&keypair.to_base58_string()));
+            assert!(!debug.contains(&format!("{:?}", &bytes[..32])));
+        }
+    }
+
+    #[test]
+    fn test_local_signer_rejects_invalid_keypairs() {
+        let keypair = Keypair::new();
+        let mut mismatched = keypair.to_bytes();
+        mismatched[63] ^= 1;
+
+        for result in [
+            LocalSigner::from_bytes(&keypair.to_bytes()[..32]),
+            LocalSigner::from_bytes(&mismatched),
+            LocalSigner::from_base58("not base58!"),
+            LocalSigner::from_json("[1, 2, 3]"),
+            LocalSigner::from_json("{\"secret\": 1}"),
+            LocalSigner::from_json(&format!("[{}]", ["256"; 64].join(","))),
+            LocalSigner::from_file("/nonexistent/id.json"),
+        ] {
+            assert!(matches!(
+                result,
+                Err(JupiterClientError::ValidationError(_))
+            ));
+        }
+    }
+}
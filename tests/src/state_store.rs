@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod state_store_tests {
+    use std::{
+        path::PathBuf,
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use jup_ag_sdk::{
+        JupiterClient,
+        order_monitor::{OrderEvent, OrderMonitor},
+        state_store::{FileStore, MemoryStore, StateStore},
+    };
+    use serde_json::json;
+
+    use crate::{
+        common::TEST_USER_PUBKEY,
+        mock::{MockResponse, MockServer},
+    };
+
+    fn temp_dir() -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        std::env::temp_dir().join(format!("jup-ag-sdk-state-{nanos}"))
+    }
+
+    async fn exercise(store: &dyn StateStore) {
+        assert_eq!(store.get("a/1").await.unwrap(), None);
+        store.put("a/1", b"one".to_vec()).await.unwrap();
+        store.put("a/2", b"two".to_vec()).await.unwrap();
+        store.put("b/1", b"other".to_vec()).await.unwrap();
+        store.put("a/1", b"uno".to_vec()).await.unwrap();
+
+        assert_eq!(store.get("a/1").await.unwrap(), Some(b"uno".to_vec()));
+        assert_eq!(store.list("a/").await.unwrap(), vec!["a/1", "a/2"]);
+        assert_eq!(store.list("").await.unwrap().len(), 3);
+
+        store.delete("a/1").await.unwrap();
+        store.delete("missing").await.unwrap();
+        assert_eq!(store.get("a/1").await.unwrap(), None);
+        assert_eq!(store.list("a/").await.unwrap(), vec!["a/2"]);
+    }
+
+    #[tokio::test]
+    async fn test_memory_store() {
+        exercise(&MemoryStore::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_file_store() {
+        let dir = temp_dir();
+        let store = FileStore::new(&dir).unwrap();
+        exercise(&store).await;
+
+        let store: Arc<dyn StateStore> = Arc::new(FileStore::new(&dir).unwrap());
+        store
+            .put_json("order_monitor/wallet.sol:1", &json!({ "x": 1 }))
+            .await
+            .unwrap();
+        let reopened: Arc<dyn StateStore> = Arc::new(FileStore::new(&dir).unwrap());
+        assert_eq!(
+            reopened
+                .get_json::<serde_json::Value>("order_monitor/wallet.sol:1")
+                .await
+                .unwrap(),
+            Some(json!({ "x": 1 }))
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_order_monitor_resumes_from_store() {
+        let page = |trades: Vec<&str>| {
+            let trades: Vec<_> = trades.iter().map(|tx| json!({ "txId": tx })).collect();
+            json!({
+                "user": TEST_USER_PUBKEY,
+                "orderStatus": "active",
+                "all": [{ "orderKey": "order-1", "trades": trades }],
+                "totalPages": 1,
+                "page": 1
+            })
+            .to_string()
+        };
+        let rounds = Arc::new([page(vec!["tx-1"]), page(vec!["tx-1", "tx-2"])]);
+        let round = Arc::new(AtomicUsize::new(0));
+        let server = MockServer::start(move |_| {
+            let body = rounds[round.fetch_add(1, Ordering::SeqCst).min(1)].clone();
+            async move { MockResponse::json(&body) }
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+        let store: Arc<dyn StateStore> = Arc::new(MemoryStore::new());
+
+        let mut monitor = OrderMonitor::new(TEST_USER_PUBKEY)
+            .recurring_only()
+            .state_store(store.clone());
+        assert!(monitor.refresh(&client).await.unwrap().is_empty());
+        drop(monitor);
+
+        // a new process picks up the stored snapshot and reports the fill made meanwhile
+        let mut restarted = OrderMonitor::new(TEST_USER_PUBKEY)
+            .recurring_only()
+            .state_store(store.clone());
+        let events = restarted.refresh(&client).await.unwrap();
+        assert!(matches!(
+            &events[..],
+            [OrderEvent::Filled { fill, .. }] if fill.tx_id == "tx-2"
+        ));
+        assert_eq!(
+            store.list("order_monitor/").await.unwrap(),
+            vec![format!("order_monitor/{TEST_USER_PUBKEY}/recurring")]
+        );
+    }
+}
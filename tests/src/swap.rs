@@ -1,19 +1,29 @@
 #[cfg(test)]
 mod swap_tests {
+    use std::sync::{Arc, Mutex};
+
     use jup_ag_sdk::{
         JupiterClient,
-        types::{QuoteGetSwapModeEnum, QuoteRequest, SwapRequest},
+        error::QuoteErrorKind,
+        types::{
+            AccountConstraint, AccountMeta, Bps, Instruction, PriorityLevel, Profile, QuoteRequest,
+            QuoteResponse, SwapMode, SwapRequest,
+        },
     };
+    use serde_json::{Value, json};
 
-    use crate::common::{
-        BASE_URL, DEFAULT_SLIPPAGE_BPS, JUP_MINT, SOL_MINT, TEST_AMOUNT, TEST_USER_PUBKEY,
-        create_test_client,
+    use crate::{
+        common::{
+            BASE_URL, DEFAULT_SLIPPAGE_BPS, JUP_MINT, SOL_MINT, TEST_AMOUNT, TEST_USER_PUBKEY,
+            create_test_client, quote_json,
+        },
+        mock::{MockResponse, MockServer},
     };
 
     fn create_default_quote_request() -> QuoteRequest {
         QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
             .slippage_bps(DEFAULT_SLIPPAGE_BPS)
-            .swap_mode(QuoteGetSwapModeEnum::ExactOut)
+            .swap_mode(SwapMode::ExactOut)
     }
 
     #[test]
@@ -26,7 +36,7 @@ mod swap_tests {
     fn test_quote_request_builder_methods() {
         let request = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
             .slippage_bps(DEFAULT_SLIPPAGE_BPS)
-            .swap_mode(QuoteGetSwapModeEnum::ExactOut)
+            .swap_mode(SwapMode::ExactOut)
             .dexes(vec!["Orca".to_string(), "Meteora+DLMM".to_string()])
             .exclude_dexes(vec!["Raydium".to_string()])
             .restrict_intermediate_tokens(false)
@@ -40,12 +50,12 @@ mod swap_tests {
 
         assert_eq!(
             request.slippage_bps,
-            Some(DEFAULT_SLIPPAGE_BPS),
+            Some(Bps::new(DEFAULT_SLIPPAGE_BPS).unwrap()),
             "slippage_bps should match"
         );
         assert_eq!(
             request.platform_fee_bps,
-            Some(10),
+            Some(Bps::new(10).unwrap()),
             "platform fee should match"
         );
 
@@ -77,6 +87,29 @@ mod swap_tests {
         );
     }
 
+    #[test]
+    fn test_quote_request_profiles() {
+        let fast = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT).with_profile(Profile::Fast);
+        assert_eq!(fast.slippage_bps, Some(Bps::new(100).unwrap()));
+        assert_eq!(fast.only_direct_routes, Some(true));
+        assert_eq!(fast.restrict_intermediate_tokens, Some(true));
+        assert_eq!(fast.max_accounts, Some(32));
+        assert_eq!(fast.dynamic_slippage, Some(false));
+
+        let cheap = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+            .dynamic_slippage(true)
+            .with_profile(Profile::CheapGas)
+            .max_accounts(24);
+        assert_eq!(cheap.max_accounts, Some(24));
+        assert_eq!(cheap.only_direct_routes, Some(false));
+        assert_eq!(cheap.dynamic_slippage, Some(false));
+
+        let conservative =
+            QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT).with_profile(Profile::Conservative);
+        assert_eq!(conservative.slippage_bps, Some(Bps::new(30).unwrap()));
+        assert_eq!(conservative.max_accounts, Some(64));
+    }
+
     #[tokio::test]
     async fn test_get_quote_successful() {
         let client = create_test_client();
@@ -93,8 +126,7 @@ mod swap_tests {
                     "output mint should match"
                 );
                 assert_eq!(
-                    quote_res.out_amount,
-                    TEST_AMOUNT.to_string(),
+                    quote_res.out_amount, TEST_AMOUNT,
                     "output amount should match"
                 );
                 assert_eq!(
@@ -142,8 +174,7 @@ mod swap_tests {
                     "input mint should match"
                 );
                 assert_eq!(
-                    swap.quote_response.out_amount,
-                    TEST_AMOUNT.to_string(),
+                    swap.quote_response.out_amount, TEST_AMOUNT,
                     "output amount should match"
                 );
             }
@@ -175,4 +206,237 @@ mod swap_tests {
             Err(err) => panic!("Failed to get swap transaction: {:?}", err),
         }
     }
+
+    fn instruction(program_id: &str) -> Value {
+        json!({ "programId": program_id, "accounts": [], "data": "" })
+    }
+
+    #[tokio::test]
+    async fn test_token_ledger_swap_instructions() {
+        let server = MockServer::start(|req| async move {
+            let body: Value = serde_json::from_str(&req.body).unwrap();
+            let ledger = body["useTokenLedger"] == true;
+            MockResponse::json(
+                &json!({
+                    "tokenLedgerInstruction": ledger.then(|| instruction("ledger")),
+                    "computeBudgetInstructions": [instruction("budget")],
+                    "setupInstructions": [instruction("setup")],
+                    "swapInstruction": instruction("swap"),
+                    "cleanupInstruction": instruction("cleanup"),
+                    "otherInstructions": [instruction("other")],
+                    "addressLookupTableAddresses": []
+                })
+                .to_string(),
+            )
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+
+        let quote: QuoteResponse = serde_json::from_value(quote_json()).unwrap();
+        let payload = SwapRequest::new(TEST_USER_PUBKEY, quote).use_token_ledger(true);
+
+        let swap_instructions = client.get_swap_instructions(&payload).await.unwrap();
+        let (ledger, swap) = swap_instructions.token_ledger_pair().unwrap();
+        assert_eq!(
+            (ledger.program_id.as_str(), swap.program_id.as_str()),
+            ("ledger", "swap")
+        );
+
+        let withdraw = Instruction {
+            program_id: "withdraw".to_string(),
+            accounts: vec![AccountMeta {
+                pubkey: TEST_USER_PUBKEY.to_string(),
+                is_signer: true,
+                is_writable: true,
+            }],
+            data: String::new(),
+        };
+        let order: Vec<_> = swap_instructions
+            .with_token_ledger(vec![withdraw])
+            .unwrap()
+            .into_iter()
+            .map(|ix| ix.program_id)
+            .collect();
+        assert_eq!(
+            order,
+            [
+                "budget", "setup", "ledger", "withdraw", "swap", "cleanup", "other"
+            ]
+        );
+    }
+
+    /// Serves quotes and swaps whose transaction only fits once the last quote
+    /// query contains `fits_with`; `too_large` answers oversized swaps.
+    async fn sizing_server(
+        fits_with: &'static str,
+        too_large: fn() -> MockResponse,
+    ) -> (MockServer, Arc<Mutex<Vec<String>>>) {
+        let quotes = Arc::new(Mutex::new(Vec::new()));
+        let seen = quotes.clone();
+        let server = MockServer::start(move |req| {
+            let quotes = seen.clone();
+            async move {
+                if req.path.starts_with("/swap/v1/quote") {
+                    quotes.lock().unwrap().push(req.path.clone());
+                    return MockResponse::json(&quote_json().to_string());
+                }
+                let last = quotes.lock().unwrap().last().cloned().unwrap_or_default();
+                if !last.contains(fits_with) {
+                    return too_large();
+                }
+                MockResponse::json(
+                    &json!({
+                        "swapTransaction": "A".repeat(1000),
+                        "lastValidBlockHeight": 1,
+                        "prioritizationFeeLamports": 0
+                    })
+                    .to_string(),
+                )
+            }
+        })
+        .await;
+        (server, quotes)
+    }
+
+    fn oversized_transaction() -> MockResponse {
+        MockResponse::json(
+            &json!({
+                "swapTransaction": "A".repeat(2000),
+                "lastValidBlockHeight": 1,
+                "prioritizationFeeLamports": 0
+            })
+            .to_string(),
+        )
+    }
+
+    fn too_many_accounts() -> MockResponse {
+        MockResponse::json(
+            &json!({
+                "swapTransaction": "A".repeat(1000),
+                "lastValidBlockHeight": 1,
+                "prioritizationFeeLamports": 0,
+                "simulationError": { "errorCode": "TooManyAccountLocks", "error": "TooManyAccountLocks" }
+            })
+            .to_string(),
+        )
+    }
+
+    fn request() -> QuoteRequest {
+        QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+    }
+
+    fn build(quote: QuoteResponse) -> SwapRequest {
+        SwapRequest::new(TEST_USER_PUBKEY, quote)
+    }
+
+    #[tokio::test]
+    async fn test_fitted_swap_lowers_max_accounts() {
+        let (server, quotes) = sizing_server("maxAccounts=32", oversized_transaction).await;
+        let client = JupiterClient::new(&server.url);
+
+        let fitted = client
+            .get_fitted_swap_transaction(&request(), build)
+            .await
+            .unwrap();
+
+        assert_eq!(fitted.constraint, AccountConstraint::MaxAccounts(32));
+        assert_eq!(fitted.attempts, 3);
+        assert_eq!(fitted.response.transaction_size(), 750);
+        let quotes = quotes.lock().unwrap();
+        assert!(!quotes[0].contains("maxAccounts"));
+        assert!(quotes[1].contains("maxAccounts=48"));
+    }
+
+    #[tokio::test]
+    async fn test_fitted_swap_falls_back_to_direct_routes() {
+        let (server, _) = sizing_server("onlyDirectRoutes=true", too_many_accounts).await;
+        let client = JupiterClient::new(&server.url);
+
+        let fitted = client
+            .get_fitted_swap_transaction(&request().max_accounts(40), build)
+            .await
+            .unwrap();
+
+        // 40 accounts already rules out the 48 step
+        assert_eq!(fitted.constraint, AccountConstraint::OnlyDirectRoutes);
+        assert_eq!(fitted.attempts, 4);
+    }
+
+    #[tokio::test]
+    async fn test_fitted_swap_gives_up() {
+        let (server, _) = sizing_server("never", || {
+            MockResponse::status(400, r#"{"error": "Transaction too large"}"#)
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+
+        let err = client
+            .get_fitted_swap_transaction(&request(), build)
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            err.quote_error_kind(),
+            Some(QuoteErrorKind::TransactionTooLarge)
+        );
+        assert_eq!(server.hits(), 10);
+    }
+
+    #[tokio::test]
+    async fn test_swap_prioritization_report() {
+        let server = MockServer::start(|req| async move {
+            let body: Value = serde_json::from_str(&req.body).unwrap();
+            let prioritization_type = if body["prioritizationFeeLamports"]["jitoTipLamports"].is_null() {
+                json!({ "computeBudget": { "microLamports": 25715, "estimatedMicroLamports": 785154 } })
+            } else {
+                json!({ "jito": { "lamports": 10000 } })
+            };
+            MockResponse::json(
+                &json!({
+                    "swapTransaction": "AQID",
+                    "lastValidBlockHeight": 1,
+                    "prioritizationFeeLamports": 10000,
+                    "computeUnitLimit": 388876,
+                    "prioritizationType": prioritization_type
+                })
+                .to_string(),
+            )
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+        let quote: QuoteResponse = serde_json::from_value(quote_json()).unwrap();
+
+        let request = SwapRequest::new(TEST_USER_PUBKEY, quote).prioritization_fee_config(
+            None,
+            10000,
+            PriorityLevel::VeryHigh,
+        );
+        let report = client
+            .get_swap_transaction(&request)
+            .await
+            .unwrap()
+            .prioritization();
+        assert_eq!(report.total_lamports, 10000);
+        assert_eq!(report.compute_unit_limit, Some(388876));
+        assert_eq!(report.compute_unit_price_micro_lamports, Some(25715));
+        assert_eq!(
+            report.estimated_compute_unit_price_micro_lamports,
+            Some(785154)
+        );
+        assert_eq!(report.jito_tip_lamports, None);
+        assert_eq!(report.priority_level, Some(PriorityLevel::VeryHigh));
+        assert!(report.is_capped());
+
+        let quote: QuoteResponse = serde_json::from_value(quote_json()).unwrap();
+        let request = SwapRequest::new(TEST_USER_PUBKEY, quote).prioritization_fee_jito_tip(10000);
+        let report = client
+            .get_swap_transaction(&request)
+            .await
+            .unwrap()
+            .prioritization();
+        assert_eq!(report.jito_tip_lamports, Some(10000));
+        assert_eq!(report.compute_unit_price_micro_lamports, None);
+        assert_eq!(report.priority_level, None);
+        assert!(!report.is_capped());
+    }
 }
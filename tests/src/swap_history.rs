@@ -0,0 +1,232 @@
+#[cfg(test)]
+mod swap_history_tests {
+    use std::sync::{Arc, Mutex};
+
+    use jup_ag_sdk::{
+        JupiterClient,
+        types::{Amount, ExecutionStatus, JUPITER_PROGRAM_ID, SwapHistoryRequest, Validate},
+    };
+    use serde_json::{Value, json};
+
+    use crate::{
+        common::{JUP_MINT, SOL_MINT, TEST_USER_PUBKEY, USDC_MINT},
+        mock::{MockResponse, MockServer},
+    };
+
+    const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+    const WRAPPER_PROGRAM: &str = "45ruCyfdRkWpRNGEqWzjCiXRHkZs8WXCLQ67Pnpye7Hp";
+    const ROUTE_DISCRIMINATOR: [u8; 8] = [229, 23, 203, 151, 122, 227, 173, 42];
+
+    /// `route` instruction data with a one-step plan.
+    fn route_data(in_amount: u64, quoted_out_amount: u64, slippage_bps: u16) -> String {
+        let mut data = ROUTE_DISCRIMINATOR.to_vec();
+        data.extend(1u32.to_le_bytes());
+        data.extend([7, 100, 0, 1]);
+        data.extend(in_amount.to_le_bytes());
+        data.extend(quoted_out_amount.to_le_bytes());
+        data.extend(slippage_bps.to_le_bytes());
+        data.push(0);
+        bs58::encode(data).into_string()
+    }
+
+    fn token_balance(index: usize, mint: &str, amount: u64) -> Value {
+        json!({
+            "accountIndex": index,
+            "mint": mint,
+            "owner": TEST_USER_PUBKEY,
+            "uiTokenAmount": { "amount": amount.to_string(), "decimals": 6 }
+        })
+    }
+
+    /// SOL -> JUP through a top-level `route` instruction.
+    fn sol_to_jup() -> Value {
+        json!({
+            "slot": 300,
+            "blockTime": 1_700_000_000,
+            "meta": {
+                "err": null,
+                "fee": 15000,
+                "preBalances": [5_000_000_000u64, 0, 1],
+                "postBalances": [3_999_985_000u64, 0, 1],
+                "preTokenBalances": [token_balance(1, JUP_MINT, 0)],
+                "postTokenBalances": [token_balance(1, JUP_MINT, 421_000_000)],
+                "innerInstructions": []
+            },
+            "transaction": {
+                "signatures": ["sig-sol-jup"],
+                "message": {
+                    "accountKeys": [TEST_USER_PUBKEY, "JupTokenAccount1111111111111111111111111111", JUPITER_PROGRAM_ID],
+                    "instructions": [
+                        { "programIdIndex": 2, "accounts": [0, 1], "data": route_data(1_000_000_000, 421_337_000, 50) }
+                    ]
+                }
+            }
+        })
+    }
+
+    /// USDC -> JUP through a wrapper program calling Jupiter, loaded from a lookup table.
+    /// The wallet also pays rent for its new JUP account.
+    fn usdc_to_jup_via_cpi() -> Value {
+        json!({
+            "slot": 200,
+            "blockTime": 1_699_999_000,
+            "meta": {
+                "err": null,
+                "fee": 5000,
+                "preBalances": [1_000_000_000u64, 0, 1],
+                "postBalances": [997_955_720u64, 2_039_280, 1],
+                "preTokenBalances": [token_balance(3, USDC_MINT, 50_000_000)],
+                "postTokenBalances": [
+                    token_balance(3, USDC_MINT, 30_000_000),
+                    token_balance(1, JUP_MINT, 40_000_000)
+                ],
+                "innerInstructions": [
+                    { "index": 0, "instructions": [{ "programIdIndex": 4, "accounts": [], "data": "3Bxs" }] }
+                ],
+                "loadedAddresses": { "writable": ["UsdcTokenAccount111111111111111111111111111"], "readonly": [JUPITER_PROGRAM_ID] }
+            },
+            "transaction": {
+                "signatures": ["sig-usdc-jup"],
+                "message": {
+                    "accountKeys": [TEST_USER_PUBKEY, "JupTokenAccount1111111111111111111111111111", WRAPPER_PROGRAM],
+                    "instructions": [{ "programIdIndex": 2, "accounts": [], "data": "" }]
+                }
+            }
+        })
+    }
+
+    fn transfer() -> Value {
+        json!({
+            "slot": 100,
+            "blockTime": 1_699_998_000,
+            "meta": {
+                "err": null,
+                "fee": 5000,
+                "preBalances": [1_000_000_000u64, 0, 1],
+                "postBalances": [899_995_000u64, 100_000_000, 1]
+            },
+            "transaction": {
+                "signatures": ["sig-transfer"],
+                "message": {
+                    "accountKeys": [TEST_USER_PUBKEY, "Recipient111111111111111111111111111111111", SYSTEM_PROGRAM],
+                    "instructions": [{ "programIdIndex": 2, "accounts": [0, 1], "data": "3Bxs" }]
+                }
+            }
+        })
+    }
+
+    /// An RPC serving the transactions above, recording the requests it got.
+    async fn rpc_server() -> (MockServer, Arc<Mutex<Vec<Value>>>) {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        let server = MockServer::start(move |req| {
+            let recorded = recorded.clone();
+            async move {
+                let request: Value = serde_json::from_str(&req.body).unwrap();
+                recorded.lock().unwrap().push(request.clone());
+                let result = match request["method"].as_str().unwrap() {
+                    "getSignaturesForAddress" => json!([
+                        { "signature": "sig-sol-jup", "slot": 300, "err": null },
+                        { "signature": "sig-failed", "slot": 250, "err": { "InstructionError": [0, "Custom"] } },
+                        { "signature": "sig-usdc-jup", "slot": 200, "err": null },
+                        { "signature": "sig-transfer", "slot": 100, "err": null },
+                        { "signature": "sig-pruned", "slot": 50, "err": null }
+                    ]),
+                    "getTransaction" => match request["params"][0].as_str().unwrap() {
+                        "sig-sol-jup" => sol_to_jup(),
+                        "sig-usdc-jup" => usdc_to_jup_via_cpi(),
+                        "sig-transfer" => transfer(),
+                        _ => Value::Null,
+                    },
+                    method => panic!("unexpected method {method}"),
+                };
+                MockResponse::json(&json!({ "jsonrpc": "2.0", "id": 1, "result": result }).to_string())
+            }
+        })
+        .await;
+        (server, requests)
+    }
+
+    #[tokio::test]
+    async fn test_wallet_swap_history() {
+        let (server, requests) = rpc_server().await;
+        let client = JupiterClient::new(&server.url);
+
+        let swaps = client
+            .wallet_swap_history(&server.url, &SwapHistoryRequest::new(TEST_USER_PUBKEY))
+            .await
+            .unwrap();
+
+        assert_eq!(swaps.len(), 2);
+        let sol_jup = &swaps[0];
+        assert_eq!(sol_jup.api, None);
+        assert_eq!(sol_jup.status, ExecutionStatus::Success);
+        assert_eq!(sol_jup.signature.as_deref(), Some("sig-sol-jup"));
+        assert_eq!(sol_jup.slot, Some(300));
+        assert_eq!(sol_jup.input_mint, SOL_MINT);
+        assert_eq!(sol_jup.output_mint, JUP_MINT);
+        assert_eq!(sol_jup.executed_in_amount, Some(Amount::new(1_000_000_000)));
+        assert_eq!(sol_jup.executed_out_amount, Some(Amount::new(421_000_000)));
+        assert_eq!(sol_jup.quoted_in_amount, Some(Amount::new(1_000_000_000)));
+        assert_eq!(sol_jup.quoted_out_amount, Some(Amount::new(421_337_000)));
+        assert_eq!(sol_jup.slippage_bps, Some(50));
+        assert_eq!(sol_jup.prioritization_fee_lamports, Some(10000));
+        assert_eq!(sol_jup.executed_at_ms, Some(1_700_000_000_000));
+
+        // the rent for the new JUP account doesn't make SOL the input
+        let usdc_jup = &swaps[1];
+        assert_eq!(usdc_jup.input_mint, USDC_MINT);
+        assert_eq!(usdc_jup.output_mint, JUP_MINT);
+        assert_eq!(usdc_jup.executed_in_amount, Some(Amount::new(20_000_000)));
+        assert_eq!(usdc_jup.executed_out_amount, Some(Amount::new(40_000_000)));
+        assert_eq!(usdc_jup.quoted_out_amount, None);
+        assert_eq!(usdc_jup.slippage_bps, None);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0]["params"][1], json!({ "limit": 100 }));
+        let fetched: Vec<&str> = requests[1..]
+            .iter()
+            .map(|request| request["params"][0].as_str().unwrap())
+            .collect();
+        assert!(!fetched.contains(&"sig-failed"));
+        assert_eq!(fetched.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_history_paging_params() {
+        let (server, requests) = rpc_server().await;
+        let client = JupiterClient::new(&server.url);
+
+        let request = SwapHistoryRequest::new(TEST_USER_PUBKEY)
+            .limit(10)
+            .before("sig-newer")
+            .until("sig-older");
+        client
+            .wallet_swap_history(&server.url, &request)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            requests.lock().unwrap()[0]["params"],
+            json!([TEST_USER_PUBKEY, { "limit": 10, "before": "sig-newer", "until": "sig-older" }])
+        );
+    }
+
+    #[test]
+    fn test_history_request_validation() {
+        assert!(SwapHistoryRequest::new(TEST_USER_PUBKEY).validate().is_ok());
+        assert!(SwapHistoryRequest::new("not-a-wallet").validate().is_err());
+        assert!(
+            SwapHistoryRequest::new(TEST_USER_PUBKEY)
+                .limit(0)
+                .validate()
+                .is_err()
+        );
+        assert!(
+            SwapHistoryRequest::new(TEST_USER_PUBKEY)
+                .limit(1001)
+                .validate()
+                .is_err()
+        );
+    }
+}
@@ -17,7 +17,7 @@ mod token_tests {
                 .get("2zMMhcVQEXDtdE6vsFS7S7D5oUodfJHE8vd1gnBouauv")
                 .expect("pengu token not found")
                 .amount,
-            516176755.to_string(),
+            516176755,
         )
     }
 
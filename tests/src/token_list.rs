@@ -0,0 +1,180 @@
+#[cfg(test)]
+mod token_list_tests {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError,
+        token_list::{TagChange, TokenList, TokenListEvent, TokenListManager},
+        types::TokenTag,
+    };
+    use serde_json::{Value, json};
+
+    use crate::{
+        common::{JUP_MINT, SOL_MINT, USDC_MINT},
+        mock::{MockResponse, MockServer},
+    };
+
+    fn token(mint: &str, tags: &[&str]) -> Value {
+        json!({
+            "address": mint,
+            "name": "Test Token",
+            "symbol": &mint[..4],
+            "decimals": 6,
+            "logoURI": null,
+            "tags": tags,
+            "daily_volume": null,
+            "created_at": "2024-01-01T00:00:00Z",
+            "freeze_authority": null,
+            "mint_authority": null,
+            "permanent_delegate": null,
+            "minted_at": null,
+            "extensions": {}
+        })
+    }
+
+    /// Serves the verified and tradable lists, moving to the second version after
+    /// the first refresh.
+    async fn lists_server() -> MockServer {
+        let calls = Arc::new(AtomicUsize::new(0));
+        MockServer::start(move |req| {
+            let calls = calls.clone();
+            async move {
+                let refresh = calls.fetch_add(1, Ordering::SeqCst) / 2;
+                let body = match (req.path.as_str(), refresh) {
+                    ("/tokens/v1/tagged/verified", 0) => json!([
+                        token(SOL_MINT, &["verified"]),
+                        token(USDC_MINT, &["verified"]),
+                    ]),
+                    ("/tokens/v1/tagged/verified", _) => json!([
+                        token(SOL_MINT, &["verified", "strict"]),
+                        token(JUP_MINT, &["verified"]),
+                    ]),
+                    ("/tokens/v1/mints/tradable", 0) => json!([SOL_MINT]),
+                    ("/tokens/v1/mints/tradable", _) => json!([SOL_MINT, JUP_MINT]),
+                    (path, _) => return MockResponse::status(404, path),
+                };
+                MockResponse::json(&body.to_string())
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_first_refresh_is_the_baseline() {
+        let server = lists_server().await;
+        let client = JupiterClient::new(&server.url);
+        let mut manager = TokenListManager::new([TokenList::Verified, TokenList::Tradable]);
+        let mut events = manager.subscribe();
+
+        assert!(!manager.contains(&TokenList::Verified, SOL_MINT));
+        let diffs = manager.refresh(&client).await.unwrap();
+
+        assert!(diffs.is_empty());
+        assert!(events.try_recv().is_err());
+        assert!(manager.contains(&TokenList::Verified, USDC_MINT));
+        assert!(manager.contains(&TokenList::Tradable, SOL_MINT));
+        assert_eq!(manager.mints(&TokenList::Tradable).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_reports_diffs_and_events() {
+        let server = lists_server().await;
+        let client = JupiterClient::new(&server.url);
+        let mut manager = TokenListManager::new([TokenList::Verified, TokenList::Tradable]);
+        manager.refresh(&client).await.unwrap();
+        let mut events = manager.subscribe();
+
+        let diffs = manager.refresh(&client).await.unwrap();
+
+        assert_eq!(diffs.len(), 2);
+        let verified = &diffs[0];
+        assert_eq!(verified.list, TokenList::Verified);
+        assert_eq!(verified.added, [JUP_MINT]);
+        assert_eq!(verified.removed, [USDC_MINT]);
+        assert_eq!(
+            verified.tags_changed,
+            [TagChange {
+                mint: SOL_MINT.to_string(),
+                added: vec!["strict".to_string()],
+                removed: vec![],
+            }]
+        );
+        assert_eq!(diffs[1].list, TokenList::Tradable);
+        assert_eq!(diffs[1].added, [JUP_MINT]);
+
+        let received: Vec<TokenListEvent> = std::iter::from_fn(|| events.try_recv().ok()).collect();
+        assert_eq!(received.len(), 4);
+        assert_eq!(
+            received[0],
+            TokenListEvent::Added {
+                list: TokenList::Verified,
+                mint: JUP_MINT.to_string()
+            }
+        );
+        assert_eq!(
+            received[1],
+            TokenListEvent::Removed {
+                list: TokenList::Verified,
+                mint: USDC_MINT.to_string()
+            }
+        );
+        assert!(matches!(received[2], TokenListEvent::TagsChanged { .. }));
+
+        let tags = manager.tags(&TokenList::Verified, SOL_MINT).unwrap();
+        assert!(tags.contains("strict"));
+    }
+
+    #[tokio::test]
+    async fn test_failed_refresh_keeps_snapshot() {
+        let server = MockServer::start(|_| async { MockResponse::status(500, "down") }).await;
+        let client = JupiterClient::new(&server.url);
+        let mut manager = TokenListManager::new([TokenList::Tag("lst".to_string())]);
+
+        assert!(manager.refresh(&client).await.is_err());
+        assert!(manager.mints(&TokenList::Tag("lst".to_string())).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_tokens_by_tag() {
+        let server = lists_server().await;
+        let client = JupiterClient::new(&server.url);
+
+        let verified = client.get_tokens_by_tag(TokenTag::Verified).await.unwrap();
+        assert_eq!(verified.len(), 2);
+        assert!(verified.iter().all(|t| t.has_tag(&TokenTag::Verified)));
+        assert!(!verified[0].has_tag(&TokenTag::Lst));
+
+        assert_eq!(TokenTag::from("token-2022"), TokenTag::Token2022);
+        assert_eq!(TokenTag::from("strict").to_string(), "strict");
+        for invalid in ["", "lst/../all", "verified,lst"] {
+            assert!(matches!(
+                client.get_tokens_by_tag(invalid.into()).await,
+                Err(JupiterClientError::ValidationError(_))
+            ));
+        }
+        assert_eq!(server.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_tradable_mint_set() {
+        let server = lists_server().await;
+        let client = JupiterClient::new(&server.url);
+
+        let tradable = client.get_tradable_mint_set().await.unwrap();
+        assert_eq!(tradable.len(), 1);
+        assert!(tradable.contains(SOL_MINT));
+        assert!(tradable.check_pair(SOL_MINT, SOL_MINT).is_ok());
+        match tradable.check_pair(SOL_MINT, USDC_MINT) {
+            Err(JupiterClientError::ValidationError(message)) => {
+                assert_eq!(
+                    message,
+                    format!("output_mint {USDC_MINT} is not tradable on Jupiter")
+                )
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,223 @@
+#[cfg(test)]
+mod token_metadata_tests {
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Duration,
+    };
+
+    use jup_ag_sdk::{
+        JupiterClient,
+        types::{Amount, QuoteResponse, TokenMetadata, TokenMetadataCache},
+    };
+    use serde_json::json;
+
+    use crate::{
+        common::{JUP_MINT, SOL_MINT, USDC_MINT, quote_json},
+        mock::{MockResponse, MockServer},
+    };
+
+    const PENGU_MINT: &str = "2zMMhcVQEXDtdE6vsFS7S7D5oUodfJHE8vd1gnBouauv";
+
+    fn metadata(mint: &str, symbol: &str, decimals: u8) -> TokenMetadata {
+        TokenMetadata {
+            mint: mint.to_string(),
+            symbol: symbol.to_string(),
+            decimals,
+            logo_uri: None,
+        }
+    }
+
+    fn token_info(mint: &str) -> MockResponse {
+        MockResponse::json(
+            &json!({
+                "address": mint,
+                "name": "Test Token",
+                "symbol": &mint[..4],
+                "decimals": 6,
+                "logoURI": "https://example.com/logo.png",
+                "tags": ["verified"],
+                "daily_volume": null,
+                "created_at": "2024-01-01T00:00:00Z",
+                "freeze_authority": null,
+                "mint_authority": null,
+                "permanent_delegate": null,
+                "minted_at": null,
+                "extensions": {}
+            })
+            .to_string(),
+        )
+    }
+
+    fn requested_mint(path: &str) -> String {
+        path.rsplit('/').next().unwrap_or_default().to_string()
+    }
+
+    /// Serves `/tokens/v1/token/{mint}` as a 6-decimal token named after the mint's prefix.
+    async fn tokens_server() -> MockServer {
+        MockServer::start(|req| async move { token_info(&requested_mint(&req.path)) }).await
+    }
+
+    #[test]
+    fn test_cache_evicts_least_recently_used() {
+        let cache = TokenMetadataCache::new(2);
+        cache.insert(metadata(SOL_MINT, "SOL", 9));
+        cache.insert(metadata(USDC_MINT, "USDC", 6));
+
+        // SOL becomes the most recently used, so USDC goes first
+        assert!(cache.get(SOL_MINT).is_some());
+        cache.insert(metadata(JUP_MINT, "JUP", 6));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(USDC_MINT).is_none());
+        assert_eq!(cache.get(SOL_MINT).unwrap().symbol, "SOL");
+        assert_eq!(cache.get(JUP_MINT).unwrap().symbol, "JUP");
+
+        let disabled = TokenMetadataCache::new(0);
+        disabled.insert(metadata(SOL_MINT, "SOL", 9));
+        assert!(disabled.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_token_metadata_is_fetched_once() {
+        let server = tokens_server().await;
+        let client = JupiterClient::new(&server.url);
+
+        let first = client.token_metadata(PENGU_MINT).await.unwrap();
+        let second = client.token_metadata(PENGU_MINT).await.unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(first.symbol, "2zMM");
+        assert_eq!(first.decimals, 6);
+        assert_eq!(
+            first.logo_uri.as_deref(),
+            Some("https://example.com/logo.png")
+        );
+        assert_eq!(server.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_token_metadata_cache_disabled() {
+        let server = tokens_server().await;
+        let client = JupiterClient::builder(&server.url)
+            .token_metadata_cache(0)
+            .build()
+            .unwrap();
+
+        client.token_metadata(PENGU_MINT).await.unwrap();
+        client.token_metadata(PENGU_MINT).await.unwrap();
+
+        assert_eq!(server.hits(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_decimal_aware_amounts() {
+        let server = tokens_server().await;
+        let client = JupiterClient::new(&server.url);
+
+        let raw = client.amount_from_ui(PENGU_MINT, 12.5).await.unwrap();
+        assert_eq!(raw, Amount::new(12_500_000));
+        assert_eq!(
+            client.amount_to_ui_string(PENGU_MINT, raw).await.unwrap(),
+            "12.5"
+        );
+        assert_eq!(server.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_quote_display_with_cached_metadata() {
+        let mut quote = quote_json();
+        quote["outputMint"] = json!(PENGU_MINT);
+        quote["outAmount"] = json!("5161767550");
+        quote["otherAmountThreshold"] = json!("5135958712");
+        let quote: QuoteResponse = serde_json::from_value(quote).unwrap();
+        assert!(
+            quote
+                .to_string()
+                .starts_with("1 SOL → 5161767550 2zMM…uauv")
+        );
+
+        let server = tokens_server().await;
+        let client = JupiterClient::new(&server.url);
+        client.token_metadata(PENGU_MINT).await.unwrap();
+
+        assert_eq!(
+            quote
+                .display_with(client.token_metadata_cache())
+                .to_string(),
+            "1 SOL → 5161.76755 2zMM, min 5135.958712 2zMM, impact 0%, slippage 50 bps"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_token_infos_dedupes_and_skips_unknown_mints() {
+        let server = MockServer::start(|req| async move {
+            let mint = requested_mint(&req.path);
+            if mint == USDC_MINT {
+                return MockResponse::status(404, r#"{"error":"Token not found"}"#);
+            }
+            token_info(&mint)
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+
+        let mints = [JUP_MINT, PENGU_MINT, USDC_MINT, JUP_MINT].map(String::from);
+        let infos = client.get_token_infos(&mints).await.unwrap();
+
+        assert_eq!(infos.len(), 2);
+        assert_eq!(infos[JUP_MINT].symbol, "JUPy");
+        assert_eq!(infos[PENGU_MINT].symbol, "2zMM");
+        assert_eq!(server.hits(), 3, "each unique mint is requested once");
+
+        client.token_metadata(PENGU_MINT).await.unwrap();
+        assert_eq!(server.hits(), 3, "metadata cache was filled");
+    }
+
+    #[tokio::test]
+    async fn test_get_token_infos_respects_concurrency_limit() {
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let (current, max) = (in_flight.clone(), peak.clone());
+        let server = MockServer::start(move |req| {
+            let (current, max) = (current.clone(), max.clone());
+            async move {
+                let now = current.fetch_add(1, Ordering::SeqCst) + 1;
+                max.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                current.fetch_sub(1, Ordering::SeqCst);
+                token_info(&requested_mint(&req.path))
+            }
+        })
+        .await;
+        let client = JupiterClient::builder(&server.url)
+            .max_concurrent_requests(2)
+            .build()
+            .unwrap();
+
+        let mints = [SOL_MINT, USDC_MINT, JUP_MINT, PENGU_MINT].map(String::from);
+        let infos = client.get_token_infos(&mints).await.unwrap();
+
+        assert_eq!(infos.len(), 4);
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_token_infos_fails_on_server_error() {
+        let server =
+            MockServer::start(|_| async { MockResponse::status(500, "internal error") }).await;
+        let client = JupiterClient::new(&server.url);
+
+        let result = client.get_token_infos(&[JUP_MINT.to_string()]).await;
+        assert!(result.is_err());
+
+        let invalid = client.get_token_infos(&["not-a-mint".to_string()]).await;
+        assert!(invalid.is_err());
+        assert_eq!(
+            server.hits(),
+            1,
+            "invalid mints are rejected before any request"
+        );
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tower_tests {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use jup_ag_sdk::{JupiterClient, JupiterClientError, error::BoxError};
+    use reqwest::header::HeaderValue;
+    use tower::{layer::layer_fn, service_fn, util::MapRequestLayer};
+
+    use crate::{
+        common::JUP_MINT,
+        mock::{MockResponse, MockServer},
+    };
+
+    /// Echoes the `x-middleware` header back in the error body.
+    async fn echo_server() -> MockServer {
+        MockServer::start(|req| async move {
+            let header = req.header("x-middleware").unwrap_or("missing").to_string();
+            MockResponse::status(400, &header)
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_requests_go_through_service_layer() {
+        let server = echo_server().await;
+        let client = JupiterClient::builder(&server.url)
+            .service_layer(MapRequestLayer::new(|mut request: reqwest::Request| {
+                request
+                    .headers_mut()
+                    .insert("x-middleware", HeaderValue::from_static("applied"));
+                request
+            }))
+            .build()
+            .unwrap();
+
+        let err = client.get_token_info(JUP_MINT).await.unwrap_err();
+
+        assert!(
+            matches!(&err, JupiterClientError::ApiError(e) if e.body == "applied"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_service_layer_survives_rebuild() {
+        let server = echo_server().await;
+        let client = JupiterClient::builder(&server.url)
+            .service_layer(MapRequestLayer::new(|mut request: reqwest::Request| {
+                request
+                    .headers_mut()
+                    .insert("x-middleware", HeaderValue::from_static("applied"));
+                request
+            }))
+            .build()
+            .unwrap()
+            .with_api_key("test-key");
+
+        let err = client.get_token_info(JUP_MINT).await.unwrap_err();
+        assert!(err.to_string().contains("applied"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_middleware_errors_are_service_errors() {
+        let server = echo_server().await;
+        let calls = Arc::new(AtomicUsize::new(0));
+        let seen = calls.clone();
+        let client = JupiterClient::builder(&server.url)
+            .service_layer(layer_fn(move |_client| {
+                let seen = seen.clone();
+                service_fn(move |_request: reqwest::Request| {
+                    seen.fetch_add(1, Ordering::SeqCst);
+                    async { Err::<reqwest::Response, BoxError>("load shed".into()) }
+                })
+            }))
+            .build()
+            .unwrap();
+
+        let err = client.get_token_info(JUP_MINT).await.unwrap_err();
+
+        assert!(
+            matches!(&err, JupiterClientError::ServiceError(e) if e.to_string() == "load shed"),
+            "{err}"
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(server.hits(), 0);
+    }
+}
@@ -0,0 +1,243 @@
+#[cfg(test)]
+mod transaction_tests {
+    use base64::{Engine, engine::general_purpose::STANDARD};
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError,
+        transaction::{self, SwapTransaction, Transaction, VersionedTransaction},
+        types::{MAX_TRANSACTION_SIZE, SwapInstructions},
+    };
+    use serde_json::json;
+    use solana_hash::Hash;
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_keypair::Keypair;
+    use solana_message::{AddressLookupTableAccount, Message, VersionedMessage, v0};
+    use solana_pubkey::Pubkey;
+    use solana_signer::Signer;
+
+    use crate::mock::{MockResponse, MockServer};
+
+    fn instruction(user: &Pubkey) -> Instruction {
+        Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[1, 2, 3],
+            vec![AccountMeta::new(*user, true)],
+        )
+    }
+
+    fn legacy(user: &Pubkey) -> String {
+        let message =
+            Message::new_with_blockhash(&[instruction(user)], Some(user), &Hash::default());
+        SwapTransaction::Legacy(Transaction::new_unsigned(message))
+            .to_base64()
+            .unwrap()
+    }
+
+    fn versioned(user: &Pubkey) -> String {
+        let message =
+            v0::Message::try_compile(user, &[instruction(user)], &[], Hash::default()).unwrap();
+        let tx = VersionedTransaction {
+            signatures: vec![Default::default()],
+            message: VersionedMessage::V0(message),
+        };
+        SwapTransaction::Versioned(tx).to_base64().unwrap()
+    }
+
+    #[test]
+    fn test_decodes_legacy_and_versioned() {
+        let user = Keypair::new();
+
+        let legacy = SwapTransaction::from_base64(&legacy(&user.pubkey())).unwrap();
+        assert!(legacy.is_legacy());
+        assert!(matches!(legacy, SwapTransaction::Legacy(_)));
+
+        let versioned = SwapTransaction::from_base64(&versioned(&user.pubkey())).unwrap();
+        assert!(!versioned.is_legacy());
+        assert_eq!(versioned.signer_keys(), [user.pubkey()]);
+
+        assert!(matches!(
+            SwapTransaction::from_base64("not base64!"),
+            Err(JupiterClientError::DeserializationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_sign_and_reencode() {
+        let user = Keypair::new();
+
+        for encoded in [legacy(&user.pubkey()), versioned(&user.pubkey())] {
+            let mut tx = SwapTransaction::from_base64(&encoded).unwrap();
+            tx.sign(&[&user]).unwrap();
+
+            let signed = SwapTransaction::from_base64(&tx.to_base64().unwrap()).unwrap();
+            assert_eq!(signed, tx);
+
+            let signature = match &signed {
+                SwapTransaction::Legacy(tx) => tx.signatures[0],
+                SwapTransaction::Versioned(tx) => tx.signatures[0],
+            };
+            assert!(signature.verify(user.pubkey().as_ref(), &signed.message_bytes()));
+        }
+    }
+
+    #[test]
+    fn test_debug_hides_signed_transaction() {
+        let user = Keypair::new();
+        let mut tx = SwapTransaction::from_base64(&versioned(&user.pubkey())).unwrap();
+        tx.sign(&[&user]).unwrap();
+        let signature = match &tx {
+            SwapTransaction::Legacy(tx) => tx.signatures[0],
+            SwapTransaction::Versioned(tx) => tx.signatures[0],
+        };
+
+        let debug = format!("{tx:?}");
+        assert!(debug.starts_with("Versioned {"), "{debug}");
+        assert!(debug.contains("signed: 1"));
+        assert!(debug.contains(&format!("{:?}", user.pubkey())));
+        assert!(!debug.contains(&format!("{signature:?}")));
+    }
+
+    #[test]
+    fn test_sign_rejects_unknown_signer() {
+        let user = Keypair::new();
+        let mut tx = SwapTransaction::from_base64(&legacy(&user.pubkey())).unwrap();
+
+        assert!(matches!(
+            tx.sign(&[&Keypair::new()]),
+            Err(JupiterClientError::ValidationError(_))
+        ));
+    }
+
+    fn wide_instruction(user: &Pubkey, accounts: &[Pubkey]) -> Instruction {
+        let mut metas = vec![AccountMeta::new(*user, true)];
+        metas.extend(
+            accounts
+                .iter()
+                .map(|key| AccountMeta::new_readonly(*key, false)),
+        );
+        Instruction::new_with_bytes(Pubkey::new_unique(), &[0; 8], metas)
+    }
+
+    #[test]
+    fn test_estimate_size_with_lookup_tables() {
+        let user = Pubkey::new_unique();
+        let accounts: Vec<_> = (0..40).map(|_| Pubkey::new_unique()).collect();
+        let instructions = [wide_instruction(&user, &accounts)];
+
+        let plain = transaction::estimate_size(&user, &instructions, &[]).unwrap();
+        assert!(!plain.fits());
+        assert!(plain.margin() < 0);
+
+        let table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: accounts,
+        };
+        let compressed = transaction::estimate_size(&user, &instructions, &[table]).unwrap();
+        assert!(compressed.fits());
+        assert_eq!(
+            compressed.margin(),
+            (MAX_TRANSACTION_SIZE - compressed.size) as isize
+        );
+
+        // matches the size of the transaction once it is actually built
+        let message = v0::Message::try_compile(&user, &instructions, &[], Hash::default()).unwrap();
+        let built = SwapTransaction::Versioned(VersionedTransaction {
+            signatures: vec![Default::default()],
+            message: VersionedMessage::V0(message),
+        });
+        assert_eq!(built.size().unwrap(), plain);
+    }
+
+    #[test]
+    fn test_swap_instructions_estimate_size() {
+        let user = Pubkey::new_unique();
+        let api_instruction = |program: Pubkey| {
+            json!({
+                "programId": program.to_string(),
+                "accounts": [{ "pubkey": user.to_string(), "isSigner": true, "isWritable": true }],
+                "data": STANDARD.encode([1, 2, 3]),
+            })
+        };
+        let instructions: SwapInstructions = serde_json::from_value(json!({
+            "computeBudgetInstructions": [api_instruction(Pubkey::new_unique())],
+            "setupInstructions": [],
+            "swapInstruction": api_instruction(Pubkey::new_unique()),
+            "cleanupInstruction": null,
+            "otherInstructions": null,
+            "addressLookupTableAddresses": []
+        }))
+        .unwrap();
+
+        let alone = instructions
+            .estimate_size(&user.to_string(), &[], &[])
+            .unwrap();
+        let with_extra = instructions
+            .estimate_size(&user.to_string(), &[instruction(&user)], &[])
+            .unwrap();
+        assert!(alone.fits());
+        assert!(with_extra.size > alone.size);
+
+        assert!(matches!(
+            instructions.estimate_size("not-a-key", &[], &[]),
+            Err(JupiterClientError::ValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_address_lookup_tables() {
+        let (table, first, second) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let mut data = vec![0u8; 56];
+        data.extend_from_slice(first.as_ref());
+        data.extend_from_slice(second.as_ref());
+        let account = json!({
+            "owner": "AddressLookupTab1e1111111111111111111111111",
+            "data": [STANDARD.encode(&data), "base64"]
+        });
+
+        let known = table.to_string();
+        let server = MockServer::start(move |req| {
+            let (account, known) = (account.clone(), known.clone());
+            async move {
+                let request: serde_json::Value = serde_json::from_str(&req.body).unwrap();
+                let value: Vec<_> = request["params"][0]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|address| (address == &known).then(|| account.clone()))
+                    .collect();
+                MockResponse::json(
+                    &json!({
+                        "jsonrpc": "2.0",
+                        "id": 1,
+                        "result": { "context": { "slot": 1 }, "value": value }
+                    })
+                    .to_string(),
+                )
+            }
+        })
+        .await;
+        let client = JupiterClient::new("https://lite-api.jup.ag");
+
+        let tables = client
+            .resolve_address_lookup_tables(&server.url, &[table.to_string()])
+            .await
+            .unwrap();
+        assert_eq!(
+            tables,
+            [AddressLookupTableAccount {
+                key: table,
+                addresses: vec![first, second],
+            }]
+        );
+
+        let missing = Pubkey::new_unique().to_string();
+        let err = client
+            .resolve_address_lookup_tables(&server.url, &[table.to_string(), missing])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not found"), "{err}");
+    }
+}
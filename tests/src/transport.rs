@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod transport_tests {
+    use std::sync::{Arc, Mutex};
+
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError,
+        client::{HttpResponse, HttpTransport, TransportFuture},
+        config::RateLimitPlan,
+    };
+    use reqwest::{
+        StatusCode,
+        header::{HeaderMap, HeaderValue},
+    };
+
+    /// The URL and `x-api-key` header of a sent request.
+    type Sent = (String, Option<String>);
+
+    /// Answers every request with a canned response and records what was sent, no socket involved.
+    #[derive(Debug, Clone)]
+    struct FakeTransport {
+        status: StatusCode,
+        headers: HeaderMap,
+        body: &'static str,
+        sent: Arc<Mutex<Vec<Sent>>>,
+    }
+
+    impl FakeTransport {
+        fn new(status: StatusCode, body: &'static str) -> Self {
+            Self {
+                status,
+                headers: HeaderMap::new(),
+                body,
+                sent: Arc::default(),
+            }
+        }
+
+        fn sent(&self) -> Vec<Sent> {
+            self.sent.lock().unwrap().clone()
+        }
+    }
+
+    impl HttpTransport for FakeTransport {
+        fn execute(&self, request: reqwest::Request) -> TransportFuture<'_> {
+            let api_key = request
+                .headers()
+                .get("x-api-key")
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string);
+            self.sent
+                .lock()
+                .unwrap()
+                .push((request.url().to_string(), api_key));
+            Box::pin(async move {
+                Ok(HttpResponse {
+                    status: self.status,
+                    headers: self.headers.clone(),
+                    body: self.body.as_bytes().to_vec(),
+                })
+            })
+        }
+    }
+
+    const ROUTERS: &str = r#"[{"id": "metis", "name": "Metis", "icon": ""}]"#;
+
+    #[tokio::test]
+    async fn test_requests_go_through_custom_transport() {
+        let transport = FakeTransport::new(StatusCode::OK, ROUTERS);
+        let client = JupiterClient::builder("http://jupiter.invalid")
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+
+        let routers = client.routers().await.unwrap();
+
+        assert_eq!(routers[0].id, "metis");
+        assert_eq!(
+            transport.sent(),
+            vec![(
+                "http://jupiter.invalid/ultra/v1/order/routers".to_string(),
+                None
+            )]
+        );
+        assert_eq!(client.stats().requests, 1);
+    }
+
+    #[tokio::test]
+    async fn test_error_status_from_transport_is_api_error() {
+        let transport = FakeTransport::new(StatusCode::BAD_REQUEST, "bad mint");
+        let client = JupiterClient::builder("http://jupiter.invalid")
+            .transport(transport)
+            .build()
+            .unwrap();
+
+        let err = client.routers().await.unwrap_err();
+
+        assert!(
+            matches!(&err, JupiterClientError::ApiError(e) if e.status == StatusCode::BAD_REQUEST && e.message == "bad mint"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transport_survives_rebuild_with_client_headers() {
+        let transport = FakeTransport::new(StatusCode::OK, ROUTERS);
+        let client = JupiterClient::builder("http://jupiter.invalid")
+            .transport(transport.clone())
+            .build()
+            .unwrap()
+            .with_api_key("test-key");
+
+        client.routers().await.unwrap();
+
+        let sent = transport.sent();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].1.as_deref(), Some("test-key"));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_headers_from_transport_are_observed() {
+        let mut transport = FakeTransport::new(StatusCode::OK, ROUTERS);
+        transport
+            .headers
+            .insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        let client = JupiterClient::builder("http://jupiter.invalid")
+            .transport(transport.clone())
+            .rate_limit(RateLimitPlan::Custom {
+                requests: 100,
+                window: std::time::Duration::from_millis(50),
+            })
+            .build()
+            .unwrap();
+
+        client.routers().await.unwrap();
+        client.routers().await.unwrap();
+
+        assert_eq!(transport.sent().len(), 2);
+        assert_eq!(client.stats().rate_limit_waits, 1);
+    }
+}
@@ -1,10 +1,19 @@
 #[cfg(test)]
 mod ultra_tests {
-    use jup_ag_sdk::types::{
-        CreateTriggerOrder, ExecuteTriggerOrder, GetTriggerOrders, OrderStatus,
+    use std::sync::{Arc, Mutex};
+
+    use jup_ag_sdk::{
+        JupiterClient,
+        types::{
+            CreateTriggerOrder, ExecuteTriggerOrder, GetTriggerOrders, Order, OrderStatus, Params,
+        },
     };
+    use serde_json::{Value, json};
 
-    use crate::common::{SOL_MINT, TEST_USER_PUBKEY, USDC_MINT, create_test_client};
+    use crate::{
+        common::{SOL_MINT, TEST_USER_PUBKEY, USDC_MINT, create_test_client},
+        mock::{MockResponse, MockServer},
+    };
 
     #[test]
     fn test_trigger_create_order_builder() {
@@ -111,4 +120,102 @@ mod ultra_tests {
             "order status should be history"
         );
     }
+
+    const OLD_ORDER_KEY: &str = "EyZ8SCWqKR7V6WzU8N3qNgbU5eTsMCAVf5Ldg2kP4Pq2";
+
+    fn active_order() -> Order {
+        serde_json::from_value(json!({
+            "userPubkey": TEST_USER_PUBKEY,
+            "orderKey": OLD_ORDER_KEY,
+            "inputMint": SOL_MINT,
+            "outputMint": USDC_MINT,
+            "makingAmount": "1",
+            "takingAmount": "150",
+            "remainingMakingAmount": "1",
+            "remainingTakingAmount": "150",
+            "rawMakingAmount": "1000000000",
+            "rawTakingAmount": "150000000",
+            "rawRemainingMakingAmount": "1000000000",
+            "rawRemainingTakingAmount": "150000000",
+            "slippageBps": "0",
+            "createdAt": "2025-06-01T00:00:00Z",
+            "updatedAt": "2025-06-01T00:00:00Z",
+            "status": "Open",
+            "openTx": "",
+            "closeTx": "",
+            "programVersion": "j1o2qRpjcyUwEvwtcfhEQefh773ZgjxcVRry7LDqg5X",
+            "trades": []
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_replace_trigger_order() {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let seen = requests.clone();
+        let server = MockServer::start(move |req| {
+            let seen = seen.clone();
+            async move {
+                let body: Value = serde_json::from_str(&req.body).unwrap();
+                seen.lock().unwrap().push((req.path.clone(), body));
+                let name = req.path.trim_start_matches("/trigger/v1/");
+                MockResponse::json(
+                    &json!({
+                        "requestId": format!("{name}-request"),
+                        "transaction": format!("{name}-tx"),
+                        "order": "NewOrder111111111111111111111111111111111111",
+                        "code": 0
+                    })
+                    .to_string(),
+                )
+            }
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+
+        let replacement = client
+            .replace_trigger_order(&active_order(), Params::new(1_000_000_000, 180_000_000))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            replacement.transactions(),
+            ["cancelOrder-tx", "createOrder-tx"]
+        );
+        let [cancel, create] = replacement.execute_requests("signed-cancel", "signed-create");
+        assert_eq!(cancel.request_id, "cancelOrder-request");
+        assert_eq!(cancel.signed_transaction, "signed-cancel");
+        assert_eq!(create.request_id, "createOrder-request");
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        let (cancel_path, cancel_body) = &requests[0];
+        assert_eq!(cancel_path, "/trigger/v1/cancelOrder");
+        assert_eq!(cancel_body["maker"], TEST_USER_PUBKEY);
+        assert_eq!(cancel_body["order"], OLD_ORDER_KEY);
+
+        let (create_path, create_body) = &requests[1];
+        assert_eq!(create_path, "/trigger/v1/createOrder");
+        assert_eq!(create_body["inputMint"], SOL_MINT);
+        assert_eq!(create_body["outputMint"], USDC_MINT);
+        assert_eq!(create_body["maker"], TEST_USER_PUBKEY);
+        assert_eq!(create_body["payer"], TEST_USER_PUBKEY);
+        assert_eq!(create_body["params"]["takingAmount"], "180000000");
+    }
+
+    #[tokio::test]
+    async fn test_replace_trigger_order_does_not_create_when_cancel_fails() {
+        let server = MockServer::start(|_| async {
+            MockResponse::status(400, r#"{"error":"order not found"}"#)
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+
+        let result = client
+            .replace_trigger_order(&active_order(), Params::new(1_000_000_000, 180_000_000))
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(server.hits(), 1);
+    }
 }
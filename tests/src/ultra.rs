@@ -1,8 +1,99 @@
 #[cfg(test)]
 mod ultra_tests {
-    use jup_ag_sdk::types::{UltraExecuteOrderRequest, UltraOrderRequest};
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError,
+        types::{UltraOrderRequest, UltraRouting},
+    };
+    use serde_json::json;
 
-    use crate::common::{JUP_MINT, SOL_MINT, TEST_AMOUNT, TEST_USER_PUBKEY, create_test_client};
+    use crate::{
+        common::{JUP_MINT, SOL_MINT, TEST_AMOUNT},
+        mock::{MockResponse, MockServer},
+    };
+
+    #[test]
+    fn test_ultra_routing() {
+        let order =
+            |routing| UltraOrderRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT).routing(routing);
+
+        let aggregator = order(UltraRouting::AggregatorOnly).exclude_routers.unwrap();
+        assert_eq!(aggregator, UltraRouting::RFQ_ROUTERS);
+
+        let rfq = order(UltraRouting::RfqOnly).exclude_routers.unwrap();
+        assert_eq!(rfq, UltraRouting::AGGREGATOR_ROUTERS);
+
+        let all = UltraOrderRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+            .exclude_routers(vec!["okx".to_string()])
+            .routing(UltraRouting::All);
+        assert_eq!(all.exclude_routers, None);
+    }
+
+    #[tokio::test]
+    async fn test_ultra_routing_query() {
+        // echo the request path back as the error body
+        let server =
+            MockServer::start(|req| async move { MockResponse::status(400, &req.path) }).await;
+        let client = JupiterClient::new(&server.url);
+
+        let order = UltraOrderRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+            .routing(UltraRouting::AggregatorOnly);
+        let err = client.get_ultra_order(&order).await.unwrap_err();
+
+        assert!(
+            err.to_string()
+                .contains("excludeRouters=jupiterz%2Chashflow%2Cpyth"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_tokens() {
+        let server = MockServer::start(|req| async move {
+            assert_eq!(req.path, "/ultra/v1/search?query=JUP");
+            MockResponse::json(
+                &json!([
+                    {
+                        "id": JUP_MINT,
+                        "name": "Jupiter",
+                        "symbol": "JUP",
+                        "icon": "https://static.jup.ag/jup/icon.png",
+                        "decimals": 6,
+                        "isVerified": true,
+                        "tags": ["verified", "strict"],
+                        "organicScore": 98.5,
+                        "usdPrice": 0.42,
+                        "holderCount": 800000,
+                        "circSupply": 3000000000u64
+                    },
+                    {
+                        "id": "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCM",
+                        "name": "Jupiter Copy",
+                        "symbol": "JUP",
+                        "icon": null,
+                        "decimals": 9,
+                        "tags": null
+                    }
+                ])
+                .to_string(),
+            )
+        })
+        .await;
+        let client = JupiterClient::new(&server.url);
+
+        let results = client.search_tokens(" JUP ").await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].mint, JUP_MINT);
+        assert!(results[0].is_verified());
+        assert_eq!(results[0].organic_score, Some(98.5));
+        assert!(!results[1].is_verified());
+        assert!(results[1].tags.is_empty());
+
+        assert!(matches!(
+            client.search_tokens("  ").await,
+            Err(JupiterClientError::ValidationError(_))
+        ));
+        assert_eq!(server.hits(), 1);
+    }
 
     // #[test]
     // fn test_ultra_order_request_builder() {
@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod validation_tests {
+    use jup_ag_sdk::{
+        JupiterClientError,
+        types::{
+            CancelTriggerOrders, CreateRecurringOrderRequest, CreateTriggerOrder,
+            GetRecurringOrders, GetTriggerOrders, OrderStatus, QuoteRequest, RecurringOrderType,
+            SwapMode, SwapRequest, UltraOrderRequest, Validate, validate_pubkey,
+        },
+    };
+
+    use crate::common::{
+        JUP_MINT, SOL_MINT, TEST_AMOUNT, TEST_USER_PUBKEY, create_test_client, quote_json,
+    };
+
+    fn assert_validation_error<T: std::fmt::Debug>(
+        result: Result<T, JupiterClientError>,
+        field: &str,
+    ) {
+        match result {
+            Err(JupiterClientError::ValidationError(msg)) => {
+                assert!(msg.contains(field), "error should name {field}: {msg}")
+            }
+            other => panic!("expected validation error for {field}, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_validate_pubkey() {
+        assert!(validate_pubkey("mint", SOL_MINT).is_ok());
+        assert!(validate_pubkey("mint", TEST_USER_PUBKEY).is_ok());
+
+        assert_validation_error(validate_pubkey("mint", ""), "mint");
+        // 0, O, I and l are not in the base58 alphabet
+        assert_validation_error(
+            validate_pubkey("mint", "So1111111111111111111111111111111111111111O"),
+            "mint",
+        );
+        // valid base58, wrong length
+        assert_validation_error(validate_pubkey("mint", "3yZe7d"), "mint");
+    }
+
+    #[test]
+    fn test_request_validation() {
+        assert!(
+            QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+                .validate()
+                .is_ok()
+        );
+
+        let order = UltraOrderRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT).add_taker("me");
+        assert_validation_error(order.validate(), "taker");
+
+        let trigger = CreateTriggerOrder::new(
+            SOL_MINT,
+            JUP_MINT,
+            TEST_USER_PUBKEY,
+            TEST_USER_PUBKEY,
+            TEST_AMOUNT,
+            TEST_AMOUNT,
+        )
+        .fee_account("fee");
+        assert_validation_error(trigger.validate(), "fee_account");
+
+        let cancel = CancelTriggerOrders {
+            maker: TEST_USER_PUBKEY.to_string(),
+            order: vec![SOL_MINT.to_string(), "bad".to_string()],
+            compute_unit_price: None,
+        };
+        assert_validation_error(cancel.validate(), "order");
+    }
+
+    #[test]
+    fn test_bps_out_of_range_fails_validation() {
+        let quote = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT).slippage_bps(10_001);
+        assert_validation_error(quote.validate(), "slippage_bps");
+        let quote = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT).platform_fee_bps(20_000);
+        assert_validation_error(quote.validate(), "platform_fee_bps");
+
+        let trigger = |slippage| {
+            CreateTriggerOrder::new(
+                SOL_MINT,
+                JUP_MINT,
+                TEST_USER_PUBKEY,
+                TEST_USER_PUBKEY,
+                TEST_AMOUNT,
+                TEST_AMOUNT,
+            )
+            .slippage_bps(slippage)
+        };
+        assert_validation_error(trigger(10_001).validate(), "slippage_bps");
+        assert!(trigger(50).validate().is_ok());
+        // the Trigger API takes bps as strings
+        assert_eq!(
+            serde_json::to_value(trigger(50)).unwrap()["params"]["slippageBps"],
+            "50"
+        );
+    }
+
+    #[test]
+    fn test_builders_fail_at_build() {
+        let quote = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+            .slippage_bps(50)
+            .build()
+            .unwrap();
+        assert_eq!(quote.input_mint, SOL_MINT);
+        assert_validation_error(QuoteRequest::new("mint", JUP_MINT, 1).build(), "input_mint");
+
+        let response = serde_json::from_value(quote_json()).unwrap();
+        assert_validation_error(SwapRequest::new("me", response).build(), "user_public_key");
+
+        let trigger =
+            CreateTriggerOrder::new(SOL_MINT, JUP_MINT, "maker", TEST_USER_PUBKEY, 1, 1).build();
+        assert_validation_error(trigger, "maker");
+
+        let recurring =
+            CreateRecurringOrderRequest::new_time_order("user", SOL_MINT, JUP_MINT, 1, 2, 60)
+                .build();
+        assert_validation_error(recurring, "user");
+    }
+
+    #[test]
+    fn test_unknown_enum_values_fail_validation() {
+        let quote = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT).swap_mode(SwapMode::Unknown);
+        assert_validation_error(quote.validate(), "swap_mode");
+        let quote =
+            QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT).swap_mode(SwapMode::ExactOut);
+        assert!(quote.validate().is_ok());
+
+        let orders = GetTriggerOrders::new(TEST_USER_PUBKEY, OrderStatus::Unknown);
+        assert_validation_error(orders.validate(), "order_status");
+        let orders = GetRecurringOrders::new(
+            RecurringOrderType::All,
+            OrderStatus::Unknown,
+            TEST_USER_PUBKEY,
+        );
+        assert_validation_error(orders.validate(), "order_status");
+    }
+
+    #[tokio::test]
+    async fn test_client_rejects_invalid_input_before_sending() {
+        let client = create_test_client();
+
+        let quote = QuoteRequest::new(SOL_MINT, "", TEST_AMOUNT);
+        assert_validation_error(client.get_quote(&quote).await, "output_mint");
+
+        assert_validation_error(client.get_token_balances("nope").await, "address");
+    }
+}